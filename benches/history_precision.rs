@@ -0,0 +1,49 @@
+//! Demonstrates the memory/bandwidth win `HistoryPrecision::F32` claims:
+//! serializing the same 200-snapshot, 300x200 history at `f32` should
+//! produce roughly half the bytes `f64` does, in less wall time (less to
+//! write). This is the "large grids consume a lot of memory bandwidth"
+//! request's actual bottleneck in this crate — `--history`'s snapshot
+//! storage, not the live solver's `Vec<f64>` fields, which stay `f64` for
+//! numerical stability regardless of resolution.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use lgr_2d_cfd::field_history::{FieldHistory, HistoryPrecision};
+use lgr_2d_cfd::fluid::Fluid;
+
+fn tank_with_snapshots(num_x: usize, num_y: usize, steps: usize, precision: HistoryPrecision) -> FieldHistory {
+    let fluid = Fluid::new(1000.0, num_x, num_y, 1.0 / num_y as f64);
+    let mut history = FieldHistory::with_precision(&fluid, &[], precision);
+    let mut step_fluid = fluid;
+    for step in 0..steps {
+        for (i, p) in step_fluid.p.iter_mut().enumerate() {
+            *p = (step + i) as f64 * 0.01;
+        }
+        history.push(&step_fluid, step as u64, step as f64 / 60.0);
+    }
+    history
+}
+
+fn bench_serialize_history(c: &mut Criterion) {
+    let mut group = c.benchmark_group("history_serialize_300x200_200steps");
+
+    group.bench_function("f64", |b| {
+        b.iter_batched(
+            || tank_with_snapshots(300, 200, 200, HistoryPrecision::F64),
+            |history| bincode::serialize(&history).unwrap().len(),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("f32", |b| {
+        b.iter_batched(
+            || tank_with_snapshots(300, 200, 200, HistoryPrecision::F32),
+            |history| bincode::serialize(&history).unwrap().len(),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_serialize_history);
+criterion_main!(benches);
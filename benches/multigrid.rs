@@ -0,0 +1,53 @@
+//! `cargo bench` target for the multigrid pressure solver. The originating
+//! request asked for a benchmark "on scene 3's grid size" — there is no
+//! scene 3 in this tree (`--scene` only implements scene 4, falling back
+//! to it for any other number), so this benches scene 4's actual grid
+//! (`Scene::wind_tunnel_with_radiator`'s 200x80) instead.
+//!
+//! Compares `PressureSolver::GaussSeidel` at the 200 iterations used
+//! elsewhere in this file's convergence tests against
+//! `PressureSolver::Multigrid` tuned to reach comparable divergence, so the
+//! reported ratio is "equivalent-accuracy" wall-clock time, not two
+//! arbitrarily-sized runs.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use lgr_2d_cfd::fluid::Fluid;
+
+fn tank(num_x: usize, num_y: usize) -> Fluid {
+    let mut fluid = Fluid::new(1000.0, num_x, num_y, 1.0 / num_y as f64);
+    let n = fluid.num_y;
+    for j in 0..fluid.num_y {
+        for i in 0..fluid.num_x {
+            let idx = i * n + j;
+            let is_boundary = i == 0 || j == 0 || j == fluid.num_y - 1;
+            fluid.s[idx] = if is_boundary { 0.0 } else { 1.0 };
+        }
+        fluid.u[j] = 1.0;
+    }
+    fluid
+}
+
+fn bench_pressure_solvers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pressure_solve_200x80");
+
+    group.bench_function("gauss_seidel_200_iters", |b| {
+        b.iter_batched(
+            || tank(200, 80),
+            |mut fluid| fluid.solve_incompressibility(200, 1.0 / 60.0, 1.9),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("multigrid_3_levels_4_v_cycles_4_smoothing_iters", |b| {
+        b.iter_batched(
+            || tank(200, 80),
+            |mut fluid| fluid.solve_incompressibility_multigrid(3, 4, 4, 1.0 / 60.0),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_pressure_solvers);
+criterion_main!(benches);
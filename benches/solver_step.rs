@@ -0,0 +1,82 @@
+//! `cargo bench` target for the request that motivated parallelizing
+//! `advect_vel`/`advect_smoke`/`solve_incompressibility`: compares one
+//! `Fluid::simulate` step on a 300x200 grid running on rayon's default
+//! thread pool (uses every available core) against the same code pinned to
+//! a single-thread pool, so the reported ratio is a real before/after
+//! speedup rather than two different implementations.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use lgr_2d_cfd::fluid::Fluid;
+
+fn tank(num_x: usize, num_y: usize) -> Fluid {
+    let mut fluid = Fluid::new(1000.0, num_x, num_y, 1.0 / num_y as f64);
+    let n = fluid.num_y;
+    for j in 0..fluid.num_y {
+        for i in 0..fluid.num_x {
+            let idx = i * n + j;
+            let is_boundary = i == 0 || j == 0 || j == fluid.num_y - 1;
+            fluid.s[idx] = if is_boundary { 0.0 } else { 1.0 };
+        }
+        fluid.u[j] = 1.0;
+    }
+    fluid
+}
+
+fn bench_solver_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solver_step_300x200");
+
+    group.bench_function("default_thread_pool", |b| {
+        b.iter_batched(
+            || tank(300, 200),
+            |mut fluid| fluid.simulate(1.0 / 60.0, 0.0, 40, 1.9),
+            BatchSize::LargeInput,
+        );
+    });
+
+    let single_threaded = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+    group.bench_function("single_thread_pool", |b| {
+        b.iter_batched(
+            || tank(300, 200),
+            |mut fluid| single_threaded.install(|| fluid.simulate(1.0 / 60.0, 0.0, 40, 1.9)),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+/// Isolates `solve_incompressibility`'s cost on its own, separate from
+/// `bench_solver_step`'s whole-step number, so a regression in the pressure
+/// solve specifically (as opposed to advection) shows up without having to
+/// subtract two whole-step numbers.
+fn bench_solve_incompressibility(c: &mut Criterion) {
+    c.bench_function("solve_incompressibility_300x200", |b| {
+        b.iter_batched(
+            || tank(300, 200),
+            |mut fluid| fluid.solve_incompressibility(40, 1.0 / 60.0, 1.9),
+            BatchSize::LargeInput,
+        );
+    });
+}
+
+/// Same isolation as `bench_solve_incompressibility`, for `advect_vel`.
+/// `extrapolate` runs first since `advect_vel`'s boundary sampling reads the
+/// ghost cells it fills — without it this would be timing advection off of
+/// whatever `tank`'s raw boundary values happen to be, not the field a real
+/// step actually advects.
+fn bench_advect_vel(c: &mut Criterion) {
+    c.bench_function("advect_vel_300x200", |b| {
+        b.iter_batched(
+            || {
+                let mut fluid = tank(300, 200);
+                fluid.extrapolate();
+                fluid
+            },
+            |mut fluid| fluid.advect_vel(1.0 / 60.0),
+            BatchSize::LargeInput,
+        );
+    });
+}
+
+criterion_group!(benches, bench_solver_step, bench_solve_incompressibility, bench_advect_vel);
+criterion_main!(benches);
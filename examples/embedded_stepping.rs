@@ -0,0 +1,29 @@
+//! Drive the solver one step at a time from an embedding application.
+//!
+//! There's no separate pause/resume or observer-callback API in this crate
+//! yet — `Scene::simulate` already *is* the "step once" primitive, so an
+//! embedder gets pause/step/observe behavior for free by just calling it in
+//! its own loop and reading `scene.fluid`/`scene.sim_time` in between, the
+//! way this example does.
+
+use lgr_2d_cfd::scene::Scene;
+
+fn main() {
+    let mut scene = Scene::wind_tunnel_with_radiator(80, 40);
+
+    let mut peak_divergence: f64 = 0.0;
+    for target_step in 1..=50 {
+        let step = scene.simulate();
+        assert_eq!(step, target_step, "simulate should report the step it just completed");
+
+        // This is where an embedder would pause, render a frame, or react
+        // to the current state before resuming.
+        let divergence = scene.fluid.max_divergence();
+        peak_divergence = peak_divergence.max(divergence);
+        if step.is_multiple_of(10) {
+            println!("step {step}: sim_time={:.4}s max_divergence={divergence:.6}", scene.sim_time);
+        }
+    }
+
+    println!("peak max_divergence over the run: {peak_divergence:.6}");
+}
@@ -0,0 +1,23 @@
+//! Smallest possible use of the library: build the built-in wind-tunnel
+//! scene, step it a handful of times, and print the diagnostics every other
+//! example ends up computing one way or another.
+
+use lgr_2d_cfd::scene::Scene;
+
+fn main() {
+    let mut scene = Scene::wind_tunnel_with_radiator(200, 80);
+
+    for _ in 0..100 {
+        scene.simulate();
+    }
+
+    let divergence = scene.fluid.max_divergence();
+    println!("step_count = {}", scene.step_count());
+    println!("sim_time = {:.4} s", scene.sim_time);
+    println!("max_divergence = {divergence:.6}");
+    // This scene keeps forcing new divergence in every step (inflow at the
+    // boundary, drag through the radiator), so it never settles to zero the
+    // way a source-free tank case does — just confirm the solve is still
+    // producing a sane, finite field rather than diverging outright.
+    assert!(divergence.is_finite() && divergence < 10.0, "solver appears to be diverging: {divergence}");
+}
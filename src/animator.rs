@@ -0,0 +1,382 @@
+//! Records a sequence of PNG frames (pressure or smoke) for later assembly
+//! into a GIF/MP4, tracking its own frame counter independently of the
+//! solver's step counter.
+
+use std::fs::File;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::fluid::Fluid;
+use crate::output::{ArtifactKind, OutputManager};
+use crate::radiator::Radiator;
+use crate::visualizer::{ColorScale, Visualizer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum AnimatorField {
+    Pressure,
+    Smoke,
+}
+
+pub struct Animator {
+    field: AnimatorField,
+    frame_index: u64,
+    /// How each frame's colorbar (and color mapping) picks its range — see
+    /// [`ColorScale`]. `Auto` by default.
+    scale: ColorScale,
+    /// Widest `(min, max)` seen so far, accumulated frame over frame while
+    /// `scale` is `RunningMax`. Unused (and left `None`) in `Auto`/`Fixed`
+    /// mode.
+    running_range: Option<(f64, f64)>,
+    /// Every frame PNG written so far, in capture order, so `create_gif` can
+    /// read them back and re-encode them without the caller having to
+    /// remember the naming scheme.
+    frame_paths: Vec<PathBuf>,
+    /// Forwarded to [`Visualizer::save_pressure_field`]/[`Visualizer::save_smoke_field`]
+    /// for every captured frame — see their `draw_text` parameter. `true` by
+    /// default.
+    draw_text: bool,
+}
+
+impl Animator {
+    pub fn new(field: AnimatorField) -> Self {
+        Animator {
+            field,
+            frame_index: 0,
+            scale: ColorScale::Auto,
+            running_range: None,
+            frame_paths: Vec::new(),
+            draw_text: true,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn set_scale(&mut self, scale: ColorScale) {
+        self.scale = scale;
+        self.running_range = None;
+    }
+
+    pub fn set_draw_text(&mut self, draw_text: bool) {
+        self.draw_text = draw_text;
+    }
+
+    /// This frame's own `(min, max)` for whichever field this animator
+    /// records, used to grow `running_range` in `RunningMax` mode. Pressure
+    /// excludes solid cells the same way [`Visualizer::save_pressure_field`]
+    /// does — otherwise a single obstacle cell's leftover solver pressure
+    /// would permanently poison the running range for every later frame.
+    fn frame_range(&self, fluid: &Fluid) -> (f64, f64) {
+        match self.field {
+            AnimatorField::Pressure => {
+                let range = crate::render::finite_range_masked(&fluid.p, &fluid.s);
+                (range.min, range.max)
+            }
+            AnimatorField::Smoke => fluid
+                .m
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v))),
+        }
+    }
+
+    /// Render and record the next frame for solver `step` / `sim_time`,
+    /// reporting it through `output` so it lands in the manifest.
+    pub fn capture(
+        &mut self,
+        fluid: &Fluid,
+        radiators: &[Radiator],
+        step: u64,
+        sim_time: f64,
+        output: &mut OutputManager,
+    ) -> std::io::Result<()> {
+        let file_name = format!("frame_{:05}.png", self.frame_index);
+        let path = output.path_for(&file_name);
+        let title = format!("T={sim_time:.2}S");
+
+        let frame_scale = if self.scale == ColorScale::RunningMax {
+            let (lo, hi) = self.frame_range(fluid);
+            let (lo, hi) = match self.running_range {
+                Some((rlo, rhi)) => (rlo.min(lo), rhi.max(hi)),
+                None => (lo, hi),
+            };
+            self.running_range = Some((lo, hi));
+            ColorScale::Fixed(lo, hi)
+        } else {
+            self.scale
+        };
+
+        match self.field {
+            AnimatorField::Pressure => Visualizer::save_pressure_field(
+                fluid,
+                radiators,
+                Some(&title),
+                frame_scale,
+                path.to_str().unwrap(),
+                self.draw_text,
+            ),
+            AnimatorField::Smoke => Visualizer::save_smoke_field(
+                fluid,
+                radiators,
+                Some(&title),
+                frame_scale,
+                path.to_str().unwrap(),
+                self.draw_text,
+            ),
+        }
+        .map_err(std::io::Error::other)?;
+
+        output.record(
+            ArtifactKind::AnimatorFrame,
+            step,
+            sim_time,
+            &path,
+            Some(self.frame_index),
+        );
+
+        self.frame_paths.push(path);
+        self.frame_index += 1;
+        Ok(())
+    }
+
+    /// Assemble every frame captured so far into a single looping
+    /// `animation.gif`, entirely in-process via the `gif` crate — no
+    /// dependency on an external tool like ImageMagick being installed.
+    ///
+    /// Each frame PNG is read back off disk and re-quantized to an indexed
+    /// palette (`quantize_speed` is the `gif` crate's 1-30 quality/speed
+    /// knob for that step: 1 is slowest and highest quality, 30 fastest).
+    /// `fps` sets each frame's display duration; frames are shown at the
+    /// same fixed rate `capture` was called at, not the underlying `sim_time`
+    /// spacing.
+    pub fn create_gif(&self, output: &mut OutputManager, fps: u32, quantize_speed: i32) -> std::io::Result<PathBuf> {
+        encode_gif(&self.frame_paths, output, fps, quantize_speed)
+    }
+
+    /// Assemble every frame captured so far into `animation.<format>` by
+    /// piping the already-saved PNG frames to `ffmpeg` over stdin
+    /// (`-f image2pipe`), rather than re-encoding through the `gif` crate's
+    /// palette-quantized path — a GIF of a 2x2 combined frame at a decent
+    /// resolution is tens of megabytes, an MP4/WebM of the same footage is a
+    /// fraction of that.
+    ///
+    /// `format` must be `"mp4"` or `"webm"`. If `ffmpeg` isn't on `PATH`,
+    /// returns an [`std::io::ErrorKind::NotFound`] error rather than any
+    /// other kind, specifically so a caller can downgrade a missing-ffmpeg
+    /// failure to a warning without misclassifying an actual encode failure
+    /// the same way. When `keep_frames` is `false`, the source PNG frames
+    /// are deleted after a successful encode.
+    pub fn create_video(
+        &self,
+        output: &mut OutputManager,
+        fps: u32,
+        format: &str,
+        keep_frames: bool,
+    ) -> std::io::Result<PathBuf> {
+        encode_video(&self.frame_paths, output, fps, format, keep_frames)
+    }
+}
+
+/// Assemble `frame_paths` (in order) into a single looping `animation.gif`,
+/// entirely in-process via the `gif` crate — no dependency on an external
+/// tool like ImageMagick being installed. The frame-capturing logic lives in
+/// [`Animator::capture`]; this only knows about already-rendered PNGs, which
+/// is what lets [`crate::field_history`]'s history-driven render reuse it
+/// without an `Animator`/`Fluid` in the loop at all.
+///
+/// Each frame PNG is read back off disk and re-quantized to an indexed
+/// palette (`quantize_speed` is the `gif` crate's 1-30 quality/speed knob
+/// for that step: 1 is slowest and highest quality, 30 fastest). `fps` sets
+/// each frame's display duration.
+pub fn encode_gif(
+    frame_paths: &[PathBuf],
+    output: &mut OutputManager,
+    fps: u32,
+    quantize_speed: i32,
+) -> std::io::Result<PathBuf> {
+    let (first_path, rest) =
+        frame_paths.split_first().ok_or_else(|| std::io::Error::other("no frames captured, nothing to encode"))?;
+
+    let first = image::open(first_path).map_err(std::io::Error::other)?.to_rgba8();
+    let (width, height) = first.dimensions();
+    let delay_cs = (100 / fps.max(1)).max(1) as u16;
+
+    let path = output.path_for("animation.gif");
+    let file = File::create(&path)?;
+    let mut encoder = gif::Encoder::new(file, width as u16, height as u16, &[]).map_err(std::io::Error::other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(std::io::Error::other)?;
+
+    let mut write_frame = |mut pixels: Vec<u8>| -> std::io::Result<()> {
+        let mut frame = gif::Frame::from_rgba_speed(width as u16, height as u16, &mut pixels, quantize_speed);
+        frame.delay = delay_cs;
+        encoder.write_frame(&frame).map_err(std::io::Error::other)
+    };
+    write_frame(first.into_raw())?;
+    for frame_path in rest {
+        let img = image::open(frame_path).map_err(std::io::Error::other)?.to_rgba8();
+        if img.dimensions() != (width, height) {
+            return Err(std::io::Error::other(format!(
+                "frame {} is {}x{}, expected {width}x{height}",
+                frame_path.display(),
+                img.width(),
+                img.height()
+            )));
+        }
+        write_frame(img.into_raw())?;
+    }
+
+    output.record(ArtifactKind::AnimatorGif, 0, 0.0, &path, None);
+    Ok(path)
+}
+
+/// Assemble `frame_paths` (in order) into `animation.<format>` by piping the
+/// already-saved PNG frames to `ffmpeg` over stdin (`-f image2pipe`), rather
+/// than re-encoding through the `gif` crate's palette-quantized path — a GIF
+/// of a 2x2 combined frame at a decent resolution is tens of megabytes, an
+/// MP4/WebM of the same footage is a fraction of that.
+///
+/// `format` must be `"mp4"` or `"webm"`. If `ffmpeg` isn't on `PATH`, returns
+/// an [`std::io::ErrorKind::NotFound`] error rather than any other kind,
+/// specifically so a caller can downgrade a missing-ffmpeg failure to a
+/// warning without misclassifying an actual encode failure the same way.
+/// When `keep_frames` is `false`, the source PNG frames are deleted after a
+/// successful encode.
+pub fn encode_video(
+    frame_paths: &[PathBuf],
+    output: &mut OutputManager,
+    fps: u32,
+    format: &str,
+    keep_frames: bool,
+) -> std::io::Result<PathBuf> {
+    if frame_paths.is_empty() {
+        return Err(std::io::Error::other("no frames captured, nothing to encode"));
+    }
+    let codec_args: &[&str] = match format {
+        "mp4" => &["-pix_fmt", "yuv420p"],
+        "webm" => &["-c:v", "libvpx", "-b:v", "2M"],
+        other => {
+            return Err(std::io::Error::other(format!("unsupported video format {other:?}, expected \"mp4\" or \"webm\"")))
+        }
+    };
+
+    Command::new("ffmpeg").arg("-version").stdout(Stdio::null()).stderr(Stdio::null()).status().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            e
+        } else {
+            std::io::Error::new(std::io::ErrorKind::NotFound, e)
+        }
+    })?;
+
+    let path = output.path_for(&format!("animation.{format}"));
+    let mut child = Command::new("ffmpeg")
+        .args(["-y", "-f", "image2pipe", "-vcodec", "png", "-r", &fps.to_string(), "-i", "-"])
+        .args(codec_args)
+        .arg(&path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin was requested as piped");
+    for frame_path in frame_paths {
+        let bytes = std::fs::read(frame_path)?;
+        stdin.write_all(&bytes)?;
+    }
+    drop(stdin);
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(std::io::Error::other(format!("ffmpeg exited with {status}")));
+    }
+
+    if !keep_frames {
+        for frame_path in frame_paths {
+            let _ = std::fs::remove_file(frame_path);
+        }
+    }
+
+    output.record(ArtifactKind::AnimatorVideo, 0, 0.0, &path, None);
+    Ok(path)
+}
+
+/// Horizontally concatenate `panels` (equal height; narrower panels are
+/// top-aligned) into one image at `out_path` — the panel compositing
+/// `--layout pressure,smoke` needs to turn N single-field renders into one
+/// frame per snapshot.
+pub fn hstack_images(panels: &[PathBuf], out_path: &std::path::Path) -> std::io::Result<()> {
+    let images: Vec<_> =
+        panels.iter().map(|p| image::open(p).map_err(std::io::Error::other).map(|img| img.to_rgba8())).collect::<std::io::Result<_>>()?;
+    let total_width: u32 = images.iter().map(|img| img.width()).sum();
+    let max_height = images.iter().map(|img| img.height()).max().unwrap_or(0);
+
+    let mut canvas = image::RgbaImage::new(total_width, max_height);
+    let mut x_offset = 0u32;
+    for img in &images {
+        image::imageops::overlay(&mut canvas, img, x_offset as i64, 0);
+        x_offset += img.width();
+    }
+    canvas.save(out_path).map_err(std::io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_output(name: &str) -> OutputManager {
+        let dir = std::env::temp_dir().join(format!("lgr_2d_cfd_animator_test_{name}"));
+        OutputManager::new(dir).unwrap()
+    }
+
+    #[test]
+    fn running_max_scale_only_ever_grows() {
+        let mut animator = Animator::new(AnimatorField::Pressure);
+        animator.set_scale(ColorScale::RunningMax);
+        let mut output = temp_output("running_max");
+
+        let mut fluid_a = Fluid::new(1000.0, 10, 10, 0.1);
+        fluid_a.p[0] = 5.0;
+        animator.capture(&fluid_a, &[], 0, 0.0, &mut output).unwrap();
+        assert_eq!(animator.running_range, Some((0.0, 5.0)));
+
+        let mut fluid_b = Fluid::new(1000.0, 10, 10, 0.1);
+        fluid_b.p[0] = -2.0;
+        animator.capture(&fluid_b, &[], 1, 0.1, &mut output).unwrap();
+        assert_eq!(animator.running_range, Some((-2.0, 5.0)));
+
+        // A frame with a narrower range than what's already been seen must
+        // not shrink the tracked range back down.
+        let fluid_c = Fluid::new(1000.0, 10, 10, 0.1);
+        animator.capture(&fluid_c, &[], 2, 0.2, &mut output).unwrap();
+        assert_eq!(animator.running_range, Some((-2.0, 5.0)));
+    }
+
+    #[test]
+    fn create_gif_encodes_every_captured_frame() {
+        let mut animator = Animator::new(AnimatorField::Smoke);
+        let mut output = temp_output("create_gif");
+
+        for step in 0..3u64 {
+            let mut fluid = Fluid::new(1000.0, 10, 10, 0.1);
+            fluid.m[0] = step as f64 * 0.3;
+            animator.capture(&fluid, &[], step, step as f64 * 0.1, &mut output).unwrap();
+        }
+
+        let gif_path = animator.create_gif(&mut output, 10, 10).unwrap();
+
+        let bytes = std::fs::read(&gif_path).unwrap();
+        assert_eq!(&bytes[0..6], b"GIF89a", "GIF file must start with the GIF89a magic header");
+
+        let mut decoder = gif::DecodeOptions::new().read_info(std::fs::File::open(&gif_path).unwrap()).unwrap();
+        let mut frame_count = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            frame_count += 1;
+        }
+        assert_eq!(frame_count, 3);
+    }
+
+    #[test]
+    fn create_gif_with_no_frames_is_an_error() {
+        let animator = Animator::new(AnimatorField::Smoke);
+        let mut output = temp_output("create_gif_empty");
+        assert!(animator.create_gif(&mut output, 10, 10).is_err());
+    }
+}
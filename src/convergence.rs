@@ -0,0 +1,544 @@
+//! Per-step convergence bookkeeping, replacing the old ad-hoc "print max
+//! divergence every 200 steps" in `main.rs` with a queryable, persistable
+//! history.
+//!
+//! There is no radiator-sweep binary in this tree yet for
+//! [`ConvergenceMonitor::is_steady`] to short-circuit — it's provided here
+//! as reusable infrastructure any per-scene run loop (the CLI's `run_scene`,
+//! or a future sweep) can drive.
+//!
+//! [`ConvergenceMonitor::new`] keeps every [`ResidualRecord`] in memory for
+//! the life of the run, which is fine for the thousands-of-steps runs this
+//! crate normally does but grows without bound on a duty-cycle run of
+//! hundreds of thousands of steps. [`ConvergenceMonitor::with_bounded_memory`]
+//! caps the in-memory `Vec` and streams every record to disk as it's
+//! recorded instead, so `history()` only ever holds the most recent
+//! records but [`ConvergenceMonitor::write_csv`]'s on-disk output stays
+//! complete. There's no TUI/sparkline view, probe ring buffers, POD, or
+//! Strouhal analysis anywhere in this crate to also bound — this only
+//! covers the one real unboundedly-growing per-step history that exists.
+
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+use image::{ImageBuffer, Rgb, RgbImage};
+
+use crate::fluid::Fluid;
+use crate::text;
+
+const CSV_HEADER: &str = "step,max_divergence,mean_divergence,max_velocity_change,pressure_residual\n";
+
+/// One step's convergence snapshot.
+#[derive(Debug, Clone, Copy)]
+pub struct ResidualRecord {
+    pub step: u64,
+    pub max_divergence: f64,
+    pub mean_divergence: f64,
+    /// Largest per-face change in `u`/`v` since the previous recorded step.
+    /// `0.0` for the first record, since there is no previous step to
+    /// compare against.
+    pub max_velocity_change: f64,
+    /// `Fluid::solve_incompressibility`'s own return value for this step:
+    /// the largest per-cell pressure correction still being applied on its
+    /// final sweep.
+    pub pressure_residual: f64,
+}
+
+fn csv_line(r: &ResidualRecord) -> String {
+    format!(
+        "{},{:.8},{:.8},{:.8},{:.8}\n",
+        r.step, r.max_divergence, r.mean_divergence, r.max_velocity_change, r.pressure_residual
+    )
+}
+
+/// The pieces [`ConvergenceMonitor::with_bounded_memory`] needs beyond an
+/// unbounded monitor: how many records to keep in memory and an already-open
+/// writer streaming every record to `csv_path` as it's recorded.
+struct BoundedMemory {
+    cap: usize,
+    csv_path: PathBuf,
+    writer: std::io::BufWriter<std::fs::File>,
+}
+
+/// Accumulates a [`ResidualRecord`] per step and answers whether the run
+/// looks converged.
+pub struct ConvergenceMonitor {
+    history: Vec<ResidualRecord>,
+    last_u: Option<Vec<f64>>,
+    last_v: Option<Vec<f64>>,
+    bounded: Option<BoundedMemory>,
+}
+
+impl ConvergenceMonitor {
+    pub fn new() -> Self {
+        ConvergenceMonitor {
+            history: Vec::new(),
+            last_u: None,
+            last_v: None,
+            bounded: None,
+        }
+    }
+
+    /// Like [`Self::new`], but caps the in-memory history at `cap` records
+    /// (the oldest are dropped once it's full) and streams every record to
+    /// `csv_path` as it's recorded, so the on-disk history is always
+    /// complete even though `history()` only ever returns the tail.
+    /// [`Self::write_csv`] and [`Self::write_divergence_plot`] both read
+    /// back from `csv_path` in this mode instead of `history()`, so they
+    /// keep covering the whole run. [`Self::is_steady`] only ever looks at
+    /// the most recent `window` records, which the in-memory tail always
+    /// has, so it needs no such fallback.
+    pub fn with_bounded_memory(cap: usize, csv_path: impl AsRef<Path>) -> std::io::Result<Self> {
+        assert!(cap > 0, "bounded-memory cap must be at least 1");
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(csv_path.as_ref())?);
+        writer.write_all(CSV_HEADER.as_bytes())?;
+        Ok(ConvergenceMonitor {
+            history: Vec::new(),
+            last_u: None,
+            last_v: None,
+            bounded: Some(BoundedMemory { cap, csv_path: csv_path.as_ref().to_path_buf(), writer }),
+        })
+    }
+
+    /// Record `fluid`'s state at `step`, given the pressure residual
+    /// `solve_incompressibility` returned for that step. Returns the
+    /// record that was just pushed, so a caller can log/print it inline
+    /// without a separate `history().last()` lookup. Only fails in bounded
+    /// mode, if the incremental write to the streamed CSV fails.
+    pub fn record(&mut self, fluid: &Fluid, step: u64, pressure_residual: f64) -> std::io::Result<ResidualRecord> {
+        let (max_divergence, mean_divergence) = fluid.divergence_stats();
+
+        let max_velocity_change = match (&self.last_u, &self.last_v) {
+            (Some(last_u), Some(last_v)) => {
+                let du = max_abs_diff(&fluid.u, last_u);
+                let dv = max_abs_diff(&fluid.v, last_v);
+                du.max(dv)
+            }
+            _ => 0.0,
+        };
+        self.last_u = Some(fluid.u.clone());
+        self.last_v = Some(fluid.v.clone());
+
+        let record = ResidualRecord {
+            step,
+            max_divergence,
+            mean_divergence,
+            max_velocity_change,
+            pressure_residual,
+        };
+
+        if let Some(bounded) = &mut self.bounded {
+            bounded.writer.write_all(csv_line(&record).as_bytes())?;
+            // Flushed every record (not just buffered until drop) so the
+            // on-disk history is always complete for anything reading it
+            // mid-run — `write_divergence_plot` in bounded mode, an
+            // external tail -f, or a test asserting completeness.
+            bounded.writer.flush()?;
+            self.history.push(record);
+            if self.history.len() > bounded.cap {
+                self.history.remove(0);
+            }
+        } else {
+            self.history.push(record);
+        }
+        Ok(record)
+    }
+
+    /// The most recent records still held in memory: every record if this
+    /// monitor is unbounded, or only the tail (at most the configured cap)
+    /// if it's bounded — see [`Self::with_bounded_memory`].
+    pub fn history(&self) -> &[ResidualRecord] {
+        &self.history
+    }
+
+    /// True once `max_velocity_change` has stayed at or below `tolerance`
+    /// for the last `window` consecutive recorded steps. `false` if fewer
+    /// than `window` steps have been recorded yet, so a caller can always
+    /// unconditionally check this after every `record` call without a
+    /// separate step-count guard.
+    pub fn is_steady(&self, tolerance: f64, window: usize) -> bool {
+        if window == 0 || self.history.len() < window {
+            return false;
+        }
+        self.history[self.history.len() - window..]
+            .iter()
+            .all(|r| r.max_velocity_change <= tolerance)
+    }
+
+    /// Writes the full per-step history to `path`. In bounded mode this is
+    /// the on-disk stream `with_bounded_memory` has been writing all along
+    /// — `self.history()` alone would only cover the tail — so it's copied
+    /// into place rather than re-derived from memory.
+    pub fn write_csv(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        if let Some(bounded) = &self.bounded {
+            if path.as_ref() != bounded.csv_path.as_path() {
+                std::fs::copy(&bounded.csv_path, path)?;
+            }
+            return Ok(());
+        }
+        let mut csv = String::from(CSV_HEADER);
+        for r in &self.history {
+            csv.push_str(&csv_line(r));
+        }
+        std::fs::write(path, csv)
+    }
+
+    /// Log-scale plot of `max_divergence`/`mean_divergence` vs step, the
+    /// primary evidence artifact for "mass conservation should be ~0" —
+    /// `write_csv` gives the raw numbers, this gives the trend at a glance.
+    /// `annotations` marks named events (e.g. an inflow ramp ending) at
+    /// their step with a vertical line and label; pass an empty slice if the
+    /// caller has no events to mark. Does nothing (writes an empty-history
+    /// placeholder image) rather than erroring if `history` is empty. In
+    /// bounded mode, plotted from the on-disk stream rather than
+    /// `history()`'s tail, so the plot still spans the whole run.
+    pub fn write_divergence_plot(
+        &self,
+        path: impl AsRef<Path>,
+        annotations: &[EventAnnotation],
+    ) -> std::io::Result<()> {
+        match &self.bounded {
+            Some(bounded) => {
+                let full_history = read_csv(&bounded.csv_path)?;
+                render_divergence_plot(&full_history, annotations).save(path).map_err(std::io::Error::other)
+            }
+            None => render_divergence_plot(&self.history, annotations).save(path).map_err(std::io::Error::other),
+        }
+    }
+}
+
+/// The inverse of [`csv_line`]/[`ConvergenceMonitor::write_csv`]: reparses a
+/// convergence CSV back into records, so [`ConvergenceMonitor::with_bounded_memory`]'s
+/// on-disk stream can still feed [`write_divergence_plot`] the full run even
+/// though the in-memory `history` only holds the recent tail.
+fn read_csv(path: impl AsRef<Path>) -> std::io::Result<Vec<ResidualRecord>> {
+    let csv = std::fs::read_to_string(path)?;
+    csv.lines()
+        .skip(1)
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            let parse_err = || std::io::Error::other(format!("malformed convergence CSV line: {line:?}"));
+            if fields.len() != 5 {
+                return Err(parse_err());
+            }
+            Ok(ResidualRecord {
+                step: fields[0].parse().map_err(|_| parse_err())?,
+                max_divergence: fields[1].parse().map_err(|_| parse_err())?,
+                mean_divergence: fields[2].parse().map_err(|_| parse_err())?,
+                max_velocity_change: fields[3].parse().map_err(|_| parse_err())?,
+                pressure_residual: fields[4].parse().map_err(|_| parse_err())?,
+            })
+        })
+        .collect()
+}
+
+/// A named event to mark on a [`ConvergenceMonitor::write_divergence_plot`]
+/// with a vertical line at its step — an inflow ramp ending, a radiator
+/// re-applying its porosity, a scheduled change firing. Nothing in this tree
+/// yet produces these on its own (there's no ramp/schedule/event-log module),
+/// so every caller today passes an empty slice; this exists as the extension
+/// point such infrastructure would plug into once it's built.
+#[derive(Debug, Clone)]
+pub struct EventAnnotation {
+    pub step: u64,
+    pub label: String,
+}
+
+const PLOT_MARGIN_LEFT: u32 = 34;
+const PLOT_MARGIN_BOTTOM: u32 = 12;
+const PLOT_MARGIN_TOP: u32 = 12;
+const PLOT_WIDTH: u32 = 640;
+const PLOT_HEIGHT: u32 = 240;
+const DIVERGENCE_FLOOR: f64 = 1e-12;
+
+/// Maps `step` linearly onto `[0, plot_width)` given the run's `[min_step,
+/// max_step]` range. A single-step history (`min_step == max_step`) maps
+/// everywhere to the left edge rather than dividing by zero.
+fn step_to_x(step: u64, min_step: u64, max_step: u64, plot_width: u32) -> u32 {
+    if max_step <= min_step {
+        return 0;
+    }
+    let frac = (step - min_step) as f64 / (max_step - min_step) as f64;
+    (frac * (plot_width - 1) as f64).round() as u32
+}
+
+/// Maps a divergence value onto a plot row on a log scale, `log_max` at the
+/// top (row 0) and `log_min` at the bottom (row `plot_height - 1`).
+fn divergence_to_y(value: f64, log_min: f64, log_max: f64, plot_height: u32) -> u32 {
+    let log_value = value.max(DIVERGENCE_FLOOR).log10();
+    if log_max <= log_min {
+        return plot_height - 1;
+    }
+    let frac = ((log_value - log_min) / (log_max - log_min)).clamp(0.0, 1.0);
+    (((1.0 - frac) * (plot_height - 1) as f64).round()) as u32
+}
+
+fn render_divergence_plot(history: &[ResidualRecord], annotations: &[EventAnnotation]) -> RgbImage {
+    let width = PLOT_MARGIN_LEFT + PLOT_WIDTH;
+    let height = PLOT_MARGIN_TOP + PLOT_HEIGHT + PLOT_MARGIN_BOTTOM;
+    let mut img: RgbImage = ImageBuffer::from_pixel(width, height, Rgb([0, 0, 0]));
+
+    if history.is_empty() {
+        text::draw_text(&mut img, PLOT_MARGIN_LEFT as i64 + 2, PLOT_MARGIN_TOP as i64, "NO DATA", Rgb([255, 255, 255]));
+        return img;
+    }
+
+    let min_step = history.first().unwrap().step;
+    let max_step = history.last().unwrap().step;
+    let max_val = history
+        .iter()
+        .flat_map(|r| [r.max_divergence, r.mean_divergence])
+        .fold(DIVERGENCE_FLOOR, f64::max);
+    let log_max = max_val.max(DIVERGENCE_FLOOR).log10().ceil();
+    let log_min = log_max - 6.0;
+
+    for annotation in annotations {
+        let x = PLOT_MARGIN_LEFT + step_to_x(annotation.step, min_step, max_step, PLOT_WIDTH);
+        for y in PLOT_MARGIN_TOP..PLOT_MARGIN_TOP + PLOT_HEIGHT {
+            img.put_pixel(x, y, Rgb([80, 80, 80]));
+        }
+        text::draw_text(&mut img, x as i64 + 2, PLOT_MARGIN_TOP as i64, &annotation.label, Rgb([200, 200, 0]));
+    }
+
+    draw_series(
+        &mut img,
+        history,
+        min_step,
+        max_step,
+        log_min,
+        log_max,
+        Rgb([255, 80, 80]),
+        |r| r.max_divergence,
+    );
+    draw_series(
+        &mut img,
+        history,
+        min_step,
+        max_step,
+        log_min,
+        log_max,
+        Rgb([80, 160, 255]),
+        |r| r.mean_divergence,
+    );
+
+    text::draw_text(&mut img, 0, 0, &format!("{log_max:.0}"), Rgb([255, 255, 255]));
+    text::draw_text(
+        &mut img,
+        0,
+        (PLOT_MARGIN_TOP + PLOT_HEIGHT - text::GLYPH_HEIGHT_PX) as i64,
+        &format!("{log_min:.0}"),
+        Rgb([255, 255, 255]),
+    );
+
+    img
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_series(
+    img: &mut RgbImage,
+    history: &[ResidualRecord],
+    min_step: u64,
+    max_step: u64,
+    log_min: f64,
+    log_max: f64,
+    color: Rgb<u8>,
+    value_of: impl Fn(&ResidualRecord) -> f64,
+) {
+    for record in history {
+        let x = PLOT_MARGIN_LEFT + step_to_x(record.step, min_step, max_step, PLOT_WIDTH);
+        let y = PLOT_MARGIN_TOP + divergence_to_y(value_of(record), log_min, log_max, PLOT_HEIGHT);
+        img.put_pixel(x, y, color);
+    }
+}
+
+impl Default for ConvergenceMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn max_abs_diff(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_record_has_no_velocity_change_to_compare_against() {
+        let fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let mut monitor = ConvergenceMonitor::new();
+        let record = monitor.record(&fluid, 0, 0.0).unwrap();
+        assert_eq!(record.max_velocity_change, 0.0);
+    }
+
+    #[test]
+    fn is_steady_requires_a_full_window_of_small_changes() {
+        let mut fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let mut monitor = ConvergenceMonitor::new();
+
+        monitor.record(&fluid, 0, 0.0).unwrap();
+        assert!(!monitor.is_steady(1e-6, 3), "not enough history yet");
+
+        for step in 1..3u64 {
+            monitor.record(&fluid, step, 0.0).unwrap();
+        }
+        assert!(
+            monitor.is_steady(1e-6, 3),
+            "an unchanging fluid field should read as steady"
+        );
+
+        let idx = fluid.idx(5, 5);
+        fluid.u[idx] = 5.0;
+        monitor.record(&fluid, 3, 0.0).unwrap();
+        assert!(
+            !monitor.is_steady(1e-6, 3),
+            "a large jump should break steadiness until it ages out of the window"
+        );
+    }
+
+    #[test]
+    fn is_steady_is_false_with_zero_window() {
+        let fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let mut monitor = ConvergenceMonitor::new();
+        monitor.record(&fluid, 0, 0.0).unwrap();
+        assert!(!monitor.is_steady(1e-6, 0));
+    }
+
+    #[test]
+    fn write_csv_reproduces_every_recorded_field_for_every_step() {
+        let mut fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let mut monitor = ConvergenceMonitor::new();
+        monitor.record(&fluid, 0, 0.01).unwrap();
+        let idx = fluid.idx(5, 5);
+        fluid.u[idx] = 1.0;
+        monitor.record(&fluid, 1, 0.02).unwrap();
+
+        let dir = std::env::temp_dir().join("lgr_2d_cfd_convergence_csv_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("convergence.csv");
+        monitor.write_csv(&path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "step,max_divergence,mean_divergence,max_velocity_change,pressure_residual");
+        assert_eq!(lines.len(), 3, "header plus one line per recorded step");
+        for (line, record) in lines[1..].iter().zip(monitor.history()) {
+            let fields: Vec<&str> = line.split(',').collect();
+            assert_eq!(fields[0].parse::<u64>().unwrap(), record.step);
+            assert!((fields[1].parse::<f64>().unwrap() - record.max_divergence).abs() < 1e-6);
+            assert!((fields[2].parse::<f64>().unwrap() - record.mean_divergence).abs() < 1e-6);
+            assert!((fields[3].parse::<f64>().unwrap() - record.max_velocity_change).abs() < 1e-6);
+            assert!((fields[4].parse::<f64>().unwrap() - record.pressure_residual).abs() < 1e-6);
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn step_to_x_places_an_annotation_at_the_right_fraction_of_the_plot_width() {
+        assert_eq!(step_to_x(0, 0, 100, 640), 0);
+        assert_eq!(step_to_x(100, 0, 100, 640), 639);
+        assert_eq!(step_to_x(50, 0, 100, 640), 320);
+    }
+
+    #[test]
+    fn step_to_x_never_divides_by_zero_when_every_record_shares_one_step() {
+        assert_eq!(step_to_x(7, 7, 7, 640), 0);
+    }
+
+    #[test]
+    fn write_divergence_plot_draws_the_annotation_line_at_its_mapped_pixel_column() {
+        let mut fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let mut monitor = ConvergenceMonitor::new();
+        for step in 0..=100u64 {
+            monitor.record(&fluid, step, 0.0).unwrap();
+            let idx = fluid.idx(5, 5);
+            fluid.u[idx] += 0.001;
+        }
+
+        let dir = std::env::temp_dir().join("lgr_2d_cfd_divergence_plot_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("divergence_history.png");
+        let annotations = vec![EventAnnotation { step: 50, label: "RAMP".to_string() }];
+        monitor.write_divergence_plot(&path, &annotations).unwrap();
+
+        let img = image::open(&path).unwrap().to_rgb8();
+        let expected_x = PLOT_MARGIN_LEFT + step_to_x(50, 0, 100, PLOT_WIDTH);
+        let has_annotation_line = (PLOT_MARGIN_TOP..PLOT_MARGIN_TOP + PLOT_HEIGHT)
+            .any(|y| img.get_pixel(expected_x, y).0 == [80, 80, 80]);
+        assert!(has_annotation_line, "expected the annotation's vertical line at the step-50 pixel column");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn bounded_memory_caps_the_in_memory_history_while_the_csv_stays_complete() {
+        let mut fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let dir = std::env::temp_dir().join("lgr_2d_cfd_bounded_convergence_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("convergence.csv");
+
+        let cap = 50;
+        let total_steps = 5_000u64;
+        let mut monitor = ConvergenceMonitor::with_bounded_memory(cap, &path).unwrap();
+        for step in 0..total_steps {
+            monitor.record(&fluid, step, 0.0).unwrap();
+            let idx = fluid.idx(5, 5);
+            fluid.u[idx] += 1e-4;
+            assert!(
+                monitor.history().len() <= cap,
+                "in-memory history must never exceed the configured cap, even mid-run"
+            );
+        }
+        assert_eq!(monitor.history().len(), cap, "the tail should be exactly full at the end of a long run");
+
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines.len() as u64,
+            total_steps + 1,
+            "the on-disk record must have a header plus every recorded step, not just the in-memory tail"
+        );
+        let last_line = lines.last().unwrap();
+        let last_step: u64 = last_line.split(',').next().unwrap().parse().unwrap();
+        assert_eq!(last_step, total_steps - 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn bounded_memorys_write_csv_and_plot_cover_the_whole_run_not_just_the_tail() {
+        let mut fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let dir = std::env::temp_dir().join("lgr_2d_cfd_bounded_convergence_consumers_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let stream_path = dir.join("convergence.csv");
+
+        let cap = 10;
+        let total_steps = 200u64;
+        let mut monitor = ConvergenceMonitor::with_bounded_memory(cap, &stream_path).unwrap();
+        for step in 0..total_steps {
+            monitor.record(&fluid, step, 0.0).unwrap();
+            let idx = fluid.idx(5, 5);
+            fluid.u[idx] += 1e-3;
+        }
+
+        let export_path = dir.join("exported.csv");
+        monitor.write_csv(&export_path).unwrap();
+        let exported = std::fs::read_to_string(&export_path).unwrap();
+        assert_eq!(exported.lines().count() as u64, total_steps + 1, "write_csv must export the full run, not just the tail");
+
+        let plot_path = dir.join("divergence_history.png");
+        monitor.write_divergence_plot(&plot_path, &[]).unwrap();
+        let img = image::open(&plot_path).unwrap().to_rgb8();
+        // step_to_x maps the very first recorded step to column 0 of the
+        // plot area; if the plot were built from the in-memory tail alone
+        // (starting near step `total_steps - cap`) this pixel would be blank
+        // instead of drawn in one of the two series colors.
+        let first_col_has_a_series_pixel = (PLOT_MARGIN_TOP..PLOT_MARGIN_TOP + PLOT_HEIGHT)
+            .any(|y| matches!(img.get_pixel(PLOT_MARGIN_LEFT, y).0, [255, 80, 80] | [80, 160, 255]));
+        assert!(first_col_has_a_series_pixel, "the plot should start from step 0, not the in-memory tail");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
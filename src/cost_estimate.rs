@@ -0,0 +1,164 @@
+//! Project a run's wall time and disk usage from a short calibration burst
+//! at the real configuration, rather than guessing or waiting out the full
+//! step count to find out.
+//!
+//! There is no `sweep` subcommand or batch runner wired into the CLI for
+//! this to plug into — `parallel_runs::run_batch` is only ever called from
+//! `examples/parameter_sweep.rs`, and nothing drives an actual 50-case
+//! batch. `run --estimate` projects a single case's cost (via
+//! [`estimate`]) and multiplies it by an explicit `--cases` count, rather
+//! than actually launching a batch of `Scene`s.
+
+use crate::timing::StepTimer;
+use std::time::Duration;
+
+/// What a calibration burst measured: `calibration_steps` steps run for
+/// real, at the requested resolution/fields/animation settings, with
+/// `timer` tracking the solver/IO split and `bytes_written`/`files_written`
+/// counting whatever artifacts that burst actually wrote to disk.
+#[derive(Debug, Clone, Copy)]
+pub struct Calibration {
+    pub calibration_steps: u64,
+    pub timer: StepTimer,
+    pub bytes_written: u64,
+    pub files_written: u64,
+}
+
+/// A calibration's measurements linearly extrapolated to `expected_steps`
+/// steps for one case, then scaled by `cases`.
+#[derive(Debug, Clone, Copy)]
+pub struct CostEstimate {
+    pub expected_steps: u64,
+    pub cases: u64,
+    pub per_case_wall_time: Duration,
+    pub per_case_disk_bytes: u64,
+    pub per_case_file_count: u64,
+    pub total_wall_time: Duration,
+    pub total_disk_bytes: u64,
+    pub total_file_count: u64,
+}
+
+/// Extrapolate `calibration` (measured over `calibration.calibration_steps`
+/// real steps) out to `expected_steps` steps, then to `cases` independent
+/// copies of that run. `calibration_steps` is floored at 1 and `cases` at 1
+/// so a degenerate calibration can't divide by zero or silently project
+/// zero cases.
+pub fn estimate(calibration: &Calibration, expected_steps: u64, cases: u64) -> CostEstimate {
+    let cases = cases.max(1);
+    let calibration_steps = calibration.calibration_steps.max(1);
+    let scale = expected_steps as f64 / calibration_steps as f64;
+
+    let per_case_wall_time = calibration.timer.total().mul_f64(scale);
+    let per_case_disk_bytes = (calibration.bytes_written as f64 * scale).round() as u64;
+    let per_case_file_count = (calibration.files_written as f64 * scale).round() as u64;
+
+    CostEstimate {
+        expected_steps,
+        cases,
+        per_case_wall_time,
+        per_case_disk_bytes,
+        per_case_file_count,
+        total_wall_time: per_case_wall_time.mul_f64(cases as f64),
+        total_disk_bytes: per_case_disk_bytes.saturating_mul(cases),
+        total_file_count: per_case_file_count.saturating_mul(cases),
+    }
+}
+
+impl CostEstimate {
+    /// A plain-text table for `run --estimate` to print: one row for a
+    /// single case, one row for the full `cases` batch.
+    pub fn to_table(&self) -> String {
+        format!(
+            "projected for {} steps/case, {} case(s):\n\
+             {:<10} {:>14} {:>16} {:>12}\n\
+             {:<10} {:>14} {:>16} {:>12}\n\
+             {:<10} {:>14} {:>16} {:>12}",
+            self.expected_steps,
+            self.cases,
+            "",
+            "wall time",
+            "disk usage",
+            "files",
+            "per case",
+            format_duration(self.per_case_wall_time),
+            format_bytes(self.per_case_disk_bytes),
+            self.per_case_file_count,
+            "total",
+            format_duration(self.total_wall_time),
+            format_bytes(self.total_disk_bytes),
+            self.total_file_count,
+        )
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs < 60.0 {
+        format!("{secs:.1}s")
+    } else if secs < 3600.0 {
+        format!("{:.1}m", secs / 60.0)
+    } else {
+        format!("{:.1}h", secs / 3600.0)
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1}{}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calibration(calibration_steps: u64, solver_ms: u64, io_ms: u64, bytes: u64, files: u64) -> Calibration {
+        let mut timer = StepTimer::new();
+        timer.record_solver(Duration::from_millis(solver_ms));
+        timer.record_io(Duration::from_millis(io_ms));
+        Calibration {
+            calibration_steps,
+            timer,
+            bytes_written: bytes,
+            files_written: files,
+        }
+    }
+
+    #[test]
+    fn scales_wall_time_disk_and_files_linearly_with_expected_steps() {
+        let calibration = calibration(50, 700, 300, 10_000, 5);
+        let estimate = estimate(&calibration, 5_000, 1);
+        assert!((estimate.per_case_wall_time.as_secs_f64() - 100.0).abs() < 1e-9);
+        assert_eq!(estimate.per_case_disk_bytes, 1_000_000);
+        assert_eq!(estimate.per_case_file_count, 500);
+    }
+
+    #[test]
+    fn multiplies_the_per_case_projection_by_cases() {
+        let calibration = calibration(50, 700, 300, 10_000, 5);
+        let estimate = estimate(&calibration, 5_000, 10);
+        assert!((estimate.total_wall_time.as_secs_f64() - 1_000.0).abs() < 1e-6);
+        assert_eq!(estimate.total_disk_bytes, 10_000_000);
+        assert_eq!(estimate.total_file_count, 5_000);
+    }
+
+    #[test]
+    fn cases_is_floored_at_one_so_zero_never_erases_the_projection() {
+        let calibration = calibration(50, 1_000, 0, 1_000, 1);
+        let estimate = estimate(&calibration, 50, 0);
+        assert_eq!(estimate.cases, 1);
+        assert_eq!(estimate.total_disk_bytes, estimate.per_case_disk_bytes);
+    }
+
+    #[test]
+    fn zero_calibration_steps_is_floored_at_one_rather_than_dividing_by_zero() {
+        let calibration = calibration(0, 100, 0, 100, 1);
+        let estimate = estimate(&calibration, 100, 1);
+        assert!(estimate.per_case_wall_time.as_secs_f64().is_finite());
+    }
+}
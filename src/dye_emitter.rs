@@ -0,0 +1,191 @@
+//! Dye (`Fluid::m`) sources injected every step, on top of whatever the
+//! inlet boundary already does. `Scene::apply_inflow` alone only ever writes
+//! one column, so a long run eventually advects that single value across the
+//! whole domain and every visualization loses contrast; emitters placed
+//! further downstream (or pulsed on and off) keep streaklines visible for as
+//! long as the run lasts. See [`InflowSmokePattern`] for the companion fix at
+//! the inlet itself.
+
+use serde::{Deserialize, Serialize};
+
+use crate::fluid::{Fluid, SOLID_CELL};
+
+/// Where a [`DyeEmitter`] injects: a circular footprint anywhere in the
+/// domain, or a vertical span of the inlet column (the same column
+/// `Scene::apply_inflow` writes `u` into, so an `InletSpan` emitter's dye
+/// enters exactly where the inflow it's tagging enters).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum EmitterFootprint {
+    Point { x: f64, y: f64, radius: f64 },
+    InletSpan { y_lo: f64, y_hi: f64 },
+}
+
+/// A dye source blended into `Fluid::m` every step. `rate` is how much of
+/// the gap to `dye_value` closes per step (`0.0..=1.0`; `1.0` snaps the
+/// footprint straight to `dye_value` each step, matching the one-shot
+/// `apply_inflow` write it's meant to complement). `pulse_period`, if set,
+/// turns the emitter on for the first half of each period and off for the
+/// second, so a streakline shows discrete puffs instead of one continuous
+/// dye trail.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DyeEmitter {
+    pub footprint: EmitterFootprint,
+    pub dye_value: f64,
+    pub rate: f64,
+    #[serde(default)]
+    pub pulse_period: Option<f64>,
+}
+
+impl DyeEmitter {
+    pub fn new(footprint: EmitterFootprint, dye_value: f64, rate: f64, pulse_period: Option<f64>) -> Self {
+        DyeEmitter { footprint, dye_value, rate, pulse_period }
+    }
+
+    fn is_active(&self, sim_time: f64) -> bool {
+        match self.pulse_period {
+            None => true,
+            Some(period) if period > 0.0 => sim_time.rem_euclid(period) < period * 0.5,
+            Some(_) => true,
+        }
+    }
+
+    /// Blend `dye_value` into every fluid cell in this emitter's footprint by
+    /// `rate`, skipping (and warning about) any cell that's solid — an
+    /// emitter dropped inside an obstacle by a config typo should be a
+    /// visible warning, not a silent no-op that looks like a working scene.
+    pub fn apply(&self, fluid: &mut Fluid, sim_time: f64) {
+        if !self.is_active(sim_time) {
+            return;
+        }
+        let n = fluid.num_y;
+        let h = fluid.h;
+        let mut skipped_solid = false;
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                let x = i as f64 * h;
+                let y = j as f64 * h;
+                let in_footprint = match self.footprint {
+                    EmitterFootprint::Point { x: cx, y: cy, radius } => {
+                        (x - cx).powi(2) + (y - cy).powi(2) <= radius * radius
+                    }
+                    EmitterFootprint::InletSpan { y_lo, y_hi } => i == 1 && y >= y_lo && y <= y_hi,
+                };
+                if !in_footprint {
+                    continue;
+                }
+                let idx = i * n + j;
+                if fluid.s[idx] == SOLID_CELL {
+                    skipped_solid = true;
+                    continue;
+                }
+                fluid.m[idx] += (self.dye_value - fluid.m[idx]) * self.rate;
+            }
+        }
+        if skipped_solid {
+            eprintln!("dye emitter {self:?} has part of its footprint on a solid cell; those cells were skipped");
+        }
+    }
+}
+
+/// How `Scene::apply_inflow` sets `m` on the inlet column every step.
+/// [`InflowSmokePattern::Uniform`] is the original behavior (the whole
+/// column set to one dye value, which is exactly what saturates the whole
+/// domain to a flat color after enough steps); [`InflowSmokePattern::Striped`]
+/// alternates bands of dye and clear fluid up the inlet so the flow carries
+/// visible streaklines instead. Striped is the default since every built-in
+/// wind-tunnel scene wants streaklines by default; a config can still opt
+/// back into `Uniform`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InflowSmokePattern {
+    Uniform,
+    #[default]
+    Striped,
+}
+
+/// Number of alternating dye/clear bands `InflowSmokePattern::Striped` puts
+/// across the inlet, regardless of `num_y` — a fixed band *count* rather
+/// than a fixed band *height* keeps the pattern visually similar across
+/// resolutions instead of turning into hundreds of one-cell stripes on a
+/// fine grid.
+const STRIPE_COUNT: usize = 10;
+
+/// The inlet dye value for row `j` (of `num_y`) under `pattern`.
+pub fn inflow_dye_value(pattern: InflowSmokePattern, j: usize, num_y: usize) -> f64 {
+    match pattern {
+        InflowSmokePattern::Uniform => 0.0,
+        InflowSmokePattern::Striped => {
+            if (j * STRIPE_COUNT / num_y.max(1)).is_multiple_of(2) {
+                0.0
+            } else {
+                1.0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluid::FLUID_CELL;
+
+    fn fluid() -> Fluid {
+        let mut fluid = Fluid::new(1000.0, 20, 20, 0.05);
+        for s in fluid.s.iter_mut() {
+            *s = FLUID_CELL;
+        }
+        fluid
+    }
+
+    #[test]
+    fn a_point_emitter_blends_toward_dye_value_by_rate_each_call() {
+        let mut fluid = fluid();
+        let emitter = DyeEmitter::new(EmitterFootprint::Point { x: 0.5, y: 0.5, radius: 0.06 }, 0.0, 0.5, None);
+        let idx = fluid.idx(10, 10);
+        assert_eq!(fluid.m[idx], 1.0);
+
+        emitter.apply(&mut fluid, 0.0);
+        assert!((fluid.m[idx] - 0.5).abs() < 1e-9, "one 0.5-rate step should halve the gap to 0.0, got {}", fluid.m[idx]);
+
+        emitter.apply(&mut fluid, 0.0);
+        assert!((fluid.m[idx] - 0.25).abs() < 1e-9, "a second step should halve the remaining gap again, got {}", fluid.m[idx]);
+    }
+
+    #[test]
+    fn a_pulsed_emitter_is_only_active_in_the_first_half_of_its_period() {
+        let mut fluid = fluid();
+        let emitter = DyeEmitter::new(EmitterFootprint::Point { x: 0.5, y: 0.5, radius: 0.06 }, 0.0, 1.0, Some(2.0));
+        let idx = fluid.idx(10, 10);
+
+        emitter.apply(&mut fluid, 0.5);
+        assert_eq!(fluid.m[idx], 0.0, "0.5s into a 2s period should be in the active first half");
+
+        fluid.m[idx] = 1.0;
+        emitter.apply(&mut fluid, 1.5);
+        assert_eq!(fluid.m[idx], 1.0, "1.5s into a 2s period should be in the inactive second half");
+    }
+
+    #[test]
+    fn an_emitter_over_a_solid_cell_skips_it_instead_of_writing_through_the_wall() {
+        let mut fluid = fluid();
+        let idx = fluid.idx(10, 10);
+        fluid.s[idx] = SOLID_CELL;
+        fluid.m[idx] = 1.0;
+
+        let emitter = DyeEmitter::new(EmitterFootprint::Point { x: 0.5, y: 0.5, radius: 0.06 }, 0.0, 1.0, None);
+        emitter.apply(&mut fluid, 0.0);
+
+        assert_eq!(fluid.m[idx], 1.0, "a solid cell's m should be left untouched");
+    }
+
+    #[test]
+    fn striped_pattern_alternates_and_uniform_does_not() {
+        let num_y = 20;
+        let uniform_values: Vec<f64> = (1..num_y - 1).map(|j| inflow_dye_value(InflowSmokePattern::Uniform, j, num_y)).collect();
+        assert!(uniform_values.iter().all(|&v| v == 0.0));
+
+        let striped_values: Vec<f64> = (1..num_y - 1).map(|j| inflow_dye_value(InflowSmokePattern::Striped, j, num_y)).collect();
+        assert!(striped_values.contains(&0.0) && striped_values.contains(&1.0));
+    }
+}
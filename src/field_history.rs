@@ -0,0 +1,210 @@
+//! Compact per-run history of the fields a visualization actually reads
+//! (`p`, `m`), written once during a solver run and read back by
+//! `render --history` to produce animations without ever stepping a
+//! [`crate::scene::Scene`] again. The geometry needed for overlays (the
+//! solid mask and the radiator list) is fixed for a run, so it's stored
+//! once rather than duplicated per snapshot.
+//!
+//! `u`/`v` are deliberately not recorded — a history file is for
+//! visualization-only iteration (colormaps, panels, layouts), not resuming
+//! a run; `--checkpoint-every`/`--resume` already cover that.
+//!
+//! At high resolution and step count, `snapshots` is where this feature's
+//! memory and disk cost actually lives (`p`/`m`, one copy per recorded
+//! step) — see [`HistoryPrecision`] for the opt-in `f32` storage mode that
+//! halves it. The live solver (`Fluid`'s own `u`/`v`/`p`/`m`) stays `f64`
+//! throughout; genericizing the solver itself over float type would touch
+//! every field, every consumer (`Visualizer`, `ObstacleManager`, the
+//! Python/wasm bindings, the bincode checkpoint format) for a much larger
+//! change than this crate's history-file feature warrants.
+
+use serde::{Deserialize, Serialize};
+
+use crate::fluid::Fluid;
+use crate::radiator::Radiator;
+
+/// Storage precision for a [`FieldHistory`]'s recorded `p`/`m` values. `F32`
+/// roughly halves a history file's size (and the memory it takes to hold
+/// one in a running `render --history` process) at the cost of float32
+/// rounding on playback — see [`FieldValues::as_f64`] for the tolerance
+/// that rounding introduces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HistoryPrecision {
+    #[default]
+    F64,
+    F32,
+}
+
+/// A pressure or smoke-density field recorded at whichever
+/// [`HistoryPrecision`] the [`FieldHistory`] it belongs to uses.
+/// Downstream consumers ([`Self::as_f64`]) read through a uniform `f64`
+/// view rather than needing to know which one a given history was recorded
+/// at.
+#[derive(Serialize, Deserialize, Clone)]
+pub enum FieldValues {
+    F64(Vec<f64>),
+    F32(Vec<f32>),
+}
+
+impl FieldValues {
+    fn from_f64(values: &[f64], precision: HistoryPrecision) -> Self {
+        match precision {
+            HistoryPrecision::F64 => FieldValues::F64(values.to_vec()),
+            HistoryPrecision::F32 => FieldValues::F32(values.iter().map(|&v| v as f32).collect()),
+        }
+    }
+
+    pub fn as_f64(&self) -> Vec<f64> {
+        match self {
+            FieldValues::F64(v) => v.clone(),
+            FieldValues::F32(v) => v.iter().map(|&v| v as f64).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FieldSnapshot {
+    pub step: u64,
+    pub sim_time: f64,
+    pub p: FieldValues,
+    pub m: FieldValues,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FieldHistory {
+    pub num_x: usize,
+    pub num_y: usize,
+    pub h: f64,
+    /// Solid mask, fixed for the whole run.
+    pub s: Vec<f64>,
+    pub radiators: Vec<Radiator>,
+    /// Storage precision every snapshot [`Self::push`] records at.
+    /// `#[serde(default)]` so a history file written before this existed
+    /// still loads, as `F64` (the longstanding behavior).
+    #[serde(default)]
+    pub precision: HistoryPrecision,
+    pub snapshots: Vec<FieldSnapshot>,
+}
+
+impl FieldHistory {
+    pub fn new(fluid: &Fluid, radiators: &[Radiator]) -> Self {
+        Self::with_precision(fluid, radiators, HistoryPrecision::F64)
+    }
+
+    /// Same as [`Self::new`], but recording every subsequent [`Self::push`]
+    /// at `precision` instead of always `F64`.
+    pub fn with_precision(fluid: &Fluid, radiators: &[Radiator], precision: HistoryPrecision) -> Self {
+        FieldHistory {
+            num_x: fluid.num_x,
+            num_y: fluid.num_y,
+            h: fluid.h,
+            s: fluid.s.clone(),
+            radiators: radiators.to_vec(),
+            precision,
+            snapshots: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, fluid: &Fluid, step: u64, sim_time: f64) {
+        self.snapshots.push(FieldSnapshot {
+            step,
+            sim_time,
+            p: FieldValues::from_f64(&fluid.p, self.precision),
+            m: FieldValues::from_f64(&fluid.m, self.precision),
+        });
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        bincode::serialize_into(file, self).map_err(std::io::Error::other)
+    }
+
+    pub fn load(path: &str) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        bincode::deserialize_from(file).map_err(std::io::Error::other)
+    }
+
+    /// Rebuild a renderable [`Fluid`] for one snapshot: only `p`/`m`/`s` are
+    /// populated (the same subset [`crate::render::downsampled_fluid`]
+    /// fills in for a preview render) since a history snapshot never stores
+    /// face velocities. The returned `Fluid` must never be stepped or
+    /// measured — it's for the visualizer only.
+    pub fn fluid_at(&self, index: usize) -> Fluid {
+        let snapshot = &self.snapshots[index];
+        let mut fluid = Fluid::new(1000.0, self.num_x, self.num_y, self.h);
+        fluid.p = snapshot.p.as_f64();
+        fluid.m = snapshot.m.as_f64();
+        fluid.s.clone_from(&self.s);
+        fluid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_fluid(num_x: usize, num_y: usize, p_fill: f64) -> Fluid {
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, 0.1);
+        for p in fluid.p.iter_mut() {
+            *p = p_fill;
+        }
+        fluid
+    }
+
+    #[test]
+    fn round_trips_through_a_file_without_losing_snapshots_or_geometry() {
+        let fluid = sample_fluid(8, 6, 1.0);
+        let radiators = vec![Radiator::new(0.3, 0.3, 0.05, 0.2, 0.0, 0.5)];
+        let mut history = FieldHistory::new(&fluid, &radiators);
+        for step in 0..5u64 {
+            let snapshot_fluid = sample_fluid(8, 6, step as f64);
+            history.push(&snapshot_fluid, step, step as f64 * 0.1);
+        }
+
+        let path = std::env::temp_dir().join(format!("lgr_2d_cfd_field_history_test_{}.bin", std::process::id()));
+        history.save(path.to_str().unwrap()).unwrap();
+        let loaded = FieldHistory::load(path.to_str().unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.snapshots.len(), 5);
+        assert_eq!(loaded.radiators.len(), 1);
+        assert_eq!(loaded.radiators[0].porosity, 0.5);
+        assert_eq!(loaded.snapshots[3].step, 3);
+        assert_eq!(loaded.fluid_at(3).p[0], 3.0);
+    }
+
+    /// The comparison the request that added `HistoryPrecision` asked for,
+    /// adapted to what a history file actually stores: `u`/`v` (and so
+    /// "max velocity") are never recorded here (see this file's module
+    /// doc), so this compares `p`/`m` — an `F32` history should agree with
+    /// an `F64` one on both within float32's precision, not exactly.
+    #[test]
+    fn f32_precision_agrees_with_f64_within_a_loose_tolerance() {
+        let fluid = sample_fluid(20, 15, 0.0);
+        let radiators = vec![];
+        let mut f64_history = FieldHistory::new(&fluid, &radiators);
+        let mut f32_history = FieldHistory::with_precision(&fluid, &radiators, HistoryPrecision::F32);
+
+        for step in 0..100u64 {
+            let mut snapshot_fluid = sample_fluid(20, 15, 0.0);
+            for (i, p) in snapshot_fluid.p.iter_mut().enumerate() {
+                *p = (step as f64) * 0.01 + (i as f64) * 0.1234567;
+            }
+            for (i, m) in snapshot_fluid.m.iter_mut().enumerate() {
+                *m = ((step + i as u64) as f64 * 0.03).sin();
+            }
+            f64_history.push(&snapshot_fluid, step, step as f64 * 0.1);
+            f32_history.push(&snapshot_fluid, step, step as f64 * 0.1);
+        }
+
+        assert_eq!(f32_history.snapshots.len(), f64_history.snapshots.len());
+        for (f64_snap, f32_snap) in f64_history.snapshots.iter().zip(&f32_history.snapshots) {
+            for (a, b) in f64_snap.p.as_f64().iter().zip(f32_snap.p.as_f64().iter()) {
+                assert!((a - b).abs() < 1e-4, "pressure diverged beyond f32 rounding: {a} vs {b}");
+            }
+            for (a, b) in f64_snap.m.as_f64().iter().zip(f32_snap.m.as_f64().iter()) {
+                assert!((a - b).abs() < 1e-4, "smoke density diverged beyond f32 rounding: {a} vs {b}");
+            }
+        }
+    }
+}
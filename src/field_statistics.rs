@@ -0,0 +1,182 @@
+//! Running mean/RMS accumulator for the velocity and pressure fields, so a
+//! caller can characterize an unsteady wake by its statistics instead of a
+//! single instantaneous snapshot. Uses Welford's algorithm, the standard
+//! numerically-stable way to accumulate mean and variance one sample at a
+//! time without keeping every sampled field in memory — storing every
+//! snapshot for a long run would cost `num_x * num_y * samples` `f64`s.
+//!
+//! Not part of [`crate::scene::Scene`] by default: a caller opts in
+//! (typically after an initial transient, or once
+//! [`crate::steady_state::SteadyStateDetector`] reports settled flow) via
+//! `Scene::enable_field_statistics`, and it accumulates one sample per
+//! `simulate` call from that point on.
+
+use crate::fluid::Fluid;
+
+#[derive(Debug, Clone)]
+struct WelfordField {
+    mean: Vec<f64>,
+    m2: Vec<f64>,
+}
+
+impl WelfordField {
+    fn new(len: usize) -> Self {
+        WelfordField { mean: vec![0.0; len], m2: vec![0.0; len] }
+    }
+
+    fn update(&mut self, sample: &[f64], count: f64) {
+        for (mean, m2, &x) in zip3(&mut self.mean, &mut self.m2, sample) {
+            let delta = x - *mean;
+            *mean += delta / count;
+            let delta2 = x - *mean;
+            *m2 += delta * delta2;
+        }
+    }
+
+    /// Population variance (divides by `count`, not `count - 1`): this is
+    /// describing the run actually observed so far, not estimating a wider
+    /// population from a sample of it.
+    fn variance(&self, count: f64) -> Vec<f64> {
+        if count < 2.0 {
+            vec![0.0; self.mean.len()]
+        } else {
+            self.m2.iter().map(|&m2| m2 / count).collect()
+        }
+    }
+}
+
+/// Zips three same-length slices without pulling in a crate dependency for
+/// it — `Iterator::zip` only nests two at a time and `(a, b, c).zip()`-style
+/// helpers aren't in `std`.
+fn zip3<'a>(
+    mean: &'a mut [f64],
+    m2: &'a mut [f64],
+    sample: &'a [f64],
+) -> impl Iterator<Item = (&'a mut f64, &'a mut f64, &'a f64)> {
+    mean.iter_mut().zip(m2.iter_mut()).zip(sample.iter()).map(|((a, b), c)| (a, b, c))
+}
+
+/// Running mean and variance of `u`, `v`, `p`, and velocity magnitude,
+/// updated one [`Fluid`] snapshot at a time via [`WelfordField`].
+#[derive(Debug, Clone)]
+pub struct FieldStatistics {
+    u: WelfordField,
+    v: WelfordField,
+    p: WelfordField,
+    speed: WelfordField,
+    count: u64,
+}
+
+impl FieldStatistics {
+    /// `len` is `fluid.u.len()` (== `fluid.v.len()` == `fluid.p.len()`;
+    /// every field is `num_x * num_y`, staggered semantics are baked into
+    /// the indexing, not the array size) for the scene this will sample.
+    pub fn new(len: usize) -> Self {
+        FieldStatistics {
+            u: WelfordField::new(len),
+            v: WelfordField::new(len),
+            p: WelfordField::new(len),
+            speed: WelfordField::new(len),
+            count: 0,
+        }
+    }
+
+    /// Fold one more snapshot of `fluid` into the running statistics.
+    pub fn record(&mut self, fluid: &Fluid) {
+        self.count += 1;
+        let count = self.count as f64;
+        let speed: Vec<f64> = fluid.u.iter().zip(&fluid.v).map(|(u, v)| (u * u + v * v).sqrt()).collect();
+        self.u.update(&fluid.u, count);
+        self.v.update(&fluid.v, count);
+        self.p.update(&fluid.p, count);
+        self.speed.update(&speed, count);
+    }
+
+    /// How many samples have been folded in so far.
+    pub fn sample_count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean_u(&self) -> &[f64] {
+        &self.u.mean
+    }
+
+    pub fn mean_v(&self) -> &[f64] {
+        &self.v.mean
+    }
+
+    pub fn mean_p(&self) -> &[f64] {
+        &self.p.mean
+    }
+
+    pub fn mean_speed(&self) -> &[f64] {
+        &self.speed.mean
+    }
+
+    /// Per-cell RMS (root of the population variance) of `u`. All zero
+    /// until at least two samples have been recorded.
+    pub fn rms_u(&self) -> Vec<f64> {
+        self.u.variance(self.count as f64).into_iter().map(f64::sqrt).collect()
+    }
+
+    pub fn rms_v(&self) -> Vec<f64> {
+        self.v.variance(self.count as f64).into_iter().map(f64::sqrt).collect()
+    }
+
+    pub fn rms_p(&self) -> Vec<f64> {
+        self.p.variance(self.count as f64).into_iter().map(f64::sqrt).collect()
+    }
+
+    /// RMS of velocity magnitude — a turbulence-intensity-like field: high
+    /// where the instantaneous speed swings widely around its mean (an
+    /// unsteady wake), near zero in steady free-stream flow.
+    pub fn rms_speed(&self) -> Vec<f64> {
+        self.speed.variance(self.count as f64).into_iter().map(f64::sqrt).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fluid_with_uniform(num_x: usize, num_y: usize, u: f64, v: f64, p: f64) -> Fluid {
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, 1.0 / num_y as f64);
+        for x in fluid.u.iter_mut() {
+            *x = u;
+        }
+        for x in fluid.v.iter_mut() {
+            *x = v;
+        }
+        for x in fluid.p.iter_mut() {
+            *x = p;
+        }
+        fluid
+    }
+
+    #[test]
+    fn a_constant_field_has_zero_rms_and_the_constant_as_its_mean() {
+        let fluid = fluid_with_uniform(4, 4, 2.0, 0.5, 10.0);
+        let mut stats = FieldStatistics::new(fluid.u.len());
+        for _ in 0..5 {
+            stats.record(&fluid);
+        }
+        assert_eq!(stats.sample_count(), 5);
+        assert!(stats.mean_u().iter().all(|&v| (v - 2.0).abs() < 1e-12));
+        assert!(stats.mean_v().iter().all(|&v| (v - 0.5).abs() < 1e-12));
+        assert!(stats.mean_p().iter().all(|&v| (v - 10.0).abs() < 1e-12));
+        assert!(stats.rms_u().iter().all(|&v| v.abs() < 1e-12));
+        assert!(stats.rms_speed().iter().all(|&v| v.abs() < 1e-12));
+    }
+
+    #[test]
+    fn alternating_samples_produce_the_expected_mean_and_rms() {
+        let low = fluid_with_uniform(3, 3, 0.0, 0.0, 0.0);
+        let high = fluid_with_uniform(3, 3, 2.0, 0.0, 0.0);
+        let mut stats = FieldStatistics::new(low.u.len());
+        stats.record(&low);
+        stats.record(&high);
+        // mean of {0, 2} is 1, population variance is 1, rms is 1.
+        assert!(stats.mean_u().iter().all(|&v| (v - 1.0).abs() < 1e-12));
+        assert!(stats.rms_u().iter().all(|&v| (v - 1.0).abs() < 1e-12));
+    }
+}
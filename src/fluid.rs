@@ -0,0 +1,1293 @@
+//! Staggered-grid (MAC) incompressible fluid solver.
+//!
+//! Ported from the classic "Eulerian fluid simulator" scheme: velocities
+//! live on cell faces (`u` on the left face, `v` on the bottom face),
+//! pressure/smoke/solid-mask live at cell centers. Cell `(0, j)`, `(i, 0)`,
+//! `(num_x - 1, j)` and `(i, num_y - 1)` are ghost cells used to enforce
+//! boundary conditions and are not part of the simulated interior.
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const FLUID_CELL: f64 = 1.0;
+pub const SOLID_CELL: f64 = 0.0;
+
+/// What [`Fluid::validate`] found: the first field it scanned that had gone
+/// non-finite, and where. `field` is one of `"u"`, `"v"`, `"p"`, `"m"` — the
+/// same names those arrays are exposed under on [`Fluid`] itself, so a
+/// caller can print `report.field` straight into an error message without a
+/// separate enum-to-string mapping to keep in sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstabilityReport {
+    pub field: &'static str,
+    pub i: usize,
+    pub j: usize,
+}
+
+/// Order to run one step's integrate/project/extrapolate/advect stages in.
+///
+/// [`StepOrdering::ProjectThenAdvect`] is this solver's original order,
+/// ported along with the rest of the scheme: pressure is projected before
+/// the semi-Lagrangian advection, so advection samples an already
+/// (nearly) divergence-free field. Its downside is exactly what it sounds
+/// like — advection is not itself divergence-preserving, so the velocity
+/// field a caller reads back out at the end of the step (for rendering, a
+/// mass-conservation diagnostic, or the next step's diagnostics) is not
+/// actually divergence-free, even though the solve converged.
+/// [`StepOrdering::AdvectThenProject`] projects last instead, so
+/// `max_divergence`/`divergence_stats` read immediately after a step are
+/// as small as the solve tolerance allows, at the cost of advecting a
+/// field that still carries the previous step's divergence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepOrdering {
+    #[default]
+    ProjectThenAdvect,
+    AdvectThenProject,
+}
+
+/// Boundary condition a domain face is nominally set to. On the right
+/// (downstream) face, only [`BoundaryCondition::Outflow`] is wired to a real
+/// enforcement path — [`Fluid::conserve_outflow_mass`], called from
+/// [`crate::scene::Scene::simulate`] once per step — because that's the one
+/// this solver's boundary handling actually got wrong: the right face's
+/// velocity comes out of advection with no correction, so whatever flux an
+/// obstacle deflects out the top/bottom isn't subtracted back out of it and
+/// the domain slowly gains or loses mass over a long run. `NoSlip`/`Slip`
+/// on the right/left faces already describe this scheme's existing fixed
+/// treatment (solid-cell walls, `Scene::apply_inflow`) rather than something
+/// a caller can turn on — they exist here so a config can name the boundary
+/// it wants, but setting them doesn't change solver behavior on that face.
+///
+/// On [`Fluid::top_bottom_boundary`], `Periodic` *is* wired up: [`sample_slice`]
+/// wraps its `y` lookup into the interior row band instead of clamping to
+/// the domain edge, so a smoke blob (or a velocity feature) advected past
+/// the bottom row re-enters from the top and vice versa. That wrap is
+/// advection-only, though — `extrapolate`'s ghost-row fill and
+/// `solve_incompressibility`'s stencil (built by [`NeighborWeights`]) don't
+/// know about it, so the pressure solve still treats the top/bottom ghost
+/// rows the same way regardless of this setting. A periodic case still
+/// wants those ghost rows *not* marked solid (leave `s` at `FLUID_CELL`
+/// there, the default) — marking them solid gets you a wall no matter what
+/// `top_bottom_boundary` says.
+/// `NoSlip` and `Slip` are stored and round-tripped separately but produce
+/// identical solver behavior today: neither one is read by anything in
+/// `fluid.rs` or `scene.rs` beyond the solid/fluid mask that both share.
+/// Only `Outflow` (see `Fluid::conserve_outflow_mass`) and `Periodic` (see
+/// `Fluid::sample_slice`, and only on `top_bottom_boundary` — it isn't
+/// wired for left/right) actually change how a step runs. `Slip` exists as
+/// a distinct variant so a caller (or a config file) can record the
+/// physically-intended boundary honestly instead of mislabeling a
+/// non-horizontal-inflow wall as `NoSlip`, ahead of an actual tangential-
+/// slip implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundaryCondition {
+    Inflow(f64),
+    NoSlip,
+    Slip,
+    #[default]
+    Outflow,
+    Periodic,
+}
+
+/// How [`Fluid::solve_incompressibility`]'s Poisson pressure-correction gets
+/// solved. `GaussSeidel` is this solver's original scheme — plain red-black
+/// Gauss-Seidel over `num_iters` full sweeps, applying every cell's
+/// correction to `u`/`v` as soon as it's computed.
+///
+/// `Multigrid` accelerates convergence at high resolution, where
+/// low-frequency error decays slowly under Gauss-Seidel alone, via a
+/// geometric V-cycle: `levels` grid coarsenings (fewer if the grid is too
+/// small to coarsen that far), `v_cycles` repetitions of the full cycle,
+/// and `smoothing_iters` red-black sweeps per level. See [`crate::multigrid`]
+/// and [`Fluid::solve_incompressibility_multigrid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureSolver {
+    #[default]
+    GaussSeidel,
+    Multigrid {
+        levels: usize,
+        v_cycles: usize,
+        smoothing_iters: usize,
+    },
+}
+
+/// A named field a caller can sample at an arbitrary point via
+/// [`Fluid::sample_field`]/[`Fluid::extract_line`], rather than only ever
+/// reading `u`/`v`/`p`/`m` at whole-cell indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    U,
+    V,
+    Pressure,
+    Smoke,
+    /// Per-cell solid coverage (`1.0 - s`). Mostly `0.0`/`1.0`; only
+    /// interesting where [`crate::scene::mark_obstacle_solid_cut_cell`]'s
+    /// supersampling has left `s` fractional.
+    SolidFraction,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Fluid {
+    pub density: f64,
+    pub num_x: usize,
+    pub num_y: usize,
+    pub h: f64,
+    pub u: Vec<f64>,
+    pub v: Vec<f64>,
+    pub new_u: Vec<f64>,
+    pub new_v: Vec<f64>,
+    pub p: Vec<f64>,
+    /// 0.0 for solid cells, 1.0 for fluid cells.
+    pub s: Vec<f64>,
+    pub m: Vec<f64>,
+    pub new_m: Vec<f64>,
+    /// Right (downstream) face boundary condition. See [`BoundaryCondition`]
+    /// for which variants actually change solver behavior. Defaults to
+    /// `Outflow`, this solver's original (if previously unenforced) intent
+    /// for that face.
+    #[serde(default)]
+    pub right_boundary: BoundaryCondition,
+    /// Top/bottom face boundary condition. See [`BoundaryCondition`] for
+    /// which variants actually change solver behavior. Defaults to
+    /// `NoSlip`, matching every existing scene's solid-wall top/bottom.
+    #[serde(default = "default_top_bottom_boundary")]
+    pub top_bottom_boundary: BoundaryCondition,
+    /// Fraction of `m` lost per second of advection, applied in
+    /// [`Self::advect_smoke`]. `0.0` (the default) reproduces the original
+    /// behavior of dye never fading; a run with continuous dye sources
+    /// (see [`crate::dye_emitter`]) wants this above zero so old dye
+    /// eventually clears instead of accumulating toward full saturation.
+    #[serde(default)]
+    pub smoke_decay: f64,
+    /// CFL number (`max(|u|, |v|) * dt / h`) above which [`Self::advect_vel`]
+    /// and [`Self::advect_smoke`] split their trace-back into multiple
+    /// sub-steps instead of one, so a backtrace never skips over a feature
+    /// (a thin radiator, say) that's narrower than one step's displacement.
+    /// Defaults to `1.0`; a `dt` small enough to keep CFL at or below that
+    /// everywhere gets exactly the original single-step behavior.
+    #[serde(default = "default_advection_cfl_threshold")]
+    pub advection_cfl_threshold: f64,
+    /// Kinematic viscosity, m^2/s, from whatever [`crate::working_fluid::WorkingFluid`]
+    /// a scene was set up with. `0.0` by default (`Fluid::new`'s callers
+    /// that don't set this get the same behavior as before this field
+    /// existed). Nothing in this solver's step reads it back out yet — see
+    /// the [`crate::working_fluid`] module doc comment for why — so this is
+    /// reported metadata, not a live solver parameter.
+    #[serde(default)]
+    pub kinematic_viscosity: f64,
+}
+
+fn default_top_bottom_boundary() -> BoundaryCondition {
+    BoundaryCondition::NoSlip
+}
+
+fn default_advection_cfl_threshold() -> f64 {
+    1.0
+}
+
+impl Fluid {
+    pub fn new(density: f64, num_x: usize, num_y: usize, h: f64) -> Self {
+        let n = num_x * num_y;
+        Fluid {
+            density,
+            num_x,
+            num_y,
+            h,
+            u: vec![0.0; n],
+            v: vec![0.0; n],
+            new_u: vec![0.0; n],
+            new_v: vec![0.0; n],
+            p: vec![0.0; n],
+            s: vec![FLUID_CELL; n],
+            m: vec![1.0; n],
+            new_m: vec![1.0; n],
+            right_boundary: BoundaryCondition::default(),
+            top_bottom_boundary: default_top_bottom_boundary(),
+            smoke_decay: 0.0,
+            advection_cfl_threshold: default_advection_cfl_threshold(),
+            kinematic_viscosity: 0.0,
+        }
+    }
+
+    /// Physical width of the domain: `num_x * h`. `Fluid::new` stores
+    /// `num_x`/`num_y` exactly as passed (no ghost-cell padding is added
+    /// or hidden here), so this is equivalent to writing `num_x as f64 *
+    /// h` at a call site — it exists so a caller placing something at a
+    /// fraction of the domain (a radiator, an obstacle, a streamline seed)
+    /// doesn't have to spell out that multiplication itself, and can't get
+    /// it wrong by using `num_x - 1` or forgetting the cast.
+    #[inline]
+    pub fn domain_width(&self) -> f64 {
+        self.num_x as f64 * self.h
+    }
+
+    /// Physical height of the domain: `num_y * h`. See [`Self::domain_width`].
+    #[inline]
+    pub fn domain_height(&self) -> f64 {
+        self.num_y as f64 * self.h
+    }
+
+    #[inline]
+    pub fn idx(&self, i: usize, j: usize) -> usize {
+        i * self.num_y + j
+    }
+
+    /// Interpolated velocity at an arbitrary point in the domain: the
+    /// average of the two nearest `u` faces and the two nearest `v` faces
+    /// around the cell `(x, y)` falls in. Shared by streamline tracing
+    /// ([`crate::visualizer::trace_streamlines_with_options`]) and
+    /// [`crate::particle_tracer::ParticleTracer`] so both advect through the
+    /// exact same sampled field.
+    pub fn sample_velocity(&self, x: f64, y: f64) -> (f64, f64) {
+        let n = self.num_y;
+        let h = self.h;
+        let i = ((x / h) as usize).min(self.num_x - 2).max(1);
+        let j = ((y / h) as usize).min(self.num_y - 2).max(1);
+        let idx = i * n + j;
+        let u = (self.u[idx] + self.u[(i + 1) * n + j]) * 0.5;
+        let v = (self.v[idx] + self.v[idx + 1]) * 0.5;
+        (u, v)
+    }
+
+    /// Pressure at an arbitrary point in the domain, nearest-cell (`p` has
+    /// no staggered offset to interpolate across the way `u`/`v` do) —
+    /// same out-of-range clamping as [`Fluid::sample_velocity`].
+    fn sample_pressure(&self, x: f64, y: f64) -> f64 {
+        let n = self.num_y;
+        let h = self.h;
+        let i = ((x / h) as usize).min(self.num_x - 2).max(1);
+        let j = ((y / h) as usize).min(self.num_y - 2).max(1);
+        self.p[i * n + j]
+    }
+
+    /// Smoke concentration at an arbitrary point, bilinearly interpolated
+    /// the same way [`advect_smoke`](Fluid::advect_smoke) samples it.
+    fn sample_smoke(&self, x: f64, y: f64) -> f64 {
+        let wrap_y = self.top_bottom_boundary == BoundaryCondition::Periodic;
+        sample_slice(self.num_x, self.num_y, self.h, x, y, FieldKind::Smoke, &self.m, &self.s, wrap_y)
+    }
+
+    /// Solid coverage (`1.0 - s`) at an arbitrary point, nearest-cell —
+    /// same convention as [`Self::sample_pressure`].
+    fn sample_solid_fraction(&self, x: f64, y: f64) -> f64 {
+        let n = self.num_y;
+        let h = self.h;
+        let i = ((x / h) as usize).min(self.num_x - 2).max(1);
+        let j = ((y / h) as usize).min(self.num_y - 2).max(1);
+        1.0 - self.s[i * n + j]
+    }
+
+    /// One field, sampled at an arbitrary point — the common entry point
+    /// [`Fluid::extract_line`] uses so a caller doesn't have to know which
+    /// of `sample_velocity`/`sample_pressure`/`sample_smoke` a given field
+    /// needs.
+    pub fn sample_field(&self, field: FieldType, x: f64, y: f64) -> f64 {
+        match field {
+            FieldType::U => self.sample_velocity(x, y).0,
+            FieldType::V => self.sample_velocity(x, y).1,
+            FieldType::Pressure => self.sample_pressure(x, y),
+            FieldType::Smoke => self.sample_smoke(x, y),
+            FieldType::SolidFraction => self.sample_solid_fraction(x, y),
+        }
+    }
+
+    /// Samples `field` at `samples` evenly spaced points from `start` to
+    /// `end` (both inclusive), returning `(distance_from_start, value)`
+    /// pairs — a 1D profile along an arbitrary line, e.g. `u(y)` down a
+    /// vertical cut or pressure along the centerline. `start`/`end` may
+    /// fall outside the domain; the underlying `sample_*` calls clamp to
+    /// the nearest valid cell rather than erroring; `samples < 2` still
+    /// returns that many points, all at `start`.
+    pub fn extract_line(&self, start: (f64, f64), end: (f64, f64), samples: usize, field: FieldType) -> Vec<(f64, f64)> {
+        let denom = (samples.max(2) - 1) as f64;
+        let length = ((end.0 - start.0).powi(2) + (end.1 - start.1).powi(2)).sqrt();
+        (0..samples)
+            .map(|k| {
+                let t = if samples <= 1 { 0.0 } else { k as f64 / denom };
+                let x = start.0 + t * (end.0 - start.0);
+                let y = start.1 + t * (end.1 - start.1);
+                (t * length, self.sample_field(field, x, y))
+            })
+            .collect()
+    }
+
+    pub fn integrate(&mut self, dt: f64, gravity: f64) {
+        let n = self.num_y;
+        for i in 1..self.num_x - 1 {
+            for j in 1..self.num_y - 1 {
+                let center = i * n + j;
+                if self.s[center] != SOLID_CELL && self.s[center - 1] != SOLID_CELL {
+                    self.v[center] += gravity * dt;
+                }
+            }
+        }
+    }
+
+    /// The solid mask (`s`) is fixed for the whole run in most scenes, but
+    /// re-reading four `s` neighbors and branching twice per cell, on every
+    /// one of the `num_iters` Gauss-Seidel sweeps, adds that cost `num_iters`
+    /// times over for no reason. Pack the per-cell neighbor weights once per
+    /// call instead, so the sweep loop below does a single skip test
+    /// (`inv_s_sum == 0.0`, true for solid cells and for fluid cells with no
+    /// open neighbor) and otherwise runs branch-free.
+    /// Returns the largest per-cell pressure correction (`p_corr`, before
+    /// the `cp`/`over_relaxation` scaling) applied on the *final* sweep — a
+    /// residual for how far from converged the solve was when it stopped.
+    /// A caller doing convergence monitoring (see [`crate::convergence`])
+    /// reads this rather than needing its own extra divergence pass.
+    ///
+    /// Red-black ordered: each iteration does a "red" (`(i + j)` even) sweep
+    /// followed by a "black" (`(i + j)` odd) sweep. A cell's face writes
+    /// (`u`/`v`/`p` at its own center and its `+1` faces) never overlap with
+    /// another same-colored cell's, since same-colored cells are never
+    /// face-adjacent — so every cell within one color sweep can compute its
+    /// correction from the other color's *already-settled* values in
+    /// parallel, with the same per-sweep dependency order (and therefore the
+    /// same converged fixed point) as the original single-threaded raster
+    /// sweep.
+    pub fn solve_incompressibility(&mut self, num_iters: usize, dt: f64, over_relaxation: f64) -> f64 {
+        let cp = self.density * self.h / dt;
+        let weights = NeighborWeights::build(self);
+
+        for p in self.p.iter_mut() {
+            *p = 0.0;
+        }
+
+        let mut last_max_p_corr: f64 = 0.0;
+        for iter in 0..num_iters {
+            let is_last = iter == num_iters - 1;
+            let red = self.color_sweep(&weights, cp, over_relaxation, 0, is_last);
+            let black = self.color_sweep(&weights, cp, over_relaxation, 1, is_last);
+            last_max_p_corr = red.max(black);
+        }
+        last_max_p_corr
+    }
+
+    /// One color's worth of Gauss-Seidel updates: computes every cell's
+    /// correction in parallel (each reads only already-settled neighbor
+    /// values, see [`Self::solve_incompressibility`]), then applies them.
+    /// Returns the largest `|p_corr|` seen, or `0.0` unless `track_max` is
+    /// set (only the final iteration's max is ever used).
+    fn color_sweep(
+        &mut self,
+        weights: &NeighborWeights,
+        cp: f64,
+        over_relaxation: f64,
+        color: usize,
+        track_max: bool,
+    ) -> f64 {
+        let n = self.num_y;
+        let num_x = self.num_x;
+        let num_y = self.num_y;
+        let u = &self.u;
+        let v = &self.v;
+
+        let corrections: Vec<(usize, f64)> = (1..num_x - 1)
+            .into_par_iter()
+            .flat_map_iter(|i| {
+                (1..num_y - 1).filter_map(move |j| {
+                    let center = i * n + j;
+                    if (i + j) % 2 != color {
+                        return None;
+                    }
+                    let inv_s_sum = weights.inv_s_sum[center];
+                    if inv_s_sum == 0.0 {
+                        return None;
+                    }
+                    let div = u[(i + 1) * n + j] - u[center] + v[center + 1] - v[center];
+                    let p_corr = -div * inv_s_sum * over_relaxation;
+                    Some((center, p_corr))
+                })
+            })
+            .collect();
+
+        let mut max_p_corr: f64 = 0.0;
+        for (center, p_corr) in corrections {
+            self.p[center] += cp * p_corr;
+            self.u[center] -= weights.sx0[center] * p_corr;
+            self.u[center + n] += weights.sx1[center] * p_corr;
+            self.v[center] -= weights.sy0[center] * p_corr;
+            self.v[center + 1] += weights.sy1[center] * p_corr;
+            if track_max {
+                max_p_corr = max_p_corr.max(p_corr.abs());
+            }
+        }
+        max_p_corr
+    }
+
+    /// Multigrid counterpart to [`Self::solve_incompressibility`]: solves
+    /// the same pressure-correction equation
+    /// (`sum_neighbors(phi_neighbor - phi_center) = divergence`) via
+    /// [`crate::multigrid::solve`] instead of plain Gauss-Seidel, then
+    /// applies the converged correction to `u`/`v`/`p` in one pass —
+    /// unlike the single-grid solver, which mutates `u`/`v` as each cell's
+    /// correction is computed rather than only once at the end. Returns the
+    /// largest remaining `|divergence|` after the correction is applied, in
+    /// place of the single-grid solver's "largest final-sweep correction";
+    /// the two aren't the same quantity, but both answer the same "did this
+    /// converge" question a [`crate::convergence::ConvergenceMonitor`] asks.
+    pub fn solve_incompressibility_multigrid(&mut self, levels: usize, v_cycles: usize, smoothing_iters: usize, dt: f64) -> f64 {
+        let cp = self.density * self.h / dt;
+        let n = self.num_y;
+
+        for p in self.p.iter_mut() {
+            *p = 0.0;
+        }
+
+        let mut div0 = vec![0.0; self.num_x * self.num_y];
+        for i in 1..self.num_x - 1 {
+            for j in 1..self.num_y - 1 {
+                let c = i * n + j;
+                if self.s[c] == SOLID_CELL {
+                    continue;
+                }
+                div0[c] = self.u[(i + 1) * n + j] - self.u[c] + self.v[c + 1] - self.v[c];
+            }
+        }
+
+        let phi = crate::multigrid::solve(&div0, &self.s, self.num_x, self.num_y, levels, v_cycles, smoothing_iters);
+        let weights = NeighborWeights::build(self);
+
+        for i in 1..self.num_x - 1 {
+            for j in 1..self.num_y - 1 {
+                let c = i * n + j;
+                if weights.inv_s_sum[c] == 0.0 {
+                    continue;
+                }
+                self.p[c] = cp * phi[c];
+                self.u[c] -= weights.sx0[c] * phi[c];
+                self.u[c + n] += weights.sx1[c] * phi[c];
+                self.v[c] -= weights.sy0[c] * phi[c];
+                self.v[c + 1] += weights.sy1[c] * phi[c];
+            }
+        }
+
+        let mut max_div: f64 = 0.0;
+        for i in 1..self.num_x - 1 {
+            for j in 1..self.num_y - 1 {
+                let c = i * n + j;
+                if self.s[c] == SOLID_CELL {
+                    continue;
+                }
+                let div = self.u[(i + 1) * n + j] - self.u[c] + self.v[c + 1] - self.v[c];
+                max_div = max_div.max(div.abs());
+            }
+        }
+        max_div
+    }
+
+    pub fn extrapolate(&mut self) {
+        let n = self.num_y;
+        for i in 0..self.num_x {
+            self.u[i * n] = self.u[i * n + 1];
+            self.u[i * n + self.num_y - 1] = self.u[i * n + self.num_y - 2];
+        }
+        for j in 0..self.num_y {
+            self.v[j] = self.v[n + j];
+            self.v[(self.num_x - 1) * n + j] = self.v[(self.num_x - 2) * n + j];
+        }
+    }
+
+    /// Pins the top ghost row's tangential velocity (`u`) to `wall_u` and
+    /// its normal velocity (`v`) to zero, the same way [`Self::extrapolate`]
+    /// mirrors an interior row into that ghost row — except this overrides
+    /// it with a fixed value instead of copying the interior, giving that
+    /// row a moving-wall (rather than zero-gradient) boundary. Must be
+    /// called every step *after* `extrapolate`, which would otherwise
+    /// overwrite the pin with the interior row below it. That row also has
+    /// to be left as an ordinary fluid cell (not [`SOLID_CELL`]) for the
+    /// pinned velocity to be seen by advection and the pressure solve at
+    /// all; a solid row's velocity is never read back out. Used directly by
+    /// a plane Couette flow, and indirectly (via
+    /// [`crate::scene::Scene::lid_driven_cavity`]) by the lid-driven cavity.
+    pub fn pin_top_wall_velocity(&mut self, wall_u: f64) {
+        let n = self.num_y;
+        let top = self.num_y - 1;
+        for i in 1..self.num_x - 1 {
+            self.u[i * n + top] = wall_u;
+            self.v[i * n + top] = 0.0;
+        }
+    }
+
+    /// Total flux (per unit depth) crossing `column` (an `i` index),
+    /// summed over the interior rows — the same quantity on the left
+    /// (`column = 0`) is the domain's inflow, on the right
+    /// (`column = num_x - 1`) its outflow.
+    fn column_flux(&self, column: usize) -> f64 {
+        let n = self.num_y;
+        (1..self.num_y - 1).map(|j| self.u[column * n + j]).sum()
+    }
+
+    /// Scale the right-boundary column's `u` so its total outflow flux
+    /// matches the left boundary's total inflow flux, rather than leaving
+    /// whatever advection produced there uncorrected. A radiator (or any
+    /// obstacle) deflects some inflow out through the top/bottom walls,
+    /// which this domain has no through-flow for, so without this
+    /// correction the right face slowly drifts away from actually
+    /// balancing the left face and the pressure field drifts with it over
+    /// a long run. No-op unless `right_boundary` is [`BoundaryCondition::Outflow`].
+    pub fn conserve_outflow_mass(&mut self) {
+        if self.right_boundary != BoundaryCondition::Outflow {
+            return;
+        }
+        let inflow_flux = self.column_flux(0);
+        let outflow_flux = self.column_flux(self.num_x - 1);
+        if outflow_flux.abs() < 1e-9 {
+            return;
+        }
+        let scale = inflow_flux / outflow_flux;
+        let n = self.num_y;
+        let column = self.num_x - 1;
+        for j in 1..self.num_y - 1 {
+            self.u[column * n + j] *= scale;
+        }
+    }
+
+    /// `inflow_flux - outflow_flux` across the left/right boundary columns
+    /// — the global mass-conservation diagnostic printed by `run_scene`.
+    /// Should sit near 0 once [`Self::conserve_outflow_mass`] has run.
+    pub fn boundary_flux_imbalance(&self) -> f64 {
+        self.column_flux(0) - self.column_flux(self.num_x - 1)
+    }
+
+    pub fn avg_u(&self, i: usize, j: usize) -> f64 {
+        avg_u_slice(&self.u, self.num_y, i, j)
+    }
+
+    pub fn avg_v(&self, i: usize, j: usize) -> f64 {
+        avg_v_slice(&self.v, self.num_y, i, j)
+    }
+
+    /// Largest CFL number (`max(|u|, |v|) * dt / h`) over every cell —
+    /// how many cell widths a single-step backtrace would cross.
+    fn max_cfl(&self, dt: f64) -> f64 {
+        let max_speed = self
+            .u
+            .iter()
+            .chain(self.v.iter())
+            .fold(0.0_f64, |acc, &speed| acc.max(speed.abs()));
+        max_speed * dt / self.h
+    }
+
+    /// Hard ceiling on [`Self::advection_substeps`]'s output. A velocity
+    /// field that's already gone to infinity (a diverged pressure solve,
+    /// say) pushes `max_cfl` to infinity too, and `f64::INFINITY as usize`
+    /// saturates to `usize::MAX` rather than panicking — so without this
+    /// cap, one step of [`Self::advect_vel`]/[`Self::advect_smoke`] would
+    /// try to loop that many times and never return, and [`Self::validate`]
+    /// (which only runs at the *end* of a step) would never get the chance
+    /// to report the real problem. Capping trades a blown-up step's
+    /// accuracy — which is already lost — for guaranteed termination.
+    const MAX_ADVECTION_SUBSTEPS: usize = 10_000;
+
+    /// Number of sub-steps [`Self::advect_vel`]/[`Self::advect_smoke`]
+    /// should split `dt` into: `1` (i.e. no splitting) while the CFL number
+    /// stays at or below `advection_cfl_threshold`, otherwise
+    /// `ceil(max_cfl)` sub-steps, each re-tracing by `dt / substeps` from
+    /// the previous sub-step's already-advected field, capped at
+    /// [`Self::MAX_ADVECTION_SUBSTEPS`].
+    fn advection_substeps(&self, dt: f64) -> usize {
+        let cfl = self.max_cfl(dt);
+        if cfl > self.advection_cfl_threshold {
+            (cfl.ceil() as usize).min(Self::MAX_ADVECTION_SUBSTEPS)
+        } else {
+            1
+        }
+    }
+
+    /// Advects `u`/`v` (the semi-Lagrangian trace-back-and-sample step),
+    /// internally split into [`Self::advection_substeps`] sub-steps when
+    /// `dt`'s CFL number exceeds `advection_cfl_threshold`, so a backtrace
+    /// never skips clean over a thin feature. Each sub-step re-samples from
+    /// the previous one's output, using the same `s` mask throughout — a
+    /// single call at CFL <= threshold behaves exactly as before.
+    pub fn advect_vel(&mut self, dt: f64) {
+        let substeps = self.advection_substeps(dt);
+        let sub_dt = dt / substeps as f64;
+        for _ in 0..substeps {
+            self.advect_vel_step(sub_dt);
+        }
+    }
+
+    fn advect_vel_step(&mut self, dt: f64) {
+        self.new_u.copy_from_slice(&self.u);
+        self.new_v.copy_from_slice(&self.v);
+
+        let n = self.num_y;
+        let num_x = self.num_x;
+        let num_y = self.num_y;
+        let h = self.h;
+        let h2 = 0.5 * h;
+        let u = &self.u;
+        let v = &self.v;
+        let s = &self.s;
+        let wrap_y = self.top_bottom_boundary == BoundaryCondition::Periodic;
+
+        self.new_u.par_chunks_mut(n).enumerate().skip(1).for_each(|(i, row)| {
+            for j in 1..num_y - 1 {
+                let center = i * n + j;
+                if s[center] != SOLID_CELL && s[(i - 1) * n + j] != SOLID_CELL {
+                    let x = i as f64 * h;
+                    let y = j as f64 * h + h2;
+                    let x = x - dt * u[center];
+                    let y = y - dt * avg_v_slice(v, n, i, j);
+                    row[j] = sample_slice(num_x, num_y, h, x, y, FieldKind::U, u, s, wrap_y);
+                }
+            }
+        });
+
+        self.new_v.par_chunks_mut(n).enumerate().skip(1).take(num_x - 2).for_each(|(i, row)| {
+            for j in 1..num_y {
+                let center = i * n + j;
+                if s[center] != SOLID_CELL && s[i * n + j - 1] != SOLID_CELL {
+                    let x = i as f64 * h + h2;
+                    let y = j as f64 * h;
+                    let x = x - dt * avg_u_slice(u, n, i, j);
+                    let y = y - dt * v[center];
+                    row[j] = sample_slice(num_x, num_y, h, x, y, FieldKind::V, v, s, wrap_y);
+                }
+            }
+        });
+
+        std::mem::swap(&mut self.u, &mut self.new_u);
+        std::mem::swap(&mut self.v, &mut self.new_v);
+    }
+
+    /// Advects `m` the same trivially-parallel way as [`Self::advect_vel`],
+    /// including the same CFL-driven sub-stepping (see
+    /// [`Self::advection_substeps`]) so a smoke blob can't skip over a thin
+    /// obstacle any more than velocity can. Also applies `smoke_decay`, if
+    /// set, so dye fades instead of only ever being diluted by advection —
+    /// applied once per sub-step at that sub-step's own `dt`, converging to
+    /// the un-split case's decay as the sub-step count grows.
+    pub fn advect_smoke(&mut self, dt: f64) {
+        let substeps = self.advection_substeps(dt);
+        let sub_dt = dt / substeps as f64;
+        for _ in 0..substeps {
+            self.advect_smoke_step(sub_dt);
+        }
+    }
+
+    fn advect_smoke_step(&mut self, dt: f64) {
+        self.new_m.copy_from_slice(&self.m);
+
+        let n = self.num_y;
+        let num_x = self.num_x;
+        let num_y = self.num_y;
+        let h = self.h;
+        let h2 = 0.5 * h;
+        let u = &self.u;
+        let v = &self.v;
+        let s = &self.s;
+        let m = &self.m;
+        let wrap_y = self.top_bottom_boundary == BoundaryCondition::Periodic;
+        let decay = (1.0 - self.smoke_decay * dt).max(0.0);
+
+        self.new_m.par_chunks_mut(n).enumerate().skip(1).take(num_x - 2).for_each(|(i, row)| {
+            for j in 1..num_y - 1 {
+                let center = i * n + j;
+                if s[center] == SOLID_CELL {
+                    continue;
+                }
+                let uu = (u[center] + u[(i + 1) * n + j]) * 0.5;
+                let vv = (v[center] + v[center + 1]) * 0.5;
+                let x = i as f64 * h + h2 - dt * uu;
+                let y = j as f64 * h + h2 - dt * vv;
+                row[j] = sample_slice(num_x, num_y, h, x, y, FieldKind::Smoke, m, s, wrap_y) * decay;
+            }
+        });
+
+        std::mem::swap(&mut self.m, &mut self.new_m);
+    }
+
+    /// Serialize every array plus the grid parameters needed to resume a
+    /// run: `density`, `h`, `num_x`, `num_y`, `u`, `v`, `p`, `s`, `m`.
+    pub fn save_checkpoint(&self, path: &str) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        bincode::serialize_into(file, self).map_err(std::io::Error::other)
+    }
+
+    pub fn load_checkpoint(path: &str) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        bincode::deserialize_from(file).map_err(std::io::Error::other)
+    }
+
+    /// Largest `|div u|` over interior fluid cells, i.e. how far the current
+    /// velocity field is from satisfying incompressibility. Used by
+    /// `--self-test` to confirm the pressure solve is actually converging on
+    /// a fresh install rather than silently no-opping.
+    pub fn max_divergence(&self) -> f64 {
+        self.divergence_stats().0
+    }
+
+    /// `(max, mean)` absolute divergence over every fluid cell, the two
+    /// quantities [`crate::convergence::ConvergenceMonitor`] records each
+    /// step alongside the pressure residual.
+    pub fn divergence_stats(&self) -> (f64, f64) {
+        let n = self.num_y;
+        let mut max_div: f64 = 0.0;
+        let mut sum_div = 0.0;
+        let mut count = 0usize;
+        for i in 1..self.num_x - 1 {
+            for j in 1..self.num_y - 1 {
+                let center = i * n + j;
+                if self.s[center] == SOLID_CELL {
+                    continue;
+                }
+                let div = self.u[(i + 1) * n + j] - self.u[center] + self.v[center + 1]
+                    - self.v[center];
+                max_div = max_div.max(div.abs());
+                sum_div += div.abs();
+                count += 1;
+            }
+        }
+        let mean_div = if count > 0 { sum_div / count as f64 } else { 0.0 };
+        (max_div, mean_div)
+    }
+
+    /// First non-finite value found across `u`, `v`, `p`, `m`, if any. Fields
+    /// are scanned in that order and each scanned start-to-end, so two
+    /// blown-up runs of the same scenario always report the same field and
+    /// cell rather than whichever a `HashMap` or parallel scan happened to
+    /// visit first — the point being a stable, reproducible answer to "where
+    /// did this go wrong", not the fastest possible scan.
+    pub fn validate(&self) -> Result<(), InstabilityReport> {
+        for (field, values) in [("u", &self.u), ("v", &self.v), ("p", &self.p), ("m", &self.m)] {
+            if let Some(index) = values.iter().position(|v| !v.is_finite()) {
+                let i = index / self.num_y;
+                let j = index % self.num_y;
+                return Err(InstabilityReport { field, i, j });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn simulate(&mut self, dt: f64, gravity: f64, num_iters: usize, over_relaxation: f64) -> f64 {
+        self.simulate_with_ordering(dt, gravity, num_iters, over_relaxation, StepOrdering::ProjectThenAdvect)
+    }
+
+    /// Same one-step integrate/project/extrapolate/advect sequence as
+    /// [`Self::simulate`], but with the project/advect order chosen by
+    /// `ordering` instead of hard-coded to
+    /// [`StepOrdering::ProjectThenAdvect`]. `extrapolate` always runs
+    /// immediately before `advect_vel` regardless of ordering — it only
+    /// fills the ghost cells advection's boundary sampling needs, and has
+    /// no bearing on whether the field it's advecting is divergence-free.
+    pub fn simulate_with_ordering(
+        &mut self,
+        dt: f64,
+        gravity: f64,
+        num_iters: usize,
+        over_relaxation: f64,
+        ordering: StepOrdering,
+    ) -> f64 {
+        self.integrate(dt, gravity);
+        match ordering {
+            StepOrdering::ProjectThenAdvect => {
+                let residual = self.solve_incompressibility(num_iters, dt, over_relaxation);
+                self.extrapolate();
+                self.advect_vel(dt);
+                self.advect_smoke(dt);
+                residual
+            }
+            StepOrdering::AdvectThenProject => {
+                self.extrapolate();
+                self.advect_vel(dt);
+                self.advect_smoke(dt);
+                self.solve_incompressibility(num_iters, dt, over_relaxation)
+            }
+        }
+    }
+}
+
+enum FieldKind {
+    U,
+    V,
+    Smoke,
+}
+
+/// Bilinearly samples `f` (the field named by `field`, on its own staggered
+/// offset) at world position `(x, y)`. Takes explicit slices/dimensions
+/// rather than `&self` so `advect_vel`/`advect_smoke` can call it while
+/// `self.u`/`self.v` are only immutably borrowed and
+/// `self.new_u`/`self.new_v`/`self.new_m` are borrowed mutably in the same
+/// parallel closure.
+///
+/// `wrap_y` is [`Fluid::top_bottom_boundary`] `== `[`BoundaryCondition::Periodic`]:
+/// instead of clamping `y` to the domain edge (losing whatever crossed it),
+/// it wraps `y` around the same `[h, num_y * h)` span the clamp would have
+/// used, so a lookup that traced back past the bottom row picks up the top
+/// row's value (and vice versa).
+///
+/// `s` gates which of the four bilinear corners are allowed to contribute:
+/// a trace-back that lands just outside an obstacle can still straddle a
+/// solid cell, and blending its (usually stale near-zero) value in the same
+/// as the three fluid corners is exactly how a wake ends up with velocity
+/// pulled from inside the obstacle, and how smoke gets advected into it.
+/// Solid corners are dropped and the remaining fluid corners' weights are
+/// renormalized back up to sum to 1, so a sample right next to a curved
+/// cut-cell boundary still reads close to the true local velocity instead
+/// of one artificially damped by the missing corner. See the renormalization
+/// itself for why that scale-up is floored rather than unconditional.
+#[allow(clippy::too_many_arguments)]
+fn sample_slice(num_x: usize, num_y: usize, h: f64, x: f64, y: f64, field: FieldKind, f: &[f64], s: &[f64], wrap_y: bool) -> f64 {
+    let n = num_y;
+    let h1 = 1.0 / h;
+    let h2 = 0.5 * h;
+
+    let x = x.max(h).min(num_x as f64 * h);
+    let y = if wrap_y {
+        let span = (num_y as f64 - 1.0) * h;
+        h + (y - h).rem_euclid(span)
+    } else {
+        y.max(h).min(num_y as f64 * h)
+    };
+
+    let (dx, dy) = match field {
+        FieldKind::U => (0.0, h2),
+        FieldKind::V => (h2, 0.0),
+        FieldKind::Smoke => (h2, h2),
+    };
+
+    let x0 = ((x - dx) * h1).floor().min(num_x as f64 - 1.0).max(0.0);
+    let tx = (x - dx - x0 * h) * h1;
+    let x1 = (x0 + 1.0).min(num_x as f64 - 1.0);
+
+    let y0 = ((y - dy) * h1).floor().min(num_y as f64 - 1.0).max(0.0);
+    let ty = (y - dy - y0 * h) * h1;
+    let y1 = (y0 + 1.0).min(num_y as f64 - 1.0);
+
+    let sx = 1.0 - tx;
+    let sy = 1.0 - ty;
+
+    let (x0, x1, y0, y1) = (x0 as usize, x1 as usize, y0 as usize, y1 as usize);
+    let (i00, i10, i11, i01) = (x0 * n + y0, x1 * n + y0, x1 * n + y1, x0 * n + y1);
+
+    let w00 = if s[i00] == SOLID_CELL { 0.0 } else { sx * sy };
+    let w10 = if s[i10] == SOLID_CELL { 0.0 } else { tx * sy };
+    let w11 = if s[i11] == SOLID_CELL { 0.0 } else { tx * ty };
+    let w01 = if s[i01] == SOLID_CELL { 0.0 } else { sx * ty };
+
+    // Renormalizing the remaining fluid corners' weights back up to sum to 1
+    // is what makes this match an ordinary bilinear sample away from any
+    // solid corner, but doing it unconditionally lets a sample point that
+    // lands almost entirely on top of the other, now-solid, corners
+    // renormalize a small remaining weight up disproportionately — a
+    // once-lightweight neighbor suddenly carrying most of the result. For a
+    // static obstacle that's just a locally biased sample; for a body whose
+    // footprint moves every step (see `vortex_induced_body`), it turns into
+    // a feedback loop, since the biased sample feeds the very force that
+    // moves the body next. Flooring the denominator caps the scale-up (1.5x
+    // here) rather than leaving it unconditional.
+    //
+    // There's no clean closed-form bound to derive this from: a solid
+    // corner's bilinear weight still dominates arbitrarily close to that
+    // corner regardless of how many other corners are open, so any floor is
+    // a tradeoff picked empirically rather than solved for. `2.0 / 3.0` is
+    // the loosest cap that still keeps
+    // `obstacle_analysis::tests::cut_cell_cylinder_drag_converges_across_resolutions`
+    // (now checked at several cylinder radii, not just one) converging —
+    // which wants close to the full, unfloored renormalization to resolve a
+    // curved boundary well — while also keeping
+    // `vortex_induced_body::tests::amplitude_grows_with_reduced_velocity_across_a_lock_in_sweep`'s
+    // two lowest-U* cases from spuriously exciting themselves. If either
+    // regression's geometry or thresholds change, re-check this constant
+    // against both rather than assuming it still holds.
+    let w_sum = w00 + w10 + w11 + w01;
+    if w_sum == 0.0 {
+        return 0.0;
+    }
+    let inv = 1.0 / w_sum.max(2.0 / 3.0);
+    w00 * inv * f[i00] + w10 * inv * f[i10] + w11 * inv * f[i11] + w01 * inv * f[i01]
+}
+
+/// Free-function form of `Fluid::avg_u`, taking an explicit slice — see
+/// [`sample_slice`] for why.
+fn avg_u_slice(u: &[f64], n: usize, i: usize, j: usize) -> f64 {
+    (u[i * n + j - 1] + u[i * n + j] + u[(i + 1) * n + j - 1] + u[(i + 1) * n + j]) * 0.25
+}
+
+/// Free-function form of `Fluid::avg_v`, taking an explicit slice — see
+/// [`sample_slice`] for why.
+fn avg_v_slice(v: &[f64], n: usize, i: usize, j: usize) -> f64 {
+    (v[(i - 1) * n + j] + v[i * n + j] + v[(i - 1) * n + j + 1] + v[i * n + j + 1]) * 0.25
+}
+
+/// Packed per-cell neighbor weights for the pressure solve: `sxN`/`syN` are
+/// just the raw `s` values of the four face neighbors, and `inv_s_sum` is
+/// `1 / (sx0 + sx1 + sy0 + sy1)`, or `0.0` for solid cells and fluid cells
+/// with no open neighbor (both cases the old code skipped explicitly).
+struct NeighborWeights {
+    sx0: Vec<f64>,
+    sx1: Vec<f64>,
+    sy0: Vec<f64>,
+    sy1: Vec<f64>,
+    inv_s_sum: Vec<f64>,
+}
+
+impl NeighborWeights {
+    fn build(fluid: &Fluid) -> Self {
+        let n = fluid.num_y;
+        let len = fluid.s.len();
+        let mut weights = NeighborWeights {
+            sx0: vec![0.0; len],
+            sx1: vec![0.0; len],
+            sy0: vec![0.0; len],
+            sy1: vec![0.0; len],
+            inv_s_sum: vec![0.0; len],
+        };
+
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                let center = i * n + j;
+                if fluid.s[center] == SOLID_CELL {
+                    continue;
+                }
+
+                let sx0 = fluid.s[(i - 1) * n + j];
+                let sx1 = fluid.s[(i + 1) * n + j];
+                let sy0 = fluid.s[i * n + j - 1];
+                let sy1 = fluid.s[i * n + j + 1];
+                let s_sum = sx0 + sx1 + sy0 + sy1;
+                if s_sum == 0.0 {
+                    continue;
+                }
+
+                weights.sx0[center] = sx0;
+                weights.sx1[center] = sx1;
+                weights.sy0[center] = sy0;
+                weights.sy1[center] = sy1;
+                weights.inv_s_sum[center] = 1.0 / s_sum;
+            }
+        }
+
+        weights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tank-case setup shared by both orderings below (this tree has no
+    /// dedicated cavity/channel validation scenes to run the comparison the
+    /// original request asked for; this is `self_test`'s tank case, the
+    /// closest thing to one that exists here).
+    fn tank() -> Fluid {
+        let mut fluid = Fluid::new(1000.0, 16, 16, 1.0 / 16.0);
+        let n = fluid.num_y;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                let idx = i * n + j;
+                let is_boundary = i == 0 || j == 0 || j == fluid.num_y - 1;
+                fluid.s[idx] = if is_boundary { 0.0 } else { 1.0 };
+            }
+            fluid.u[j] = 1.0;
+        }
+        fluid
+    }
+
+    #[test]
+    fn both_orderings_converge_to_the_same_steady_state() {
+        let mut project_then_advect = tank();
+        let mut advect_then_project = tank();
+
+        for _ in 0..50 {
+            project_then_advect.simulate_with_ordering(1.0 / 60.0, 0.0, 40, 1.9, StepOrdering::ProjectThenAdvect);
+            advect_then_project.simulate_with_ordering(1.0 / 60.0, 0.0, 40, 1.9, StepOrdering::AdvectThenProject);
+        }
+
+        let max_u_diff = project_then_advect
+            .u
+            .iter()
+            .zip(advect_then_project.u.iter())
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f64::max);
+        assert!(
+            max_u_diff < 1e-2,
+            "orderings should settle to close to the same steady-state u field, got max diff {max_u_diff}"
+        );
+
+        // The whole point of `AdvectThenProject`: the field a caller reads
+        // back out immediately after the step is itself divergence-free,
+        // which `ProjectThenAdvect` never guarantees since advection runs
+        // after the last projection.
+        let project_then_advect_div = project_then_advect.max_divergence();
+        let advect_then_project_div = advect_then_project.max_divergence();
+        assert!(
+            advect_then_project_div <= project_then_advect_div,
+            "advect-then-project should read back at least as divergence-free: {advect_then_project_div} vs {project_then_advect_div}"
+        );
+    }
+
+    /// `solve_incompressibility`/`advect_vel`/`advect_smoke` run on rayon's
+    /// global thread pool, sized to the machine's core count by default.
+    /// Pinning a pool to one thread exercises the same red-black/row-major
+    /// parallel code path serially, which is the "serial" side of the
+    /// "serial and parallel agree" comparison the request asked for — there
+    /// is no separate non-parallel implementation left to compare against.
+    #[test]
+    fn single_threaded_and_multi_threaded_pools_agree() {
+        let single = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+        let multi = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+
+        let mut serial = tank();
+        let mut parallel = tank();
+        for _ in 0..20 {
+            single.install(|| serial.simulate(1.0 / 60.0, 0.0, 40, 1.9));
+            multi.install(|| parallel.simulate(1.0 / 60.0, 0.0, 40, 1.9));
+        }
+
+        let max_u_diff = serial.u.iter().zip(parallel.u.iter()).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+        let max_v_diff = serial.v.iter().zip(parallel.v.iter()).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+        let max_m_diff = serial.m.iter().zip(parallel.m.iter()).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+        assert!(max_u_diff < 1e-9, "u fields diverged between thread counts: {max_u_diff}");
+        assert!(max_v_diff < 1e-9, "v fields diverged between thread counts: {max_v_diff}");
+        assert!(max_m_diff < 1e-9, "smoke fields diverged between thread counts: {max_m_diff}");
+    }
+
+    #[test]
+    fn conserve_outflow_mass_matches_the_right_column_flux_to_the_left() {
+        let mut fluid = tank();
+        fluid.u[fluid.num_y * (fluid.num_x - 1) + 3] = 5.0;
+        assert!(fluid.boundary_flux_imbalance().abs() > 1e-6);
+
+        fluid.conserve_outflow_mass();
+
+        assert!(
+            fluid.boundary_flux_imbalance().abs() < 1e-9,
+            "expected ~0 flux imbalance after correction, got {}",
+            fluid.boundary_flux_imbalance()
+        );
+    }
+
+    /// A blob advected down past the bottom row should reappear at the top
+    /// when `top_bottom_boundary` is `Periodic` — and, with the exact same
+    /// velocity field, stay clamped to the (unrelated) top ghost row's
+    /// default value when it isn't.
+    #[test]
+    fn periodic_top_bottom_boundary_wraps_smoke_advected_past_the_bottom_row() {
+        let blob_at = |periodic: bool| {
+            let mut fluid = Fluid::new(1000.0, 4, 6, 1.0);
+            fluid.top_bottom_boundary =
+                if periodic { BoundaryCondition::Periodic } else { BoundaryCondition::NoSlip };
+            // This test wants one single large jump to exercise the wrap,
+            // not CFL sub-stepping (see [`Fluid::advection_substeps`]), so
+            // opt out of splitting it.
+            fluid.advection_cfl_threshold = f64::MAX;
+            for v in fluid.v.iter_mut() {
+                *v = 7.0;
+            }
+            let blob = fluid.idx(2, 2);
+            fluid.m[blob] = 9.0;
+            fluid.advect_smoke(1.0);
+            let top = fluid.idx(2, fluid.num_y - 2);
+            fluid.m[top]
+        };
+
+        let wrapped = blob_at(true);
+        let clamped = blob_at(false);
+        assert!(wrapped > 8.0, "periodic wrap should carry the blob's value to the top row, got {wrapped}");
+        assert!(
+            clamped < 2.0,
+            "without wraparound the top row should see the (unrelated) default smoke value, not the blob, got {clamped}"
+        );
+    }
+
+    /// `Scene::wind_tunnel_with_radiator_sized` seeds the whole domain with
+    /// the free-stream velocity before any stepping happens, so a vertical
+    /// line profile taken near the inlet should read back that exact
+    /// imposed value everywhere along it.
+    #[test]
+    fn extract_line_of_u_near_the_inlet_matches_the_imposed_inflow_velocity() {
+        let scene = crate::scene::Scene::wind_tunnel_with_radiator(60, 30);
+        let domain_height = scene.fluid.domain_height();
+
+        let profile = scene.fluid.extract_line((0.02, 0.0), (0.02, domain_height), 20, FieldType::U);
+
+        for (_, u) in &profile {
+            assert!(
+                (u - scene.inflow_u).abs() < 1e-9,
+                "expected {} at every sample near the inlet, got {u}",
+                scene.inflow_u
+            );
+        }
+    }
+
+    #[test]
+    fn conserve_outflow_mass_is_a_no_op_when_the_right_boundary_is_not_outflow() {
+        let mut fluid = tank();
+        fluid.right_boundary = BoundaryCondition::NoSlip;
+        fluid.u[fluid.num_y * (fluid.num_x - 1) + 3] = 5.0;
+        let before = fluid.boundary_flux_imbalance();
+
+        fluid.conserve_outflow_mass();
+
+        assert_eq!(fluid.boundary_flux_imbalance(), before);
+    }
+
+    /// A thin (one-cell) full-height "radiator" column with free-stream
+    /// velocity either side of it.
+    fn fluid_with_thin_radiator_column(num_x: usize, num_y: usize, h: f64, inflow_u: f64) -> Fluid {
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        let n = fluid.num_y;
+        let radiator_i = num_x / 2;
+        for i in 0..num_x {
+            for j in 0..num_y {
+                let idx = i * n + j;
+                let is_wall = j == 0 || j == num_y - 1;
+                let is_radiator = i == radiator_i && !is_wall;
+                fluid.s[idx] = if is_wall || is_radiator { SOLID_CELL } else { FLUID_CELL };
+            }
+            fluid.u[i * n + num_y / 2] = inflow_u;
+        }
+        fluid.m.fill(1.0);
+        fluid
+    }
+
+    /// A single call with a deliberately huge `dt` clamps `smoke_decay`'s
+    /// per-step factor straight to `0.0` (all dye gone in one shot,
+    /// regardless of how large `dt` actually is past the point of full
+    /// decay). Splitting the same `dt` into sub-steps applies that factor
+    /// `n` times instead, converging toward `exp(-decay * dt)` rather than
+    /// flooring at zero — a more faithful (if still approximate, this
+    /// isn't an implicit or exponential integrator) decay under a `dt` that
+    /// would otherwise blow straight through it.
+    #[test]
+    fn sub_stepped_decay_does_not_floor_to_zero_the_way_a_single_oversized_step_does() {
+        let h = 0.1;
+        let (num_x, num_y) = (20, 8);
+        let inflow_u = 5.0;
+        let dt = 1.0; // max_cfl = inflow_u * dt / h = 50.0, well above the default threshold of 1.0
+
+        let mut substepped = fluid_with_thin_radiator_column(num_x, num_y, h, inflow_u);
+        substepped.smoke_decay = 5.0;
+        let mut single_step = fluid_with_thin_radiator_column(num_x, num_y, h, inflow_u);
+        single_step.smoke_decay = 5.0;
+        single_step.advection_cfl_threshold = f64::MAX;
+
+        substepped.advect_smoke(dt);
+        single_step.advect_smoke(dt);
+
+        let probe = 3 * num_y + num_y / 2;
+        assert_eq!(single_step.m[probe], 0.0, "a single 1 - decay*dt step should clamp straight to 0");
+        assert!(
+            substepped.m[probe] > 0.0,
+            "sub-stepping the same dt should decay gradually instead of flooring to exactly 0, got {}",
+            substepped.m[probe]
+        );
+    }
+
+    /// The invariant the request asked for directly: dye stays bounded in
+    /// `[0, 1]` even under a huge `dt`, sub-stepped or not.
+    #[test]
+    fn advect_smoke_keeps_dye_bounded_under_a_deliberately_huge_dt() {
+        let h = 0.1;
+        let (num_x, num_y) = (20, 8);
+        let mut fluid = fluid_with_thin_radiator_column(num_x, num_y, h, 5.0);
+
+        fluid.advect_smoke(10.0); // max_cfl = 5.0 * 10.0 / 0.1 = 500
+
+        for (idx, &m) in fluid.m.iter().enumerate() {
+            assert!((0.0..=1.0).contains(&m), "m[{idx}] = {m} left the [0, 1] range it started in");
+        }
+    }
+
+    #[test]
+    fn advection_substeps_is_one_below_the_cfl_threshold() {
+        let fluid = fluid_with_thin_radiator_column(20, 8, 0.1, 5.0);
+        assert_eq!(fluid.advection_substeps(0.02), 1, "max_cfl = 5.0 * 0.02 / 0.1 = 1.0, at the default threshold");
+    }
+
+    #[test]
+    fn advection_substeps_is_capped_when_velocity_has_gone_to_infinity() {
+        let mut fluid = fluid_with_thin_radiator_column(20, 8, 0.1, 5.0);
+        fluid.u[0] = f64::INFINITY;
+        // Uncapped this would be `usize::MAX` (`f64::INFINITY as usize`
+        // saturates rather than panicking), which would turn one step's
+        // advection into a practically infinite loop.
+        assert_eq!(fluid.advection_substeps(0.02), Fluid::MAX_ADVECTION_SUBSTEPS);
+    }
+
+    #[test]
+    fn validate_passes_on_a_freshly_constructed_fluid() {
+        let fluid = tank();
+        assert_eq!(fluid.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_reports_the_first_non_finite_cell_and_which_field_it_was_in() {
+        let mut fluid = tank();
+        let i = 3;
+        let j = 2;
+        let idx = fluid.idx(i, j);
+        fluid.v[idx] = f64::NAN;
+        // `u` is scanned first, so a later NaN placed only in `v` should
+        // still surface as `v`, not get shadowed by `u` reporting clean.
+        assert_eq!(fluid.validate(), Err(InstabilityReport { field: "v", i, j }));
+    }
+
+    /// A few normal-`dt` steps of `Scene::wind_tunnel_with_radiator` carve
+    /// out a low-velocity wake directly behind the radiator. Re-advecting
+    /// that same field with one deliberately huge `dt` should still show a
+    /// slower wake than a single oversized step that re-samples clean
+    /// free-stream velocity from far upstream in one jump.
+    #[test]
+    fn a_thin_radiators_wake_survives_a_deliberately_huge_advection_step_better_than_a_single_jump() {
+        let mut scene = crate::scene::Scene::wind_tunnel_with_radiator(60, 30);
+        for _ in 0..20 {
+            scene.simulate();
+        }
+        let radiator = &scene.obstacles.radiators()[0];
+        let probe = scene
+            .fluid
+            .idx((radiator.center_x / scene.fluid.h) as usize + 2, (radiator.center_y / scene.fluid.h) as usize);
+        let wake_u_before = scene.fluid.u[probe];
+        assert!(
+            wake_u_before < scene.inflow_u * 0.9,
+            "expected 20 steps to have already carved out a slower wake behind the radiator, got {wake_u_before} vs inflow {}",
+            scene.inflow_u
+        );
+
+        let mut substepped = scene.fluid;
+        let mut single_step = substepped.clone();
+        single_step.advection_cfl_threshold = f64::MAX;
+
+        substepped.advect_vel(10.0); // deliberately huge dt
+        single_step.advect_vel(10.0);
+
+        assert!(
+            substepped.u[probe] < single_step.u[probe],
+            "sub-stepping should preserve more of the wake ({}) than a single oversized jump back to free-stream ({})",
+            substepped.u[probe],
+            single_step.u[probe]
+        );
+    }
+
+    /// The comparison the multigrid request asked for: run the same tank
+    /// case's pressure solve both ways and check they land on the same
+    /// (near-zero) divergence — 200 single-grid GS iterations vs far fewer
+    /// equivalent fine-grid sweeps under multigrid (`levels * v_cycles *
+    /// smoothing_iters * 2` red-black sweeps, here `3 * 4 * 4 * 2 = 96`,
+    /// most of which run on grids far smaller than the fine one).
+    #[test]
+    fn multigrid_reaches_the_same_divergence_as_two_hundred_gauss_seidel_iterations() {
+        let mut gs = tank();
+        gs.solve_incompressibility(200, 1.0 / 60.0, 1.9);
+        let gs_max_div = gs.max_divergence();
+
+        let mut mg = tank();
+        mg.solve_incompressibility_multigrid(3, 4, 4, 1.0 / 60.0);
+        let mg_max_div = mg.max_divergence();
+
+        assert!(gs_max_div < 1e-3, "200 GS iterations should have converged the tank case, got {gs_max_div}");
+        assert!(
+            mg_max_div < 1e-3,
+            "multigrid should reach comparably small divergence, got {mg_max_div} (GS reference: {gs_max_div})"
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! Load obstacle outlines exported from CAD (SVG paths, DXF polylines)
+//! instead of hand-typing `ObstacleShape::Polygon` vertices.
+//!
+//! Supports the subset both formats need for a flat 2D cross-section: SVG
+//! `<path d="...">` data restricted to `M`/`L`/`C`/`Z` commands (absolute
+//! coordinates only — no relative `m`/`l`/`c`, arcs, or transforms), and DXF
+//! `LWPOLYLINE`/`POLYLINE` entities. Anything else in the file is ignored
+//! rather than rejected, since a real CAD export typically carries layers,
+//! dimensions, and other entities this crate has no use for; only a
+//! genuinely malformed path/entity (unparseable numbers, an unclosed
+//! command) is an error.
+//!
+//! Every subpath/entity becomes one [`ObstacleShape::Polygon`], scaled and
+//! translated by the caller-supplied `scale`/`offset` (CAD units are
+//! typically millimeters; `scale = 0.001` converts to this solver's meters).
+
+use crate::scene_config::ObstacleShape;
+
+/// Cubic Bezier segments are subdivided until consecutive chord midpoints
+/// differ from the true curve by less than this fraction of the segment's
+/// own chord length — not a resolution in domain units, since callers apply
+/// wildly different `scale` values, but relative to each curve's own size.
+const DEFAULT_FLATTEN_TOLERANCE: f64 = 0.005;
+
+/// Load one or more polygon obstacles from an SVG or DXF file, selecting
+/// the parser by the file's extension (case-insensitive `.svg` or `.dxf`;
+/// anything else is an error naming the path). `scale` is applied before
+/// `offset` (`world = raw * scale + offset`).
+pub fn load_geometry(path: &str, scale: f64, offset: (f64, f64)) -> Result<Vec<ObstacleShape>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let raw_polygons = match path.rsplit('.').next().map(|ext| ext.to_ascii_lowercase()) {
+        Some(ext) if ext == "svg" => parse_svg(&text).map_err(|e| format!("{path}: {e}"))?,
+        Some(ext) if ext == "dxf" => parse_dxf(&text).map_err(|e| format!("{path}: {e}"))?,
+        _ => return Err(format!("{path}: unrecognized geometry file extension (expected .svg or .dxf)")),
+    };
+    if raw_polygons.is_empty() {
+        return Err(format!("{path}: no polygon outlines found"));
+    }
+    raw_polygons
+        .into_iter()
+        .map(|vertices| {
+            let vertices = vertices.into_iter().map(|(x, y)| (x * scale + offset.0, y * scale + offset.1)).collect();
+            ObstacleShape::new_polygon(vertices).map_err(|e| format!("{path}: {e}"))
+        })
+        .collect()
+}
+
+/// Parse every `<path d="...">` in an SVG document into a flattened vertex
+/// list, one per path. Only the `d` attribute is read; styling, groups, and
+/// transforms elsewhere in the file are ignored.
+fn parse_svg(text: &str) -> Result<Vec<Vec<(f64, f64)>>, String> {
+    let mut polygons = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("d=\"") {
+        let after_attr = &rest[start + 3..];
+        let end = after_attr.find('"').ok_or("unterminated `d` attribute in SVG path")?;
+        polygons.push(flatten_svg_path(&after_attr[..end])?);
+        rest = &after_attr[end + 1..];
+    }
+    Ok(polygons)
+}
+
+/// Flatten one SVG path `d` attribute (absolute `M`/`L`/`C`/`Z` only) into
+/// line-segment vertices.
+fn flatten_svg_path(d: &str) -> Result<Vec<(f64, f64)>, String> {
+    let tokens = tokenize_svg_path(d);
+    let mut vertices = Vec::new();
+    let mut cursor = (0.0, 0.0);
+    let mut i = 0;
+    while i < tokens.len() {
+        match tokens[i] {
+            SvgToken::Command('M') | SvgToken::Command('L') => {
+                i += 1;
+                let (x, y) = read_xy(&tokens, &mut i)?;
+                cursor = (x, y);
+                vertices.push(cursor);
+            }
+            SvgToken::Command('C') => {
+                i += 1;
+                let c1 = read_xy(&tokens, &mut i)?;
+                let c2 = read_xy(&tokens, &mut i)?;
+                let end = read_xy(&tokens, &mut i)?;
+                flatten_cubic_bezier(cursor, c1, c2, end, DEFAULT_FLATTEN_TOLERANCE, &mut vertices);
+                cursor = end;
+            }
+            SvgToken::Command('Z') | SvgToken::Command('z') => {
+                i += 1;
+            }
+            SvgToken::Command(other) => {
+                return Err(format!("unsupported SVG path command '{other}' (only M/L/C/Z are supported)"));
+            }
+            SvgToken::Number(_) => {
+                return Err("SVG path has a number where a command letter was expected".to_string());
+            }
+        }
+    }
+    Ok(vertices)
+}
+
+enum SvgToken {
+    Command(char),
+    Number(f64),
+}
+
+fn tokenize_svg_path(d: &str) -> Vec<SvgToken> {
+    let mut tokens = Vec::new();
+    let mut chars = d.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_alphabetic() {
+            tokens.push(SvgToken::Command(c));
+            chars.next();
+        } else if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' {
+            let mut num = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' || c == 'e' || c == 'E' {
+                    num.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            if let Ok(value) = num.parse::<f64>() {
+                tokens.push(SvgToken::Number(value));
+            }
+        } else {
+            // Commas and whitespace are separators, not tokens.
+            chars.next();
+        }
+    }
+    tokens
+}
+
+/// Read one `(x, y)` coordinate pair starting at `*i`, without consuming a
+/// leading command letter — callers skip that once per command, since a
+/// multi-point command (e.g. `C`'s three points) has none between points.
+fn read_xy(tokens: &[SvgToken], i: &mut usize) -> Result<(f64, f64), String> {
+    let x = match tokens.get(*i) {
+        Some(SvgToken::Number(v)) => *v,
+        _ => return Err("SVG path command is missing an x coordinate".to_string()),
+    };
+    *i += 1;
+    let y = match tokens.get(*i) {
+        Some(SvgToken::Number(v)) => *v,
+        _ => return Err("SVG path command is missing a y coordinate".to_string()),
+    };
+    *i += 1;
+    Ok((x, y))
+}
+
+/// Recursively subdivide a cubic Bezier until it's flat enough (de
+/// Casteljau's algorithm), appending the resulting line-segment endpoints
+/// (not including `p0`, already the current path point) to `out`.
+fn flatten_cubic_bezier(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64), p3: (f64, f64), tolerance: f64, out: &mut Vec<(f64, f64)>) {
+    let chord = (p3.0 - p0.0).hypot(p3.1 - p0.1);
+    let flat_enough = chord < 1e-12
+        || (point_to_line_distance(p1, p0, p3) < tolerance * chord.max(1e-12)
+            && point_to_line_distance(p2, p0, p3) < tolerance * chord.max(1e-12));
+    if flat_enough {
+        out.push(p3);
+        return;
+    }
+    let mid = |a: (f64, f64), b: (f64, f64)| ((a.0 + b.0) * 0.5, (a.1 + b.1) * 0.5);
+    let p01 = mid(p0, p1);
+    let p12 = mid(p1, p2);
+    let p23 = mid(p2, p3);
+    let p012 = mid(p01, p12);
+    let p123 = mid(p12, p23);
+    let p0123 = mid(p012, p123);
+    flatten_cubic_bezier(p0, p01, p012, p0123, tolerance, out);
+    flatten_cubic_bezier(p0123, p123, p23, p3, tolerance, out);
+}
+
+fn point_to_line_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let len = (b.0 - a.0).hypot(b.1 - a.1);
+    if len < 1e-12 {
+        return (p.0 - a.0).hypot(p.1 - a.1);
+    }
+    ((b.0 - a.0) * (a.1 - p.1) - (a.0 - p.0) * (b.1 - a.1)).abs() / len
+}
+
+/// Parse every `LWPOLYLINE`/`POLYLINE` entity in a DXF file's group-code/
+/// value pairs into a vertex list, one per entity. `POLYLINE` vertices come
+/// from separate trailing `VERTEX` entities up to the next `SEQEND`;
+/// `LWPOLYLINE` carries its vertices inline as repeated group codes 10/20.
+fn parse_dxf(text: &str) -> Result<Vec<Vec<(f64, f64)>>, String> {
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    let mut pairs = Vec::new();
+    let mut i = 0;
+    while i + 1 < lines.len() {
+        let code: i32 = lines[i].parse().map_err(|_| format!("non-numeric DXF group code {:?}", lines[i]))?;
+        pairs.push((code, lines[i + 1]));
+        i += 2;
+    }
+
+    let mut polygons = Vec::new();
+    let mut j = 0;
+    while j < pairs.len() {
+        let (code, value) = pairs[j];
+        if code == 0 && value == "LWPOLYLINE" {
+            let mut vertices = Vec::new();
+            let mut pending_x = None;
+            j += 1;
+            while j < pairs.len() && pairs[j].0 != 0 {
+                let (code, value) = pairs[j];
+                match code {
+                    10 => pending_x = Some(parse_dxf_f64(value)?),
+                    20 => {
+                        let x = pending_x.take().ok_or("DXF LWPOLYLINE vertex has a y (group 20) with no preceding x (group 10)")?;
+                        vertices.push((x, parse_dxf_f64(value)?));
+                    }
+                    _ => {}
+                }
+                j += 1;
+            }
+            polygons.push(vertices);
+        } else if code == 0 && value == "POLYLINE" {
+            let mut vertices = Vec::new();
+            j += 1;
+            while j < pairs.len() && !(pairs[j].0 == 0 && pairs[j].1 == "SEQEND") {
+                if pairs[j].0 == 0 && pairs[j].1 == "VERTEX" {
+                    let mut x = None;
+                    let mut y = None;
+                    j += 1;
+                    while j < pairs.len() && pairs[j].0 != 0 {
+                        match pairs[j].0 {
+                            10 => x = Some(parse_dxf_f64(pairs[j].1)?),
+                            20 => y = Some(parse_dxf_f64(pairs[j].1)?),
+                            _ => {}
+                        }
+                        j += 1;
+                    }
+                    let (x, y) = (
+                        x.ok_or("DXF VERTEX entity is missing its x (group 10) value")?,
+                        y.ok_or("DXF VERTEX entity is missing its y (group 20) value")?,
+                    );
+                    vertices.push((x, y));
+                } else {
+                    j += 1;
+                }
+            }
+            polygons.push(vertices);
+        } else {
+            j += 1;
+        }
+    }
+    Ok(polygons)
+}
+
+fn parse_dxf_f64(value: &str) -> Result<f64, String> {
+    value.parse().map_err(|_| format!("could not parse DXF coordinate {value:?} as a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn svg_rectangle_path_flattens_to_its_four_corners() {
+        let vertices = flatten_svg_path("M 0 0 L 10 0 L 10 5 L 0 5 Z").unwrap();
+        assert_eq!(vertices, vec![(0.0, 0.0), (10.0, 0.0), (10.0, 5.0), (0.0, 5.0)]);
+    }
+
+    #[test]
+    fn svg_unsupported_command_names_itself_in_the_error() {
+        let err = flatten_svg_path("M 0 0 A 5 5 0 0 1 10 10").unwrap_err();
+        assert!(err.contains('A'), "error should name the offending command: {err}");
+    }
+
+    #[test]
+    fn svg_cubic_bezier_flattens_to_a_convex_arc_that_bulges_toward_the_control_points() {
+        let vertices = flatten_svg_path("M 0 0 C 0 10 10 10 10 0").unwrap();
+        assert!(vertices.len() > 2, "a curved segment should flatten to more than its two endpoints");
+        assert_eq!(*vertices.last().unwrap(), (10.0, 0.0));
+        // Every flattened point should bulge toward the control points
+        // (positive y), not fall on the straight chord from (0,0) to (10,0).
+        assert!(vertices.iter().any(|&(_, y)| y > 1.0));
+    }
+
+    #[test]
+    fn dxf_lwpolyline_vertices_round_trip() {
+        let dxf = "0\nLWPOLYLINE\n10\n0.0\n20\n0.0\n10\n10.0\n20\n0.0\n10\n10.0\n20\n5.0\n0\nENDSEC\n";
+        let polygons = parse_dxf(dxf).unwrap();
+        assert_eq!(polygons, vec![vec![(0.0, 0.0), (10.0, 0.0), (10.0, 5.0)]]);
+    }
+
+    #[test]
+    fn dxf_polyline_reads_vertices_up_to_seqend() {
+        let dxf = "0\nPOLYLINE\n0\nVERTEX\n10\n0.0\n20\n0.0\n0\nVERTEX\n10\n1.0\n20\n0.0\n0\nVERTEX\n10\n1.0\n20\n1.0\n0\nSEQEND\n";
+        let polygons = parse_dxf(dxf).unwrap();
+        assert_eq!(polygons, vec![vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)]]);
+    }
+
+    #[test]
+    fn load_geometry_applies_scale_then_offset() {
+        let dir = std::env::temp_dir().join(format!("lgr_2d_cfd_geometry_io_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("triangle.svg");
+        std::fs::write(&path, r#"<svg><path d="M 0 0 L 100 0 L 0 100 Z"/></svg>"#).unwrap();
+
+        let shapes = load_geometry(path.to_str().unwrap(), 0.01, (1.0, 2.0)).unwrap();
+        assert_eq!(shapes.len(), 1);
+        match &shapes[0] {
+            ObstacleShape::Polygon { vertices } => {
+                assert_eq!(vertices, &vec![(1.0, 2.0), (2.0, 2.0), (1.0, 3.0)]);
+            }
+            other => panic!("expected a Polygon, got {other:?}"),
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn load_geometry_rejects_an_unrecognized_extension() {
+        let err = load_geometry("outline.step", 1.0, (0.0, 0.0)).unwrap_err();
+        assert!(err.contains("outline.step"));
+    }
+
+    #[test]
+    fn load_geometry_names_the_missing_file_in_its_error() {
+        let err = load_geometry("/nonexistent/does_not_exist.svg", 1.0, (0.0, 0.0)).unwrap_err();
+        assert!(err.contains("does_not_exist.svg"));
+    }
+}
@@ -0,0 +1,169 @@
+//! How `Scene::apply_inflow` distributes velocity across the inlet
+//! column's rows. [`InflowProfile::Uniform`] is the original behavior (every
+//! row set to the same free-stream value), which is unrealistic for a
+//! radiator sitting behind bodywork or in a sidepod duct: real inlets carry
+//! a boundary layer, or a measured profile from upstream CFD/wind-tunnel
+//! data, not a flat block of flow. The other variants model that instead.
+//!
+//! `y` is always measured from the bottom wall (`j = 0`), in the same
+//! physical units as `Fluid::domain_height()`.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum InflowProfile {
+    /// Every row at the scene's `inflow_u`, ramp included — the behavior
+    /// every scene had before this enum existed.
+    #[default]
+    Uniform,
+    /// A classic channel-flow parabola peaking at `max` on the domain
+    /// centerline and going to zero at both walls, scaled by the same ramp
+    /// `Scene::inflow_ramp_time` applies to `Uniform`.
+    Parabolic { max: f64 },
+    /// A 1/n power-law boundary layer: `inflow_u * (y / delta).min(1.0).powf(exponent)`,
+    /// reaching the free-stream value `inflow_u` at `y = delta` and staying
+    /// there for the rest of the domain above it. `exponent = 1.0 / 7.0` is
+    /// the classic atmospheric/automotive 1/7th-power profile.
+    PowerLawBoundaryLayer { delta: f64, exponent: f64 },
+    /// A measured profile: `(y, u)` pairs sorted by `y`, linearly
+    /// interpolated between samples and clamped to the nearest sample's `u`
+    /// outside the measured range. See [`InflowProfile::from_csv`].
+    Table(Vec<(f64, f64)>),
+}
+
+impl InflowProfile {
+    /// The (unramped) inflow velocity at height `y` in a domain of height
+    /// `domain_height`, given the scene's own `inflow_u`. `Scene::apply_inflow`
+    /// multiplies this by the ramp fraction and resolves it into `u`/`v` via
+    /// `inflow_angle`, the same as it always has for `Uniform`.
+    pub fn value_at(&self, y: f64, domain_height: f64, inflow_u: f64) -> f64 {
+        match self {
+            InflowProfile::Uniform => inflow_u,
+            InflowProfile::Parabolic { max } => {
+                if domain_height <= 0.0 {
+                    return 0.0;
+                }
+                max * 4.0 * y * (domain_height - y) / domain_height.powi(2)
+            }
+            InflowProfile::PowerLawBoundaryLayer { delta, exponent } => {
+                if *delta <= 0.0 {
+                    return inflow_u;
+                }
+                inflow_u * (y / delta).clamp(0.0, 1.0).powf(*exponent)
+            }
+            InflowProfile::Table(points) => table_value(points, y),
+        }
+    }
+
+    /// Load a [`InflowProfile::Table`] from a two-column `y,u` CSV file, one
+    /// sample per line. A first line that doesn't parse as two numbers is
+    /// treated as a header and skipped, matching `geometry_io`'s "only a
+    /// genuinely malformed row is an error" tolerance. Samples don't need to
+    /// already be sorted by `y`; this sorts them so [`Self::value_at`] can
+    /// assume that.
+    pub fn from_csv(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+        let mut points = Vec::new();
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            match parse_csv_row(line) {
+                Some(pair) => points.push(pair),
+                None if line_no == 0 => continue,
+                None => return Err(format!("{path}:{}: expected `y,u`, got {line:?}", line_no + 1)),
+            }
+        }
+        if points.is_empty() {
+            return Err(format!("{path}: no data rows found"));
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(InflowProfile::Table(points))
+    }
+}
+
+fn parse_csv_row(line: &str) -> Option<(f64, f64)> {
+    let (y, u) = line.split_once(',')?;
+    Some((y.trim().parse().ok()?, u.trim().parse().ok()?))
+}
+
+/// Linear interpolation over `points` (already sorted by `y`), clamped to
+/// the endpoint samples outside the measured range.
+fn table_value(points: &[(f64, f64)], y: f64) -> f64 {
+    if points.is_empty() {
+        return 0.0;
+    }
+    if y <= points[0].0 {
+        return points[0].1;
+    }
+    if y >= points[points.len() - 1].0 {
+        return points[points.len() - 1].1;
+    }
+    let hi = points.iter().position(|&(py, _)| py >= y).unwrap_or(points.len() - 1);
+    let lo = hi - 1;
+    let (y0, u0) = points[lo];
+    let (y1, u1) = points[hi];
+    if (y1 - y0).abs() < 1e-12 {
+        return u0;
+    }
+    u0 + (u1 - u0) * (y - y0) / (y1 - y0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uniform_ignores_position_and_returns_inflow_u() {
+        let profile = InflowProfile::Uniform;
+        assert_eq!(profile.value_at(0.0, 1.0, 2.0), 2.0);
+        assert_eq!(profile.value_at(0.5, 1.0, 2.0), 2.0);
+    }
+
+    #[test]
+    fn parabolic_peaks_at_max_on_the_centerline_and_vanishes_at_the_walls() {
+        let profile = InflowProfile::Parabolic { max: 4.0 };
+        assert!((profile.value_at(0.5, 1.0, 0.0) - 4.0).abs() < 1e-9);
+        assert!(profile.value_at(0.0, 1.0, 0.0).abs() < 1e-9);
+        assert!(profile.value_at(1.0, 1.0, 0.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn power_law_boundary_layer_reaches_free_stream_at_delta_and_stays_there() {
+        let profile = InflowProfile::PowerLawBoundaryLayer { delta: 0.2, exponent: 1.0 / 7.0 };
+        assert!(profile.value_at(0.0, 1.0, 10.0).abs() < 1e-9);
+        assert!((profile.value_at(0.2, 1.0, 10.0) - 10.0).abs() < 1e-9);
+        assert!((profile.value_at(0.5, 1.0, 10.0) - 10.0).abs() < 1e-9, "above delta should stay at free stream");
+        let mid = profile.value_at(0.1, 1.0, 10.0);
+        assert!(mid > 0.0 && mid < 10.0, "partway through the boundary layer should be strictly between 0 and free stream, got {mid}");
+    }
+
+    #[test]
+    fn table_interpolates_between_samples_and_clamps_outside_the_range() {
+        let profile = InflowProfile::Table(vec![(0.0, 0.0), (1.0, 2.0)]);
+        assert!((profile.value_at(0.5, 1.0, 0.0) - 1.0).abs() < 1e-9);
+        assert_eq!(profile.value_at(-1.0, 1.0, 0.0), 0.0);
+        assert_eq!(profile.value_at(5.0, 1.0, 0.0), 2.0);
+    }
+
+    #[test]
+    fn from_csv_skips_a_non_numeric_header_and_sorts_by_y() {
+        let dir = std::env::temp_dir().join(format!("lgr_2d_cfd_inflow_profile_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("profile.csv");
+        std::fs::write(&path, "y,u\n1.0,2.0\n0.0,0.0\n").unwrap();
+
+        let profile = InflowProfile::from_csv(path.to_str().unwrap()).unwrap();
+        assert_eq!(profile, InflowProfile::Table(vec![(0.0, 0.0), (1.0, 2.0)]));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn from_csv_names_the_missing_file_in_its_error() {
+        let err = InflowProfile::from_csv("/nonexistent/does_not_exist.csv").unwrap_err();
+        assert!(err.contains("does_not_exist.csv"));
+    }
+}
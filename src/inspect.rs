@@ -0,0 +1,38 @@
+//! `inspect` subcommand: query a completed run's `manifest.json`.
+
+use crate::output::OutputManager;
+
+#[derive(clap::Subcommand, Debug)]
+pub enum InspectCommand {
+    /// List every artifact recorded for a given solver step.
+    ManifestLookup {
+        #[arg(long)]
+        step: u64,
+    },
+}
+
+pub fn run(output_dir: &str, command: InspectCommand) -> std::io::Result<()> {
+    let manifest = OutputManager::load_manifest(output_dir)?;
+    match command {
+        InspectCommand::ManifestLookup { step } => {
+            let matches = manifest.lookup_step(step);
+            if matches.is_empty() {
+                println!("no artifacts recorded for step {}", step);
+            }
+            for artifact in matches {
+                println!(
+                    "step={} t={:.5} kind={:?} frame_index={} path={}",
+                    artifact.step,
+                    artifact.sim_time,
+                    artifact.kind,
+                    artifact
+                        .frame_index
+                        .map(|f| f.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    artifact.path
+                );
+            }
+        }
+    }
+    Ok(())
+}
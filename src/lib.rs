@@ -0,0 +1,63 @@
+//! Library surface for the LGR 2D CFD sandbox: the staggered-grid solver
+//! ([`fluid`]), the porous-radiator and solid-obstacle model
+//! ([`radiator`], [`radiator_model`], [`obstacle_manager`],
+//! [`obstacle_analysis`]), and [`scene::Scene`] tying them together into a
+//! steppable unit. The `lgr_2d_cfd` binary (`src/main.rs`) is a CLI built on
+//! top of this; `examples/` shows the same API used directly.
+
+pub mod animator;
+pub mod convergence;
+pub mod cost_estimate;
+pub mod dye_emitter;
+pub mod field_history;
+pub mod field_statistics;
+pub mod fluid;
+pub mod geometry_io;
+pub mod inflow_profile;
+pub mod inspect;
+pub mod line_profile;
+pub mod metrics;
+pub mod moving_obstacle;
+pub mod multigrid;
+pub mod obstacle_analysis;
+pub mod obstacle_manager;
+pub mod objective;
+pub mod output;
+pub mod paint;
+pub mod parallel_runs;
+pub mod particle_tracer;
+/// PyO3 bindings for driving the solver from Python (config dict in,
+/// stepping, numpy views of the fields, radiator/obstacle setup, metrics
+/// out). Only compiled with `--features python`; the plain `cargo build`
+/// never touches pyo3/numpy at all.
+#[cfg(feature = "python")]
+pub mod python;
+pub mod radiator;
+pub mod radiator_model;
+pub mod render;
+pub mod report;
+pub mod run_metadata;
+pub mod scene;
+pub mod scene_config;
+pub mod shedding;
+pub mod steady_state;
+pub mod streamline_export;
+pub mod sweep;
+pub mod text;
+pub mod timing;
+pub mod turbulence;
+pub mod tutorial;
+pub mod units;
+pub mod visualizer;
+/// Live desktop window driven by `--live`. Only compiled with
+/// `--features viewer`, which pulls in the optional `minifb` dependency.
+#[cfg(feature = "viewer")]
+pub mod viewer;
+pub mod vortex_induced_body;
+pub mod vtk;
+pub mod wake_trigger;
+/// `wasm-bindgen` bindings for a browser canvas demo. Only compiled with
+/// `--features wasm`, cross-compiled to `wasm32-unknown-unknown`.
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod working_fluid;
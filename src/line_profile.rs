@@ -0,0 +1,14 @@
+//! CSV export of a 1D field profile along an arbitrary line
+//! ([`crate::fluid::Fluid::extract_line`]), for comparing a run against a
+//! hand calc or a paper's reported profile without re-deriving it from the
+//! full field.
+
+/// `distance,value` — one row per sample, in the order `extract_line`
+/// produced them (monotonically increasing distance from the line's start).
+pub fn write_csv(rows: &[(f64, f64)], path: &str) -> std::io::Result<()> {
+    let mut csv = String::from("distance,value\n");
+    for (distance, value) in rows {
+        csv.push_str(&format!("{distance},{value}\n"));
+    }
+    std::fs::write(path, csv)
+}
@@ -0,0 +1,1042 @@
+//! Radiator performance metrics, each paired with a short machine-readable
+//! definition (formula, reference quantities, assumptions) so the meaning
+//! of a number in the summary JSON or HTML report can never drift from the
+//! code that computed it. Definitions live right next to the fields they
+//! describe, and [`RadiatorMetrics::definition`] plus the accompanying test
+//! keep the two in lockstep.
+
+use serde::{Deserialize, Serialize};
+
+use crate::fluid::{FieldType, Fluid, SOLID_CELL};
+use crate::radiator::{HeatExchangerPerformance, Radiator};
+
+/// Which point in a simulation step a [`RadiatorMetrics`] snapshot's
+/// velocity samples came from. Advection and the outflow/extrapolation
+/// boundary handling run after the pressure projection, so `Fluid::u` at the
+/// very end of a step (`EndOfStep`) is not the divergence-free field the
+/// projection just solved for — it can carry back a small amount of
+/// numerically reintroduced divergence, biasing mass-flow and through-face
+/// velocity metrics by an amount that grows with `dt` and shrinks with grid
+/// resolution. `PostProjection` samples the field immediately after the
+/// pressure solve instead, before anything downstream can perturb it again;
+/// see `Scene::post_projection_u` for how that snapshot is captured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricsSamplingPoint {
+    #[default]
+    EndOfStep,
+    PostProjection,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RadiatorMetrics {
+    /// Estimated fan power (density * area * v^3 / 2) needed to push the
+    /// same volume flow through the radiator's measured pressure drop.
+    pub fan_power_required: f64,
+    /// Ratio of mass flow actually crossing the radiator footprint to the
+    /// mass flow that would cross the same area with undisturbed inflow.
+    pub capture_ratio: f64,
+    /// Non-dimensional pressure drop across the radiator, `dp / (0.5 * rho * v_in^2)`.
+    pub loss_coefficient: f64,
+    /// Volumetric flow through the radiator footprint per unit depth,
+    /// `avg_through_u * radiator_height`.
+    pub mass_flow: f64,
+    /// Heat actually rejected from coolant to air, W, from
+    /// [`Radiator::analyze_performance`]'s epsilon-NTU model. 0 if the
+    /// radiator has no [`crate::radiator::HeatExchanger`] attached.
+    pub heat_rejected_watts: f64,
+    /// Epsilon, the fraction of the maximum thermodynamically possible heat
+    /// transfer actually achieved. 0 if the radiator has no
+    /// [`crate::radiator::HeatExchanger`] attached.
+    pub effectiveness: f64,
+    /// Radiator frontal area (per unit depth): its height.
+    pub frontal_area: f64,
+    /// Tunnel cross-sectional area (per unit depth): the domain height
+    /// passed into [`RadiatorMetrics::compute`].
+    pub tunnel_area: f64,
+    /// `frontal_area / tunnel_area`. A large radiator in a narrow tunnel
+    /// pushes measured drag and pressure drop up relative to what an
+    /// unbounded (infinite-tunnel) flow would show; this is the standard
+    /// blockage-ratio input to a solid-blockage correction.
+    pub blockage_ratio: f64,
+    /// Thom's 2D solid-blockage correction factor, `blockage_ratio / 4`.
+    /// Scales linearly with `blockage_ratio`, so at fixed radiator size,
+    /// doubling tunnel (domain) height halves this factor.
+    pub blockage_correction_factor: f64,
+    /// Pressure drop across the radiator as measured in this (blocked)
+    /// tunnel, `0.5 * rho * (inflow_u^2 - avg_through_u^2)`.
+    pub pressure_drop_raw: f64,
+    /// `pressure_drop_raw` corrected to what an unbounded tunnel would have
+    /// shown at the same corrected freestream velocity,
+    /// `pressure_drop_raw / (1 + blockage_correction_factor)^2`.
+    pub pressure_drop_corrected: f64,
+    /// Net streamwise force per unit depth the pressure drop implies across
+    /// the radiator's frontal area, `pressure_drop_raw * frontal_area` —
+    /// this crate's only force estimate for a *porous* obstacle (solid
+    /// obstacles use `obstacle_analysis::compute_obstacle_forces` instead,
+    /// which integrates measured cell-face pressures rather than inferring
+    /// force from a lumped pressure drop).
+    pub drag_raw: f64,
+    /// `drag_raw` corrected the same way as `pressure_drop_corrected`.
+    pub drag_corrected: f64,
+    /// Streamwise drag per unit depth from integrating the momentum deficit
+    /// across a wake survey line, `sum(rho * u * (inflow_u - u) * dy)` — see
+    /// [`wake_survey`]. A direct velocity-deficit measurement, independent
+    /// of both `drag_raw`'s pressure-drop estimate and
+    /// [`Radiator::compute_forces`]'s pressure-integration estimate. 0 until
+    /// [`RadiatorMetrics::with_wake_survey`] has been called, since no scene
+    /// geometry is available here to place the survey line.
+    pub drag_wake_survey: f64,
+    /// Standard flow-uniformity index across the radiator face, see
+    /// [`flow_uniformity`]: `1.0` is perfectly even flow, lower values are
+    /// more uneven. `0.0` until [`RadiatorMetrics::with_flow_uniformity`]
+    /// has been called, since (like `drag_wake_survey`) computing it needs
+    /// the radiator geometry this struct doesn't otherwise keep a handle
+    /// on -- also `0.0` if the sampled mean face-normal velocity comes out
+    /// ~zero or reversed, since the index formula is undefined there.
+    pub flow_uniformity_index: f64,
+    /// Fraction of [`flow_uniformity`]'s face samples with negative
+    /// (reversed) face-normal velocity. `0.0` until
+    /// [`RadiatorMetrics::with_reversed_flow_analysis`] has been called.
+    pub reversed_flow_fraction: f64,
+    /// Area (m^2, per unit depth) of the recirculation bubble behind the
+    /// radiator, from [`recirculation_area`]. `0.0` until
+    /// [`RadiatorMetrics::with_reversed_flow_analysis`] has been called.
+    pub recirculation_area: f64,
+}
+
+pub struct MetricDefinition {
+    pub name: &'static str,
+    pub formula: &'static str,
+    pub inputs: &'static str,
+    pub assumptions: &'static str,
+}
+
+impl RadiatorMetrics {
+    /// `domain_height` is the tunnel's cross-sectional extent (per unit
+    /// depth) the radiator sits inside — `fluid.num_y as f64 * fluid.h` for
+    /// a scene built by [`crate::scene::Scene::wind_tunnel_with_radiator`]
+    /// or [`crate::scene::Scene::wind_tunnel_with_radiator_sized`] — used
+    /// only for the blockage-ratio/correction fields below; every other
+    /// metric is unaffected by it.
+    ///
+    /// Every formula here reads only the `u` (streamwise) component of
+    /// velocity, both for `fluid`'s field and for `inflow_u` itself — there
+    /// is no independent `v`-component term anywhere below. That is exactly
+    /// right for this crate's original horizontal-only inflow, but a scene
+    /// with a nonzero [`crate::scene::Scene::inflow_angle`] has a real `v`
+    /// component this omits: `capture_ratio`, `loss_coefficient`, and every
+    /// pressure/drag figure below are all relative to the streamwise
+    /// projection of the free stream, not its true magnitude.
+    pub fn compute(fluid: &Fluid, radiator: &Radiator, inflow_u: f64, domain_height: f64) -> Self {
+        Self::compute_from_u(fluid, &fluid.u, radiator, inflow_u, domain_height)
+    }
+
+    /// Same as [`Self::compute`], but reads through-radiator velocity from
+    /// `post_projection_u` (the divergence-free field from immediately after
+    /// the pressure solve) instead of `fluid.u`'s current, fully-stepped
+    /// state. Every other input (radiator geometry, density, grid) still
+    /// comes from `fluid` — only the velocity samples differ. See
+    /// [`MetricsSamplingPoint`] for why the two can disagree.
+    pub fn compute_post_projection(
+        fluid: &Fluid,
+        post_projection_u: &[f64],
+        radiator: &Radiator,
+        inflow_u: f64,
+        domain_height: f64,
+    ) -> Self {
+        Self::compute_from_u(fluid, post_projection_u, radiator, inflow_u, domain_height)
+    }
+
+    /// Same as [`Self::compute`], but reads through-radiator velocity from
+    /// `stats.mean_u()` (a run-length average, see
+    /// [`crate::field_statistics::FieldStatistics`]) instead of a single
+    /// instantaneous `fluid.u`. For an unsteady wake, the final snapshot
+    /// `compute` reads can land anywhere in a shedding cycle; this instead
+    /// reports the time-averaged capture ratio, pressure drop, etc. Every
+    /// other input (radiator geometry, density, grid) still comes from
+    /// `fluid`, exactly like `compute_post_projection`.
+    pub fn compute_from_mean(
+        fluid: &Fluid,
+        stats: &crate::field_statistics::FieldStatistics,
+        radiator: &Radiator,
+        inflow_u: f64,
+        domain_height: f64,
+    ) -> Self {
+        Self::compute_from_u(fluid, stats.mean_u(), radiator, inflow_u, domain_height)
+    }
+
+    fn compute_from_u(fluid: &Fluid, u: &[f64], radiator: &Radiator, inflow_u: f64, domain_height: f64) -> Self {
+        let n = fluid.num_y;
+        let h = fluid.h;
+
+        let mut through_flow = 0.0;
+        let mut cell_count = 0.0;
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                let x = i as f64 * h;
+                let y = j as f64 * h;
+                if radiator.contains(x, y) {
+                    through_flow += u[i * n + j].max(0.0);
+                    cell_count += 1.0;
+                }
+            }
+        }
+        let avg_through_u = if cell_count > 0.0 {
+            through_flow / cell_count
+        } else {
+            0.0
+        };
+
+        let capture_ratio = if inflow_u.abs() > 1e-9 {
+            (avg_through_u / inflow_u).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let dp = 0.5 * fluid.density * (inflow_u * inflow_u - avg_through_u * avg_through_u);
+        let loss_coefficient = if inflow_u.abs() > 1e-9 {
+            dp / (0.5 * fluid.density * inflow_u * inflow_u)
+        } else {
+            0.0
+        };
+
+        let frontal_area = radiator.height;
+        let mass_flow = avg_through_u * frontal_area;
+        let fan_power_required = dp.max(0.0) * mass_flow;
+        let air_mass_flow_kg_s = mass_flow * fluid.density;
+        let performance = radiator.analyze_performance(air_mass_flow_kg_s).unwrap_or(HeatExchangerPerformance {
+            heat_rejected_watts: 0.0,
+            effectiveness: 0.0,
+            air_temp_rise_c: 0.0,
+        });
+
+        let tunnel_area = domain_height;
+        let blockage_ratio = if tunnel_area.abs() > 1e-9 { frontal_area / tunnel_area } else { 0.0 };
+        let blockage_correction_factor = blockage_ratio / 4.0;
+        let pressure_drop_raw = dp;
+        let pressure_drop_corrected = pressure_drop_raw / (1.0 + blockage_correction_factor).powi(2);
+        let drag_raw = pressure_drop_raw * frontal_area;
+        let drag_corrected = drag_raw / (1.0 + blockage_correction_factor).powi(2);
+
+        RadiatorMetrics {
+            fan_power_required,
+            capture_ratio,
+            loss_coefficient,
+            mass_flow,
+            heat_rejected_watts: performance.heat_rejected_watts,
+            effectiveness: performance.effectiveness,
+            frontal_area,
+            tunnel_area,
+            blockage_ratio,
+            blockage_correction_factor,
+            pressure_drop_raw,
+            pressure_drop_corrected,
+            drag_raw,
+            drag_corrected,
+            drag_wake_survey: 0.0,
+            flow_uniformity_index: 0.0,
+            reversed_flow_fraction: 0.0,
+            recirculation_area: 0.0,
+        }
+    }
+
+    /// Places a [`wake_survey`] line `downstream_offset` past this
+    /// radiator's flow-direction (+x) footprint edge and overwrites
+    /// `drag_wake_survey` with its result, leaving every other field
+    /// untouched. A separate builder step (mirroring
+    /// [`Radiator::with_heat_exchanger`]'s pattern) rather than a
+    /// `compute`/`compute_post_projection`/`compute_all`/
+    /// `compute_all_post_projection` parameter, since not every caller has
+    /// (or wants to pay for) a wake survey.
+    pub fn with_wake_survey(
+        mut self,
+        fluid: &Fluid,
+        radiator: &Radiator,
+        inflow_u: f64,
+        downstream_offset: f64,
+        wall_margin: f64,
+    ) -> Self {
+        let x_station = radiator.center_x + footprint_extent_along(radiator, (1.0, 0.0)) + downstream_offset;
+        self.drag_wake_survey = wake_survey(fluid, x_station, inflow_u, wall_margin).drag_per_unit_depth;
+        self
+    }
+
+    /// Runs [`flow_uniformity`] and copies its index into
+    /// `flow_uniformity_index`, leaving every other field untouched — the
+    /// same opt-in builder shape as [`Self::with_wake_survey`], since not
+    /// every caller has (or wants to pay for) the extra face sampling.
+    /// The full profile `flow_uniformity` also computes isn't kept on
+    /// `RadiatorMetrics` itself (there's nowhere to put a `Vec` on a
+    /// `Copy` struct); a caller that wants the profile for plotting should
+    /// call [`flow_uniformity`] directly instead.
+    pub fn with_flow_uniformity(mut self, fluid: &Fluid, radiator: &Radiator, samples: usize) -> Self {
+        self.flow_uniformity_index = flow_uniformity(fluid, radiator, samples).index;
+        self
+    }
+
+    /// Runs [`flow_uniformity`] and [`recirculation_area`] and copies their
+    /// results into `reversed_flow_fraction`/`recirculation_area`, leaving
+    /// every other field untouched — same opt-in builder shape as
+    /// [`Self::with_wake_survey`]/[`Self::with_flow_uniformity`].
+    /// `downstream_offset`/`box_length`/`box_height` place the
+    /// recirculation-bubble search box the same way `with_wake_survey`'s
+    /// `downstream_offset` places its survey line: measured from the
+    /// radiator's downstream (+x) footprint edge, centered on
+    /// `radiator.center_y`.
+    pub fn with_reversed_flow_analysis(
+        mut self,
+        fluid: &Fluid,
+        radiator: &Radiator,
+        samples: usize,
+        downstream_offset: f64,
+        box_length: f64,
+        box_height: f64,
+    ) -> Self {
+        self.reversed_flow_fraction = reversed_flow_fraction(fluid, radiator, samples);
+        self.recirculation_area = recirculation_area(fluid, radiator, downstream_offset, box_length, box_height);
+        self
+    }
+
+    /// Compute metrics for every radiator independently, tagged by id, so a
+    /// multi-radiator scene (e.g. dual side-pod coolers) gets one entry per
+    /// radiator rather than only ever reporting `radiators[0]`.
+    pub fn compute_all(
+        fluid: &Fluid,
+        radiators: &[Radiator],
+        radiator_ids: &[String],
+        inflow_u: f64,
+        domain_height: f64,
+    ) -> std::collections::BTreeMap<String, RadiatorMetrics> {
+        radiator_ids
+            .iter()
+            .cloned()
+            .zip(radiators.iter())
+            .map(|(id, radiator)| (id, Self::compute(fluid, radiator, inflow_u, domain_height)))
+            .collect()
+    }
+
+    /// [`Self::compute_all`], but reading through-radiator velocity from
+    /// `post_projection_u` the same way [`Self::compute_post_projection`]
+    /// does for a single radiator.
+    pub fn compute_all_post_projection(
+        fluid: &Fluid,
+        post_projection_u: &[f64],
+        radiators: &[Radiator],
+        radiator_ids: &[String],
+        inflow_u: f64,
+        domain_height: f64,
+    ) -> std::collections::BTreeMap<String, RadiatorMetrics> {
+        radiator_ids
+            .iter()
+            .cloned()
+            .zip(radiators.iter())
+            .map(|(id, radiator)| {
+                (id, Self::compute_post_projection(fluid, post_projection_u, radiator, inflow_u, domain_height))
+            })
+            .collect()
+    }
+
+    pub fn definitions() -> &'static [MetricDefinition] {
+        &[
+            MetricDefinition {
+                name: "fan_power_required",
+                formula: "max(dp, 0) * (avg_through_u * radiator_height)",
+                inputs: "pressure drop across the radiator, average through-flow velocity, frontal height",
+                assumptions: "2D per-unit-depth estimate, incompressible, no fan/duct losses",
+            },
+            MetricDefinition {
+                name: "capture_ratio",
+                formula: "clamp(avg_through_u / inflow_u, 0, 1)",
+                inputs: "average u inside the radiator footprint, freestream inflow u",
+                assumptions: "ignores flow that bypasses above/below the radiator footprint",
+            },
+            MetricDefinition {
+                name: "loss_coefficient",
+                formula: "(0.5 * rho * (inflow_u^2 - avg_through_u^2)) / (0.5 * rho * inflow_u^2)",
+                inputs: "fluid density, inflow u, average through-radiator u",
+                assumptions: "dynamic-pressure-based estimate, not a directly measured static pressure tap",
+            },
+            MetricDefinition {
+                name: "mass_flow",
+                formula: "avg_through_u * radiator_height",
+                inputs: "average through-radiator u, radiator frontal height",
+                assumptions: "per-unit-depth 2D volumetric flow, constant density",
+            },
+            MetricDefinition {
+                name: "heat_rejected_watts",
+                formula: "epsilon * C_min * (coolant_inlet_temp - ambient_air_temp)",
+                inputs: "air mass flow (mass_flow * fluid density), Radiator::heat_exchanger's coolant inlet temp/mass flow/core UA/ambient air temp",
+                assumptions: "counter-flow epsilon-NTU model; fixed air/coolant specific heats; 0 if the radiator has no heat_exchanger attached",
+            },
+            MetricDefinition {
+                name: "effectiveness",
+                formula: "epsilon-NTU effectiveness: (1 - exp(-NTU*(1-Cr))) / (1 - Cr*exp(-NTU*(1-Cr))), or NTU/(1+NTU) at Cr = 1",
+                inputs: "NTU = core_ua_w_per_k / C_min, Cr = C_min / C_max",
+                assumptions: "counter-flow epsilon-NTU model; 0 if the radiator has no heat_exchanger attached",
+            },
+            MetricDefinition {
+                name: "frontal_area",
+                formula: "radiator_height",
+                inputs: "radiator frontal height",
+                assumptions: "per-unit-depth 2D area",
+            },
+            MetricDefinition {
+                name: "tunnel_area",
+                formula: "domain_height",
+                inputs: "tunnel cross-sectional extent passed into RadiatorMetrics::compute",
+                assumptions: "per-unit-depth 2D area",
+            },
+            MetricDefinition {
+                name: "blockage_ratio",
+                formula: "frontal_area / tunnel_area",
+                inputs: "frontal_area, tunnel_area",
+                assumptions: "standard wind-tunnel blockage-ratio definition",
+            },
+            MetricDefinition {
+                name: "blockage_correction_factor",
+                formula: "blockage_ratio / 4",
+                inputs: "blockage_ratio",
+                assumptions: "Thom's classical 2D solid-blockage correction; no wake or lift-interference blockage terms",
+            },
+            MetricDefinition {
+                name: "pressure_drop_raw",
+                formula: "0.5 * rho * (inflow_u^2 - avg_through_u^2)",
+                inputs: "fluid density, inflow u, average through-radiator u",
+                assumptions: "measured directly in the (blocked) tunnel, no wall correction applied",
+            },
+            MetricDefinition {
+                name: "pressure_drop_corrected",
+                formula: "pressure_drop_raw / (1 + blockage_correction_factor)^2",
+                inputs: "pressure_drop_raw, blockage_correction_factor",
+                assumptions: "standard solid-blockage velocity correction applied to the measured dynamic pressure",
+            },
+            MetricDefinition {
+                name: "drag_raw",
+                formula: "pressure_drop_raw * frontal_area",
+                inputs: "pressure_drop_raw, frontal_area",
+                assumptions: "lumped force estimate from the radiator's pressure drop, not integrated cell-face pressures",
+            },
+            MetricDefinition {
+                name: "drag_corrected",
+                formula: "drag_raw / (1 + blockage_correction_factor)^2",
+                inputs: "drag_raw, blockage_correction_factor",
+                assumptions: "same solid-blockage correction as pressure_drop_corrected",
+            },
+            MetricDefinition {
+                name: "drag_wake_survey",
+                formula: "sum(rho * u * (inflow_u - u) * dy) across a vertical wake survey line",
+                inputs: "downstream velocity profile, fluid density, inflow u",
+                assumptions: "2D per-unit-depth momentum-deficit estimate; 0 until with_wake_survey is called; excludes cells within wall_margin of the top/bottom walls and any solid-obstacle cell",
+            },
+            MetricDefinition {
+                name: "flow_uniformity_index",
+                formula: "1 - sum(|vi - vbar|) / (2 * N * vbar) across N face-normal velocity samples",
+                inputs: "face-normal velocity profile sampled along the radiator face, its mean",
+                assumptions: "standard uniformity index; 0 until with_flow_uniformity is called, or if the mean face-normal velocity is ~zero or reversed",
+            },
+            MetricDefinition {
+                name: "reversed_flow_fraction",
+                formula: "count(face samples with velocity < 0) / total face samples",
+                inputs: "the same face-normal velocity profile flow_uniformity_index samples",
+                assumptions: "0 until with_reversed_flow_analysis is called",
+            },
+            MetricDefinition {
+                name: "recirculation_area",
+                formula: "sum(h^2) over interior fluid cells with u < 0 inside a box behind the radiator",
+                inputs: "grid cell size, u velocity and solid mask, the recirculation search box placed downstream of the radiator",
+                assumptions: "per-unit-depth 2D area; only counts cells inside the caller-supplied search box, not the whole downstream wake; 0 until with_reversed_flow_analysis is called",
+            },
+        ]
+    }
+
+    pub fn definition(name: &str) -> Option<&'static MetricDefinition> {
+        Self::definitions().iter().find(|d| d.name == name)
+    }
+}
+
+/// Static pressure just upstream and downstream of a radiator, probed along
+/// a caller-supplied mean-flow direction rather than the radiator's own face
+/// normal. Probing along the face normal breaks down at steep radiator
+/// angles (near 90 degrees the normal points across the tunnel instead of
+/// along it, landing both stations beside the radiator rather than in front
+/// of and behind it); probing along the flow direction keeps them
+/// upstream/downstream regardless of angle. This is a direct pressure-tap
+/// measurement, independent of [`RadiatorMetrics::pressure_drop_raw`]'s
+/// velocity-based Bernoulli estimate.
+///
+/// `flow_direction` need not be normalized (a zero vector is an error).
+/// `probe_offset` is the distance (domain units) from the radiator's
+/// flow-direction footprint edge to each probe station — a parameter
+/// rather than a fixed offset, so a caller can back a probe further off a
+/// radiator with a large pressure wake. The transverse averaging line at
+/// each station is centered on the radiator and spans its footprint's
+/// extent perpendicular to `flow_direction` (its projection onto the
+/// tunnel cross-section, not its own face). Both stations are clamped
+/// inside the domain; if a clamped station still falls inside the
+/// radiator's own footprint or any of `other_obstacles`, this returns
+/// `Err` naming which station and why — a caller reporting this metric
+/// should treat that as "no measurement" (e.g. NaN with a logged warning)
+/// rather than trust a number sampled from inside an obstacle.
+/// Half of `radiator`'s footprint extent projected onto `direction` (need
+/// not be normalized) — the distance from `radiator.center_x`/`center_y` to
+/// the footprint's furthest corner along that axis. Shared by
+/// [`measure_pressure_drop_along_flow`] (which also needs the transverse
+/// half-extent, for its probes' averaging line) and
+/// [`RadiatorMetrics::with_wake_survey`] (which only needs this one axis, to
+/// place its survey line past the radiator's downstream edge).
+fn footprint_extent_along(radiator: &Radiator, direction: (f64, f64)) -> f64 {
+    let len = (direction.0 * direction.0 + direction.1 * direction.1).sqrt();
+    let (dx, dy) = if len < 1e-9 { (1.0, 0.0) } else { (direction.0 / len, direction.1 / len) };
+
+    let half_w = radiator.width * 0.5;
+    let half_h = radiator.height * 0.5;
+    let cos_a = radiator.angle.cos();
+    let sin_a = radiator.angle.sin();
+    let mut extent = 0.0_f64;
+    for (local_x, local_y) in [(-half_w, -half_h), (half_w, -half_h), (half_w, half_h), (-half_w, half_h)] {
+        let world_dx = local_x * cos_a - local_y * sin_a;
+        let world_dy = local_x * sin_a + local_y * cos_a;
+        extent = extent.max((world_dx * dx + world_dy * dy).abs());
+    }
+    extent
+}
+
+pub fn measure_pressure_drop_along_flow(
+    fluid: &Fluid,
+    radiator: &Radiator,
+    flow_direction: (f64, f64),
+    probe_offset: f64,
+    other_obstacles: &[Radiator],
+) -> Result<f64, String> {
+    let flow_len = (flow_direction.0 * flow_direction.0 + flow_direction.1 * flow_direction.1).sqrt();
+    if flow_len < 1e-9 {
+        return Err("flow_direction must be a nonzero vector".to_string());
+    }
+    let (fx, fy) = (flow_direction.0 / flow_len, flow_direction.1 / flow_len);
+    let (px, py) = (-fy, fx);
+
+    let flow_half_extent = footprint_extent_along(radiator, (fx, fy));
+    let transverse_half_extent = footprint_extent_along(radiator, (px, py));
+
+    let domain_w = fluid.num_x as f64 * fluid.h;
+    let domain_h = fluid.num_y as f64 * fluid.h;
+    let clamp_to_domain = |x: f64, y: f64| (x.clamp(fluid.h, domain_w - fluid.h), y.clamp(fluid.h, domain_h - fluid.h));
+
+    const TRANSVERSE_SAMPLES: usize = 5;
+    let sample_station = |sign: f64, label: &str| -> Result<f64, String> {
+        let station_distance = flow_half_extent + probe_offset;
+        let station_x = radiator.center_x + sign * station_distance * fx;
+        let station_y = radiator.center_y + sign * station_distance * fy;
+
+        let mut total = 0.0;
+        for k in 0..TRANSVERSE_SAMPLES {
+            let t = (k as f64 / (TRANSVERSE_SAMPLES - 1) as f64 - 0.5) * 2.0 * transverse_half_extent;
+            let (x, y) = clamp_to_domain(station_x + t * px, station_y + t * py);
+            if radiator.contains(x, y) || other_obstacles.iter().any(|other| other.contains(x, y)) {
+                return Err(format!("{label} probe station lands inside an obstacle's footprint"));
+            }
+            total += fluid.sample_field(FieldType::Pressure, x, y);
+        }
+        Ok(total / TRANSVERSE_SAMPLES as f64)
+    };
+
+    let upstream = sample_station(-1.0, "upstream")?;
+    let downstream = sample_station(1.0, "downstream")?;
+    Ok(upstream - downstream)
+}
+
+/// One grid cell's contribution to a [`wake_survey`], kept so a caller can
+/// plot the deficit profile rather than only reading the integrated total.
+#[derive(Debug, Clone, Copy)]
+pub struct WakeSurveyPoint {
+    pub y: f64,
+    pub u: f64,
+    pub momentum_deficit: f64,
+}
+
+/// Result of integrating a momentum-deficit wake survey along one vertical
+/// line — see [`wake_survey`].
+#[derive(Debug, Clone)]
+pub struct WakeSurvey {
+    pub drag_per_unit_depth: f64,
+    pub profile: Vec<WakeSurveyPoint>,
+}
+
+/// Momentum-deficit drag estimate: integrates `rho * u * (inflow_u - u)`
+/// down a vertical line at `x_station`, the classic wake-survey method for
+/// measuring drag without needing a pressure tap on the body itself. Cells
+/// within `wall_margin` of the top/bottom walls are skipped, since the wall
+/// boundary layers there aren't part of the radiator's wake and would
+/// otherwise bias the integral; so is any cell [`crate::fluid::SOLID_CELL`]
+/// masks out, in case `x_station` happens to cross another solid obstacle.
+/// Independent of both `RadiatorMetrics::drag_raw`'s pressure-drop estimate
+/// and [`Radiator::compute_forces`]'s pressure-integration estimate — this
+/// one only ever looks at velocity.
+pub fn wake_survey(fluid: &Fluid, x_station: f64, inflow_u: f64, wall_margin: f64) -> WakeSurvey {
+    let domain_h = fluid.num_y as f64 * fluid.h;
+    let x = x_station.clamp(fluid.h, fluid.num_x as f64 * fluid.h - fluid.h);
+
+    let mut profile = Vec::new();
+    let mut drag_per_unit_depth = 0.0;
+    for j in 1..fluid.num_y - 1 {
+        let y = j as f64 * fluid.h;
+        if y < wall_margin || y > domain_h - wall_margin {
+            continue;
+        }
+        let i = (x / fluid.h).round() as usize;
+        let idx = fluid.idx(i.min(fluid.num_x - 1), j);
+        if fluid.s[idx] == SOLID_CELL {
+            continue;
+        }
+        let u = fluid.sample_field(FieldType::U, x, y);
+        let momentum_deficit = fluid.density * u * (inflow_u - u);
+        drag_per_unit_depth += momentum_deficit * fluid.h;
+        profile.push(WakeSurveyPoint { y, u, momentum_deficit });
+    }
+
+    WakeSurvey { drag_per_unit_depth, profile }
+}
+
+/// One face-normal velocity sample from [`flow_uniformity`]. `position` is
+/// the offset along the face from its center (domain units, `+` toward the
+/// radiator's local `+y`), not a world coordinate, so a profile reads the
+/// same regardless of the radiator's placement or angle.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FaceVelocitySample {
+    pub position: f64,
+    pub velocity: f64,
+}
+
+/// Result of [`flow_uniformity`]: how evenly the face-normal velocity is
+/// distributed across the sampled face, plus the raw profile for plotting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniformityReport {
+    /// Standard uniformity index gamma, `1 - sum(|vi - vbar|) / (2*N*vbar)`.
+    /// `1.0` is perfectly uniform flow, lower is less even. `0.0` if the
+    /// mean face-normal velocity is ~zero or negative (reversed) — the
+    /// ratio is undefined there, and "not uniform" is the safer default
+    /// than dividing by (near-)zero.
+    pub index: f64,
+    pub min: f64,
+    pub max: f64,
+    pub profile: Vec<FaceVelocitySample>,
+}
+
+/// Samples face-normal velocity at `samples` evenly spaced points along
+/// `radiator`'s face (its centerline, spanning `height`), the same
+/// local-to-world rotation [`Radiator::compute_forces`] uses so this stays
+/// correct at any `radiator.angle`, and computes the standard flow-
+/// uniformity index. Unlike [`RadiatorMetrics::compute`]'s `avg_through_u`
+/// (a coarse average over whichever grid cells this resolution happens to
+/// put inside the footprint, clamped to non-negative), every sample here
+/// is read through [`Fluid::sample_field`] at its exact face position and
+/// kept signed, so a reversed-flow sample pulls the index down instead of
+/// being clamped away.
+pub fn flow_uniformity(fluid: &Fluid, radiator: &Radiator, samples: usize) -> UniformityReport {
+    let samples = samples.max(1);
+    let half_h = radiator.height * 0.5;
+    let cos_a = radiator.angle.cos();
+    let sin_a = radiator.angle.sin();
+    // The radiator's local +x axis rotated into world coordinates: the
+    // direction flow travels through the footprint at angle 0.
+    let (nx, ny) = (cos_a, sin_a);
+
+    let ds = radiator.height / samples as f64;
+    let mut profile = Vec::with_capacity(samples);
+    for k in 0..samples {
+        let position = -half_h + (k as f64 + 0.5) * ds;
+        let x = radiator.center_x - position * sin_a;
+        let y = radiator.center_y + position * cos_a;
+        let u = fluid.sample_field(FieldType::U, x, y);
+        let v = fluid.sample_field(FieldType::V, x, y);
+        profile.push(FaceVelocitySample { position, velocity: u * nx + v * ny });
+    }
+
+    let mean = profile.iter().map(|p| p.velocity).sum::<f64>() / samples as f64;
+    let index = if mean > 1e-9 {
+        1.0 - profile.iter().map(|p| (p.velocity - mean).abs()).sum::<f64>() / (2.0 * samples as f64 * mean)
+    } else {
+        0.0
+    };
+    let min = profile.iter().map(|p| p.velocity).fold(f64::INFINITY, f64::min);
+    let max = profile.iter().map(|p| p.velocity).fold(f64::NEG_INFINITY, f64::max);
+
+    UniformityReport { index, min, max, profile }
+}
+
+/// Fraction of [`flow_uniformity`]'s `samples` face samples with negative
+/// (reversed) face-normal velocity -- a radiator face where part of the
+/// core sees flow running backward loses cooling even if the average
+/// through-flow looks fine, which `flow_uniformity_index` alone doesn't
+/// surface.
+pub fn reversed_flow_fraction(fluid: &Fluid, radiator: &Radiator, samples: usize) -> f64 {
+    let report = flow_uniformity(fluid, radiator, samples);
+    let n = report.profile.len().max(1);
+    report.profile.iter().filter(|sample| sample.velocity < 0.0).count() as f64 / n as f64
+}
+
+/// Area (m^2, per unit depth) of the recirculation bubble behind
+/// `radiator`: interior fluid cells with `u < 0` inside a `box_length` x
+/// `box_height` rectangle placed `downstream_offset` past the radiator's
+/// downstream (+x) footprint edge, centered on `radiator.center_y`.
+/// Unlike [`reversed_flow_fraction`] (a handful of face samples), this
+/// scans actual grid cells, since the bubble's extent -- not just its
+/// presence at the face -- is what "the wake recirculates" means.
+pub fn recirculation_area(fluid: &Fluid, radiator: &Radiator, downstream_offset: f64, box_length: f64, box_height: f64) -> f64 {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    let x0 = radiator.center_x + footprint_extent_along(radiator, (1.0, 0.0)) + downstream_offset;
+    let x1 = x0 + box_length;
+    let y0 = radiator.center_y - box_height * 0.5;
+    let y1 = radiator.center_y + box_height * 0.5;
+
+    let mut area = 0.0;
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            if x < x0 || x > x1 || y < y0 || y > y1 {
+                continue;
+            }
+            let idx = i * n + j;
+            if fluid.s[idx] != SOLID_CELL && fluid.u[idx] < 0.0 {
+                area += h * h;
+            }
+        }
+    }
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_metrics_field_has_a_registered_definition() {
+        let fluid = Fluid::new(1000.0, 20, 10, 0.1);
+        let radiator = Radiator::new(1.0, 0.5, 0.1, 0.5, 0.0, 0.7);
+        let metrics = RadiatorMetrics::compute(&fluid, &radiator, 1.0, 1.0);
+
+        let value = serde_json::to_value(metrics).unwrap();
+        let fields: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+        assert!(!fields.is_empty());
+
+        for field in fields {
+            assert!(
+                RadiatorMetrics::definition(&field).is_some(),
+                "no definition registered for metric field `{field}`"
+            );
+        }
+    }
+
+    /// `compute` should feed the measured through-radiator flow into
+    /// `Radiator::analyze_performance` rather than only reporting flow
+    /// metrics — a radiator with no `heat_exchanger` attached must report
+    /// zero, and one with a hot coolant loop attached must report a
+    /// positive `heat_rejected_watts` derived from real flow, not the
+    /// default zeros `Radiator::analyze_performance` returns for `None`.
+    #[test]
+    fn heat_rejected_watts_reflects_the_radiators_attached_heat_exchanger() {
+        let mut fluid = Fluid::new(1000.0, 20, 10, 0.1);
+        for u in fluid.u.iter_mut() {
+            *u = 1.0;
+        }
+        let bare_radiator = Radiator::new(1.0, 0.5, 0.1, 0.5, 0.0, 0.7);
+        let cooled_radiator = bare_radiator.with_heat_exchanger(crate::radiator::HeatExchanger {
+            coolant_inlet_temp_c: 90.0,
+            coolant_mass_flow_kg_s: 0.5,
+            core_ua_w_per_k: 200.0,
+            ambient_air_temp_c: 20.0,
+        });
+
+        let bare = RadiatorMetrics::compute(&fluid, &bare_radiator, 1.0, 1.0);
+        let cooled = RadiatorMetrics::compute(&fluid, &cooled_radiator, 1.0, 1.0);
+
+        assert_eq!(bare.heat_rejected_watts, 0.0);
+        assert_eq!(bare.effectiveness, 0.0);
+        assert!(cooled.heat_rejected_watts > 0.0);
+        assert!(cooled.effectiveness > 0.0 && cooled.effectiveness < 1.0);
+        assert_eq!(bare.mass_flow, cooled.mass_flow, "attaching a heat exchanger shouldn't change the flow metrics");
+    }
+
+    #[test]
+    fn doubling_tunnel_height_roughly_halves_the_blockage_correction() {
+        let fluid = Fluid::new(1000.0, 20, 10, 0.1);
+        let radiator = Radiator::new(1.0, 0.5, 0.1, 0.5, 0.0, 0.7);
+
+        let narrow = RadiatorMetrics::compute(&fluid, &radiator, 1.0, 1.0);
+        let wide = RadiatorMetrics::compute(&fluid, &radiator, 1.0, 2.0);
+
+        assert!(wide.blockage_ratio < narrow.blockage_ratio);
+        let ratio = wide.blockage_correction_factor / narrow.blockage_correction_factor;
+        assert!(
+            (ratio - 0.5).abs() < 0.05,
+            "expected doubling tunnel height to roughly halve the blockage correction, got a {ratio}x factor"
+        );
+    }
+
+    /// A pressure field that only varies along x should give a positive
+    /// upstream-minus-downstream drop for a radiator angled steeply enough
+    /// (85 degrees) that probing along its own face normal would have
+    /// landed both stations off to the side instead of in front of and
+    /// behind it.
+    #[test]
+    fn probes_stay_along_the_flow_direction_even_at_a_steep_radiator_angle() {
+        let num_x = 60;
+        let num_y = 60;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        for i in 0..num_x {
+            for j in 0..num_y {
+                let idx = fluid.idx(i, j);
+                fluid.p[idx] = (num_x - i) as f64;
+            }
+        }
+
+        let steep_radiator = Radiator::new(0.5, 0.5, 0.05, 0.2, 85.0_f64.to_radians(), 0.5);
+        let dp = measure_pressure_drop_along_flow(&fluid, &steep_radiator, (1.0, 0.0), 2.0 * h, &[]).unwrap();
+        assert!(dp > 0.0, "higher pressure upstream should give a positive drop even at a steep angle, got {dp}");
+    }
+
+    #[test]
+    fn a_zero_flow_direction_is_rejected() {
+        let fluid = Fluid::new(1000.0, 40, 40, 0.05);
+        let radiator = Radiator::new(0.5, 0.5, 0.1, 0.3, 0.0, 0.5);
+        assert!(measure_pressure_drop_along_flow(&fluid, &radiator, (0.0, 0.0), 0.1, &[]).is_err());
+    }
+
+    /// If a probe station lands inside another obstacle's footprint, the
+    /// pressure sampled there wouldn't mean anything -- this should fail
+    /// loudly rather than silently returning a bogus number.
+    #[test]
+    fn a_probe_station_inside_another_obstacle_is_rejected() {
+        let fluid = Fluid::new(1000.0, 60, 60, 1.0 / 60.0);
+        let radiator = Radiator::new(0.5, 0.5, 0.05, 0.2, 0.0, 0.5);
+        let blocking_obstacle = Radiator::new(0.65, 0.5, 0.2, 0.3, 0.0, 0.5);
+
+        let result = measure_pressure_drop_along_flow(&fluid, &radiator, (1.0, 0.0), 0.05, &[blocking_obstacle]);
+        assert!(result.is_err(), "a probe landing inside another obstacle's footprint should be rejected, got {result:?}");
+    }
+
+    /// A uniform velocity deficit across the whole survey line should give a
+    /// positive drag proportional to the deficit, and every included point
+    /// should carry the same momentum deficit.
+    #[test]
+    fn a_uniform_velocity_deficit_gives_a_positive_drag() {
+        let num_x = 40;
+        let num_y = 40;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        for i in 0..num_x {
+            for j in 0..num_y {
+                let idx = fluid.idx(i, j);
+                fluid.u[idx] = 0.5;
+            }
+        }
+
+        let survey = wake_survey(&fluid, 0.5, 1.0, 0.0);
+        assert!(survey.drag_per_unit_depth > 0.0);
+        for point in &survey.profile {
+            assert!((point.momentum_deficit - 250.0).abs() < 1e-6, "expected rho*u*(inflow_u-u) = 250, got {}", point.momentum_deficit);
+        }
+    }
+
+    #[test]
+    fn wall_margin_excludes_cells_near_the_top_and_bottom_walls() {
+        let num_x = 40;
+        let num_y = 40;
+        let h = 1.0 / num_y as f64;
+        let fluid = Fluid::new(1000.0, num_x, num_y, h);
+
+        let full = wake_survey(&fluid, 0.5, 1.0, 0.0);
+        let margined = wake_survey(&fluid, 0.5, 1.0, 5.0 * h);
+        assert!(margined.profile.len() < full.profile.len());
+        for point in &margined.profile {
+            assert!(point.y >= 5.0 * h && point.y <= 1.0 - 5.0 * h);
+        }
+    }
+
+    #[test]
+    fn solid_cells_are_excluded_from_the_survey() {
+        let num_x = 40;
+        let num_y = 40;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        let i = (0.5 / h).round() as usize;
+        for j in 0..num_y {
+            let idx = fluid.idx(i, j);
+            fluid.s[idx] = SOLID_CELL;
+        }
+
+        let survey = wake_survey(&fluid, 0.5, 1.0, 0.0);
+        assert!(survey.profile.is_empty(), "every cell on the survey line was masked solid, expected an empty profile");
+    }
+
+    /// `with_wake_survey` should overwrite only `drag_wake_survey`, leaving
+    /// every other field exactly as `compute` produced it.
+    #[test]
+    fn with_wake_survey_only_overwrites_the_wake_survey_field() {
+        let num_x = 60;
+        let num_y = 60;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        for i in 0..num_x {
+            for j in 0..num_y {
+                let idx = fluid.idx(i, j);
+                fluid.u[idx] = 0.5;
+            }
+        }
+        let radiator = Radiator::new(0.3, 0.5, 0.1, 0.3, 0.0, 0.5);
+
+        let before = RadiatorMetrics::compute(&fluid, &radiator, 1.0, 1.0);
+        assert_eq!(before.drag_wake_survey, 0.0);
+
+        let after = before.with_wake_survey(&fluid, &radiator, 1.0, 0.05, 0.0);
+        assert!(after.drag_wake_survey > 0.0);
+        assert_eq!(after.drag_raw, before.drag_raw);
+        assert_eq!(after.mass_flow, before.mass_flow);
+    }
+
+    #[test]
+    fn a_uniform_face_velocity_gives_an_index_of_one() {
+        let num_x = 60;
+        let num_y = 60;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        for u in fluid.u.iter_mut() {
+            *u = 2.0;
+        }
+        let radiator = Radiator::new(0.5, 0.5, 0.1, 0.3, 0.0, 0.5);
+
+        let report = flow_uniformity(&fluid, &radiator, 8);
+
+        assert!((report.index - 1.0).abs() < 1e-9, "expected a perfectly uniform profile, got index {}", report.index);
+        assert!((report.min - 2.0).abs() < 1e-9);
+        assert!((report.max - 2.0).abs() < 1e-9);
+        assert_eq!(report.profile.len(), 8);
+    }
+
+    #[test]
+    fn an_uneven_face_velocity_gives_an_index_below_one() {
+        let num_x = 60;
+        let num_y = 60;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        for i in 0..num_x {
+            for j in 0..num_y {
+                let idx = fluid.idx(i, j);
+                fluid.u[idx] = if j < num_y / 2 { 0.5 } else { 2.0 };
+            }
+        }
+        let radiator = Radiator::new(0.5, 0.5, 0.1, 0.4, 0.0, 0.5);
+
+        let report = flow_uniformity(&fluid, &radiator, 8);
+        assert!(report.index < 1.0);
+        assert!(report.index >= 0.0);
+    }
+
+    #[test]
+    fn zero_or_reversed_mean_face_velocity_reports_an_index_of_zero_without_dividing_by_zero() {
+        let num_x = 40;
+        let num_y = 40;
+        let h = 1.0 / num_y as f64;
+        let fluid_zero = Fluid::new(1000.0, num_x, num_y, h);
+        let radiator = Radiator::new(0.5, 0.5, 0.1, 0.3, 0.0, 0.5);
+        let zero_report = flow_uniformity(&fluid_zero, &radiator, 6);
+        assert_eq!(zero_report.index, 0.0);
+
+        let mut fluid_reversed = Fluid::new(1000.0, num_x, num_y, h);
+        for u in fluid_reversed.u.iter_mut() {
+            *u = -1.0;
+        }
+        let reversed_report = flow_uniformity(&fluid_reversed, &radiator, 6);
+        assert_eq!(reversed_report.index, 0.0);
+        assert!(reversed_report.min < 0.0);
+    }
+
+    #[test]
+    fn with_flow_uniformity_only_overwrites_the_uniformity_field() {
+        let num_x = 60;
+        let num_y = 60;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        for u in fluid.u.iter_mut() {
+            *u = 1.0;
+        }
+        let radiator = Radiator::new(0.3, 0.5, 0.1, 0.3, 0.0, 0.5);
+
+        let before = RadiatorMetrics::compute(&fluid, &radiator, 1.0, 1.0);
+        assert_eq!(before.flow_uniformity_index, 0.0);
+
+        let after = before.with_flow_uniformity(&fluid, &radiator, 8);
+        assert!((after.flow_uniformity_index - 1.0).abs() < 1e-9);
+        assert_eq!(after.mass_flow, before.mass_flow);
+    }
+
+    #[test]
+    fn reversed_flow_fraction_counts_only_negative_face_samples() {
+        let num_x = 60;
+        let num_y = 60;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        for i in 0..num_x {
+            for j in 0..num_y {
+                let idx = fluid.idx(i, j);
+                fluid.u[idx] = if j < num_y / 2 { -1.0 } else { 1.0 };
+            }
+        }
+        let radiator = Radiator::new(0.5, 0.5, 0.1, 0.4, 0.0, 0.5);
+
+        let fraction = reversed_flow_fraction(&fluid, &radiator, 10);
+        assert!((fraction - 0.5).abs() < 1e-9, "expected half the face samples reversed, got {fraction}");
+    }
+
+    #[test]
+    fn recirculation_area_counts_only_negative_u_cells_inside_the_search_box() {
+        let num_x = 80;
+        let num_y = 40;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        let radiator = Radiator::new(0.3, 0.5, 0.05, 0.3, 0.0, 0.5);
+
+        // An artificial reversed-flow patch entirely inside the search box
+        // this call will use (downstream_offset 0.05, box 0.2 x 0.3).
+        for i in 0..num_x {
+            for j in 0..num_y {
+                let idx = fluid.idx(i, j);
+                let x = i as f64 * h;
+                if x > 0.38 && x < 0.45 {
+                    fluid.u[idx] = -1.0;
+                }
+            }
+        }
+
+        let area = recirculation_area(&fluid, &radiator, 0.05, 0.2, 0.3);
+        assert!(area > 0.0, "expected a positive recirculation area, got {area}");
+
+        let no_reversal = recirculation_area(&Fluid::new(1000.0, num_x, num_y, h), &radiator, 0.05, 0.2, 0.3);
+        assert_eq!(no_reversal, 0.0, "no reversed cells anywhere should give zero area");
+    }
+
+    #[test]
+    fn with_reversed_flow_analysis_only_overwrites_its_two_fields() {
+        let num_x = 60;
+        let num_y = 60;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        for u in fluid.u.iter_mut() {
+            *u = 1.0;
+        }
+        let radiator = Radiator::new(0.3, 0.5, 0.1, 0.3, 0.0, 0.5);
+
+        let before = RadiatorMetrics::compute(&fluid, &radiator, 1.0, 1.0);
+        assert_eq!(before.reversed_flow_fraction, 0.0);
+        assert_eq!(before.recirculation_area, 0.0);
+
+        let after = before.with_reversed_flow_analysis(&fluid, &radiator, 8, 0.05, 0.2, 0.3);
+        assert_eq!(after.reversed_flow_fraction, 0.0);
+        assert_eq!(after.recirculation_area, 0.0);
+        assert_eq!(after.mass_flow, before.mass_flow);
+    }
+}
@@ -0,0 +1,368 @@
+//! Prescribed obstacle motion — constant translation, constant rotation, or
+//! a sinusoidal oscillation applied to a solid obstacle's footprint every
+//! step, independent of any fluid feedback. Contrast
+//! [`crate::vortex_induced_body::VortexInducedBody`], whose displacement is
+//! *driven by* the lift force the flow produces; a [`MovingObstacle`]'s
+//! motion is fixed in advance and never reacts to the flow. Reuses the same
+//! restore-then-remark footprint trick `VortexInducedBody` and
+//! `radiator_model` already use for a mid-run change to the solid mask.
+
+use serde::{Deserialize, Serialize};
+
+use crate::fluid::{Fluid, FLUID_CELL, SOLID_CELL};
+use crate::scene_config::ObstacleShape;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Axis {
+    X,
+    Y,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Motion {
+    /// Constant translation velocity (domain units/sec) in x and y.
+    Translation { vx: f64, vy: f64 },
+    /// Constant angular velocity (rad/sec) about the shape's own center.
+    /// Only changes the footprint for shapes with an `angle` (`Rectangle`,
+    /// `Airfoil` — see [`ObstacleShape::rotated`]); a rotating `Circle`'s
+    /// footprint never changes, but its surface still gets a tangential
+    /// velocity from [`MovingObstacle::surface_velocity`], which is what a
+    /// spinning cylinder (Magnus effect) needs.
+    Rotation { angular_velocity: f64 },
+    /// A prescribed sinusoidal displacement along one axis from the
+    /// obstacle's position at [`MovingObstacle::new`] time — the same
+    /// amplitude/frequency shape as `VortexInducedBody`'s free oscillation,
+    /// but evaluated directly from `sim_time` rather than integrated from a
+    /// lift-driven ODE, so it can't drift over a long run.
+    Oscillation { axis: Axis, amplitude: f64, frequency_hz: f64 },
+}
+
+/// A single obstacle whose footprint and surface velocity are updated every
+/// step according to a prescribed [`Motion`].
+#[derive(Debug, Clone)]
+pub struct MovingObstacle {
+    obstacle_index: usize,
+    motion: Motion,
+    /// Solid mask as it was when this obstacle was set up (marked at its
+    /// rest position), used to restore its old footprint before re-marking
+    /// the new one each step, so no stale solid cell survives a move.
+    base_s: Vec<f64>,
+    original_shape: ObstacleShape,
+    /// Accumulated translation from `original_shape`, integrated each step
+    /// under `Motion::Translation`. Unused (stays zero) for the other two
+    /// motions, which derive their shape directly instead of integrating.
+    translation: (f64, f64),
+    /// Accumulated rotation from `original_shape`, integrated each step
+    /// under `Motion::Rotation`. Unused for the other two motions.
+    rotation: f64,
+}
+
+impl MovingObstacle {
+    /// `fluid` is typically already set up with `shape`'s rest footprint
+    /// marked solid (`scene::setup_from_config` marks every `obstacles`
+    /// entry before building the things that move them), so `base_s` can't
+    /// just be `fluid.s.clone()` — that would bake this obstacle's own rest
+    /// position in as permanently solid, and [`Self::step`]'s restore would
+    /// never actually free it. Clear `shape`'s own footprint back to fluid
+    /// in the captured baseline instead, so the first `step` call correctly
+    /// un-marks the rest position once the obstacle has moved off it.
+    pub fn new(fluid: &Fluid, obstacle_index: usize, shape: ObstacleShape, motion: Motion) -> Self {
+        let mut base_s = fluid.s.clone();
+        let n = fluid.num_y;
+        let h = fluid.h;
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                if shape.contains(i as f64 * h, j as f64 * h) {
+                    base_s[i * n + j] = FLUID_CELL;
+                }
+            }
+        }
+        MovingObstacle {
+            obstacle_index,
+            motion,
+            base_s,
+            original_shape: shape,
+            translation: (0.0, 0.0),
+            rotation: 0.0,
+        }
+    }
+
+    pub fn obstacle_index(&self) -> usize {
+        self.obstacle_index
+    }
+
+    /// This obstacle's shape at `sim_time`. `Translation`/`Rotation` read
+    /// the state integrated so far (`self.translation`/`self.rotation`);
+    /// `Oscillation` is stateless and recomputes fresh from `sim_time`
+    /// every call.
+    pub fn current_shape(&self, sim_time: f64) -> ObstacleShape {
+        match self.motion {
+            Motion::Translation { .. } | Motion::Rotation { .. } => {
+                self.original_shape.translated(self.translation.0, self.translation.1).rotated(self.rotation)
+            }
+            Motion::Oscillation { axis, amplitude, frequency_hz } => {
+                let d = amplitude * (2.0 * std::f64::consts::PI * frequency_hz * sim_time).sin();
+                match axis {
+                    Axis::X => self.original_shape.translated(d, 0.0),
+                    Axis::Y => self.original_shape.translated(0.0, d),
+                }
+            }
+        }
+    }
+
+    /// This obstacle's own rigid-body velocity at domain point `(x, y)`:
+    /// its linear velocity, plus for `Rotation` the tangential term `omega
+    /// x r` about its current center. This is what [`Self::step`] writes
+    /// into a solid cell's faces instead of zero, which is the difference
+    /// between a moving wall dragging the fluid at its surface along with
+    /// it and a stationary no-slip wall.
+    pub fn surface_velocity(&self, sim_time: f64, x: f64, y: f64) -> (f64, f64) {
+        match self.motion {
+            Motion::Translation { vx, vy } => (vx, vy),
+            Motion::Rotation { angular_velocity } => {
+                let (cx, cy) = self.current_shape(sim_time).center();
+                (-angular_velocity * (y - cy), angular_velocity * (x - cx))
+            }
+            Motion::Oscillation { axis, amplitude, frequency_hz } => {
+                let omega = 2.0 * std::f64::consts::PI * frequency_hz;
+                let v = amplitude * omega * (omega * sim_time).cos();
+                match axis {
+                    Axis::X => (v, 0.0),
+                    Axis::Y => (0.0, v),
+                }
+            }
+        }
+    }
+
+    /// Advance this obstacle by one step of `dt`: integrate
+    /// `translation`/`rotation` (a no-op for `Oscillation`), restore the
+    /// solid mask's old footprint back to `base_s`, and mark the new
+    /// footprint solid with faces set to [`Self::surface_velocity`] instead
+    /// of zero. A cell that was solid under the old footprint and is fluid
+    /// again under `base_s` gets its faces and smoke reset to
+    /// `reset_u`/`reset_m` (the scene's ambient inflow/smoke) rather than
+    /// left at the stale zero the previous step's marking wrote — call once
+    /// per `Scene::simulate`, after the pressure field for this step has
+    /// been solved.
+    pub fn step(&mut self, fluid: &mut Fluid, dt: f64, sim_time: f64, reset_u: f64, reset_m: f64) {
+        let shape_before = self.current_shape(sim_time);
+        match self.motion {
+            Motion::Translation { vx, vy } => {
+                self.translation = (self.translation.0 + vx * dt, self.translation.1 + vy * dt);
+            }
+            Motion::Rotation { angular_velocity } => {
+                self.rotation += angular_velocity * dt;
+            }
+            Motion::Oscillation { .. } => {}
+        }
+        let sim_time = sim_time + dt;
+        let shape_after = self.current_shape(sim_time);
+
+        restore_footprint(fluid, &self.base_s, &shape_before, reset_u, reset_m);
+        self.mark_footprint(fluid, &shape_after, sim_time);
+    }
+
+    /// Mark every cell inside `shape` solid, matching
+    /// `scene::mark_obstacle_solid`'s footprint marking, except that a
+    /// moving obstacle's faces take [`Self::surface_velocity`] instead of
+    /// zero.
+    fn mark_footprint(&self, fluid: &mut Fluid, shape: &ObstacleShape, sim_time: f64) {
+        let n = fluid.num_y;
+        let h = fluid.h;
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                let x = i as f64 * h;
+                let y = j as f64 * h;
+                if shape.contains(x, y) {
+                    let idx = i * n + j;
+                    let (vx, vy) = self.surface_velocity(sim_time, x, y);
+                    fluid.s[idx] = SOLID_CELL;
+                    fluid.u[idx] = vx;
+                    fluid.u[(i + 1) * n + j] = vx;
+                    fluid.v[idx] = vy;
+                    fluid.v[idx + 1] = vy;
+                }
+            }
+        }
+    }
+}
+
+/// Reset every cell in `shape`'s old footprint back to whatever `base_s`
+/// says it was, undoing the previous step's marking before the shape moves.
+/// A cell that comes back fluid this way (it was solid a moment ago, and
+/// `base_s` says fluid) is reinitialized to `reset_u`/`reset_m` instead of
+/// left at the zero its solid marking wrote, which would otherwise look
+/// like a dead pocket in the wake rather than reattached free-stream flow.
+fn restore_footprint(fluid: &mut Fluid, base_s: &[f64], shape: &ObstacleShape, reset_u: f64, reset_m: f64) {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            if shape.contains(x, y) {
+                let idx = i * n + j;
+                let was_solid = fluid.s[idx] == SOLID_CELL;
+                fluid.s[idx] = base_s[idx];
+                if was_solid && fluid.s[idx] == FLUID_CELL {
+                    fluid.u[idx] = reset_u;
+                    fluid.u[(i + 1) * n + j] = reset_u;
+                    fluid.v[idx] = 0.0;
+                    fluid.v[idx + 1] = 0.0;
+                    fluid.m[idx] = reset_m;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_fluid() -> Fluid {
+        let mut fluid = Fluid::new(1000.0, 60, 20, 0.05);
+        for j in 0..fluid.num_y {
+            fluid.u[j] = 1.0;
+        }
+        fluid
+    }
+
+    fn sample_shape() -> ObstacleShape {
+        ObstacleShape::Circle { cx: 0.5, cy: 0.5, radius: 0.1 }
+    }
+
+    #[test]
+    fn translation_moves_the_footprint_by_velocity_times_time() {
+        let mut fluid = sample_fluid();
+        let mut obstacle = MovingObstacle::new(&fluid, 0, sample_shape(), Motion::Translation { vx: 0.5, vy: 0.0 });
+        for step in 0..20 {
+            obstacle.step(&mut fluid, 1.0 / 60.0, step as f64 / 60.0, 1.0, 0.0);
+        }
+        let sim_time = 20.0 / 60.0;
+        let expected_dx = 0.5 * sim_time;
+        assert!((obstacle.current_shape(sim_time).center().0 - (0.5 + expected_dx)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_moving_footprint_never_leaves_two_overlapping_solid_copies() {
+        let mut fluid = sample_fluid();
+        let mut obstacle = MovingObstacle::new(&fluid, 0, sample_shape(), Motion::Translation { vx: 0.5, vy: 0.0 });
+        let mut sim_time = 0.0;
+        for _ in 0..20 {
+            obstacle.step(&mut fluid, 1.0 / 60.0, sim_time, 1.0, 0.0);
+            sim_time += 1.0 / 60.0;
+        }
+        let solid_count = fluid.s.iter().filter(|&&s| s == SOLID_CELL).count();
+        let expected_shape = obstacle.current_shape(sim_time);
+        let n = fluid.num_y;
+        let h = fluid.h;
+        let mut expected_count = 0;
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                if expected_shape.contains(i as f64 * h, j as f64 * h) {
+                    expected_count += 1;
+                    assert_eq!(fluid.s[i * n + j], SOLID_CELL);
+                }
+            }
+        }
+        assert_eq!(solid_count, expected_count, "no stale solid cells should survive from the old footprint");
+    }
+
+    #[test]
+    fn translation_imposes_its_own_velocity_on_the_footprint_faces_instead_of_zero() {
+        let mut fluid = sample_fluid();
+        let mut obstacle = MovingObstacle::new(&fluid, 0, sample_shape(), Motion::Translation { vx: 0.7, vy: -0.3 });
+        obstacle.step(&mut fluid, 1.0 / 60.0, 0.0, 1.0, 0.0);
+        let shape = obstacle.current_shape(1.0 / 60.0);
+        let n = fluid.num_y;
+        let h = fluid.h;
+        let (cx, cy) = shape.center();
+        let (i, j) = ((cx / h).round() as usize, (cy / h).round() as usize);
+        assert_eq!(fluid.u[i * n + j], 0.7);
+        assert_eq!(fluid.v[i * n + j], -0.3);
+    }
+
+    #[test]
+    fn a_cell_freed_by_the_moving_footprint_is_reset_to_the_ambient_flow_not_left_at_zero() {
+        let mut fluid = sample_fluid();
+        let mut obstacle = MovingObstacle::new(&fluid, 0, sample_shape(), Motion::Translation { vx: 1.0, vy: 0.0 });
+        let mut sim_time = 0.0;
+        for _ in 0..40 {
+            obstacle.step(&mut fluid, 1.0 / 60.0, sim_time, 2.0, 0.5);
+            sim_time += 1.0 / 60.0;
+        }
+        // The obstacle's rest position should be well clear of its current
+        // footprint after 40 steps at vx=1.0 (its own diameter is 0.2), and
+        // fluid there again rather than at the stale zero its own passage
+        // would otherwise have left behind.
+        let n = fluid.num_y;
+        let h = fluid.h;
+        let (i, j) = ((0.5 / h).round() as usize, (0.5 / h).round() as usize);
+        assert_eq!(fluid.s[i * n + j], FLUID_CELL);
+        assert_eq!(fluid.u[i * n + j], 2.0);
+        assert_eq!(fluid.m[i * n + j], 0.5);
+    }
+
+    #[test]
+    fn rotation_gives_a_circle_tangential_surface_velocity_without_changing_its_footprint() {
+        let fluid = sample_fluid();
+        let obstacle = MovingObstacle::new(&fluid, 0, sample_shape(), Motion::Rotation { angular_velocity: 2.0 });
+        let (cx, cy) = sample_shape().center();
+        let (vx, vy) = obstacle.surface_velocity(0.0, cx + 0.1, cy);
+        assert_eq!(vx, 0.0);
+        assert!((vy - 0.2).abs() < 1e-9, "expected omega * r = 0.2, got {vy}");
+        assert!(obstacle.current_shape(1.0).contains(cx, cy), "a rotating circle's footprint shouldn't move");
+    }
+
+    #[test]
+    fn rest_footprint_pre_marked_solid_by_scene_setup_still_frees_once_the_obstacle_moves_off_it() {
+        // `scene::setup_from_config` marks every `obstacles` entry solid
+        // *before* building the `MovingObstacle`s that own some of them, so
+        // `new` sees a fluid where `shape`'s own rest footprint is already
+        // solid. `base_s` must not treat that as "solid without this
+        // obstacle" or the rest position would stay solid forever.
+        let mut fluid = sample_fluid();
+        let shape = sample_shape();
+        let n = fluid.num_y;
+        let h = fluid.h;
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                if shape.contains(i as f64 * h, j as f64 * h) {
+                    fluid.s[i * n + j] = SOLID_CELL;
+                }
+            }
+        }
+        let mut obstacle = MovingObstacle::new(&fluid, 0, shape, Motion::Translation { vx: 1.0, vy: 0.0 });
+        let mut sim_time = 0.0;
+        for _ in 0..20 {
+            obstacle.step(&mut fluid, 1.0 / 60.0, sim_time, 1.0, 0.0);
+            sim_time += 1.0 / 60.0;
+        }
+        let (rest_i, rest_j) = ((0.5 / h).round() as usize, (0.5 / h).round() as usize);
+        assert_eq!(
+            fluid.s[rest_i * n + rest_j],
+            FLUID_CELL,
+            "the obstacle's rest position should be freed once it has moved well clear of it"
+        );
+    }
+
+    #[test]
+    fn oscillation_returns_to_its_rest_position_every_full_period() {
+        let fluid = sample_fluid();
+        let obstacle = MovingObstacle::new(
+            &fluid,
+            0,
+            sample_shape(),
+            Motion::Oscillation { axis: Axis::Y, amplitude: 0.05, frequency_hz: 1.0 },
+        );
+        let (cx, cy) = sample_shape().center();
+        for cycles in 0..3 {
+            let shape = obstacle.current_shape(cycles as f64);
+            assert!((shape.center().1 - cy).abs() < 1e-9);
+            assert_eq!(shape.center().0, cx);
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Geometric multigrid V-cycle for the pressure-correction Poisson equation
+//! [`crate::fluid::Fluid::solve_incompressibility`]'s Gauss-Seidel sweeps
+//! solve directly: `sum_over_fluid_neighbors(phi_neighbor - phi_center) =
+//! divergence` at every interior cell, `phi` (the pressure correction)
+//! starting at zero. Plain red-black GS is what that solver uses, and it's
+//! slow to remove the low-frequency (large-scale) part of the error —
+//! exactly what a V-cycle is for: smooth away the high-frequency error on
+//! the fine grid, restrict the now-smooth residual to a coarser grid where
+//! it converges in far fewer sweeps, then prolong the coarse correction
+//! back up.
+//!
+//! Unlike `solve_incompressibility` (which mutates `u`/`v` directly as each
+//! cell's correction is computed, so it never actually materializes a
+//! standalone pressure-correction field until it's done), this module works
+//! entirely in `phi`-space against a fixed initial divergence field, with
+//! [`crate::fluid::Fluid::solve_incompressibility_multigrid`] applying the
+//! converged `phi` to `u`/`v` in one pass at the end.
+//!
+//! Restriction/prolongation both use plain 2x2 block averaging/injection —
+//! not the weighted full-weighting operators a general-purpose multigrid
+//! library would use — including for the solid mask, so a fine-grid wall
+//! cell block-averaged with an adjacent fluid cell becomes a fractionally
+//! solid coarse cell rather than snapping to one or the other, per this
+//! feature's original request. That's simpler than it needs to be for
+//! optimal convergence rates, but any restriction-error it introduces is
+//! cleaned up by the post-smoothing step on every level on the way back up,
+//! same as it would be in a more careful implementation — it costs
+//! iterations, not correctness.
+
+/// Per-level face weights: `sx0`/`sx1`/`sy0`/`sy1` are this cell's four
+/// neighbors' solid-mask values (`0.0`..`1.0`, fractional on a restricted
+/// coarse grid); `inv_s_sum` is `1 / (sx0+sx1+sy0+sy1)`, `0.0` where that
+/// sum is zero (an isolated or fully solid cell has nothing to solve for).
+/// Mirrors `fluid::NeighborWeights`, generalized to operate on a standalone
+/// `s` slice instead of always reading `Fluid::s`.
+struct Weights {
+    sx0: Vec<f64>,
+    sx1: Vec<f64>,
+    sy0: Vec<f64>,
+    sy1: Vec<f64>,
+    inv_s_sum: Vec<f64>,
+}
+
+impl Weights {
+    fn build(s: &[f64], num_x: usize, num_y: usize) -> Self {
+        let n = num_y;
+        let len = s.len();
+        let mut w = Weights {
+            sx0: vec![0.0; len],
+            sx1: vec![0.0; len],
+            sy0: vec![0.0; len],
+            sy1: vec![0.0; len],
+            inv_s_sum: vec![0.0; len],
+        };
+        for i in 1..num_x - 1 {
+            for j in 1..num_y - 1 {
+                let c = i * n + j;
+                if s[c] == 0.0 {
+                    continue;
+                }
+                let sx0 = s[(i - 1) * n + j];
+                let sx1 = s[(i + 1) * n + j];
+                let sy0 = s[i * n + j - 1];
+                let sy1 = s[i * n + j + 1];
+                let sum = sx0 + sx1 + sy0 + sy1;
+                if sum == 0.0 {
+                    continue;
+                }
+                w.sx0[c] = sx0;
+                w.sx1[c] = sx1;
+                w.sy0[c] = sy0;
+                w.sy1[c] = sy1;
+                w.inv_s_sum[c] = 1.0 / sum;
+            }
+        }
+        w
+    }
+}
+
+/// `A(phi)_c = sum_neighbors s_n * (phi_n - phi_c)` at every interior cell,
+/// `0.0` elsewhere.
+fn apply_operator(phi: &[f64], weights: &Weights, num_x: usize, num_y: usize) -> Vec<f64> {
+    let n = num_y;
+    let mut out = vec![0.0; phi.len()];
+    for i in 1..num_x - 1 {
+        for j in 1..num_y - 1 {
+            let c = i * n + j;
+            if weights.inv_s_sum[c] == 0.0 {
+                continue;
+            }
+            out[c] = weights.sx0[c] * phi[c - n] + weights.sx1[c] * phi[c + n] + weights.sy0[c] * phi[c - 1]
+                + weights.sy1[c] * phi[c + 1]
+                - (weights.sx0[c] + weights.sx1[c] + weights.sy0[c] + weights.sy1[c]) * phi[c];
+        }
+    }
+    out
+}
+
+/// One red-black Gauss-Seidel sweep pair (repeated `iters` times) driving
+/// `phi` toward solving `A(phi)_c = rhs_c` — the same update
+/// [`crate::fluid::Fluid::solve_incompressibility`]'s `color_sweep` applies
+/// directly to `u`/`v`, here applied to a standalone `phi` array instead.
+fn smooth(phi: &mut [f64], rhs: &[f64], weights: &Weights, num_x: usize, num_y: usize, iters: usize) {
+    let n = num_y;
+    for _ in 0..iters {
+        for color in 0..2 {
+            for i in 1..num_x - 1 {
+                for j in 1..num_y - 1 {
+                    if (i + j) % 2 != color {
+                        continue;
+                    }
+                    let c = i * n + j;
+                    let inv = weights.inv_s_sum[c];
+                    if inv == 0.0 {
+                        continue;
+                    }
+                    let a_phi = weights.sx0[c] * phi[c - n] + weights.sx1[c] * phi[c + n]
+                        + weights.sy0[c] * phi[c - 1]
+                        + weights.sy1[c] * phi[c + 1]
+                        - (weights.sx0[c] + weights.sx1[c] + weights.sy0[c] + weights.sy1[c]) * phi[c];
+                    phi[c] += (a_phi - rhs[c]) * inv;
+                }
+            }
+        }
+    }
+}
+
+/// Averages each 2x2 block of the whole `fine_nx` x `fine_ny` grid
+/// (including its ghost ring, so a coarse cell touching the domain edge
+/// restricts from whatever the fine ghost ring held there) into one coarse
+/// cell. Odd dimensions leave a trailing 1-wide row/column averaged alone.
+fn restrict(fine: &[f64], fine_nx: usize, fine_ny: usize) -> (Vec<f64>, usize, usize) {
+    let coarse_nx = fine_nx.div_ceil(2);
+    let coarse_ny = fine_ny.div_ceil(2);
+    let mut sum = vec![0.0; coarse_nx * coarse_ny];
+    let mut count = vec![0.0; coarse_nx * coarse_ny];
+    for i in 0..fine_nx {
+        for j in 0..fine_ny {
+            let idx = (i / 2) * coarse_ny + (j / 2);
+            sum[idx] += fine[i * fine_ny + j];
+            count[idx] += 1.0;
+        }
+    }
+    let coarse: Vec<f64> = sum.iter().zip(&count).map(|(&s, &c)| if c > 0.0 { s / c } else { 0.0 }).collect();
+    (coarse, coarse_nx, coarse_ny)
+}
+
+/// Adds each coarse cell's value to every fine cell in its 2x2 block —
+/// piecewise-constant prolongation, the counterpart to [`restrict`]'s
+/// averaging.
+fn prolong_add(coarse: &[f64], coarse_ny: usize, fine: &mut [f64], fine_nx: usize, fine_ny: usize) {
+    for i in 0..fine_nx {
+        for j in 0..fine_ny {
+            let idx = (i / 2) * coarse_ny + (j / 2);
+            fine[i * fine_ny + j] += coarse[idx];
+        }
+    }
+}
+
+/// A grid is worth coarsening further only if the coarse level still has at
+/// least one real interior cell in both directions.
+fn can_coarsen(num_x: usize, num_y: usize) -> bool {
+    num_x.div_ceil(2) >= 3 && num_y.div_ceil(2) >= 3
+}
+
+/// One V-cycle: pre-smooth, restrict the residual (and `s`) to a coarser
+/// grid, recurse for the coarse correction (a handful of extra smoothing
+/// sweeps stand in for a direct solve once `levels_remaining` hits zero or
+/// the grid is too small to coarsen further), prolong the correction back
+/// and post-smooth.
+fn v_cycle(phi: &mut [f64], rhs: &[f64], s: &[f64], num_x: usize, num_y: usize, levels_remaining: usize, smoothing_iters: usize) {
+    let weights = Weights::build(s, num_x, num_y);
+    smooth(phi, rhs, &weights, num_x, num_y, smoothing_iters);
+
+    if levels_remaining == 0 || !can_coarsen(num_x, num_y) {
+        smooth(phi, rhs, &weights, num_x, num_y, smoothing_iters * 4);
+        return;
+    }
+
+    let a_phi = apply_operator(phi, &weights, num_x, num_y);
+    let residual: Vec<f64> = rhs.iter().zip(&a_phi).map(|(&r, &a)| r - a).collect();
+
+    let (coarse_s, coarse_nx, coarse_ny) = restrict(s, num_x, num_y);
+    let (coarse_rhs, _, _) = restrict(&residual, num_x, num_y);
+    let mut coarse_phi = vec![0.0; coarse_nx * coarse_ny];
+    v_cycle(&mut coarse_phi, &coarse_rhs, &coarse_s, coarse_nx, coarse_ny, levels_remaining - 1, smoothing_iters);
+
+    prolong_add(&coarse_phi, coarse_ny, phi, num_x, num_y);
+    smooth(phi, rhs, &weights, num_x, num_y, smoothing_iters);
+}
+
+/// Solves `sum_neighbors(phi_neighbor - phi_center) = rhs_center` for every
+/// interior cell of a `num_x` x `num_y` grid (ghost boundary ring at index
+/// `0`/`num_x - 1`/`num_y - 1`, matching [`crate::fluid::Fluid`]'s own
+/// layout) via `v_cycles` repetitions of a V-cycle, coarsening at most
+/// `levels` times (fewer if the grid is too small to coarsen that far),
+/// with `smoothing_iters` red-black Gauss-Seidel sweeps at each level's
+/// pre/post smoothing step.
+pub fn solve(rhs: &[f64], s: &[f64], num_x: usize, num_y: usize, levels: usize, v_cycles: usize, smoothing_iters: usize) -> Vec<f64> {
+    let mut phi = vec![0.0; rhs.len()];
+    for _ in 0..v_cycles {
+        v_cycle(&mut phi, rhs, s, num_x, num_y, levels, smoothing_iters);
+    }
+    phi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A single interior fluid cell surrounded by fluid neighbors, `rhs`
+    /// set so the exact solution is known: with every `s` at `1.0`,
+    /// `A(phi)_c = phi_up + phi_down + phi_left + phi_right - 4*phi_c`. Pin
+    /// every neighbor's `phi` to `0.0` implicitly (they're outside the
+    /// single free cell) and `rhs_c = -4.0` gives `phi_c = 1.0`.
+    #[test]
+    fn converges_to_the_exact_solution_on_a_single_free_cell() {
+        let num_x = 3;
+        let num_y = 3;
+        let s = vec![1.0; num_x * num_y];
+        let mut rhs = vec![0.0; num_x * num_y];
+        rhs[num_y + 1] = -4.0;
+
+        let phi = solve(&rhs, &s, num_x, num_y, 0, 20, 5);
+        assert!((phi[num_y + 1] - 1.0).abs() < 1e-9, "expected phi = 1.0, got {}", phi[num_y + 1]);
+    }
+
+    #[test]
+    fn zero_divergence_input_leaves_phi_at_zero() {
+        let num_x = 10;
+        let num_y = 8;
+        let s = vec![1.0; num_x * num_y];
+        let rhs = vec![0.0; num_x * num_y];
+
+        let phi = solve(&rhs, &s, num_x, num_y, 3, 3, 3);
+        assert!(phi.iter().all(|&v| v == 0.0), "no divergence to correct should leave phi untouched");
+    }
+
+    #[test]
+    fn a_deeper_v_cycle_does_not_diverge_on_a_larger_grid() {
+        let num_x = 34;
+        let num_y = 22;
+        let s = vec![1.0; num_x * num_y];
+        let mut rhs = vec![0.0; num_x * num_y];
+        for i in 1..num_x - 1 {
+            for j in 1..num_y - 1 {
+                rhs[i * num_y + j] = if (i + j) % 5 == 0 { 0.3 } else { -0.1 };
+            }
+        }
+
+        let weights = Weights::build(&s, num_x, num_y);
+        let phi = solve(&rhs, &s, num_x, num_y, 4, 12, 3);
+        let a_phi = apply_operator(&phi, &weights, num_x, num_y);
+        let max_residual =
+            rhs.iter().zip(&a_phi).map(|(&r, &a)| (r - a).abs()).fold(0.0_f64, f64::max);
+        assert!(max_residual < 1e-2, "expected the V-cycles to drive the residual down, got {max_residual}");
+    }
+}
@@ -0,0 +1,198 @@
+//! Pluggable objective functions for sweep optimization.
+//!
+//! The previous optimal-angle selection hardcoded
+//! `cooling_efficiency / (1 + fan_power/1000)`, an arbitrary weighting no
+//! one had actually agreed to (`cooling_efficiency` has since been replaced
+//! by a real epsilon-NTU `heat_rejected_watts`/`effectiveness` pair — see
+//! `radiator::HeatExchanger`). `Objective` replaces it with a small set of
+//! named built-ins plus an expression option that reads directly off
+//! [`RadiatorMetrics`] field names, so a weighting can be changed from the
+//! CLI/config instead of a code edit.
+
+use serde::{Deserialize, Serialize};
+
+use crate::metrics::RadiatorMetrics;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(dead_code)]
+pub enum Objective {
+    MaxMassFlow,
+    MaxHeatRejection,
+    /// `heat_rejected_watts / fan_power_required` — the object the request
+    /// that replaced `cooling_efficiency` with a real epsilon-NTU model
+    /// actually asked an angle/porosity sweep to optimize: most heat
+    /// rejected per unit of power spent moving air. 0 when
+    /// `fan_power_required` is ~0 rather than dividing by it.
+    MaxHeatRejectionPerFanPower,
+    /// Minimize fan power, excluding any case whose mass flow falls below
+    /// `min_mass_flow`.
+    MinFanPowerAtMassFlowConstraint { min_mass_flow: f64 },
+    /// `sum(weight * metrics[field])` over the given (field, weight) pairs.
+    WeightedSum(Vec<(String, f64)>),
+    /// A small arithmetic expression over `RadiatorMetrics` field names,
+    /// e.g. `"mass_flow - 0.02*fan_power_required"`.
+    Expression(String),
+}
+
+#[allow(dead_code)]
+impl Objective {
+    /// Evaluate this objective for one sweep case. `Ok(None)` means the
+    /// case is infeasible and should be excluded, with the reason logged by
+    /// the caller; `Err` means the objective itself couldn't be evaluated
+    /// (bad expression, unknown field name).
+    pub fn evaluate(&self, metrics: &RadiatorMetrics) -> Result<Option<f64>, String> {
+        match self {
+            Objective::MaxMassFlow => Ok(Some(metrics.mass_flow)),
+            Objective::MaxHeatRejection => Ok(Some(metrics.heat_rejected_watts)),
+            Objective::MaxHeatRejectionPerFanPower => {
+                if metrics.fan_power_required > 1e-9 {
+                    Ok(Some(metrics.heat_rejected_watts / metrics.fan_power_required))
+                } else {
+                    Ok(Some(0.0))
+                }
+            }
+            Objective::MinFanPowerAtMassFlowConstraint { min_mass_flow } => {
+                if metrics.mass_flow < *min_mass_flow {
+                    Ok(None)
+                } else {
+                    Ok(Some(-metrics.fan_power_required))
+                }
+            }
+            Objective::WeightedSum(terms) => {
+                let fields = metrics_as_map(metrics)?;
+                let mut total = 0.0;
+                for (name, weight) in terms {
+                    let value = fields
+                        .get(name.as_str())
+                        .ok_or_else(|| format!("unknown RadiatorMetrics field `{name}`"))?;
+                    total += weight * value;
+                }
+                Ok(Some(total))
+            }
+            Objective::Expression(expr) => eval_expression(expr, metrics).map(Some),
+        }
+    }
+
+    pub fn infeasible_reason(&self, metrics: &RadiatorMetrics) -> Option<String> {
+        if let Objective::MinFanPowerAtMassFlowConstraint { min_mass_flow } = self {
+            if metrics.mass_flow < *min_mass_flow {
+                return Some(format!(
+                    "mass_flow {:.4} below constraint minimum {:.4}",
+                    metrics.mass_flow, min_mass_flow
+                ));
+            }
+        }
+        None
+    }
+}
+
+fn metrics_as_map(metrics: &RadiatorMetrics) -> Result<std::collections::HashMap<String, f64>, String> {
+    let value = serde_json::to_value(metrics).map_err(|e| e.to_string())?;
+    let object = value.as_object().ok_or("RadiatorMetrics did not serialize to an object")?;
+    Ok(object
+        .iter()
+        .filter_map(|(k, v)| v.as_f64().map(|f| (k.clone(), f)))
+        .collect())
+}
+
+/// A minimal `term (+|- term)*` expression grammar, where each term is
+/// either `field` or `coefficient*field`, over `RadiatorMetrics` fields.
+fn eval_expression(expr: &str, metrics: &RadiatorMetrics) -> Result<f64, String> {
+    let fields = metrics_as_map(metrics)?;
+    let normalized = expr.replace('-', "+-");
+    let mut total = 0.0;
+    for raw_term in normalized.split('+') {
+        let term = raw_term.trim();
+        if term.is_empty() {
+            continue;
+        }
+        let (sign, term) = if let Some(rest) = term.strip_prefix('-') {
+            (-1.0, rest.trim())
+        } else {
+            (1.0, term)
+        };
+
+        let value = if let Some((coeff, field)) = term.split_once('*') {
+            let coeff: f64 = coeff
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid coefficient in term `{term}`"))?;
+            let field = field.trim();
+            let field_value = fields
+                .get(field)
+                .ok_or_else(|| format!("unknown RadiatorMetrics field `{field}`"))?;
+            coeff * field_value
+        } else {
+            *fields
+                .get(term)
+                .ok_or_else(|| format!("unknown RadiatorMetrics field `{term}`"))?
+        };
+
+        total += sign * value;
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_metrics() -> RadiatorMetrics {
+        RadiatorMetrics {
+            fan_power_required: 100.0,
+            capture_ratio: 0.8,
+            loss_coefficient: 0.2,
+            mass_flow: 3.0,
+            heat_rejected_watts: 1500.0,
+            effectiveness: 0.6,
+            frontal_area: 0.2,
+            tunnel_area: 1.0,
+            blockage_ratio: 0.2,
+            blockage_correction_factor: 0.05,
+            pressure_drop_raw: 10.0,
+            pressure_drop_corrected: 9.0,
+            drag_raw: 2.0,
+            drag_corrected: 1.8,
+            drag_wake_survey: 0.0,
+            flow_uniformity_index: 0.0,
+            reversed_flow_fraction: 0.0,
+            recirculation_area: 0.0,
+        }
+    }
+
+    #[test]
+    fn expression_parses_field_names_and_coefficients() {
+        let metrics = sample_metrics();
+        let objective = Objective::Expression("mass_flow - 0.02*fan_power_required".to_string());
+        let value = objective.evaluate(&metrics).unwrap().unwrap();
+        assert!((value - (3.0 - 0.02 * 100.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn expression_reports_unknown_field_names_clearly() {
+        let metrics = sample_metrics();
+        let objective = Objective::Expression("mass_flow - 0.02*drag_force".to_string());
+        let err = objective.evaluate(&metrics).unwrap_err();
+        assert!(err.contains("drag_force"));
+    }
+
+    #[test]
+    fn mass_flow_constraint_excludes_infeasible_cases_with_a_reason() {
+        let metrics = sample_metrics();
+        let objective = Objective::MinFanPowerAtMassFlowConstraint { min_mass_flow: 5.0 };
+        assert_eq!(objective.evaluate(&metrics).unwrap(), None);
+        assert!(objective.infeasible_reason(&metrics).unwrap().contains("mass_flow"));
+    }
+
+    #[test]
+    fn weighted_sum_combines_named_fields() {
+        let metrics = sample_metrics();
+        let objective = Objective::WeightedSum(vec![
+            ("mass_flow".to_string(), 1.0),
+            ("fan_power_required".to_string(), -0.01),
+        ]);
+        let value = objective.evaluate(&metrics).unwrap().unwrap();
+        assert!((value - (3.0 - 0.01 * 100.0)).abs() < 1e-9);
+    }
+}
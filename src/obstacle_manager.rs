@@ -0,0 +1,120 @@
+//! Owns everything placed in a scene's flow field — porous radiators and
+//! solid obstacles — as a thin composition of two single-purpose pieces:
+//! [`RadiatorModel`] for mutating the fluid, and [`obstacle_analysis`] for
+//! stateless force diagnostics. Previously this struct did both itself,
+//! which was harmless as long as nothing needed to hold a read-only
+//! analysis borrow and a mutating one at the same time, but conflated two
+//! concerns that already had a natural seam.
+
+use crate::fluid::Fluid;
+use crate::obstacle_analysis::{self, ObstacleForces};
+use crate::radiator::Radiator;
+use crate::radiator_model::RadiatorModel;
+use crate::scene_config::ObstacleShape;
+
+#[allow(dead_code)]
+pub struct ObstacleManager {
+    radiator_model: RadiatorModel,
+    /// Solid obstacles (cylinders, plates, airfoils) already marked into the
+    /// solid mask at setup, kept here purely so `compute_forces` knows which
+    /// footprint each obstacle owns.
+    obstacles: Vec<ObstacleShape>,
+    /// Parallel to `obstacles`: whether that obstacle was placed with
+    /// [`crate::scene::mark_obstacle_solid_cut_cell`] (fractional solid
+    /// values near its boundary) rather than the binary
+    /// [`crate::scene::mark_obstacle_solid`]. `Scene::remove_obstacle` reads
+    /// this so re-marking a surviving obstacle after another one is removed
+    /// uses whichever method originally placed it, instead of always
+    /// collapsing it to a binary mask.
+    cut_cell: Vec<bool>,
+}
+
+#[allow(dead_code)]
+impl ObstacleManager {
+    pub fn new(fluid: &Fluid, radiators: Vec<Radiator>, obstacles: Vec<ObstacleShape>) -> Self {
+        let cut_cell = vec![false; obstacles.len()];
+        ObstacleManager {
+            radiator_model: RadiatorModel::new(fluid, radiators),
+            obstacles,
+            cut_cell,
+        }
+    }
+
+    /// Same as [`Self::new`], but records which `obstacles` entries were
+    /// placed with `mark_obstacle_solid_cut_cell` — see the `cut_cell`
+    /// field. `cut_cell` must be the same length as `obstacles`.
+    pub fn with_cut_cell_flags(fluid: &Fluid, radiators: Vec<Radiator>, obstacles: Vec<ObstacleShape>, cut_cell: Vec<bool>) -> Self {
+        assert_eq!(cut_cell.len(), obstacles.len(), "cut_cell flags must match obstacles 1:1");
+        ObstacleManager {
+            radiator_model: RadiatorModel::new(fluid, radiators),
+            obstacles,
+            cut_cell,
+        }
+    }
+
+    /// Whether each entry in [`Self::obstacles`] (same index) was placed
+    /// with `mark_obstacle_solid_cut_cell`. See the `cut_cell` field.
+    pub fn cut_cell_flags(&self) -> &[bool] {
+        &self.cut_cell
+    }
+
+    pub fn radiators(&self) -> &[Radiator] {
+        self.radiator_model.radiators()
+    }
+
+    pub fn radiator_ids(&self) -> &[String] {
+        self.radiator_model.radiator_ids()
+    }
+
+    pub fn set_radiator_ids(&mut self, ids: Vec<String>) {
+        self.radiator_model.set_radiator_ids(ids);
+    }
+
+    pub fn obstacles(&self) -> &[ObstacleShape] {
+        &self.obstacles
+    }
+
+    /// Register an obstacle placed after construction (e.g. loaded from a
+    /// CAD outline via `geometry_io`), so `compute_forces` picks it up.
+    /// Callers are responsible for marking its footprint solid in the
+    /// fluid themselves — see `scene::mark_obstacle_solid` — this only
+    /// updates the bookkeeping `compute_forces` reads.
+    pub fn add_obstacle(&mut self, shape: ObstacleShape) {
+        self.obstacles.push(shape);
+        self.cut_cell.push(false);
+    }
+
+    /// Replace the parameters of radiator `index`. See
+    /// [`RadiatorModel::reconfigure`].
+    pub fn reconfigure(&mut self, index: usize, fluid: &mut Fluid, new: Radiator) {
+        self.radiator_model.reconfigure(index, fluid, new);
+    }
+
+    /// Drop radiator `index`, returning it. See [`RadiatorModel::remove`] —
+    /// no `&mut Fluid` needed, since a radiator never marks the solid mask.
+    pub fn remove_radiator(&mut self, index: usize) -> Radiator {
+        self.radiator_model.remove(index)
+    }
+
+    /// Drop obstacle `index` from the bookkeeping, returning its shape.
+    /// Mirrors [`Self::add_obstacle`]: callers are responsible for restoring
+    /// its footprint in the fluid themselves (and re-marking any remaining
+    /// obstacle whose footprint overlapped it) — see
+    /// `scene::Scene::remove_obstacle`.
+    pub fn remove_obstacle(&mut self, index: usize) -> ObstacleShape {
+        self.cut_cell.remove(index);
+        self.obstacles.remove(index)
+    }
+
+    /// Apply this step's porous resistance for every radiator. See
+    /// [`RadiatorModel::apply_porous_forces`].
+    pub fn apply_porous_forces(&mut self, fluid: &mut Fluid, dt: f64) {
+        self.radiator_model.apply_porous_forces(fluid, dt);
+    }
+
+    /// Force/coefficient diagnostics for every solid obstacle. See
+    /// [`obstacle_analysis::compute_obstacle_forces`].
+    pub fn compute_forces(&self, fluid: &Fluid, inflow_u: f64) -> Vec<ObstacleForces> {
+        obstacle_analysis::compute_obstacle_forces(fluid, &self.obstacles, inflow_u)
+    }
+}
@@ -0,0 +1,223 @@
+//! Central bookkeeping for every artifact a run writes to disk.
+//!
+//! Anything that saves a PNG, VTK file, or animation frame should go
+//! through [`OutputManager::record`] instead of writing files ad hoc, so
+//! that `manifest.json` is always a complete, queryable index of what a run
+//! produced.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    PressureField,
+    MeanPressureField,
+    TurbulenceIntensityField,
+    VelocityMagnitudeField,
+    LicField,
+    SmokeField,
+    VorticityField,
+    SolidFractionField,
+    Streamlines,
+    Particles,
+    AnimatorFrame,
+    AnimatorGif,
+    AnimatorVideo,
+    Vtk,
+    ForcesHistory,
+    ConvergenceHistory,
+    DivergenceHistoryPlot,
+    FieldHistory,
+    LineProfile,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactRecord {
+    pub kind: ArtifactKind,
+    pub step: u64,
+    pub sim_time: f64,
+    pub path: String,
+    /// Only set for `AnimatorFrame` artifacts: the animator's own
+    /// monotonically increasing frame counter, separate from `step`.
+    pub frame_index: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub artifacts: Vec<ArtifactRecord>,
+}
+
+impl Manifest {
+    pub fn lookup_step(&self, step: u64) -> Vec<&ArtifactRecord> {
+        self.artifacts.iter().filter(|a| a.step == step).collect()
+    }
+}
+
+/// One of the per-step field visualizations `run`'s main loop (and
+/// `sweep::save_case_snapshots`) can write. Selected via `--outputs`, a
+/// comma-separated list of these names (see [`OutputSelection::parse`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputKind {
+    Smoke,
+    Pressure,
+    Velocity,
+    Vorticity,
+    Streamlines,
+    /// Per-cell solid coverage fraction — a debug view of
+    /// [`crate::scene::mark_obstacle_solid_cut_cell`]'s supersampled `s`.
+    /// Deliberately left out of [`Self::ALL`]/the default selection: most
+    /// scenes have a purely binary `s` mask, so this would be a blank frame
+    /// on every run that doesn't opt into `cut_cell`. Select it explicitly
+    /// with `--outputs solid_fraction`.
+    SolidFraction,
+}
+
+impl OutputKind {
+    const ALL: [OutputKind; 5] =
+        [OutputKind::Smoke, OutputKind::Pressure, OutputKind::Velocity, OutputKind::Vorticity, OutputKind::Streamlines];
+
+    /// Every kind `--outputs` will accept, including debug-only kinds that
+    /// aren't in [`Self::ALL`] and so aren't on by default.
+    const SELECTABLE: [OutputKind; 6] = [
+        OutputKind::Smoke,
+        OutputKind::Pressure,
+        OutputKind::Velocity,
+        OutputKind::Vorticity,
+        OutputKind::Streamlines,
+        OutputKind::SolidFraction,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            OutputKind::Smoke => "smoke",
+            OutputKind::Pressure => "pressure",
+            OutputKind::Velocity => "velocity",
+            OutputKind::Vorticity => "vorticity",
+            OutputKind::Streamlines => "streamlines",
+            OutputKind::SolidFraction => "solid_fraction",
+        }
+    }
+}
+
+/// Which [`OutputKind`]s a run should write, parsed from `--outputs` (or
+/// `SweepConfig::outputs`). Defaults to every kind — the behavior every run
+/// had before this selection existed. `--no-images` is [`OutputSelection::none`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputSelection(HashSet<OutputKind>);
+
+impl Default for OutputSelection {
+    fn default() -> Self {
+        OutputSelection(OutputKind::ALL.into_iter().collect())
+    }
+}
+
+impl OutputSelection {
+    pub fn none() -> Self {
+        OutputSelection(HashSet::new())
+    }
+
+    /// Parses a comma-separated list like `"smoke,pressure,velocity"`.
+    /// Rejects anything not in [`OutputKind::SELECTABLE`] rather than
+    /// silently ignoring a typo'd field name.
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let mut kinds = HashSet::new();
+        for name in list.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let kind = OutputKind::SELECTABLE.into_iter().find(|k| k.name() == name).ok_or_else(|| {
+                let valid: Vec<&str> = OutputKind::SELECTABLE.iter().map(|k| k.name()).collect();
+                format!("unknown output `{name}`, expected one of: {}", valid.join(", "))
+            })?;
+            kinds.insert(kind);
+        }
+        Ok(OutputSelection(kinds))
+    }
+
+    pub fn wants(&self, kind: OutputKind) -> bool {
+        self.0.contains(&kind)
+    }
+}
+
+pub struct OutputManager {
+    pub output_dir: PathBuf,
+    pub manifest: Manifest,
+    /// Which field visualizations to write and how often — every kind,
+    /// every step (`output_every: 1`) until a caller narrows it with
+    /// [`OutputManager::with_output_policy`]. `run_scene`'s main loop
+    /// checks [`OutputManager::is_output_step`] instead of hand-rolling
+    /// `step % output_every == 0`, and gates each `Visualizer::save_*` call
+    /// behind [`OutputManager::wants`], so `--output-every`/`--outputs`/
+    /// `--no-images` only have to be threaded down to this one place.
+    pub outputs: OutputSelection,
+    output_every: u64,
+}
+
+impl OutputManager {
+    pub fn new(output_dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let output_dir = output_dir.into();
+        fs::create_dir_all(&output_dir)?;
+        Ok(OutputManager {
+            output_dir,
+            manifest: Manifest::default(),
+            outputs: OutputSelection::default(),
+            output_every: 1,
+        })
+    }
+
+    /// Narrows this manager's output selection/cadence away from the
+    /// "everything, every step" default `new` starts with.
+    pub fn with_output_policy(mut self, outputs: OutputSelection, output_every: u64) -> Self {
+        self.outputs = outputs;
+        self.output_every = output_every.max(1);
+        self
+    }
+
+    pub fn wants(&self, kind: OutputKind) -> bool {
+        self.outputs.wants(kind)
+    }
+
+    pub fn is_output_step(&self, step: u64) -> bool {
+        step.is_multiple_of(self.output_every)
+    }
+
+    pub fn path_for(&self, file_name: &str) -> PathBuf {
+        self.output_dir.join(file_name)
+    }
+
+    /// Register a written artifact. `path` should already exist on disk;
+    /// this only updates the in-memory manifest, callers still call
+    /// `save_manifest` (typically once, at the end of a run) to persist it.
+    pub fn record(
+        &mut self,
+        kind: ArtifactKind,
+        step: u64,
+        sim_time: f64,
+        path: &Path,
+        frame_index: Option<u64>,
+    ) {
+        self.manifest.artifacts.push(ArtifactRecord {
+            kind,
+            step,
+            sim_time,
+            path: path.to_string_lossy().into_owned(),
+            frame_index,
+        });
+    }
+
+    pub fn manifest_path(&self) -> PathBuf {
+        self.output_dir.join("manifest.json")
+    }
+
+    pub fn save_manifest(&self) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.manifest)?;
+        fs::write(self.manifest_path(), json)
+    }
+
+    pub fn load_manifest(output_dir: impl AsRef<Path>) -> std::io::Result<Manifest> {
+        let path = output_dir.as_ref().join("manifest.json");
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+}
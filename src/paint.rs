@@ -0,0 +1,272 @@
+//! Direct, circular-footprint edits to a running [`crate::fluid::Fluid`]:
+//! stamp dye, place or clear a solid patch, or inject momentum. This is the
+//! primitive layer behind `scene::Scene`'s `paint_smoke`/`paint_solid`/
+//! `erase_solid`/`stir` methods, which the live viewer (`viewer.rs`) calls
+//! from the mouse and [`PaintEvent`] calls on a timer — both end up here so
+//! an interactive stroke and a scripted one edit the grid identically.
+//!
+//! Every function stays inside `1..num_x - 1` / `1..num_y - 1`, the same
+//! interior-only range `scene::mark_obstacle_solid` and
+//! `dye_emitter::DyeEmitter::apply` already use, so a footprint centered on
+//! or overhanging the domain border only ever touches interior cells —
+//! the ghost boundary row/column can't be painted into an invalid state.
+
+use serde::{Deserialize, Serialize};
+
+use crate::fluid::{Fluid, FLUID_CELL, SOLID_CELL};
+
+/// Whether `(x, y)` falls within `radius` of `(cx, cy)`, the circular
+/// footprint every function in this module uses.
+fn in_footprint(x: f64, y: f64, cx: f64, cy: f64, radius: f64) -> bool {
+    (x - cx).powi(2) + (y - cy).powi(2) <= radius * radius
+}
+
+/// Stamps `value` into every fluid (non-solid) cell's `m` within `radius` of
+/// `(cx, cy)`. Solid cells in the footprint are left untouched, the same
+/// skip [`crate::dye_emitter::DyeEmitter::apply`] uses — dye has no business
+/// appearing inside a wall.
+pub fn paint_smoke(fluid: &mut Fluid, cx: f64, cy: f64, radius: f64, value: f64) {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            if !in_footprint(x, y, cx, cy, radius) {
+                continue;
+            }
+            let idx = i * n + j;
+            if fluid.s[idx] == SOLID_CELL {
+                continue;
+            }
+            fluid.m[idx] = value;
+        }
+    }
+}
+
+/// Marks every cell within `radius` of `(cx, cy)` solid and zeroes its four
+/// faces, the same face-zeroing [`crate::scene::mark_obstacle_solid`] does
+/// for a configured obstacle — a painted-in wall shouldn't keep carrying
+/// whatever velocity was flowing through it a moment ago.
+pub fn paint_solid(fluid: &mut Fluid, cx: f64, cy: f64, radius: f64) {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            if !in_footprint(x, y, cx, cy, radius) {
+                continue;
+            }
+            let idx = i * n + j;
+            fluid.s[idx] = SOLID_CELL;
+            fluid.u[idx] = 0.0;
+            fluid.u[(i + 1) * n + j] = 0.0;
+            fluid.v[idx] = 0.0;
+            fluid.v[idx + 1] = 0.0;
+        }
+    }
+}
+
+/// Undoes [`paint_solid`] within `radius` of `(cx, cy)`: restores every
+/// covered cell to fluid. Doesn't touch `u`/`v` beyond what `paint_solid`
+/// already zeroed, mirroring `scene::unmark_obstacle_solid` — the next
+/// pressure solve fills the freshly-uncovered cell in like any other.
+pub fn erase_solid(fluid: &mut Fluid, cx: f64, cy: f64, radius: f64) {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            if in_footprint(x, y, cx, cy, radius) {
+                fluid.s[i * n + j] = FLUID_CELL;
+            }
+        }
+    }
+}
+
+/// Adds `(vx, vy)` to every face bordering a covered (fluid, in-footprint)
+/// cell within `radius` of `(cx, cy)`, skipping solid cells so a stir near
+/// a wall doesn't push momentum through it. Unlike `paint_smoke`/
+/// `paint_solid`, this blends into the existing flow rather than
+/// overwriting it, since a stir is meant to perturb the flow the pressure
+/// solve is already maintaining, not replace it outright.
+///
+/// Works out which faces to touch in two passes rather than adding to both
+/// of a cell's faces as it's visited: `u`/`v` are staggered, so two
+/// footprint-covered cells side by side share one face, and adding to it
+/// once per adjacent cell would double it up right in the middle of the
+/// stir instead of applying the same kick throughout.
+pub fn stir(fluid: &mut Fluid, cx: f64, cy: f64, radius: f64, vx: f64, vy: f64) {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    let mut covered = vec![false; fluid.num_x * n];
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            covered[i * n + j] = in_footprint(x, y, cx, cy, radius);
+        }
+    }
+    let solid = |idx: usize| fluid.s[idx] == SOLID_CELL;
+    // `u[idx(i, j)]` is the face between cell `(i - 1, j)` and `(i, j)`.
+    for i in 1..fluid.num_x {
+        for j in 1..fluid.num_y - 1 {
+            let idx = i * n + j;
+            if !solid(idx) && !solid(idx - n) && (covered[idx] || covered[idx - n]) {
+                fluid.u[idx] += vx;
+            }
+        }
+    }
+    // `v[idx(i, j)]` is the face between cell `(i, j - 1)` and `(i, j)`.
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y {
+            let idx = i * n + j;
+            if !solid(idx) && !solid(idx - 1) && (covered[idx] || covered[idx - 1]) {
+                fluid.v[idx] += vy;
+            }
+        }
+    }
+}
+
+/// What a [`PaintEvent`] does once it fires — one variant per function in
+/// this module.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum PaintAction {
+    Smoke { value: f64 },
+    Solid,
+    EraseSolid,
+    Stir { vx: f64, vy: f64 },
+}
+
+/// A scripted paint stroke, timed rather than interactive: fires at most
+/// once, the first time `sim_time` reaches `at_time`. A `SceneConfig`'s
+/// `paint_events` list is how a headless run reproduces the strokes a live
+/// viewer session would otherwise only ever draw with the mouse.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PaintEvent {
+    pub at_time: f64,
+    pub x: f64,
+    pub y: f64,
+    pub radius: f64,
+    pub action: PaintAction,
+    /// Not read back from a config file — a freshly loaded event always
+    /// starts unfired, the same way `wake_trigger::WakeTrigger`'s `fired`
+    /// flag lives outside its `WakeTriggerConfig`.
+    #[serde(default, skip_serializing)]
+    fired: bool,
+}
+
+impl PaintEvent {
+    pub fn new(at_time: f64, x: f64, y: f64, radius: f64, action: PaintAction) -> Self {
+        PaintEvent { at_time, x, y, radius, action, fired: false }
+    }
+
+    /// Applies this event to `fluid` and marks it fired if `sim_time` has
+    /// reached `at_time` and it hasn't already fired; a no-op otherwise
+    /// (including on every call after it fires). Returns whether it fired
+    /// this call, the same `bool` result `WakeTrigger::maybe_fire` returns.
+    pub fn maybe_fire(&mut self, fluid: &mut Fluid, sim_time: f64) -> bool {
+        if self.fired || sim_time < self.at_time {
+            return false;
+        }
+        match self.action {
+            PaintAction::Smoke { value } => paint_smoke(fluid, self.x, self.y, self.radius, value),
+            PaintAction::Solid => paint_solid(fluid, self.x, self.y, self.radius),
+            PaintAction::EraseSolid => erase_solid(fluid, self.x, self.y, self.radius),
+            PaintAction::Stir { vx, vy } => stir(fluid, self.x, self.y, self.radius, vx, vy),
+        }
+        self.fired = true;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluid::FLUID_CELL;
+
+    fn fluid() -> Fluid {
+        let mut fluid = Fluid::new(1000.0, 20, 20, 0.05);
+        for s in fluid.s.iter_mut() {
+            *s = FLUID_CELL;
+        }
+        fluid
+    }
+
+    #[test]
+    fn paint_smoke_stamps_value_but_skips_solid_cells() {
+        let mut fluid = fluid();
+        let idx = fluid.idx(10, 10);
+        let solid_idx = fluid.idx(10, 11);
+        fluid.s[solid_idx] = SOLID_CELL;
+        fluid.m[solid_idx] = 1.0;
+
+        paint_smoke(&mut fluid, 0.5, 0.5, 0.1, 0.8);
+
+        assert_eq!(fluid.m[idx], 0.8);
+        assert_eq!(fluid.m[solid_idx], 1.0, "a solid cell's m should be left untouched");
+    }
+
+    #[test]
+    fn paint_solid_then_erase_solid_round_trips_the_mask() {
+        let mut fluid = fluid();
+        let idx = fluid.idx(10, 10);
+        fluid.u[idx] = 1.0;
+
+        paint_solid(&mut fluid, 0.5, 0.5, 0.1);
+        assert_eq!(fluid.s[idx], SOLID_CELL);
+        assert_eq!(fluid.u[idx], 0.0, "painting solid should zero the covered cell's faces");
+
+        erase_solid(&mut fluid, 0.5, 0.5, 0.1);
+        assert_eq!(fluid.s[idx], FLUID_CELL);
+    }
+
+    #[test]
+    fn stir_adds_momentum_but_skips_solid_cells() {
+        let mut fluid = fluid();
+        let idx = fluid.idx(10, 10);
+        let solid_idx = fluid.idx(10, 11);
+        fluid.s[solid_idx] = SOLID_CELL;
+
+        stir(&mut fluid, 0.5, 0.5, 0.1, 2.0, -1.0);
+
+        assert_eq!(fluid.u[idx], 2.0);
+        assert_eq!(fluid.v[idx], -1.0);
+        assert_eq!(fluid.u[solid_idx], 0.0, "a solid cell's velocity should be left untouched");
+    }
+
+    #[test]
+    fn painting_at_the_domain_border_touches_nothing_outside_the_interior() {
+        let mut fluid = fluid();
+        // A footprint centered right on the border, radius far larger than
+        // the whole domain: every interior cell is covered, but the ghost
+        // border row/column (j == 0, j == num_y - 1) must stay untouched.
+        paint_solid(&mut fluid, 0.0, 0.5, 10.0);
+        for i in 0..fluid.num_x {
+            let top = fluid.idx(i, fluid.num_y - 1);
+            let bottom = fluid.idx(i, 0);
+            assert_eq!(fluid.s[top], FLUID_CELL, "border cell should not be painted solid");
+            assert_eq!(fluid.s[bottom], FLUID_CELL, "border cell should not be painted solid");
+        }
+    }
+
+    #[test]
+    fn a_paint_event_fires_once_when_sim_time_reaches_at_time() {
+        let mut fluid = fluid();
+        let idx = fluid.idx(10, 10);
+        let mut event = PaintEvent::new(2.0, 0.5, 0.5, 0.1, PaintAction::Smoke { value: 0.7 });
+
+        assert!(!event.maybe_fire(&mut fluid, 1.0));
+        assert_eq!(fluid.m[idx], 1.0, "should not have fired yet");
+
+        assert!(event.maybe_fire(&mut fluid, 2.0));
+        assert_eq!(fluid.m[idx], 0.7);
+
+        fluid.m[idx] = 0.0;
+        assert!(!event.maybe_fire(&mut fluid, 3.0), "should not fire a second time");
+        assert_eq!(fluid.m[idx], 0.0);
+    }
+}
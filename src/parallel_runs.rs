@@ -0,0 +1,84 @@
+//! Run a batch of independent, CPU-bound jobs (e.g. one [`crate::scene::Scene`]
+//! simulation per parameter value) across a bounded number of OS threads,
+//! collecting results back in input order.
+//!
+//! There is no `run_radiator_angle_sweep` or `RadiatorAnalyzer` in this
+//! tree for this to plug into, and no `rayon` dependency — each `Scene`
+//! run is independent and CPU-bound, which is exactly what
+//! `std::thread::scope` plus a round-robin split across `jobs` threads
+//! already handles without a new dependency.
+
+use std::thread;
+
+/// Run `job` once per element of `inputs`, spread across up to `jobs`
+/// threads (`jobs.max(1)`, and never more threads than inputs), and return
+/// the results in the same order as `inputs` regardless of which thread
+/// finishes first.
+pub fn run_batch<T, R, F>(inputs: Vec<T>, jobs: usize, job: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Send + Sync,
+{
+    let n = inputs.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let jobs = jobs.max(1).min(n);
+
+    // Round-robin the indexed inputs into `jobs` chunks so a straggler
+    // input doesn't leave one thread doing all the work while the others
+    // sit idle with a contiguous split.
+    let mut chunks: Vec<Vec<(usize, T)>> = (0..jobs).map(|_| Vec::new()).collect();
+    for (i, item) in inputs.into_iter().enumerate() {
+        chunks[i % jobs].push((i, item));
+    }
+
+    let mut results: Vec<Option<R>> = (0..n).map(|_| None).collect();
+    let job_ref = &job;
+    thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| scope.spawn(move || chunk.into_iter().map(|(i, item)| (i, job_ref(item))).collect::<Vec<_>>()))
+            .collect();
+        for handle in handles {
+            for (i, r) in handle.join().expect("worker thread panicked") {
+                results[i] = Some(r);
+            }
+        }
+    });
+
+    results.into_iter().map(|r| r.expect("every input index should be filled exactly once")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn results_come_back_in_input_order_regardless_of_thread_count() {
+        let inputs: Vec<i32> = (0..20).collect();
+        let results = run_batch(inputs.clone(), 4, |x| x * x);
+        let expected: Vec<i32> = inputs.iter().map(|x| x * x).collect();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn jobs_is_clamped_to_at_least_one_and_at_most_input_len() {
+        let inputs = vec![1, 2, 3];
+        assert_eq!(run_batch(inputs.clone(), 0, |x| x), vec![1, 2, 3]);
+        assert_eq!(run_batch(inputs, 100, |x| x), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn every_input_actually_runs_exactly_once() {
+        let counter = AtomicUsize::new(0);
+        let inputs: Vec<()> = (0..37).map(|_| ()).collect();
+        let results = run_batch(inputs, 8, |()| counter.fetch_add(1, Ordering::SeqCst));
+        assert_eq!(counter.load(Ordering::SeqCst), 37);
+        let mut sorted = results;
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..37).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,184 @@
+//! Discrete massless tracer particles advected through the flow and drawn as
+//! individual dots, rather than diffused into the smoke field — closer to
+//! what a real smoke-wand test shows, since [`crate::fluid::Fluid::advect_smoke`]'s
+//! semi-Lagrangian scheme diffuses `m` enough to blur out wake structure a
+//! single traced particle keeps sharp.
+//!
+//! The request that added this named a `Fluid::sample_field` method to
+//! advect particles through; no such method exists in this solver. Particles
+//! are advected with [`crate::fluid::Fluid::sample_velocity`] instead — the
+//! same nearest-face velocity sample [`crate::visualizer::trace_streamlines_with_options`]
+//! already uses for streamlines, so a particle and a streamline seeded at the
+//! same point trace the same path.
+
+use crate::fluid::{Fluid, SOLID_CELL};
+
+/// One massless tracer particle.
+#[derive(Debug, Clone, Copy)]
+pub struct Particle {
+    pub x: f64,
+    pub y: f64,
+    pub age: f64,
+}
+
+/// Where [`ParticleTracer::step`] seeds new particles.
+#[derive(Debug, Clone)]
+pub enum ParticleSeed {
+    /// Evenly spaced down the left edge, one cell in from the inlet — the
+    /// same convention [`crate::visualizer::SeedPlacement::LeftEdge`] uses
+    /// for streamlines.
+    InletLine,
+    /// Caller-supplied points, in physical (world) coordinates, reseeded
+    /// every call up to the remaining particle budget.
+    Points(Vec<(f64, f64)>),
+}
+
+/// Advects a capped population of massless tracer particles through a
+/// [`Fluid`]'s velocity field with RK2, seeding new ones each step and
+/// removing any that leave the domain, land on a solid cell, or exceed
+/// `max_age`.
+pub struct ParticleTracer {
+    particles: Vec<Particle>,
+    seed: ParticleSeed,
+    seed_rate: usize,
+    max_age: f64,
+    max_particles: usize,
+}
+
+impl ParticleTracer {
+    /// `seed_rate` new particles are introduced per [`Self::step`] call
+    /// (subject to `max_particles`), each aged out once its `age` reaches
+    /// `max_age`, and the live population is never allowed to exceed
+    /// `max_particles` regardless of run length.
+    pub fn new(seed: ParticleSeed, seed_rate: usize, max_age: f64, max_particles: usize) -> Self {
+        ParticleTracer { particles: Vec::new(), seed, seed_rate, max_age, max_particles }
+    }
+
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    pub fn max_age(&self) -> f64 {
+        self.max_age
+    }
+
+    /// Seed new particles (up to the remaining budget), advect every live
+    /// particle one step of `dt` with RK2 through `fluid`'s velocity field,
+    /// then drop any that left the domain, landed on a solid cell, or aged
+    /// out.
+    pub fn step(&mut self, fluid: &Fluid, dt: f64) {
+        self.seed_new(fluid);
+
+        let h = fluid.h;
+        let x_hi = (fluid.num_x - 1) as f64 * h;
+        let y_hi = (fluid.num_y - 1) as f64 * h;
+
+        for particle in &mut self.particles {
+            let (u0, v0) = fluid.sample_velocity(particle.x, particle.y);
+            let (u1, v1) = fluid.sample_velocity(particle.x + u0 * dt, particle.y + v0 * dt);
+            particle.x += 0.5 * (u0 + u1) * dt;
+            particle.y += 0.5 * (v0 + v1) * dt;
+            particle.age += dt;
+        }
+
+        self.particles.retain(|p| {
+            if p.x <= h || p.x >= x_hi || p.y <= h || p.y >= y_hi || p.age >= self.max_age {
+                return false;
+            }
+            let i = ((p.x / h) as usize).min(fluid.num_x - 1);
+            let j = ((p.y / h) as usize).min(fluid.num_y - 1);
+            fluid.s[fluid.idx(i, j)] != SOLID_CELL
+        });
+    }
+
+    fn seed_new(&mut self, fluid: &Fluid) {
+        if self.particles.len() >= self.max_particles {
+            return;
+        }
+        let budget = self.max_particles - self.particles.len();
+        let h = fluid.h;
+        let new_points: Vec<(f64, f64)> = match &self.seed {
+            ParticleSeed::InletLine => {
+                let count = self.seed_rate;
+                (0..count)
+                    .map(|s| {
+                        let x = h * 1.5;
+                        let y = h + (s as f64 + 0.5) * (fluid.num_y as f64 * h - 2.0 * h) / count as f64;
+                        (x, y)
+                    })
+                    .collect()
+            }
+            ParticleSeed::Points(points) => points.clone(),
+        };
+        for (x, y) in new_points.into_iter().take(budget) {
+            self.particles.push(Particle { x, y, age: 0.0 });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uniform_flow_fluid() -> Fluid {
+        let num_x = 40;
+        let num_y = 20;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        let n = fluid.num_y;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                let idx = i * n + j;
+                let is_boundary = j == 0 || j == fluid.num_y - 1;
+                fluid.s[idx] = if is_boundary { SOLID_CELL } else { 1.0 };
+                fluid.u[idx] = 1.0;
+            }
+        }
+        fluid
+    }
+
+    #[test]
+    fn particle_population_never_exceeds_the_cap() {
+        let fluid = uniform_flow_fluid();
+        let mut tracer = ParticleTracer::new(ParticleSeed::InletLine, 10, f64::INFINITY, 15);
+        for _ in 0..5 {
+            tracer.step(&fluid, 0.01);
+            assert!(tracer.particles().len() <= 15);
+        }
+    }
+
+    #[test]
+    fn particles_age_and_get_removed_once_they_exceed_max_age() {
+        let fluid = uniform_flow_fluid();
+        let mut tracer = ParticleTracer::new(ParticleSeed::Points(vec![(fluid.h * 2.0, fluid.h * 5.0)]), 0, 0.05, 100);
+        tracer.step(&fluid, 0.01);
+        assert_eq!(tracer.particles().len(), 1);
+        tracer.step(&fluid, 0.1);
+        assert!(tracer.particles().is_empty(), "particle should have aged out past max_age");
+    }
+
+    #[test]
+    fn a_particle_seeded_on_a_solid_cell_does_not_survive_the_step_that_seeded_it() {
+        let mut fluid = uniform_flow_fluid();
+        let solid_idx = fluid.idx(10, 10);
+        fluid.s[solid_idx] = SOLID_CELL;
+        fluid.u[solid_idx] = 0.0;
+        let seed_x = 10.0 * fluid.h + fluid.h * 0.5;
+        let seed_y = 10.0 * fluid.h + fluid.h * 0.5;
+        let mut tracer = ParticleTracer::new(ParticleSeed::Points(vec![(seed_x, seed_y)]), 0, f64::INFINITY, 10);
+        tracer.step(&fluid, 0.0);
+        assert!(tracer.particles().is_empty(), "a particle seeded directly on a solid cell must not survive a step");
+    }
+
+    #[test]
+    fn particles_leaving_the_domain_are_removed() {
+        let fluid = uniform_flow_fluid();
+        let seed_x = (fluid.num_x - 2) as f64 * fluid.h;
+        let seed_y = fluid.h * 5.0;
+        let mut tracer = ParticleTracer::new(ParticleSeed::Points(vec![(seed_x, seed_y)]), 0, f64::INFINITY, 10);
+        for _ in 0..50 {
+            tracer.step(&fluid, fluid.h);
+        }
+        assert!(tracer.particles().is_empty(), "a particle advected past the outlet should be dropped, not clamped in place");
+    }
+}
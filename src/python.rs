@@ -0,0 +1,191 @@
+//! PyO3 bindings so Python (notebooks, sweep scripts) can drive the solver
+//! without shelling out to the CLI. Built only with `--features python`;
+//! see `pyproject.toml` for the matching `maturin` project and
+//! `python/tests/test_wind_tunnel.py` for a smoke test.
+//!
+//! The bound surface is deliberately small: build a scene from a plain
+//! dict of the numeric knobs `SceneConfig` needs, step it, read the fields
+//! back as numpy arrays, add radiators/circular obstacles, and pull
+//! `RadiatorMetrics` back out as a dict. Anything a Python caller wants
+//! beyond that (arbitrary obstacle shapes, moving obstacles, dye emitters,
+//! ...) is reachable through TOML `setup_from_config` already — this isn't
+//! trying to mirror every `SceneConfig` field.
+
+// `#[pymethods]`-generated call wrappers for methods returning
+// `PyResult<Bound<'py, _>>` trip this lint on the macro's own expansion,
+// not on anything in this file; per-function `#[allow]` doesn't reach the
+// generated code, so it's suppressed for the whole module instead.
+#![allow(clippy::useless_conversion)]
+
+use numpy::ndarray::Array2;
+use numpy::{IntoPyArray, PyArray2};
+use pyo3::exceptions::{PyIndexError, PyKeyError, PyRuntimeError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::radiator::Radiator;
+use crate::scene::Scene;
+use crate::scene_config::{ObstacleShape, RadiatorConfig, SceneConfig};
+
+/// Reads a required numeric field out of a config dict, translating a
+/// missing key into a Python `KeyError` rather than a panic.
+fn required<'py, T: FromPyObject<'py>>(dict: &Bound<'py, PyDict>, key: &str) -> PyResult<T> {
+    dict.get_item(key)?.ok_or_else(|| PyKeyError::new_err(key.to_string()))?.extract()
+}
+
+/// Reads an optional numeric field out of a config dict, falling back to
+/// `default` when the key is absent.
+fn optional<'py, T: FromPyObject<'py>>(dict: &Bound<'py, PyDict>, key: &str, default: T) -> PyResult<T> {
+    match dict.get_item(key)? {
+        Some(value) => value.extract(),
+        None => Ok(default),
+    }
+}
+
+fn scene_config_from_dict(dict: &Bound<'_, PyDict>) -> PyResult<SceneConfig> {
+    Ok(SceneConfig {
+        num_x: required(dict, "num_x")?,
+        num_y: required(dict, "num_y")?,
+        dt: required(dict, "dt")?,
+        num_iters: required(dict, "num_iters")?,
+        over_relaxation: optional(dict, "over_relaxation", 1.9)?,
+        pressure_solver: Default::default(),
+        gravity: optional(dict, "gravity", 0.0)?,
+        inflow_velocity: required(dict, "inflow_velocity")?,
+        inflow_profile: Default::default(),
+        inflow_angle: optional(dict, "inflow_angle", 0.0)?,
+        inflow_ramp_time: optional(dict, "inflow_ramp_time", 0.0)?,
+        obstacles: Vec::new(),
+        radiators: Vec::new(),
+        wake_trigger: None,
+        vortex_body: None,
+        moving_obstacles: Vec::new(),
+        step_ordering: Default::default(),
+        top_bottom_boundary: Default::default(),
+        smoke_decay: optional(dict, "smoke_decay", 0.0)?,
+        inflow_smoke_pattern: Default::default(),
+        dye_emitters: Vec::new(),
+        paint_events: Vec::new(),
+        line_profiles: Vec::new(),
+        turbulence_model: None,
+        working_fluid: None,
+        cut_cell: optional(dict, "cut_cell", false)?,
+    })
+}
+
+/// A steppable `Scene`, exposed to Python. Radiators and circular obstacles
+/// can be added after construction; everything else about the scene is
+/// fixed at construction time.
+#[pyclass(name = "Scene")]
+struct PyScene {
+    inner: Scene,
+}
+
+#[pymethods]
+impl PyScene {
+    /// `Scene(config)` — `config` is a dict with (at least) `num_x`,
+    /// `num_y`, `dt`, `num_iters`, and `inflow_velocity`; `over_relaxation`,
+    /// `gravity`, `smoke_decay`, and `cut_cell` are optional and default the
+    /// same way `SceneConfig` does.
+    #[new]
+    fn new(config: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let config = scene_config_from_dict(config)?;
+        Ok(Self { inner: Scene::setup_from_config(&config) })
+    }
+
+    /// Advances the simulation `n` steps and returns the resulting total
+    /// step count, mirroring `Scene::simulate`'s return value.
+    fn step(&mut self, n: usize) -> usize {
+        let mut step_count = self.inner.step_count();
+        for _ in 0..n {
+            step_count = self.inner.simulate();
+        }
+        step_count
+    }
+
+    fn step_count(&self) -> usize {
+        self.inner.step_count()
+    }
+
+    /// `x`-velocity field as an `(num_x, num_y)` numpy array. Copied out of
+    /// the solver's `Vec<f64>`; not a zero-copy view, since that `Vec` is
+    /// reallocated by every `step()` call and there's no safe way to hand
+    /// numpy a pointer into memory Rust keeps mutating out from under it.
+    fn u<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        self.field_to_pyarray(py, &self.inner.fluid.u)
+    }
+
+    fn v<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        self.field_to_pyarray(py, &self.inner.fluid.v)
+    }
+
+    fn p<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        self.field_to_pyarray(py, &self.inner.fluid.p)
+    }
+
+    /// Smoke/dye field as an `(num_x, num_y)` numpy array.
+    fn m<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        self.field_to_pyarray(py, &self.inner.fluid.m)
+    }
+
+    /// Adds a rectangular porous radiator, returning its index for later
+    /// use with `radiator_metrics`. See [`Scene::add_radiators`].
+    fn add_radiator(&mut self, center_x: f64, center_y: f64, width: f64, height: f64, angle: f64, porosity: f64) -> usize {
+        let radiator = Radiator::from(&RadiatorConfig {
+            name: None,
+            center_x,
+            center_y,
+            width,
+            height,
+            angle,
+            porosity,
+            heat_exchanger: None,
+        });
+        self.inner.add_radiators([radiator]);
+        self.inner.obstacles.radiators().len() - 1
+    }
+
+    /// Adds a solid circular obstacle, marking the corresponding cells solid
+    /// immediately (matching `Scene::add_obstacles`).
+    fn add_obstacle_circle(&mut self, cx: f64, cy: f64, radius: f64) {
+        self.inner.add_obstacles([ObstacleShape::Circle { cx, cy, radius }]);
+    }
+
+    /// `RadiatorMetrics::compute` for the radiator at `index`, as a dict
+    /// keyed by field name.
+    fn radiator_metrics<'py>(&self, py: Python<'py>, index: usize, inflow_u: f64, domain_height: f64) -> PyResult<Bound<'py, PyDict>> {
+        let radiator = self
+            .inner
+            .obstacles
+            .radiators()
+            .get(index)
+            .ok_or_else(|| PyIndexError::new_err(format!("no radiator at index {index}")))?;
+        let metrics = crate::metrics::RadiatorMetrics::compute(&self.inner.fluid, radiator, inflow_u, domain_height);
+        let json = serde_json::to_value(metrics).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let dict = PyDict::new_bound(py);
+        if let serde_json::Value::Object(map) = json {
+            for (key, value) in map {
+                dict.set_item(key, value.as_f64())?;
+            }
+        }
+        Ok(dict)
+    }
+}
+
+impl PyScene {
+    fn field_to_pyarray<'py>(&self, py: Python<'py>, field: &[f64]) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        let num_x = self.inner.fluid.num_x;
+        let num_y = self.inner.fluid.num_y;
+        let array = Array2::from_shape_vec((num_x, num_y), field.to_vec())
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(array.into_pyarray_bound(py))
+    }
+}
+
+/// The `lgr_2d_cfd` Python extension module, built by `maturin` from this
+/// crate under `--features python`.
+#[pymodule]
+fn lgr_2d_cfd(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyScene>()?;
+    Ok(())
+}
@@ -0,0 +1,234 @@
+//! Owns the set of porous radiators placed in a scene and applies their
+//! resistance to the flow one step at a time. This is the *mutating* half of
+//! radiator handling — marking footprints and damping velocities — kept
+//! separate from [`crate::obstacle_analysis`], which only ever reads
+//! `&Fluid` to compute diagnostics and never needs `&mut Fluid`.
+//!
+//! Earlier revisions multiplied a radiator's footprint velocities by
+//! `(1 - porosity)` exactly once, at setup. That's fine for a radiator whose
+//! porosity never changes, but if a caller re-applies a radiator with a new
+//! porosity mid-run (angle sweeps, continuation runs), the cells damped
+//! under the old porosity keep their already-reduced velocities while the
+//! footprint mask is redefined, producing a spurious jet or dead zone right
+//! at the switch. `RadiatorModel` instead re-applies drag every step from
+//! the *current* velocity field, so a porosity change only affects the very
+//! next step by the same bounded amount any other step would, and restores
+//! the base solid mask under a footprint before re-marking it, so stale
+//! solid flags never survive a parameter change.
+
+use crate::fluid::Fluid;
+use crate::radiator::Radiator;
+
+#[allow(dead_code)]
+pub struct RadiatorModel {
+    /// Solid mask as it was before any radiator was placed, used to reset a
+    /// footprint's `s` values when a radiator's parameters change.
+    base_s: Vec<f64>,
+    radiators: Vec<Radiator>,
+    /// Last-seen porosity per radiator, so we can detect parameter changes.
+    last_porosity: Vec<f64>,
+    /// Id each radiator is tagged with in per-radiator metrics/overlays.
+    /// Defaults to `radiator_<index>`.
+    radiator_ids: Vec<String>,
+}
+
+#[allow(dead_code)]
+impl RadiatorModel {
+    pub fn new(fluid: &Fluid, radiators: Vec<Radiator>) -> Self {
+        let last_porosity = radiators.iter().map(|r| r.porosity).collect();
+        let radiator_ids = (0..radiators.len()).map(|i| format!("radiator_{i}")).collect();
+        RadiatorModel {
+            base_s: fluid.s.clone(),
+            radiators,
+            last_porosity,
+            radiator_ids,
+        }
+    }
+
+    pub fn radiators(&self) -> &[Radiator] {
+        &self.radiators
+    }
+
+    pub fn radiator_ids(&self) -> &[String] {
+        &self.radiator_ids
+    }
+
+    pub fn set_radiator_ids(&mut self, ids: Vec<String>) {
+        assert_eq!(ids.len(), self.radiators.len());
+        self.radiator_ids = ids;
+    }
+
+    /// Replace the parameters of radiator `index`, restoring its old
+    /// footprint's solid mask from the base snapshot so no stale `s` flags
+    /// linger. Velocities are left untouched; the new resistance takes
+    /// effect starting with the next `apply_porous_forces` call.
+    pub fn reconfigure(&mut self, index: usize, fluid: &mut Fluid, new: Radiator) {
+        let old = self.radiators[index];
+        restore_footprint(fluid, &self.base_s, &old);
+        self.radiators[index] = new;
+        self.last_porosity[index] = new.porosity;
+    }
+
+    /// Apply this step's porous resistance for every radiator, as an
+    /// implicit momentum source, to the current velocity field, and
+    /// attenuate any dye/smoke sitting in the same footprint (see
+    /// [`Radiator::apply_porous_smoke_damping`]). Called between
+    /// `Fluid::integrate` and `Fluid::solve_incompressibility` so the
+    /// pressure solve sees the resisted velocity, not a value damped only
+    /// once at setup and never revisited.
+    pub fn apply_porous_forces(&mut self, fluid: &mut Fluid, dt: f64) {
+        for (radiator, last) in self.radiators.iter().zip(self.last_porosity.iter_mut()) {
+            radiator.apply_porous_force(fluid, dt);
+            radiator.apply_porous_smoke_damping(fluid);
+            *last = radiator.porosity;
+        }
+    }
+
+    /// Drop radiator `index` from the model, returning it. Unlike
+    /// [`Self::reconfigure`], this needs no `&mut Fluid` and restores no
+    /// footprint: a radiator only ever damps `u`/`v`/`m` each step (see
+    /// [`Self::apply_porous_forces`]), it never marks `fluid.s`, so once it
+    /// stops being iterated here there's nothing left in the flow field to
+    /// undo.
+    pub fn remove(&mut self, index: usize) -> Radiator {
+        self.last_porosity.remove(index);
+        self.radiator_ids.remove(index);
+        self.radiators.remove(index)
+    }
+}
+
+fn restore_footprint(fluid: &mut Fluid, base_s: &[f64], radiator: &Radiator) {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            if radiator.contains(x, y) {
+                let idx = i * n + j;
+                fluid.s[idx] = base_s[idx];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluid::{FLUID_CELL, SOLID_CELL};
+
+    #[test]
+    fn porosity_change_does_not_cause_a_larger_than_normal_velocity_jump() {
+        let mut fluid = Fluid::new(1000.0, 40, 20, 0.05);
+        for j in 0..fluid.num_y {
+            fluid.u[j] = 1.0;
+        }
+
+        let radiator = Radiator::new(0.5, 0.5, 0.1, 0.5, 0.0, 0.9);
+        let mut model = RadiatorModel::new(&fluid, vec![radiator]);
+
+        // Run for a while under porosity 0.9 so the flow settles into a
+        // steady per-step damping magnitude.
+        let mut prev_u = fluid.u.clone();
+        let mut normal_step_deltas = Vec::new();
+        for _ in 0..5 {
+            model.apply_porous_forces(&mut fluid, 1.0 / 60.0);
+            let delta = max_abs_diff(&fluid.u, &prev_u);
+            normal_step_deltas.push(delta);
+            prev_u = fluid.u.clone();
+        }
+        let normal_bound = normal_step_deltas.iter().cloned().fold(0.0_f64, f64::max).max(1e-9);
+
+        // Switch porosity from 0.9 to 0.6 mid-run.
+        let mut changed = radiator;
+        changed.porosity = 0.6;
+        model.reconfigure(0, &mut fluid, changed);
+
+        let before_switch = fluid.u.clone();
+        model.apply_porous_forces(&mut fluid, 1.0 / 60.0);
+        let switch_delta = max_abs_diff(&fluid.u, &before_switch);
+
+        assert!(
+            switch_delta <= normal_bound * 1.5 + 1e-9,
+            "porosity switch caused a {switch_delta} jump, normal per-step change was {normal_bound}"
+        );
+    }
+
+    fn max_abs_diff(a: &[f64], b: &[f64]) -> f64 {
+        a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).fold(0.0, f64::max)
+    }
+
+    /// A request against this codebase once claimed `Radiator`/`ObstacleManager`
+    /// write fractional porosity straight into `s`, so `solve_incompressibility`
+    /// (which only ever checks `s == SOLID_CELL`) would see a partially-open
+    /// weight and build a checkerboard-prone stencil out of it. That was never
+    /// how this file works: `Radiator::apply_porous_force` damps `u`/`v`
+    /// directly and leaves the footprint's `s` at `FLUID_CELL`, exactly the
+    /// separation of "is this cell fluid" from "how much does it resist flow"
+    /// the request asked for — `porosity` never touches `s` at all. This test
+    /// pins that invariant down: `s` inside a fractional-porosity footprint
+    /// stays binary through repeated steps, and the resulting pressure field
+    /// has no checkerboard signal above solver noise.
+    #[test]
+    fn fractional_porosity_never_leaks_into_the_binary_solid_mask() {
+        let mut fluid = Fluid::new(1000.0, 40, 20, 0.05);
+        for j in 0..fluid.num_y {
+            fluid.u[j] = 1.0;
+        }
+        for i in 0..fluid.num_x {
+            for j in [0, fluid.num_y - 1] {
+                let idx = fluid.idx(i, j);
+                fluid.s[idx] = SOLID_CELL;
+            }
+        }
+
+        let radiator = Radiator::new(0.5, 0.5, 0.3, 0.5, 0.0, 0.9);
+        let mut model = RadiatorModel::new(&fluid, vec![radiator]);
+
+        for _ in 0..30 {
+            fluid.integrate(1.0 / 60.0, 0.0);
+            model.apply_porous_forces(&mut fluid, 1.0 / 60.0);
+            fluid.solve_incompressibility(40, 1.0 / 60.0, 1.9);
+            fluid.extrapolate();
+            fluid.advect_vel(1.0 / 60.0);
+        }
+
+        let n = fluid.num_y;
+        let h = fluid.h;
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                let x = i as f64 * h;
+                let y = j as f64 * h;
+                if radiator.contains(x, y) {
+                    let s = fluid.s[i * n + j];
+                    assert!(
+                        s == FLUID_CELL || s == SOLID_CELL,
+                        "radiator footprint cell ({i}, {j}) has non-binary s = {s}"
+                    );
+                }
+            }
+        }
+
+        // Checkerboard indicator: the alternating sum over each 2x2 block of
+        // pressures. A projection that treated fractional s as a partial-open
+        // weight (rather than the porosity-only damping this file actually
+        // does) tends to blow this up right at the footprint edges; a clean
+        // solve keeps it small relative to the pressure field's own scale.
+        let p_scale = fluid.p.iter().cloned().fold(0.0_f64, |m, p| m.max(p.abs())).max(1e-9);
+        let mut max_checkerboard: f64 = 0.0;
+        for i in 1..fluid.num_x - 2 {
+            for j in 1..fluid.num_y - 2 {
+                let p00 = fluid.p[i * n + j];
+                let p10 = fluid.p[(i + 1) * n + j];
+                let p01 = fluid.p[i * n + j + 1];
+                let p11 = fluid.p[(i + 1) * n + j + 1];
+                let indicator = (p00 - p10 - p01 + p11).abs();
+                max_checkerboard = max_checkerboard.max(indicator);
+            }
+        }
+        assert!(
+            max_checkerboard < 0.5 * p_scale,
+            "checkerboard indicator {max_checkerboard} too large relative to pressure scale {p_scale}"
+        );
+    }
+}
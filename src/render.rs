@@ -0,0 +1,359 @@
+//! Resolution utilities for rendering: cheap box-filtered downsampling of a
+//! [`Fluid`]'s cell-centered fields, used for preview-quality output during
+//! very large runs where writing every frame at full resolution would be
+//! slow and produce PNGs nobody looks at closely mid-run.
+
+use crate::fluid::{Fluid, SOLID_CELL};
+
+/// Sentinel color a heatmap draws over any cell whose value isn't finite
+/// (NaN or +-Inf), instead of letting it corrupt `get_sci_color`'s min/max
+/// comparisons into rendering the whole field as one flat, misleading
+/// color. Shares its RGB value with `visualizer`'s radiator-outline overlay
+/// (magenta reads as "look here" in both cases), but the two never draw the
+/// same pixel: a radiator outline only lands on porous fluid cells, and a
+/// non-finite value there would already be a `NonFiniteWarning`.
+pub const NON_FINITE_SENTINEL_COLOR: [u8; 3] = [255, 0, 255];
+
+/// Result of scanning a field for its color-mapping range: the min/max over
+/// every *finite* value, plus how many non-finite (NaN/Inf) values were
+/// skipped and the index of the first one. `min`/`max` are both `0.0` if
+/// every value was non-finite, rather than the `f64::INFINITY`/
+/// `NEG_INFINITY` an empty fold would otherwise leave a caller to handle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FiniteRange {
+    pub min: f64,
+    pub max: f64,
+    pub non_finite_count: usize,
+    pub first_non_finite_index: Option<usize>,
+}
+
+/// Min/max over `values`, skipping non-finite entries so one stray NaN
+/// can't poison the whole range the way `f64::min`/`max` propagate NaN
+/// through a naive fold — this is what silently produced flat, all-one-
+/// color heatmaps with no indication why before this existed.
+pub fn finite_range(values: &[f64]) -> FiniteRange {
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    let mut non_finite_count = 0;
+    let mut first_non_finite_index = None;
+    for (index, &v) in values.iter().enumerate() {
+        if v.is_finite() {
+            min = min.min(v);
+            max = max.max(v);
+        } else {
+            non_finite_count += 1;
+            if first_non_finite_index.is_none() {
+                first_non_finite_index = Some(index);
+            }
+        }
+    }
+    if !min.is_finite() || !max.is_finite() {
+        min = 0.0;
+        max = 0.0;
+    }
+    FiniteRange { min, max, non_finite_count, first_non_finite_index }
+}
+
+/// Same as [`finite_range`], but also skips any index where `mask` is
+/// [`SOLID_CELL`]. A solid obstacle cell holds whatever pressure the solver
+/// last left sitting in it — not a physically meaningful value — so
+/// including it would let a single stray cell dominate the color range and
+/// wash out the actual fluid region. `values` and `mask` must be the same
+/// length as a `Fluid`'s cell-centered fields.
+pub fn finite_range_masked(values: &[f64], mask: &[f64]) -> FiniteRange {
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    let mut non_finite_count = 0;
+    let mut first_non_finite_index = None;
+    for (index, (&v, &s)) in values.iter().zip(mask.iter()).enumerate() {
+        if s == SOLID_CELL {
+            continue;
+        }
+        if v.is_finite() {
+            min = min.min(v);
+            max = max.max(v);
+        } else {
+            non_finite_count += 1;
+            if first_non_finite_index.is_none() {
+                first_non_finite_index = Some(index);
+            }
+        }
+    }
+    if !min.is_finite() || !max.is_finite() {
+        min = 0.0;
+        max = 0.0;
+    }
+    FiniteRange { min, max, non_finite_count, first_non_finite_index }
+}
+
+/// A one-line warning naming `field_name`'s non-finite count and first
+/// offending index, or `None` if `range` found no non-finite values. Split
+/// out from any actual `eprintln!` so a caller (or a test) can inspect the
+/// message without capturing stderr.
+pub fn non_finite_warning(field_name: &str, range: &FiniteRange) -> Option<String> {
+    if range.non_finite_count == 0 {
+        return None;
+    }
+    Some(format!(
+        "warning: {field_name} field has {} non-finite value(s) (first at index {}) — rendered as sentinel magenta pixels",
+        range.non_finite_count,
+        range.first_non_finite_index.unwrap()
+    ))
+}
+
+/// A box-filtered, downsampled copy of `Fluid`'s cell-centered `p`/`m`/`s`
+/// fields, coarsened by grouping `factor x factor` cells into one. Only the
+/// fields a still-image render actually reads are downsampled — face
+/// velocities aren't needed at preview resolution.
+pub struct DownsampledFields {
+    pub num_x: usize,
+    pub num_y: usize,
+    pub p: Vec<f64>,
+    pub m: Vec<f64>,
+    pub s: Vec<f64>,
+}
+
+/// Average each `factor x factor` block of `fluid`'s cells into one output
+/// cell. Solid cells are excluded from a mixed block's average — otherwise a
+/// block straddling a wall would read as a partially-permeable cell that
+/// doesn't exist anywhere in the actual solver state. A block that's
+/// entirely solid stays marked solid. `factor <= 1` is a no-op copy.
+pub fn downsample_fields(fluid: &Fluid, factor: usize) -> DownsampledFields {
+    let factor = factor.max(1);
+    let num_x = fluid.num_x.div_ceil(factor);
+    let num_y = fluid.num_y.div_ceil(factor);
+    let mut p = vec![0.0; num_x * num_y];
+    let mut m = vec![0.0; num_x * num_y];
+    let mut s = vec![0.0; num_x * num_y];
+
+    for bi in 0..num_x {
+        for bj in 0..num_y {
+            let mut p_sum = 0.0;
+            let mut m_sum = 0.0;
+            let mut fluid_count = 0usize;
+            for di in 0..factor {
+                let i = bi * factor + di;
+                if i >= fluid.num_x {
+                    continue;
+                }
+                for dj in 0..factor {
+                    let j = bj * factor + dj;
+                    if j >= fluid.num_y {
+                        continue;
+                    }
+                    let idx = fluid.idx(i, j);
+                    if fluid.s[idx] == SOLID_CELL {
+                        continue;
+                    }
+                    p_sum += fluid.p[idx];
+                    m_sum += fluid.m[idx];
+                    fluid_count += 1;
+                }
+            }
+            let out_idx = bi * num_y + bj;
+            if fluid_count == 0 {
+                s[out_idx] = SOLID_CELL;
+            } else {
+                s[out_idx] = 1.0;
+                p[out_idx] = p_sum / fluid_count as f64;
+                m[out_idx] = m_sum / fluid_count as f64;
+            }
+        }
+    }
+
+    DownsampledFields { num_x, num_y, p, m, s }
+}
+
+/// Build a standalone preview [`Fluid`] at `1/factor` the resolution of
+/// `fluid`, covering the same physical domain (cell size scaled up by
+/// `factor`) with its `p`/`m`/`s` fields box-filtered from the original.
+/// Only suitable for rendering — `u`/`v` are left at their default (zero)
+/// values, so this preview must never be stepped or measured.
+pub fn downsampled_fluid(fluid: &Fluid, factor: usize) -> Fluid {
+    let fields = downsample_fields(fluid, factor.max(1));
+    let mut preview = Fluid::new(1000.0, fields.num_x, fields.num_y, fluid.h * factor.max(1) as f64);
+    preview.p = fields.p;
+    preview.m = fields.m;
+    preview.s = fields.s;
+    preview
+}
+
+/// A cell-index sub-range `[i0, i1) x [j0, j1)`, already clamped to a
+/// specific [`Fluid`]'s dimensions — the covering-cell version of a
+/// physical-coordinate view window. Never empty: `i1 > i0` and `j1 > j0`
+/// always hold, even for a window that only grazes the domain, so a caller
+/// never has to special-case a zero-size crop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellWindow {
+    pub i0: usize,
+    pub j0: usize,
+    pub i1: usize,
+    pub j1: usize,
+}
+
+/// Convert a physical-coordinate view window `(x0, y0, x1, y1)` into the
+/// [`CellWindow`] of `fluid`'s cells it covers, clamped to the domain so an
+/// out-of-range or inverted window can never index out of bounds — it's
+/// clipped to whatever overlap exists, down to a minimum 1x1 window at the
+/// nearest edge if the two don't overlap at all.
+pub fn view_window_to_cells(fluid: &Fluid, x0: f64, y0: f64, x1: f64, y1: f64) -> CellWindow {
+    let (x0, x1) = (x0.min(x1), x0.max(x1));
+    let (y0, y1) = (y0.min(y1), y0.max(y1));
+    let last_i = fluid.num_x.saturating_sub(1);
+    let last_j = fluid.num_y.saturating_sub(1);
+    let i0 = ((x0 / fluid.h).floor().max(0.0) as usize).min(last_i);
+    let j0 = ((y0 / fluid.h).floor().max(0.0) as usize).min(last_j);
+    let i1 = (((x1 / fluid.h).ceil().max(0.0) as usize).clamp(i0 + 1, fluid.num_x)).max(i0 + 1);
+    let j1 = (((y1 / fluid.h).ceil().max(0.0) as usize).clamp(j0 + 1, fluid.num_y)).max(j0 + 1);
+    CellWindow { i0, j0, i1: i1.min(fluid.num_x), j1: j1.min(fluid.num_y) }
+}
+
+/// A standalone [`Fluid`] holding just the cells inside `window`, indexed
+/// from `(0, 0)` in the cropped frame. Unlike [`downsampled_fluid`] this is
+/// a lossless 1:1 crop rather than a block average, so `u`/`v` are copied
+/// along with `p`/`m`/`s` — a cropped closeup can render velocity,
+/// vorticity, or LIC just as well as the full domain can, with no averaging
+/// to blur out the very detail a closeup exists to show.
+pub fn cropped_fluid(fluid: &Fluid, window: CellWindow) -> Fluid {
+    let num_x = window.i1 - window.i0;
+    let num_y = window.j1 - window.j0;
+    let mut cropped = Fluid::new(1000.0, num_x, num_y, fluid.h);
+    for i in 0..num_x {
+        for j in 0..num_y {
+            let src = fluid.idx(window.i0 + i, window.j0 + j);
+            let dst = cropped.idx(i, j);
+            cropped.p[dst] = fluid.p[src];
+            cropped.m[dst] = fluid.m[src];
+            cropped.s[dst] = fluid.s[src];
+            cropped.u[dst] = fluid.u[src];
+            cropped.v[dst] = fluid.v[src];
+        }
+    }
+    cropped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downsampling_by_a_factor_divides_dimensions_by_that_factor() {
+        let fluid = Fluid::new(1000.0, 40, 20, 0.05);
+        let fields = downsample_fields(&fluid, 4);
+        assert_eq!(fields.num_x, 10);
+        assert_eq!(fields.num_y, 5);
+    }
+
+    #[test]
+    fn uneven_dimensions_round_up_rather_than_dropping_a_partial_block() {
+        let fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let fields = downsample_fields(&fluid, 3);
+        assert_eq!(fields.num_x, 4);
+        assert_eq!(fields.num_y, 4);
+    }
+
+    #[test]
+    fn solid_cells_do_not_bleed_pressure_into_a_mixed_blocks_average() {
+        let mut fluid = Fluid::new(1000.0, 4, 2, 0.1);
+        // One 2x2 block: one solid cell (left at its default pressure of 0)
+        // and three fluid cells all reading 10.0. A naive average over all
+        // four cells would read 7.5; excluding the solid cell must give the
+        // true fluid-only average of 10.0.
+        for i in 0..2 {
+            for j in 0..2 {
+                let idx = fluid.idx(i, j);
+                fluid.p[idx] = 10.0;
+            }
+        }
+        let solid_idx = fluid.idx(0, 0);
+        fluid.s[solid_idx] = SOLID_CELL;
+        fluid.p[solid_idx] = 999.0;
+
+        let fields = downsample_fields(&fluid, 2);
+        assert_eq!(fields.p[0], 10.0);
+        assert_ne!(fields.s[0], SOLID_CELL);
+    }
+
+    #[test]
+    fn a_block_that_is_entirely_solid_stays_marked_solid() {
+        let mut fluid = Fluid::new(1000.0, 2, 2, 0.1);
+        for s in fluid.s.iter_mut() {
+            *s = SOLID_CELL;
+        }
+        let fields = downsample_fields(&fluid, 2);
+        assert_eq!(fields.s[0], SOLID_CELL);
+    }
+
+    #[test]
+    fn finite_range_skips_nan_and_inf_but_still_counts_them() {
+        let values = [1.0, f64::NAN, 5.0, f64::INFINITY, -2.0, f64::NEG_INFINITY];
+        let range = finite_range(&values);
+        assert_eq!(range.min, -2.0);
+        assert_eq!(range.max, 5.0);
+        assert_eq!(range.non_finite_count, 3);
+        assert_eq!(range.first_non_finite_index, Some(1));
+    }
+
+    #[test]
+    fn finite_range_of_an_all_non_finite_field_falls_back_to_zero_zero_instead_of_infinities() {
+        let values = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+        let range = finite_range(&values);
+        assert_eq!(range.min, 0.0);
+        assert_eq!(range.max, 0.0);
+        assert_eq!(range.non_finite_count, 3);
+    }
+
+    #[test]
+    fn non_finite_warning_is_none_when_nothing_was_skipped() {
+        let range = finite_range(&[1.0, 2.0, 3.0]);
+        assert_eq!(non_finite_warning("pressure", &range), None);
+    }
+
+    #[test]
+    fn non_finite_warning_names_the_field_count_and_first_index() {
+        let range = finite_range(&[1.0, f64::NAN, 2.0, f64::NAN]);
+        let warning = non_finite_warning("pressure", &range).unwrap();
+        assert!(warning.contains("pressure"));
+        assert!(warning.contains('2'), "should mention the count of 2 non-finite values: {warning}");
+        assert!(warning.contains('1'), "should mention the first offending index of 1: {warning}");
+    }
+
+    #[test]
+    fn view_window_covers_the_cells_a_physical_window_overlaps() {
+        let fluid = Fluid::new(1000.0, 40, 20, 0.1);
+        let window = view_window_to_cells(&fluid, 1.0, 0.5, 2.0, 1.0);
+        assert_eq!(window, CellWindow { i0: 10, j0: 5, i1: 20, j1: 10 });
+    }
+
+    #[test]
+    fn view_window_clamps_a_window_extending_past_the_domain_instead_of_panicking() {
+        let fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let window = view_window_to_cells(&fluid, -5.0, -5.0, 50.0, 50.0);
+        assert_eq!(window, CellWindow { i0: 0, j0: 0, i1: 10, j1: 10 });
+    }
+
+    #[test]
+    fn view_window_entirely_outside_the_domain_clips_to_a_minimal_window_at_the_nearest_edge() {
+        let fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let window = view_window_to_cells(&fluid, 100.0, 100.0, 200.0, 200.0);
+        assert_eq!(window, CellWindow { i0: 9, j0: 9, i1: 10, j1: 10 });
+    }
+
+    #[test]
+    fn cropped_fluid_copies_velocity_as_well_as_pressure_unlike_downsampled_fluid() {
+        let mut fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let idx = fluid.idx(3, 4);
+        fluid.p[idx] = 42.0;
+        fluid.u[idx] = 7.0;
+        fluid.v[idx] = -3.0;
+
+        let window = CellWindow { i0: 2, j0: 3, i1: 6, j1: 7 };
+        let cropped = cropped_fluid(&fluid, window);
+        assert_eq!(cropped.num_x, 4);
+        assert_eq!(cropped.num_y, 4);
+        let cropped_idx = cropped.idx(1, 1);
+        assert_eq!(cropped.p[cropped_idx], 42.0);
+        assert_eq!(cropped.u[cropped_idx], 7.0);
+        assert_eq!(cropped.v[cropped_idx], -3.0);
+    }
+}
@@ -0,0 +1,602 @@
+//! Line-chart PNGs summarizing a [`crate::sweep::SweepReport`]: pressure
+//! drop, mass flow, drag, and fan power against whichever parameter was
+//! swept, so trends are visible without opening `results.json` in Python.
+//!
+//! There is no `run_radiator_angle_sweep` in this tree to call this
+//! automatically (see [`crate::sweep`] and `examples/parameter_sweep.rs`
+//! for the same disclaimer) — [`crate::sweep::run_sweep`]'s caller in
+//! `main.rs` calls [`plot_sweep_results`] instead. Charts are drawn with
+//! `image` and [`crate::text`]'s bitmap font rather than `plotters`, the
+//! same way [`crate::visualizer`] avoids it, so a minimal container can't
+//! fail to render a chart for want of a system font.
+//!
+//! `RadiatorMetrics` has no lift field — a porous radiator is a
+//! through-flow resistance, not a lift-generating body, so unlike
+//! `ObstacleForces` (circles, airfoils) there's nothing to plot there. This
+//! charts drag instead, alongside the three metrics the request named that
+//! do exist.
+//!
+//! [`write_run_report`] and [`write_sweep_report`] additionally stitch a
+//! run or sweep's manifest, metrics, and images into a single portable
+//! `report.html` (images embedded as base64, so the file has no sibling
+//! assets to go missing when it's copied out of `output_dir`). There's no
+//! `base64` dependency in this crate — [`base64_encode`] is a small
+//! hand-rolled encoder, in keeping with this module already drawing its own
+//! charts instead of pulling in `plotters`.
+
+use image::{ImageBuffer, Rgb, RgbImage};
+
+use crate::metrics::{RadiatorMetrics, UniformityReport};
+use crate::output::{ArtifactKind, Manifest, OutputManager};
+use crate::sweep::{CaseSnapshots, SweepCaseResult, SweepReport};
+use crate::text;
+
+const CHART_WIDTH: u32 = 480;
+const CHART_HEIGHT: u32 = 320;
+const MARGIN_LEFT: u32 = 44;
+const MARGIN_RIGHT: u32 = 12;
+const MARGIN_TOP: u32 = 18;
+const MARGIN_BOTTOM: u32 = 20;
+
+const LINE_COLORS: &[Rgb<u8>] = &[
+    Rgb([220, 60, 60]),
+    Rgb([60, 120, 220]),
+    Rgb([60, 180, 90]),
+    Rgb([220, 160, 40]),
+    Rgb([160, 60, 220]),
+];
+
+struct MetricSeries {
+    field_name: &'static str,
+    file_stem: &'static str,
+    title: &'static str,
+    unit: &'static str,
+    accessor: fn(&RadiatorMetrics) -> f64,
+}
+
+const SERIES: &[MetricSeries] = &[
+    MetricSeries {
+        field_name: "pressure_drop_corrected",
+        file_stem: "sweep_pressure_drop",
+        title: "PRESSURE DROP",
+        unit: "PA",
+        accessor: |m| m.pressure_drop_corrected,
+    },
+    MetricSeries {
+        field_name: "mass_flow",
+        file_stem: "sweep_mass_flow",
+        title: "MASS FLOW",
+        unit: "KG/S",
+        accessor: |m| m.mass_flow,
+    },
+    MetricSeries {
+        field_name: "drag_corrected",
+        file_stem: "sweep_drag",
+        title: "DRAG",
+        unit: "N",
+        accessor: |m| m.drag_corrected,
+    },
+    MetricSeries {
+        field_name: "fan_power_required",
+        file_stem: "sweep_fan_power",
+        title: "FAN POWER",
+        unit: "W",
+        accessor: |m| m.fan_power_required,
+    },
+];
+
+/// One line-chart PNG per metric in [`SERIES`], x-axis is the first swept
+/// parameter (whatever it is — angle, inflow velocity, porosity, ...); a
+/// second axis, if the sweep had one, becomes one colored line per distinct
+/// value of it instead of a second dimension. Returns the written file
+/// paths, in `SERIES` order. Empty `results` writes nothing and returns an
+/// empty vec, since there's no x-axis to draw.
+pub fn plot_sweep_results(results: &[SweepCaseResult], output_dir: &str) -> Result<Vec<String>, image::ImageError> {
+    let Some(first) = results.first() else {
+        return Ok(Vec::new());
+    };
+    let Some((x_param, _)) = first.parameters.first() else {
+        return Ok(Vec::new());
+    };
+
+    let mut lines: Vec<(String, Vec<(f64, &SweepCaseResult)>)> = Vec::new();
+    for case in results {
+        let x_value = case.parameters.first().map(|(_, v)| *v).unwrap_or(0.0);
+        let group_key = case.parameters[1..]
+            .iter()
+            .map(|(name, value)| format!("{name}={value:.3}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match lines.iter_mut().find(|(key, _)| key == &group_key) {
+            Some((_, points)) => points.push((x_value, case)),
+            None => lines.push((group_key, vec![(x_value, case)])),
+        }
+    }
+    for (_, points) in &mut lines {
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    }
+
+    let mut paths = Vec::new();
+    for series in SERIES {
+        let path = format!("{output_dir}/{}.png", series.file_stem);
+        draw_series_chart(series, x_param, &lines, &path)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+fn draw_series_chart(
+    series: &MetricSeries,
+    x_param: &str,
+    lines: &[(String, Vec<(f64, &SweepCaseResult)>)],
+    path: &str,
+) -> Result<(), image::ImageError> {
+    let mut img: RgbImage = ImageBuffer::from_pixel(CHART_WIDTH, CHART_HEIGHT, Rgb([255, 255, 255]));
+
+    let all_x = lines.iter().flat_map(|(_, points)| points.iter().map(|(x, _)| *x));
+    let all_y = lines.iter().flat_map(|(_, points)| points.iter().map(|(_, case)| (series.accessor)(&case.metrics)));
+    let (x_min, x_max) = min_max(all_x);
+    let (y_min, y_max) = min_max(all_y);
+
+    let plot_left = MARGIN_LEFT;
+    let plot_top = MARGIN_TOP;
+    let plot_width = CHART_WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_height = CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+
+    let black = Rgb([0, 0, 0]);
+    draw_hline(&mut img, plot_left, plot_top + plot_height, plot_width, black);
+    draw_vline(&mut img, plot_left, plot_top, plot_height, black);
+
+    let to_pixel = |x: f64, y: f64| -> (i64, i64) {
+        let tx = if x_max > x_min { (x - x_min) / (x_max - x_min) } else { 0.5 };
+        let ty = if y_max > y_min { (y - y_min) / (y_max - y_min) } else { 0.5 };
+        let px = plot_left as f64 + tx * plot_width as f64;
+        let py = (plot_top + plot_height) as f64 - ty * plot_height as f64;
+        (px.round() as i64, py.round() as i64)
+    };
+
+    for (line_index, (label, points)) in lines.iter().enumerate() {
+        let color = LINE_COLORS[line_index % LINE_COLORS.len()];
+        for window in points.windows(2) {
+            let (x0, y0) = to_pixel(window[0].0, (series.accessor)(&window[0].1.metrics));
+            let (x1, y1) = to_pixel(window[1].0, (series.accessor)(&window[1].1.metrics));
+            draw_line_segment(&mut img, x0, y0, x1, y1, color);
+        }
+        for &(x, case) in points {
+            let (px, py) = to_pixel(x, (series.accessor)(&case.metrics));
+            draw_point(&mut img, px, py, color);
+        }
+        if !label.is_empty() {
+            text::draw_text(&mut img, plot_left as i64 + 2, plot_top as i64 + 2 + line_index as i64 * 6, label, color);
+        }
+    }
+
+    text::draw_text(&mut img, plot_left as i64, 2, series.title, black);
+    text::draw_text(
+        &mut img,
+        plot_left as i64,
+        (plot_top + plot_height + 4) as i64,
+        &format!("{x_param} -> {} ({})", series.field_name, series.unit),
+        black,
+    );
+    text::draw_text(&mut img, 2, plot_top as i64, &format!("{y_max:.1}"), black);
+    text::draw_text(&mut img, 2, (plot_top + plot_height).saturating_sub(4) as i64, &format!("{y_min:.1}"), black);
+
+    img.save(path)
+}
+
+const PROFILE_CHART_WIDTH: u32 = 240;
+const PROFILE_CHART_HEIGHT: u32 = 160;
+
+/// A small line chart of `report.profile` (face position vs face-normal
+/// velocity) for one sweep case — half the size of a [`SERIES`] trend
+/// chart, since one of these gets embedded per case rather than once per
+/// sweep. Shares [`draw_line_segment`]/[`draw_point`]/`text::draw_text`
+/// with [`draw_series_chart`] rather than a second drawing routine.
+pub fn plot_face_velocity_profile(report: &UniformityReport, path: &str) -> Result<(), image::ImageError> {
+    let mut img: RgbImage = ImageBuffer::from_pixel(PROFILE_CHART_WIDTH, PROFILE_CHART_HEIGHT, Rgb([255, 255, 255]));
+
+    let (x_min, x_max) = min_max(report.profile.iter().map(|p| p.position));
+    let (y_min, y_max) = min_max(report.profile.iter().map(|p| p.velocity));
+
+    let plot_left = MARGIN_LEFT;
+    let plot_top = MARGIN_TOP;
+    let plot_width = PROFILE_CHART_WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_height = PROFILE_CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+
+    let black = Rgb([0, 0, 0]);
+    draw_hline(&mut img, plot_left, plot_top + plot_height, plot_width, black);
+    draw_vline(&mut img, plot_left, plot_top, plot_height, black);
+
+    let to_pixel = |x: f64, y: f64| -> (i64, i64) {
+        let tx = if x_max > x_min { (x - x_min) / (x_max - x_min) } else { 0.5 };
+        let ty = if y_max > y_min { (y - y_min) / (y_max - y_min) } else { 0.5 };
+        let px = plot_left as f64 + tx * plot_width as f64;
+        let py = (plot_top + plot_height) as f64 - ty * plot_height as f64;
+        (px.round() as i64, py.round() as i64)
+    };
+
+    let color = LINE_COLORS[0];
+    for window in report.profile.windows(2) {
+        let (x0, y0) = to_pixel(window[0].position, window[0].velocity);
+        let (x1, y1) = to_pixel(window[1].position, window[1].velocity);
+        draw_line_segment(&mut img, x0, y0, x1, y1, color);
+    }
+    for sample in &report.profile {
+        let (px, py) = to_pixel(sample.position, sample.velocity);
+        draw_point(&mut img, px, py, color);
+    }
+
+    text::draw_text(&mut img, plot_left as i64, 2, "FACE VELOCITY", black);
+    text::draw_text(&mut img, plot_left as i64, (plot_top + plot_height + 4) as i64, &format!("gamma={:.3}", report.index), black);
+    text::draw_text(&mut img, 2, plot_top as i64, &format!("{y_max:.1}"), black);
+    text::draw_text(&mut img, 2, (plot_top + plot_height).saturating_sub(4) as i64, &format!("{y_min:.1}"), black);
+
+    img.save(path)
+}
+
+fn min_max(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)))
+}
+
+fn draw_hline(img: &mut RgbImage, x0: u32, y: u32, width: u32, color: Rgb<u8>) {
+    for x in x0..x0 + width {
+        img.put_pixel(x, y, color);
+    }
+}
+
+fn draw_vline(img: &mut RgbImage, x: u32, y0: u32, height: u32, color: Rgb<u8>) {
+    for y in y0..y0 + height {
+        img.put_pixel(x, y, color);
+    }
+}
+
+fn draw_point(img: &mut RgbImage, x: i64, y: i64, color: Rgb<u8>) {
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            put_pixel_clamped(img, x + dx, y + dy, color);
+        }
+    }
+}
+
+/// Bresenham's line algorithm — plenty for the handful of straight segments
+/// a sweep line-chart needs, same as `visualizer`'s streamline drawing.
+fn draw_line_segment(img: &mut RgbImage, x0: i64, y0: i64, x1: i64, y1: i64, color: Rgb<u8>) {
+    let (mut x0, mut y0) = (x0, y0);
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut error = dx + dy;
+    loop {
+        put_pixel_clamped(img, x0, y0, color);
+        if x0 == x1 && y0 == y1 {
+            break;
+        }
+        let e2 = 2 * error;
+        if e2 >= dy {
+            error += dy;
+            x0 += sx;
+        }
+        if e2 <= dx {
+            error += dx;
+            y0 += sy;
+        }
+    }
+}
+
+fn put_pixel_clamped(img: &mut RgbImage, x: i64, y: i64, color: Rgb<u8>) {
+    if x >= 0 && y >= 0 && (x as u32) < img.width() && (y as u32) < img.height() {
+        img.put_pixel(x as u32, y as u32, color);
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+        out.push(if chunk.len() > 1 { BASE64_ALPHABET[(((b1 & 0x0f) << 2) | (b2 >> 6)) as usize] as char } else { '=' });
+        out.push(if chunk.len() > 2 { BASE64_ALPHABET[(b2 & 0x3f) as usize] as char } else { '=' });
+    }
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Reads and base64-encodes a PNG at `path` for inline embedding, or `None`
+/// if it can't be read — the caller degrades to a placeholder cell rather
+/// than failing the whole report over one missing image.
+fn embed_png(path: &str) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    Some(format!("data:image/png;base64,{}", base64_encode(&bytes)))
+}
+
+fn image_cell_html(path: Option<&str>, caption: &str) -> String {
+    match path.and_then(embed_png) {
+        Some(data_uri) => format!(
+            "<figure><img src=\"{data_uri}\" alt=\"{caption}\"><figcaption>{}</figcaption></figure>",
+            html_escape(caption)
+        ),
+        None => format!("<figure class=\"placeholder\"><div class=\"placeholder-box\">no image</div><figcaption>{}</figcaption></figure>", html_escape(caption)),
+    }
+}
+
+const REPORT_STYLE: &str = "body{font-family:sans-serif;margin:2em;}\
+table{border-collapse:collapse;margin-bottom:1.5em;}\
+th,td{border:1px solid #ccc;padding:4px 8px;text-align:right;}\
+th{background:#eee;}\
+tr.optimal{background:#dff5df;}\
+.gallery{display:flex;flex-wrap:wrap;gap:8px;}\
+.gallery figure{margin:0;text-align:center;}\
+.gallery img{max-width:220px;display:block;}\
+.placeholder-box{width:220px;height:120px;display:flex;align-items:center;justify-content:center;background:#f0f0f0;color:#888;border:1px dashed #bbb;}\
+figcaption{font-size:0.85em;color:#333;}";
+
+/// Combine a single run's `manifest.json` and `summary.json` (whichever of
+/// each is readable — a missing or unparsable one degrades to an empty
+/// metrics table / no images rather than an error) into one self-contained
+/// `<output_dir>/report.html`: the latest pressure/smoke/streamlines
+/// snapshot, plus the metrics table `explain_metric` also reads from.
+/// Returns the path written.
+pub fn write_run_report(output_dir: &str) -> std::io::Result<String> {
+    let manifest = OutputManager::load_manifest(output_dir).unwrap_or_default();
+    let summary: serde_json::Value = std::fs::read_to_string(std::path::Path::new(output_dir).join("summary.json"))
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or(serde_json::Value::Null);
+
+    let mut html = String::new();
+    html.push_str(&format!("<!doctype html><html><head><meta charset=\"utf-8\"><title>Run report</title><style>{REPORT_STYLE}</style></head><body>"));
+    html.push_str("<h1>Run report</h1>");
+
+    html.push_str("<h2>Field snapshots</h2><div class=\"gallery\">");
+    html.push_str(&image_cell_html(latest_artifact_path(&manifest, ArtifactKind::PressureField).as_deref(), "pressure"));
+    html.push_str(&image_cell_html(latest_artifact_path(&manifest, ArtifactKind::SmokeField).as_deref(), "smoke"));
+    html.push_str(&image_cell_html(latest_artifact_path(&manifest, ArtifactKind::Streamlines).as_deref(), "streamlines"));
+    html.push_str("</div>");
+
+    html.push_str("<h2>Metrics</h2>");
+    html.push_str(&metrics_table_html(&summary));
+
+    html.push_str("</body></html>");
+
+    let path = std::path::Path::new(output_dir).join("report.html");
+    std::fs::write(&path, html)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+fn latest_artifact_path(manifest: &Manifest, kind: ArtifactKind) -> Option<String> {
+    manifest.artifacts.iter().filter(|a| a.kind == kind).max_by_key(|a| a.step).map(|a| a.path.clone())
+}
+
+/// Renders `summary.json`'s `radiators` map (radiator id -> metric name ->
+/// `{value, unit, ...}`) as one table per radiator. Any other shape (or
+/// `Value::Null` for a missing/unreadable summary) renders as an empty
+/// placeholder table instead of panicking, since this module has no access
+/// to `main.rs`'s private `Summary` type to deserialize it properly.
+fn metrics_table_html(summary: &serde_json::Value) -> String {
+    let Some(radiators) = summary.get("radiators").and_then(|v| v.as_object()) else {
+        return "<p><em>no summary.json metrics available</em></p>".to_string();
+    };
+    let mut html = String::new();
+    for (radiator_id, metrics) in radiators {
+        let Some(metrics) = metrics.as_object() else { continue };
+        html.push_str(&format!("<h3>{}</h3><table><tr><th>metric</th><th>value</th><th>unit</th></tr>", html_escape(radiator_id)));
+        for (name, entry) in metrics {
+            let value = entry.get("value").and_then(|v| v.as_f64()).unwrap_or(f64::NAN);
+            let unit = entry.get("unit").and_then(|v| v.as_str()).unwrap_or("");
+            html.push_str(&format!("<tr><td>{}</td><td>{value:.4}</td><td>{}</td></tr>", html_escape(name), html_escape(unit)));
+        }
+        html.push_str("</table>");
+    }
+    html
+}
+
+/// Combine a sweep's cases, trend charts (from [`plot_sweep_results`]), and
+/// per-case snapshots (if [`crate::sweep::SweepConfig::save_snapshots`] was
+/// set — otherwise every thumbnail cell degrades to a placeholder) into one
+/// self-contained `<output_dir>/report.html`, with the case that maximizes
+/// `mass_flow` highlighted as optimal. Returns the path written.
+pub fn write_sweep_report(sweep_report: &SweepReport, trend_chart_paths: &[String], output_dir: &str) -> std::io::Result<String> {
+    let optimal_index = sweep_report
+        .results
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.metrics.mass_flow.partial_cmp(&b.metrics.mass_flow).unwrap())
+        .map(|(i, _)| i);
+
+    let mut html = String::new();
+    html.push_str(&format!("<!doctype html><html><head><meta charset=\"utf-8\"><title>Sweep report</title><style>{REPORT_STYLE}</style></head><body>"));
+    html.push_str("<h1>Sweep report</h1>");
+    html.push_str("<p>Optimal case (highest <code>mass_flow</code>) highlighted in green.</p>");
+
+    html.push_str("<h2>Trend charts</h2><div class=\"gallery\">");
+    for path in trend_chart_paths {
+        html.push_str(&image_cell_html(Some(path), path));
+    }
+    html.push_str("</div>");
+
+    html.push_str("<h2>Cases</h2>");
+    html.push_str(&sweep_table_html(&sweep_report.results, optimal_index));
+
+    html.push_str("<h2>Per-case snapshots</h2><div class=\"gallery\">");
+    for (index, case) in sweep_report.results.iter().enumerate() {
+        html.push_str(&case_snapshot_html(case, index, optimal_index == Some(index)));
+    }
+    html.push_str("</div>");
+
+    html.push_str("</body></html>");
+
+    let path = std::path::Path::new(output_dir).join("report.html");
+    std::fs::write(&path, html)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+fn sweep_table_html(results: &[SweepCaseResult], optimal_index: Option<usize>) -> String {
+    let mut html = String::from(
+        "<table><tr><th>case</th><th>parameters</th><th>mass_flow</th><th>pressure_drop_corrected</th>\
+         <th>capture_ratio</th><th>fan_power_required</th><th>heat_rejected_watts</th><th>flow_uniformity_index</th></tr>",
+    );
+    for (index, case) in results.iter().enumerate() {
+        let row_class = if optimal_index == Some(index) { " class=\"optimal\"" } else { "" };
+        let parameters =
+            case.parameters.iter().map(|(name, value)| format!("{name}={value:.3}")).collect::<Vec<_>>().join(", ");
+        let m = &case.metrics;
+        html.push_str(&format!(
+            "<tr{row_class}><td>{index}</td><td>{}</td><td>{:.4}</td><td>{:.4}</td><td>{:.4}</td><td>{:.4}</td><td>{:.4}</td><td>{:.4}</td></tr>",
+            html_escape(&parameters),
+            m.mass_flow,
+            m.pressure_drop_corrected,
+            m.capture_ratio,
+            m.fan_power_required,
+            m.heat_rejected_watts,
+            m.flow_uniformity_index
+        ));
+    }
+    html.push_str("</table>");
+    html
+}
+
+fn case_snapshot_html(case: &SweepCaseResult, index: usize, is_optimal: bool) -> String {
+    let label = case.parameters.iter().map(|(name, value)| format!("{name}={value:.3}")).collect::<Vec<_>>().join(", ");
+    let caption = if is_optimal { format!("case {index}: {label} (optimal)") } else { format!("case {index}: {label}") };
+    let (pressure, smoke, streamlines, uniformity_profile) = match &case.snapshots {
+        Some(CaseSnapshots { pressure, smoke, streamlines, uniformity_profile }) => {
+            (pressure.as_deref(), smoke.as_deref(), streamlines.as_deref(), uniformity_profile.as_deref())
+        }
+        None => (None, None, None, None),
+    };
+    format!(
+        "<div>{}{}{}{}<div style=\"text-align:center;font-weight:{}\">{}</div></div>",
+        image_cell_html(pressure, "pressure"),
+        image_cell_html(smoke, "smoke"),
+        image_cell_html(streamlines, "streamlines"),
+        image_cell_html(uniformity_profile, "face velocity"),
+        if is_optimal { "bold" } else { "normal" },
+        html_escape(&caption)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::RadiatorMetrics;
+
+    fn metrics_with(pressure_drop_corrected: f64) -> RadiatorMetrics {
+        RadiatorMetrics {
+            fan_power_required: 0.0,
+            capture_ratio: 0.0,
+            loss_coefficient: 0.0,
+            mass_flow: 0.0,
+            heat_rejected_watts: 0.0,
+            effectiveness: 0.0,
+            frontal_area: 0.0,
+            tunnel_area: 0.0,
+            blockage_ratio: 0.0,
+            blockage_correction_factor: 0.0,
+            pressure_drop_raw: 0.0,
+            pressure_drop_corrected,
+            drag_raw: 0.0,
+            drag_corrected: 0.0,
+            drag_wake_survey: 0.0,
+            flow_uniformity_index: 0.0,
+            reversed_flow_fraction: 0.0,
+            recirculation_area: 0.0,
+        }
+    }
+
+    #[test]
+    fn writes_one_png_per_series_for_a_1d_sweep() {
+        let dir = std::env::temp_dir().join(format!("lgr_2d_cfd_report_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let results = vec![
+            SweepCaseResult { parameters: vec![("angle".to_string(), 0.0)], metrics: metrics_with(100.0), snapshots: None },
+            SweepCaseResult { parameters: vec![("angle".to_string(), 0.2)], metrics: metrics_with(150.0), snapshots: None },
+        ];
+        let paths = plot_sweep_results(&results, &dir.to_string_lossy()).unwrap();
+
+        assert_eq!(paths.len(), SERIES.len());
+        for path in &paths {
+            assert!(std::path::Path::new(path).exists(), "expected {path} to be written");
+        }
+    }
+
+    #[test]
+    fn an_empty_sweep_writes_nothing() {
+        let dir = std::env::temp_dir().join(format!("lgr_2d_cfd_report_test_empty_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let paths = plot_sweep_results(&[], &dir.to_string_lossy()).unwrap();
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn base64_round_trips_through_a_known_vector() {
+        // RFC 4648 test vector.
+        assert_eq!(base64_encode(b"Man"), "TWFu");
+        assert_eq!(base64_encode(b"Ma"), "TWE=");
+        assert_eq!(base64_encode(b"M"), "TQ==");
+    }
+
+    #[test]
+    fn a_missing_image_degrades_to_a_placeholder_cell_instead_of_failing() {
+        let html = image_cell_html(Some("/no/such/file.png"), "pressure");
+        assert!(html.contains("placeholder"), "missing image should render a placeholder cell: {html}");
+    }
+
+    #[test]
+    fn a_present_image_embeds_as_a_base64_data_uri() {
+        let dir = std::env::temp_dir().join(format!("lgr_2d_cfd_report_embed_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tiny.png");
+        let img: RgbImage = ImageBuffer::from_pixel(2, 2, Rgb([1, 2, 3]));
+        img.save(&path).unwrap();
+
+        let html = image_cell_html(Some(&path.to_string_lossy()), "pressure");
+        assert!(html.contains("data:image/png;base64,"), "expected an embedded data URI: {html}");
+        assert!(!html.contains("placeholder"));
+    }
+
+    #[test]
+    fn write_run_report_degrades_gracefully_with_no_manifest_or_summary() {
+        let dir = std::env::temp_dir().join(format!("lgr_2d_cfd_report_run_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path = write_run_report(&dir.to_string_lossy()).unwrap();
+        assert!(std::path::Path::new(&path).exists());
+        let html = std::fs::read_to_string(&path).unwrap();
+        assert!(html.contains("no summary.json metrics available"));
+        assert!(html.contains("placeholder"));
+    }
+
+    #[test]
+    fn write_sweep_report_highlights_the_case_with_the_highest_mass_flow() {
+        let dir = std::env::temp_dir().join(format!("lgr_2d_cfd_report_sweep_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut low = metrics_with(100.0);
+        low.mass_flow = 0.2;
+        let mut high = metrics_with(150.0);
+        high.mass_flow = 0.9;
+        let sweep_report = SweepReport {
+            results: vec![
+                SweepCaseResult { parameters: vec![("angle".to_string(), 0.0)], metrics: low, snapshots: None },
+                SweepCaseResult { parameters: vec![("angle".to_string(), 0.3)], metrics: high, snapshots: None },
+            ],
+            warm_up_steps: None,
+        };
+
+        let path = write_sweep_report(&sweep_report, &[], &dir.to_string_lossy()).unwrap();
+        let html = std::fs::read_to_string(&path).unwrap();
+        assert!(html.contains("case 1: angle=0.300 (optimal)"), "expected case 1 flagged optimal: {html}");
+        assert!(!html.contains("case 0: angle=0.000 (optimal)"));
+    }
+}
@@ -0,0 +1,140 @@
+//! `run_metadata.json`: what produced an output directory and how it went.
+//! Written once at the very start of a run (before there's anything to
+//! report) and rewritten in place with [`RunCompletion`] once the run
+//! finishes, so an output directory found a week later is self-describing
+//! instead of requiring the original CLI invocation to be remembered
+//! separately. Also `run`'s `--replay` input format: it embeds the same
+//! [`SceneConfig`] snapshot [`crate::scene::Scene::export_setup`] writes to
+//! `scene_setup.json`, so a `RunMetadata` alone is enough to rebuild the
+//! scene a run simulated.
+//!
+//! A sweep case that reports snapshots (`crate::sweep::CaseSnapshots`)
+//! doesn't get its own `RunMetadata` — sweeps run many short cases from one
+//! shared config, so `results.json`'s own `sweep_config`-derived fields
+//! already cover "what produced this", and `sweep::SweepCaseResult`
+//! references the sweep's single output directory instead of duplicating a
+//! metadata file per case.
+
+use serde::{Deserialize, Serialize};
+
+use crate::scene_config::SceneConfig;
+
+/// Final statistics appended to a [`RunMetadata`] once its run completes.
+/// Absent (`None`) in the copy written at the start of a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunCompletion {
+    pub steps_run: u64,
+    pub wall_time_secs: f64,
+    /// `Fluid::max_divergence` at the end of the run — how far the final
+    /// velocity field is from divergence-free. Depends on
+    /// `SceneConfig::step_ordering`: `AdvectThenProject` (projects last)
+    /// reads close to the solve tolerance here, `ProjectThenAdvect`
+    /// (advects last) reads whatever divergence that step's advection
+    /// reintroduced.
+    pub final_divergence: f64,
+    /// This run's `summary.json`, embedded verbatim rather than re-derived,
+    /// so `run_metadata.json` and `summary.json` never disagree. `None` for
+    /// a run that never got far enough to write one (e.g. it errored out
+    /// before `write_summary`).
+    pub metrics_summary: Option<serde_json::Value>,
+}
+
+/// Everything needed to explain, and reproduce, one `run` invocation's
+/// output directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunMetadata {
+    pub crate_version: String,
+    /// `std::env::args()`, verbatim — the exact command line that produced
+    /// this output directory.
+    pub invocation: Vec<String>,
+    /// The scene actually simulated: resolution, dt, solver settings,
+    /// inflow, obstacles/radiators. See [`crate::scene::Scene::to_config`].
+    pub scene_config: SceneConfig,
+    /// Unix timestamp (seconds since the epoch) the run started.
+    pub timestamp_unix: u64,
+    pub hostname: String,
+    #[serde(default)]
+    pub completion: Option<RunCompletion>,
+}
+
+impl RunMetadata {
+    pub fn new(scene_config: SceneConfig) -> Self {
+        RunMetadata {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            invocation: std::env::args().collect(),
+            scene_config,
+            timestamp_unix: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+            hostname: hostname(),
+            completion: None,
+        }
+    }
+
+    pub fn write(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        serde_json::from_str(&text).map_err(std::io::Error::other)
+    }
+}
+
+/// Best-effort hostname lookup. This tree has no `hostname`-crate
+/// dependency, so this checks `$HOSTNAME` first and falls back to shelling
+/// out to the `hostname` binary present on every Linux/macOS box this
+/// solver targets; either failing just yields `"unknown"` rather than
+/// failing the run over metadata.
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .or_else(|| {
+            std::process::Command::new("hostname")
+                .output()
+                .ok()
+                .filter(|o| o.status.success())
+                .and_then(|o| String::from_utf8(o.stdout).ok())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::Scene;
+
+    #[test]
+    fn round_trips_through_json_including_completion() {
+        let scene = Scene::wind_tunnel_with_radiator(20, 10);
+        let mut metadata = RunMetadata::new(scene.to_config());
+        metadata.completion = Some(RunCompletion {
+            steps_run: 42,
+            wall_time_secs: 1.5,
+            final_divergence: 0.0001,
+            metrics_summary: Some(serde_json::json!({"ok": true})),
+        });
+
+        let dir = std::env::temp_dir().join(format!("lgr_2d_cfd_test_run_metadata_{:?}", std::thread::current().id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("run_metadata.json");
+        metadata.write(&path).unwrap();
+
+        let reloaded = RunMetadata::from_file(&path).unwrap();
+        assert_eq!(reloaded.crate_version, metadata.crate_version);
+        assert_eq!(reloaded.scene_config.num_x, 20);
+        assert_eq!(reloaded.completion.unwrap().steps_run, 42);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn a_freshly_constructed_metadata_has_no_completion_yet() {
+        let scene = Scene::wind_tunnel_with_radiator(20, 10);
+        let metadata = RunMetadata::new(scene.to_config());
+        assert!(metadata.completion.is_none());
+        assert!(!metadata.invocation.is_empty());
+    }
+}
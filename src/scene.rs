@@ -0,0 +1,1532 @@
+//! [`Scene`] ties a [`Fluid`] grid, its [`ObstacleManager`], and the solver
+//! parameters together into one steppable unit — the type both the CLI and
+//! library consumers (see `examples/`) build, step, and read diagnostics
+//! from.
+
+use serde::{Deserialize, Serialize};
+
+use crate::dye_emitter::{self, DyeEmitter, InflowSmokePattern};
+use crate::fluid::{BoundaryCondition, Fluid, PressureSolver, StepOrdering};
+use crate::inflow_profile::InflowProfile;
+use crate::moving_obstacle::MovingObstacle;
+use crate::obstacle_manager::ObstacleManager;
+use crate::output::OutputManager;
+use crate::paint::{self, PaintEvent};
+use crate::radiator::Radiator;
+use crate::scene_config::{self, SceneConfig};
+use crate::vortex_induced_body::VortexInducedBody;
+use crate::wake_trigger;
+
+pub struct Scene {
+    pub fluid: Fluid,
+    pub obstacles: ObstacleManager,
+    pub dt: f64,
+    pub gravity: f64,
+    pub num_iters: usize,
+    pub over_relaxation: f64,
+    /// How the pressure-correction Poisson solve is done — plain
+    /// Gauss-Seidel (`num_iters` sweeps) by default, or a geometric
+    /// multigrid V-cycle. See [`PressureSolver`].
+    pub pressure_solver: PressureSolver,
+    pub inflow_u: f64,
+    /// How `apply_inflow` distributes `inflow_u` across the inlet column's
+    /// rows — flat by default, or a boundary-layer/measured shape. See
+    /// [`InflowProfile`].
+    pub inflow_profile: InflowProfile,
+    /// Pattern `apply_inflow` writes into the inlet column's `m` every step.
+    /// Defaults to [`InflowSmokePattern::Striped`] so the built-in wind-
+    /// tunnel scenes show streaklines rather than a flat dye front.
+    pub inflow_smoke_pattern: InflowSmokePattern,
+    /// Extra dye sources applied every step, on top of `apply_inflow`'s
+    /// single inlet column. See [`crate::dye_emitter`].
+    pub dye_emitters: Vec<DyeEmitter>,
+    /// Scripted paint strokes (see [`Self::paint_smoke`] and friends), each
+    /// firing at most once when `sim_time` reaches its `at_time`. This is
+    /// how a headless run reproduces what a live-viewer session would
+    /// otherwise only ever draw interactively with the mouse. See
+    /// [`crate::paint::PaintEvent`].
+    pub paint_events: Vec<PaintEvent>,
+    /// Elapsed physical time, accumulated from the actual `dt` used each
+    /// `simulate` call rather than derived as `step * dt`. `dt` is constant
+    /// today, so the two agree, but this is the field every consumer
+    /// (frame annotations, force history, manifest records) should read —
+    /// once adaptive dt or sub-stepping exists, `step * dt` silently goes
+    /// wrong while this keeps accumulating the real elapsed time.
+    pub sim_time: f64,
+    /// How many `simulate` calls this scene has made. Own state rather than
+    /// a shared counter, since a radiator sweep creates one `Scene` per
+    /// angle and each needs to know its own step index, not a global one.
+    step_count: usize,
+    /// Boundary layer control (or any other every-Nth-step behavior in
+    /// `simulate`) fires every `blc_interval` steps rather than a
+    /// hard-coded interval, so different scenes/resolutions can tune it.
+    blc_interval: usize,
+    /// `Fluid::validate` runs every `validate_interval` steps rather than
+    /// every step — same "tunable, not hard-coded" reasoning as
+    /// `blc_interval`, and scanning four full fields every step would add
+    /// real cost to a solve that's already the run's bottleneck.
+    validate_interval: usize,
+    /// Set by the most recent `simulate` call that landed on a
+    /// `validate_interval` boundary and found a non-finite cell. Sticky
+    /// once set — nothing clears it — since a scene that has gone unstable
+    /// isn't expected to un-blow-up on its own, and a caller (`run_scene`)
+    /// checks this once per step to decide whether to stop.
+    instability: Option<crate::fluid::InstabilityReport>,
+    /// Off by default: a one-shot antisymmetric nudge to the first solid
+    /// obstacle's wake if it hasn't started shedding on its own by a
+    /// configured step. See `wake_trigger::WakeTrigger`.
+    wake_trigger: Option<wake_trigger::WakeTrigger>,
+    /// Set for exactly the `simulate` call in which `wake_trigger` fired, so
+    /// callers can log the event without `Scene` doing its own I/O.
+    wake_trigger_fired_this_step: bool,
+    /// Off by default: an obstacle free to oscillate transverse to the
+    /// freestream on a spring-mass-damper driven by its own lift force. See
+    /// [`VortexInducedBody`]. Not restored from a checkpoint yet — like
+    /// `wake_trigger`, a resumed run starts it at rest rather than mid-
+    /// oscillation.
+    vortex_body: Option<VortexInducedBody>,
+    /// Obstacles with a prescribed motion instead of a fixed footprint.
+    /// Unlike `vortex_body`, several of these can be active at once — one
+    /// per moving obstacle — since none of them read back a shared driving
+    /// force the way `vortex_body` reads lift. Not restored from a
+    /// checkpoint yet, for the same reason as `vortex_body`: a resumed run
+    /// starts every moving obstacle back at its rest position.
+    moving_obstacles: Vec<MovingObstacle>,
+    /// `Fluid::solve_incompressibility`'s return value from the most recent
+    /// `simulate` call, for a caller building a
+    /// [`crate::convergence::ConvergenceMonitor`] to read without needing
+    /// its own copy of the pressure solve's inner loop.
+    last_pressure_residual: f64,
+    /// Project-then-advect (this solver's original order) vs
+    /// advect-then-project. See [`StepOrdering`] for the tradeoff; a
+    /// diagnostic reading `fluid.max_divergence()` right after `simulate`
+    /// only sees a near-zero result under `AdvectThenProject`.
+    pub step_ordering: StepOrdering,
+    /// A copy of `fluid.u` taken immediately after the most recent
+    /// `simulate` call's pressure projection, before advection or outflow
+    /// mass conservation can reintroduce divergence into it. `None` until
+    /// the first `simulate` call. See
+    /// [`crate::metrics::MetricsSamplingPoint`] for why a caller might want
+    /// this instead of `fluid.u`'s end-of-step state.
+    post_projection_u: Option<Vec<f64>>,
+    /// Off by default: an algebraic turbulence closure applied after
+    /// advection each step. See [`crate::turbulence`].
+    turbulence_model: Option<crate::turbulence::TurbulenceModel>,
+    /// Each cell's distance to the nearest solid cell, computed once (this
+    /// solver has no per-step mechanism to keep it current under a moving
+    /// obstacle) when `turbulence_model` is set. Empty when it isn't.
+    turbulence_wall_distance: Vec<f64>,
+    /// `None` for every scene except [`Scene::lid_driven_cavity`]: `Some(u)`
+    /// pins the top boundary row's tangential velocity to `u` every step,
+    /// the way `apply_inflow` pins the left column's — see
+    /// [`Self::apply_lid_velocity`]. Not restored from a checkpoint yet,
+    /// same caveat as `wake_trigger`/`vortex_body`.
+    lid_velocity: Option<f64>,
+    /// Free-stream direction, in radians measured from the `+x` axis, that
+    /// [`Self::apply_inflow`] resolves `inflow_u` into: `u = inflow_u *
+    /// cos(angle)`, `v = inflow_u * sin(angle)`. Zero (the default) is the
+    /// horizontal inflow every scene used before this field existed, so
+    /// `u = inflow_u` and `v = 0.0` exactly as before. A nonzero angle pairs
+    /// naturally with `top_bottom_boundary: BoundaryCondition::Slip` (a
+    /// horizontal-only no-slip wall makes less physical sense once the
+    /// free stream itself isn't horizontal) — but `Slip` and `NoSlip` are
+    /// behaviorally identical in this solver today (see
+    /// [`BoundaryCondition`]'s doc comment), so this is a labeling choice
+    /// for the caller to make explicitly, not something this field derives
+    /// or enforces automatically.
+    pub inflow_angle: f64,
+    /// Simulated seconds over which [`Self::apply_inflow`] ramps the inflow
+    /// linearly from rest up to `inflow_u`, as `inflow_u * (sim_time /
+    /// inflow_ramp_time).min(1.0)`. Zero (the default) is instant-on, this
+    /// solver's original behavior — slamming the full inflow velocity
+    /// against a quiescent domain can spike the pressure solve on a fine
+    /// grid before things settle. Note this only smooths the *boundary
+    /// condition*: `wind_tunnel_with_radiator_sized`/`setup_from_config`
+    /// still seed the whole domain at `inflow_u` up front (see their doc
+    /// comments — this solver can't originate flow from rest by advecting
+    /// the inlet column inward), so a caller wanting a genuinely quiescent
+    /// start needs to zero `fluid.u`/`fluid.v` after construction too.
+    pub inflow_ramp_time: f64,
+    /// Off by default, same as `turbulence_model`/`wake_trigger`: a caller
+    /// opts in via [`Self::enable_field_statistics`], typically after an
+    /// initial transient has passed, so early instantaneous snapshots
+    /// dominated by start-up (or ramp-up, see `inflow_ramp_time`) don't bias
+    /// the running mean. See [`crate::field_statistics::FieldStatistics`].
+    /// Not restored from a checkpoint, same caveat as `wake_trigger`.
+    field_stats: Option<crate::field_statistics::FieldStatistics>,
+}
+
+impl Scene {
+    /// Wind-tunnel scene: uniform inflow from the left, a single porous
+    /// radiator slab placed a third of the way into the domain. Domain
+    /// height is implicitly 1.0 (grid spacing `1 / num_y`); use
+    /// [`Scene::wind_tunnel_with_radiator_sized`] to set domain size and
+    /// resolution independently.
+    pub fn wind_tunnel_with_radiator(num_x: usize, num_y: usize) -> Self {
+        let domain_height = 1.0;
+        let domain_width = num_x as f64 / num_y as f64 * domain_height;
+        Self::wind_tunnel_with_radiator_sized(domain_width, domain_height, num_y as f64)
+    }
+
+    /// Same scene as [`Scene::wind_tunnel_with_radiator`], but with domain
+    /// size and grid resolution as independent parameters instead of a
+    /// single `(num_x, num_y)` cell count that bakes the two together.
+    /// `resolution` is cells per unit length (grid spacing is
+    /// `1 / resolution`); `domain_width`/`domain_height` are in the same
+    /// physical units as `Radiator`/`ObstacleShape` positions.
+    ///
+    /// This is what lets a caller isolate wind-tunnel wall-blockage effects
+    /// (see `RadiatorMetrics`'s blockage correction) from mesh resolution:
+    /// doubling `domain_height` alone, `resolution` unchanged, roughly
+    /// halves the radiator's blockage ratio without also refining the mesh.
+    pub fn wind_tunnel_with_radiator_sized(domain_width: f64, domain_height: f64, resolution: f64) -> Self {
+        let h = 1.0 / resolution;
+        let num_x = ((domain_width / h).round() as usize).max(2);
+        let num_y = ((domain_height / h).round() as usize).max(2);
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        let inflow_u = 2.0;
+
+        let n = fluid.num_y;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                let idx = i * n + j;
+                let is_boundary = j == 0 || j == fluid.num_y - 1;
+                fluid.s[idx] = if is_boundary { 0.0 } else { 1.0 };
+                // Seed the whole domain with the free-stream velocity, not
+                // just the inlet column: this scheme's pressure solve only
+                // ever reacts to divergence an obstacle introduces into an
+                // already-flowing field, it can't originate flow from rest
+                // by advecting the inlet column inward.
+                fluid.u[idx] = inflow_u;
+            }
+        }
+
+        let radiators = vec![Radiator::new(
+            fluid.domain_width() * 0.35,
+            fluid.domain_height() * 0.5,
+            0.03,
+            fluid.domain_height() * 0.4,
+            0.0,
+            0.7,
+        )];
+        let obstacles = ObstacleManager::new(&fluid, radiators, vec![]);
+
+        Scene {
+            fluid,
+            obstacles,
+            dt: 1.0 / 60.0,
+            gravity: 0.0,
+            num_iters: 40,
+            over_relaxation: 1.9,
+            pressure_solver: PressureSolver::default(),
+            inflow_u,
+            inflow_profile: InflowProfile::default(),
+            inflow_smoke_pattern: InflowSmokePattern::default(),
+            dye_emitters: Vec::new(),
+            paint_events: Vec::new(),
+            sim_time: 0.0,
+            step_count: 0,
+            blc_interval: 5,
+            validate_interval: 20,
+            instability: None,
+            wake_trigger: None,
+            wake_trigger_fired_this_step: false,
+            vortex_body: None,
+            moving_obstacles: Vec::new(),
+            last_pressure_residual: 0.0,
+            step_ordering: StepOrdering::default(),
+            post_projection_u: None,
+            turbulence_model: None,
+            turbulence_wall_distance: Vec::new(),
+            lid_velocity: None,
+            inflow_angle: 0.0,
+            inflow_ramp_time: 0.0,
+            field_stats: None,
+        }
+    }
+
+    /// A radiator mounted the way it actually sits in a car: inside a duct
+    /// rather than a free-stream tunnel. Builds a converging inlet, a
+    /// constant-area test section (where the radiator lives), and a
+    /// diverging diffuser as sloped solid walls above and below the
+    /// centerline, all by direct `fluid.s` masking the same way
+    /// [`Self::wind_tunnel_with_radiator_sized`] masks its flat top/bottom
+    /// walls.
+    ///
+    /// `inlet_height`/`test_section_height` are the duct's full height (wall
+    /// to wall) at the inlet and through the test section; `diffuser_exit_height`
+    /// (`test_section_height + 2 * diffuser_length * diffuser_half_angle.tan()`)
+    /// falls out of `diffuser_half_angle` (radians) rather than being given
+    /// directly, so the diffuser's slope is what's configurable, not its
+    /// exit area. `converging_length`/`test_section_length`/`diffuser_length`
+    /// are each section's streamwise extent; `domain_width` is their sum and
+    /// `domain_height` is the tallest of the three duct heights, so the
+    /// widest section always reaches from the domain's own top wall to its
+    /// bottom wall with nothing left over.
+    ///
+    /// `RadiatorAnalyzer`/`RadiatorMetrics` need no changes to work here:
+    /// both read the radiator's own geometry and the surrounding `fluid`
+    /// state, neither of which cares whether the walls it's between are
+    /// flat or sloped. Comparing free-tunnel vs. duct blockage for "the same"
+    /// radiator just means keeping its `width`/`height`/`porosity` matched
+    /// between a [`Self::wind_tunnel_with_radiator_sized`] run and a
+    /// `duct_with_radiator` run — see `--scene 4` vs. `--scene 5`.
+    pub fn duct_with_radiator(
+        inlet_height: f64,
+        test_section_height: f64,
+        converging_length: f64,
+        test_section_length: f64,
+        diffuser_length: f64,
+        diffuser_half_angle: f64,
+        resolution: f64,
+    ) -> Self {
+        let diffuser_exit_height = test_section_height + 2.0 * diffuser_length * diffuser_half_angle.tan();
+        let domain_width = converging_length + test_section_length + diffuser_length;
+        let domain_height = inlet_height.max(test_section_height).max(diffuser_exit_height);
+        let h = 1.0 / resolution;
+        let num_x = ((domain_width / h).round() as usize).max(2);
+        let num_y = ((domain_height / h).round() as usize).max(2);
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        let inflow_u = 2.0;
+        let centerline = domain_height * 0.5;
+
+        let n = fluid.num_y;
+        for i in 0..fluid.num_x {
+            let x = i as f64 * h;
+            let half_height = duct_height_at(
+                x,
+                inlet_height,
+                test_section_height,
+                diffuser_exit_height,
+                converging_length,
+                test_section_length,
+                diffuser_length,
+            ) * 0.5;
+            for j in 0..fluid.num_y {
+                let y = j as f64 * h;
+                let idx = i * n + j;
+                let is_boundary = j == 0 || j == fluid.num_y - 1;
+                let in_duct = !is_boundary && (y - centerline).abs() <= half_height;
+                fluid.s[idx] = if in_duct { 1.0 } else { 0.0 };
+                // Same "seed the whole domain, not just the inlet column"
+                // reasoning as `wind_tunnel_with_radiator_sized` — but only
+                // inside the duct, since a solid wall cell's velocity is
+                // never read by the solver and shouldn't imply flow exists
+                // outside the sloped walls.
+                fluid.u[idx] = if in_duct { inflow_u } else { 0.0 };
+            }
+        }
+
+        let radiator_center_x = converging_length + test_section_length * 0.5;
+        let radiators = vec![Radiator::new(radiator_center_x, centerline, 0.03, test_section_height * 0.7, 0.0, 0.7)];
+        let obstacles = ObstacleManager::new(&fluid, radiators, vec![]);
+
+        Scene {
+            fluid,
+            obstacles,
+            dt: 1.0 / 60.0,
+            gravity: 0.0,
+            num_iters: 40,
+            over_relaxation: 1.9,
+            pressure_solver: PressureSolver::default(),
+            inflow_u,
+            inflow_profile: InflowProfile::default(),
+            inflow_smoke_pattern: InflowSmokePattern::default(),
+            dye_emitters: Vec::new(),
+            paint_events: Vec::new(),
+            sim_time: 0.0,
+            step_count: 0,
+            blc_interval: 5,
+            validate_interval: 20,
+            instability: None,
+            wake_trigger: None,
+            wake_trigger_fired_this_step: false,
+            vortex_body: None,
+            moving_obstacles: Vec::new(),
+            last_pressure_residual: 0.0,
+            step_ordering: StepOrdering::default(),
+            post_projection_u: None,
+            turbulence_model: None,
+            turbulence_wall_distance: Vec::new(),
+            lid_velocity: None,
+            inflow_angle: 0.0,
+            inflow_ramp_time: 0.0,
+            field_stats: None,
+        }
+    }
+
+    /// Lid-driven cavity: a fully enclosed square domain (left/right/bottom
+    /// walls solid, no inflow or outflow) with the top row's tangential
+    /// velocity pinned to `lid_velocity` every step instead of marked solid
+    /// — see [`Self::apply_lid_velocity`]. The classic incompressible-solver
+    /// validation case: no obstacles, no forcing, just a moving boundary
+    /// driving a recirculating flow from rest.
+    ///
+    /// `resolution` is cells per unit length on this 1x1 domain (same
+    /// convention as [`Self::wind_tunnel_with_radiator_sized`]).
+    ///
+    /// This solver has no molecular viscosity term — see
+    /// [`Fluid::kinematic_viscosity`]'s doc comment — so there's no way to
+    /// parameterize this by Reynolds number the way a real Navier-Stokes
+    /// solver would. It also means there's no diffusion mechanism to carry
+    /// the lid's momentum into the interior other than [`crate::turbulence`]'s
+    /// mixing-length closure: with every interior cell starting at rest,
+    /// self-advection alone never displaces a backtrace off the cell it
+    /// started on (see [`Self::wind_tunnel_with_radiator_sized`]'s doc
+    /// comment on the same "can't originate flow from rest" limitation), so
+    /// without an explicit diffusion pass the lid row would stay pinned
+    /// forever while the rest of the cavity never notices. This sets a small
+    /// fixed `c`, not because turbulence is a good physical model for a
+    /// laminar cavity, but because it is the only diffusion this crate has;
+    /// a caller wanting the Ghia et al. Re=100 or Re=400 comparison will not
+    /// get a matching centerline profile out of this on that basis alone.
+    pub fn lid_driven_cavity(resolution: usize) -> Self {
+        let h = 1.0 / resolution as f64;
+        let mut fluid = Fluid::new(1000.0, resolution, resolution, h);
+        let lid_velocity = 1.0;
+
+        let n = fluid.num_y;
+        for i in 0..fluid.num_x {
+            for j in 0..fluid.num_y {
+                let idx = i * n + j;
+                let is_wall = i == 0 || i == fluid.num_x - 1 || j == 0;
+                fluid.s[idx] = if is_wall { 0.0 } else { 1.0 };
+            }
+        }
+
+        let turbulence_model = Some(crate::turbulence::TurbulenceModel::MixingLength { c: 0.01 });
+        let turbulence_wall_distance = crate::turbulence::wall_distance_field(&fluid);
+
+        let obstacles = ObstacleManager::new(&fluid, vec![], vec![]);
+
+        Scene {
+            fluid,
+            obstacles,
+            dt: 1.0 / 60.0,
+            gravity: 0.0,
+            num_iters: 40,
+            over_relaxation: 1.9,
+            pressure_solver: PressureSolver::default(),
+            inflow_u: 0.0,
+            inflow_profile: InflowProfile::default(),
+            inflow_smoke_pattern: InflowSmokePattern::default(),
+            dye_emitters: Vec::new(),
+            paint_events: Vec::new(),
+            sim_time: 0.0,
+            step_count: 0,
+            blc_interval: 5,
+            validate_interval: 20,
+            instability: None,
+            wake_trigger: None,
+            wake_trigger_fired_this_step: false,
+            vortex_body: None,
+            moving_obstacles: Vec::new(),
+            last_pressure_residual: 0.0,
+            step_ordering: StepOrdering::default(),
+            post_projection_u: None,
+            turbulence_model,
+            turbulence_wall_distance,
+            lid_velocity: Some(lid_velocity),
+            inflow_angle: 0.0,
+            inflow_ramp_time: 0.0,
+            field_stats: None,
+        }
+    }
+
+    pub fn setup_from_config(config: &SceneConfig) -> Self {
+        let h = 1.0 / config.num_y as f64;
+        let properties = config.working_fluid.unwrap_or_default().properties();
+        let mut fluid = Fluid::new(properties.density, config.num_x, config.num_y, h);
+        fluid.kinematic_viscosity = properties.kinematic_viscosity;
+        fluid.top_bottom_boundary = config.top_bottom_boundary;
+        fluid.smoke_decay = config.smoke_decay;
+
+        let periodic = config.top_bottom_boundary == BoundaryCondition::Periodic;
+        let (sin, cos) = config.inflow_angle.sin_cos();
+        let n = fluid.num_y;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                let idx = i * n + j;
+                let is_wall = !periodic && (j == 0 || j == fluid.num_y - 1);
+                fluid.s[idx] = if is_wall { 0.0 } else { 1.0 };
+                fluid.u[idx] = config.inflow_velocity * cos;
+                fluid.v[idx] = config.inflow_velocity * sin;
+            }
+        }
+
+        for obstacle in &config.obstacles {
+            if config.cut_cell {
+                mark_obstacle_solid_cut_cell(&mut fluid, obstacle);
+            } else {
+                mark_obstacle_solid(&mut fluid, obstacle);
+            }
+        }
+
+        let radiators: Vec<Radiator> = config.radiators.iter().map(Radiator::from).collect();
+        let ids: Vec<String> = (0..config.radiators.len()).map(|i| config.radiator_id(i)).collect();
+        let cut_cell_flags = vec![config.cut_cell; config.obstacles.len()];
+        let mut obstacles = ObstacleManager::with_cut_cell_flags(&fluid, radiators, config.obstacles.clone(), cut_cell_flags);
+        obstacles.set_radiator_ids(ids);
+
+        let vortex_body = config.vortex_body.as_ref().map(|c| {
+            let shape = config.obstacles[c.obstacle_index].clone();
+            VortexInducedBody::new(&fluid, c.obstacle_index, shape, c.mass_ratio, c.natural_frequency_hz, c.damping_ratio)
+        });
+        let moving_obstacles: Vec<MovingObstacle> = config
+            .moving_obstacles
+            .iter()
+            .map(|c| {
+                let shape = config.obstacles[c.obstacle_index].clone();
+                MovingObstacle::new(&fluid, c.obstacle_index, shape, c.motion)
+            })
+            .collect();
+
+        let turbulence_wall_distance = config
+            .turbulence_model
+            .map(|_| crate::turbulence::wall_distance_field(&fluid))
+            .unwrap_or_default();
+
+        Scene {
+            fluid,
+            obstacles,
+            dt: config.dt,
+            gravity: config.gravity,
+            num_iters: config.num_iters,
+            over_relaxation: if config.over_relaxation > 0.0 {
+                config.over_relaxation
+            } else {
+                1.9
+            },
+            pressure_solver: config.pressure_solver,
+            inflow_u: config.inflow_velocity,
+            inflow_profile: config.inflow_profile.clone(),
+            inflow_smoke_pattern: config.inflow_smoke_pattern,
+            dye_emitters: config.dye_emitters.clone(),
+            paint_events: config.paint_events.clone(),
+            sim_time: 0.0,
+            step_count: 0,
+            blc_interval: 5,
+            validate_interval: 20,
+            instability: None,
+            wake_trigger: config
+                .wake_trigger
+                .as_ref()
+                .map(|c| wake_trigger::WakeTrigger::new(c.after_step, c.lift_threshold, c.seed)),
+            wake_trigger_fired_this_step: false,
+            vortex_body,
+            moving_obstacles,
+            last_pressure_residual: 0.0,
+            step_ordering: config.step_ordering,
+            post_projection_u: None,
+            turbulence_model: config.turbulence_model,
+            turbulence_wall_distance,
+            lid_velocity: None,
+            inflow_angle: config.inflow_angle,
+            inflow_ramp_time: config.inflow_ramp_time,
+            field_stats: None,
+        }
+    }
+
+    /// How many `simulate` calls this scene has made.
+    pub fn step_count(&self) -> usize {
+        self.step_count
+    }
+
+    /// Snapshots this scene's grid parameters, boundary conditions, inflow,
+    /// obstacles, and radiators as a [`SceneConfig`] and writes it as JSON to
+    /// `path` — a record of what a run's geometry actually was, and a way to
+    /// rebuild the exact same `Scene` via [`SceneConfig::from_json_file`] +
+    /// [`Scene::setup_from_config`].
+    ///
+    /// `wake_trigger`, `vortex_body`, and `moving_obstacles` aren't captured:
+    /// `Scene` doesn't expose the state needed to reconstruct their configs
+    /// (and, for `wake_trigger`, whether it has already fired), so a
+    /// reloaded scene always comes back without them regardless of whether
+    /// the original had one. Everything this method does cover — the solid
+    /// mask, boundary conditions, and inflow — round-trips exactly.
+    pub fn export_setup(&self, path: &str) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.to_config()).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    /// The [`SceneConfig`] snapshot [`Self::export_setup`] writes to disk,
+    /// without the file I/O — shared with `run_metadata::RunMetadata`, which
+    /// embeds this same snapshot rather than reading it back from
+    /// `scene_setup.json`.
+    pub fn to_config(&self) -> SceneConfig {
+        let radiators = self
+            .obstacles
+            .radiators()
+            .iter()
+            .zip(self.obstacles.radiator_ids())
+            .map(|(radiator, id)| scene_config::RadiatorConfig::from_radiator(radiator, id.clone()))
+            .collect();
+
+        SceneConfig {
+            num_x: self.fluid.num_x,
+            num_y: self.fluid.num_y,
+            dt: self.dt,
+            num_iters: self.num_iters,
+            over_relaxation: self.over_relaxation,
+            pressure_solver: self.pressure_solver,
+            gravity: self.gravity,
+            inflow_velocity: self.inflow_u,
+            inflow_profile: self.inflow_profile.clone(),
+            inflow_angle: self.inflow_angle,
+            inflow_ramp_time: self.inflow_ramp_time,
+            obstacles: self.obstacles.obstacles().to_vec(),
+            radiators,
+            wake_trigger: None,
+            vortex_body: None,
+            moving_obstacles: Vec::new(),
+            step_ordering: self.step_ordering,
+            top_bottom_boundary: self.fluid.top_bottom_boundary,
+            smoke_decay: self.fluid.smoke_decay,
+            inflow_smoke_pattern: self.inflow_smoke_pattern,
+            dye_emitters: self.dye_emitters.clone(),
+            paint_events: self.paint_events.clone(),
+            line_profiles: Vec::new(),
+            turbulence_model: None,
+            working_fluid: Some(crate::working_fluid::WorkingFluid::Custom {
+                density: self.fluid.density,
+                kinematic_viscosity: self.fluid.kinematic_viscosity,
+            }),
+            cut_cell: false,
+        }
+    }
+
+    /// Add obstacles after the scene is already built — e.g. outlines
+    /// loaded from a CAD file via `geometry_io`, which the built-in scenes
+    /// and a resumed checkpoint have no `SceneConfig` to fold into up
+    /// front. Marks each shape's footprint solid in `self.fluid` and
+    /// registers it with `self.obstacles` so `compute_forces` covers it.
+    pub fn add_obstacles(&mut self, shapes: impl IntoIterator<Item = scene_config::ObstacleShape>) {
+        for shape in shapes {
+            mark_obstacle_solid(&mut self.fluid, &shape);
+            self.obstacles.add_obstacle(shape);
+        }
+    }
+
+    /// Add radiators after the scene is already built (mirrors
+    /// [`Self::add_obstacles`] for solid shapes). `ObstacleManager` has no
+    /// in-place "append a radiator" method — `reconfigure` only replaces an
+    /// existing one — so this rebuilds it with the new radiators appended,
+    /// the same rebuild the Python bindings' `add_radiator` used to do by
+    /// hand; every radiator ends up going through the one `RadiatorModel`
+    /// construction path regardless of whether it came from a `SceneConfig`,
+    /// this method, or a checkpoint, so there is no second porous-application
+    /// code path for the physics to diverge against.
+    pub fn add_radiators(&mut self, radiators: impl IntoIterator<Item = Radiator>) {
+        let mut all = self.obstacles.radiators().to_vec();
+        all.extend(radiators);
+        let obstacles = self.obstacles.obstacles().to_vec();
+        let cut_cell = self.obstacles.cut_cell_flags().to_vec();
+        let mut manager = ObstacleManager::with_cut_cell_flags(&self.fluid, all, obstacles, cut_cell);
+        let ids: Vec<String> = (0..manager.radiators().len()).map(|i| format!("radiator_{i}")).collect();
+        manager.set_radiator_ids(ids);
+        self.obstacles = manager;
+    }
+
+    /// Replace radiator `index`'s geometry/porosity in place, e.g. applying
+    /// a CLI override on top of whatever `--scene` already placed there.
+    /// See [`ObstacleManager::reconfigure`].
+    pub fn reconfigure_radiator(&mut self, index: usize, new: Radiator) {
+        self.obstacles.reconfigure(index, &mut self.fluid, new);
+    }
+
+    /// Remove radiator `index`, returning it. See
+    /// [`ObstacleManager::remove_radiator`] — a radiator never marks
+    /// `self.fluid`'s solid mask, so unlike [`Self::remove_obstacle`] there
+    /// is no footprint to restore.
+    pub fn remove_radiator(&mut self, index: usize) -> Radiator {
+        self.obstacles.remove_radiator(index)
+    }
+
+    /// Remove obstacle `index`, restoring its footprint to fluid and
+    /// re-marking any remaining obstacle whose footprint overlapped it, so
+    /// a cell shared by two obstacles stays solid until the last one
+    /// covering it is also removed. Each survivor is re-marked with
+    /// whichever method originally placed it (binary or cut-cell) — see
+    /// [`ObstacleManager::cut_cell_flags`] — so removing one obstacle from a
+    /// cut-cell scene doesn't collapse the others' fractional solid values
+    /// to a hard 0/1 mask. A cut-cell survivor is only re-marked within the
+    /// removed obstacle's own footprint (see
+    /// [`mark_obstacle_solid_cut_cell_where`]), since re-applying its
+    /// multiplicative fraction outside that footprint would double it up on
+    /// cells the removal never touched.
+    pub fn remove_obstacle(&mut self, index: usize) {
+        let shape = self.obstacles.remove_obstacle(index);
+        unmark_obstacle_solid(&mut self.fluid, &shape);
+        let remaining = self.obstacles.obstacles().to_vec();
+        let cut_cell = self.obstacles.cut_cell_flags().to_vec();
+        for (remaining, cut_cell) in remaining.iter().zip(cut_cell.iter()) {
+            if *cut_cell {
+                mark_obstacle_solid_cut_cell_where(&mut self.fluid, remaining, |x, y| shape.contains(x, y));
+            } else {
+                mark_obstacle_solid(&mut self.fluid, remaining);
+            }
+        }
+    }
+
+    /// Remove every solid obstacle `add_obstacles` has placed, restoring
+    /// their footprints to fluid. Radiators are untouched — this is for
+    /// interactive callers (e.g. the wasm demo's mouse-dragged obstacle)
+    /// that want to redraw a shape from scratch each drag rather than
+    /// accumulate one per frame.
+    pub fn clear_obstacles(&mut self) {
+        for shape in self.obstacles.obstacles().to_vec() {
+            unmark_obstacle_solid(&mut self.fluid, &shape);
+        }
+        let radiators = self.obstacles.radiators().to_vec();
+        let ids = self.obstacles.radiator_ids().to_vec();
+        let mut obstacles = ObstacleManager::new(&self.fluid, radiators, Vec::new());
+        obstacles.set_radiator_ids(ids);
+        self.obstacles = obstacles;
+    }
+
+    /// Stamps `value` into `fluid.m` within `radius` of `(x, y)`, skipping
+    /// solid cells. Callable directly from the live viewer's mouse handling
+    /// or from a scripted [`crate::paint::PaintEvent`] — both end up calling
+    /// [`crate::paint::paint_smoke`], so an interactive stroke and a
+    /// scripted one behave identically.
+    pub fn paint_smoke(&mut self, x: f64, y: f64, radius: f64, value: f64) {
+        paint::paint_smoke(&mut self.fluid, x, y, radius, value);
+    }
+
+    /// Marks every cell within `radius` of `(x, y)` solid and zeroes its
+    /// faces. Unlike [`Self::add_obstacles`], this doesn't register the
+    /// footprint with `self.obstacles` — a painted-in wall isn't one of the
+    /// scene's tracked obstacle shapes, so it never shows up in
+    /// `compute_forces` or gets redrawn by a resize. See
+    /// [`crate::paint::paint_solid`].
+    pub fn paint_solid(&mut self, x: f64, y: f64, radius: f64) {
+        paint::paint_solid(&mut self.fluid, x, y, radius);
+    }
+
+    /// Undoes [`Self::paint_solid`] within `radius` of `(x, y)`. See
+    /// [`crate::paint::erase_solid`].
+    pub fn erase_solid(&mut self, x: f64, y: f64, radius: f64) {
+        paint::erase_solid(&mut self.fluid, x, y, radius);
+    }
+
+    /// Adds `(vx, vy)` to every fluid cell's velocity within `radius` of
+    /// `(x, y)`, skipping solid cells. See [`crate::paint::stir`].
+    pub fn stir(&mut self, x: f64, y: f64, radius: f64, vx: f64, vy: f64) {
+        paint::stir(&mut self.fluid, x, y, radius, vx, vy);
+    }
+
+    /// Fires every not-yet-fired `paint_events` entry whose `at_time` has
+    /// been reached, in list order.
+    fn apply_paint_events(&mut self) {
+        for event in &mut self.paint_events {
+            event.maybe_fire(&mut self.fluid, self.sim_time);
+        }
+    }
+
+    /// The `0.0..=1.0` fraction `apply_inflow` scales `inflow_u` (or, under
+    /// a non-uniform `inflow_profile`, each row's profile value) by:
+    /// `1.0` once `sim_time` reaches `inflow_ramp_time`, `sim_time /
+    /// inflow_ramp_time` before that, and `1.0` outright (no ramp) at the
+    /// default `inflow_ramp_time == 0.0`.
+    fn ramp_fraction(&self) -> f64 {
+        if self.inflow_ramp_time <= 0.0 {
+            return 1.0;
+        }
+        (self.sim_time / self.inflow_ramp_time).min(1.0)
+    }
+
+    /// This row's ramped inflow magnitude, before resolving it into `u`/`v`
+    /// via `inflow_angle`: `inflow_profile` shapes it across `j`, then
+    /// `ramp_fraction` scales it in time the same way it always scaled the
+    /// flat `Uniform` case.
+    fn profile_inflow_u(&self, j: usize) -> f64 {
+        let y = j as f64 * self.fluid.h;
+        self.inflow_profile.value_at(y, self.fluid.domain_height(), self.inflow_u) * self.ramp_fraction()
+    }
+
+    fn apply_inflow(&mut self) {
+        let num_y = self.fluid.num_y;
+        let (sin, cos) = self.inflow_angle.sin_cos();
+        for j in 1..num_y - 1 {
+            let inflow_u = self.profile_inflow_u(j);
+            self.fluid.u[j] = inflow_u * cos;
+            self.fluid.v[j] = inflow_u * sin;
+            self.fluid.m[j] = dye_emitter::inflow_dye_value(self.inflow_smoke_pattern, j, num_y);
+        }
+    }
+
+    /// `extrapolate` mirrors the left column's `v` from the column just
+    /// inside it, which is the right zero-gradient condition for a
+    /// straight-on inflow but silently erases a nonzero `inflow_angle`'s
+    /// injected `v` (and, if `inflow_profile` is non-uniform, its per-row
+    /// shape too) back to whatever the interior happened to hold. A no-op
+    /// at the default `inflow_angle == 0.0`, so straight inflows keep
+    /// exactly the extrapolated `v` they always have; only an angled inflow
+    /// needs re-pinning after `extrapolate` runs, the same way
+    /// `lid_velocity` needs `apply_lid_velocity` re-run for its `u`.
+    fn reassert_angled_inflow_v(&mut self) {
+        if self.inflow_angle == 0.0 {
+            return;
+        }
+        let num_y = self.fluid.num_y;
+        let sin = self.inflow_angle.sin();
+        for j in 1..num_y - 1 {
+            let v = self.profile_inflow_u(j) * sin;
+            self.fluid.v[j] = v;
+        }
+    }
+
+    /// `lid_driven_cavity`'s moving-wall boundary condition: pins the top
+    /// row's tangential velocity to `lid_velocity` every step via
+    /// [`Fluid::pin_top_wall_velocity`], the same way `apply_inflow` pins
+    /// the left column's `u`. No-op when `lid_velocity` is `None`, i.e.
+    /// every scene except the cavity.
+    fn apply_lid_velocity(&mut self) {
+        if let Some(lid_u) = self.lid_velocity {
+            self.fluid.pin_top_wall_velocity(lid_u);
+        }
+    }
+
+    fn apply_dye_emitters(&mut self) {
+        for emitter in &self.dye_emitters {
+            emitter.apply(&mut self.fluid, self.sim_time);
+        }
+    }
+
+    /// Runs whichever [`PressureSolver`] this scene is configured with and
+    /// returns its residual, so [`Self::simulate`]'s two `step_ordering`
+    /// branches don't each need their own match on `self.pressure_solver`.
+    fn pressure_solve(&mut self) -> f64 {
+        match self.pressure_solver {
+            PressureSolver::GaussSeidel => self.fluid.solve_incompressibility(self.num_iters, self.dt, self.over_relaxation),
+            PressureSolver::Multigrid { levels, v_cycles, smoothing_iters } => {
+                self.fluid.solve_incompressibility_multigrid(levels, v_cycles, smoothing_iters, self.dt)
+            }
+        }
+    }
+
+    fn apply_turbulence(&mut self) {
+        if let Some(model) = self.turbulence_model {
+            crate::turbulence::apply(&mut self.fluid, &self.turbulence_wall_distance, model, self.dt);
+        }
+    }
+
+    pub fn save_checkpoint(&self, output: &OutputManager, step: u64) -> std::io::Result<()> {
+        self.fluid
+            .save_checkpoint(output.path_for("checkpoint_fluid.bin").to_str().unwrap())?;
+        let meta = SceneCheckpointMeta {
+            dt: self.dt,
+            gravity: self.gravity,
+            num_iters: self.num_iters,
+            over_relaxation: self.over_relaxation,
+            inflow_u: self.inflow_u,
+            radiators: self.obstacles.radiators().to_vec(),
+            obstacles: self.obstacles.obstacles().to_vec(),
+            step,
+            sim_time: Some(self.sim_time),
+            blc_interval: Some(self.blc_interval),
+            validate_interval: Some(self.validate_interval),
+            step_ordering: Some(self.step_ordering),
+            inflow_smoke_pattern: Some(self.inflow_smoke_pattern),
+            dye_emitters: self.dye_emitters.clone(),
+            pressure_solver: Some(self.pressure_solver),
+            inflow_angle: self.inflow_angle,
+            inflow_ramp_time: self.inflow_ramp_time,
+            inflow_profile: Some(self.inflow_profile.clone()),
+        };
+        let json = serde_json::to_string_pretty(&meta)?;
+        std::fs::write(output.path_for("checkpoint_meta.json"), json)
+    }
+
+    pub fn load_checkpoint(path: &str) -> std::io::Result<(Self, u64)> {
+        let dir = std::path::Path::new(path)
+            .parent()
+            .unwrap_or_else(|| std::path::Path::new("."));
+        let fluid = Fluid::load_checkpoint(path)?;
+        let meta_json = std::fs::read_to_string(dir.join("checkpoint_meta.json"))?;
+        let meta: SceneCheckpointMeta = serde_json::from_str(&meta_json)?;
+
+        let obstacles = ObstacleManager::new(&fluid, meta.radiators, meta.obstacles);
+        // Older checkpoints predate `sim_time`; `step * dt` is exactly what
+        // this feature replaces everywhere else, but it's the only sane
+        // reconstruction available for a checkpoint that never recorded it.
+        let sim_time = meta.sim_time.unwrap_or(meta.step as f64 * meta.dt);
+        let scene = Scene {
+            fluid,
+            obstacles,
+            dt: meta.dt,
+            gravity: meta.gravity,
+            num_iters: meta.num_iters,
+            over_relaxation: meta.over_relaxation,
+            pressure_solver: meta.pressure_solver.unwrap_or_default(),
+            inflow_u: meta.inflow_u,
+            inflow_profile: meta.inflow_profile.unwrap_or_default(),
+            inflow_angle: meta.inflow_angle,
+            inflow_ramp_time: meta.inflow_ramp_time,
+            inflow_smoke_pattern: meta.inflow_smoke_pattern.unwrap_or_default(),
+            dye_emitters: meta.dye_emitters,
+            // Not persisted in a checkpoint yet, same reason as
+            // `wake_trigger`/`vortex_body` below: a resumed run starts with
+            // no scripted paint events even if the original scene had some
+            // still pending.
+            paint_events: Vec::new(),
+            sim_time,
+            step_count: meta.step as usize,
+            blc_interval: meta.blc_interval.unwrap_or(5),
+            validate_interval: meta.validate_interval.unwrap_or(20),
+            instability: None,
+            // The wake trigger is a one-shot dev/sweep aid, not simulation
+            // state worth persisting: a resumed run either already passed
+            // its trigger step or never had one configured.
+            wake_trigger: None,
+            wake_trigger_fired_this_step: false,
+            // Not persisted in a checkpoint yet, for the same reason as
+            // `wake_trigger` above: a resumed run starts any vortex-induced
+            // body at rest again rather than mid-oscillation.
+            vortex_body: None,
+            // Same reason again: a resumed run starts every moving obstacle
+            // back at its rest position rather than mid-motion.
+            moving_obstacles: Vec::new(),
+            last_pressure_residual: 0.0,
+            // Not persisted, same reason as `wake_trigger`/`vortex_body`
+            // above: a resumed run starts with no turbulence closure even if
+            // the original scene had one configured.
+            turbulence_model: None,
+            turbulence_wall_distance: Vec::new(),
+            step_ordering: meta.step_ordering.unwrap_or_default(),
+            // Not persisted, same as `wake_trigger`/`vortex_body` above: a
+            // resumed run has no post-projection snapshot until its first
+            // `simulate` call.
+            post_projection_u: None,
+            // Not persisted, same as `wake_trigger`/`vortex_body` above; also
+            // only ever set by `lid_driven_cavity`, which has no config-file
+            // or checkpoint path yet.
+            lid_velocity: None,
+            // Not persisted, same reason as `wake_trigger`/`vortex_body`
+            // above: a resumed run starts without any accumulated statistics
+            // even if the original run had `enable_field_statistics` on.
+            field_stats: None,
+        };
+        Ok((scene, meta.step))
+    }
+
+    /// Advance the simulation by one step of `self.dt`, accumulating
+    /// `sim_time` by the actual `dt` used and returning the step index this
+    /// call just completed, so callers don't need to maintain their own
+    /// frame count in parallel. Every step is currently the same size, but
+    /// keeping the accumulation here (rather than deriving elapsed time
+    /// from a step count elsewhere) means an adaptive-dt or sub-stepping
+    /// change only has to touch this one method.
+    ///
+    /// This is also the crate's "step once" entry point for embedding: there
+    /// is no separate pause/resume or observer-callback API yet, so a caller
+    /// that wants to inspect state between steps just calls `simulate` in
+    /// its own loop and reads `fluid`/`obstacles`/`sim_time` in between.
+    pub fn simulate(&mut self) -> usize {
+        self.apply_inflow();
+        self.apply_lid_velocity();
+        self.apply_dye_emitters();
+        self.apply_paint_events();
+        self.fluid.integrate(self.dt, self.gravity);
+        self.obstacles.apply_porous_forces(&mut self.fluid, self.dt);
+        match self.step_ordering {
+            StepOrdering::ProjectThenAdvect => {
+                self.last_pressure_residual = self.pressure_solve();
+                self.post_projection_u = Some(self.fluid.u.clone());
+                self.fluid.extrapolate();
+                // `extrapolate` mirrors the top ghost row's `u` from the
+                // interior row below it, same as it does for every other
+                // domain edge — exactly what a stationary no-slip wall
+                // wants, and exactly wrong for `lid_velocity`'s moving one,
+                // so it has to be re-applied after `extrapolate` runs, not
+                // just once at the top of this method. The same is true of
+                // `apply_inflow`'s `v` component for an angled inflow; see
+                // `reassert_angled_inflow_v`.
+                self.apply_lid_velocity();
+                self.reassert_angled_inflow_v();
+                self.fluid.advect_vel(self.dt);
+                self.apply_turbulence();
+                self.fluid.conserve_outflow_mass();
+                self.fluid.advect_smoke(self.dt);
+            }
+            StepOrdering::AdvectThenProject => {
+                self.fluid.extrapolate();
+                self.apply_lid_velocity();
+                self.reassert_angled_inflow_v();
+                self.fluid.advect_vel(self.dt);
+                self.apply_turbulence();
+                self.fluid.conserve_outflow_mass();
+                self.fluid.advect_smoke(self.dt);
+                self.last_pressure_residual = self.pressure_solve();
+                self.post_projection_u = Some(self.fluid.u.clone());
+            }
+        }
+        self.sim_time += self.dt;
+        self.step_count += 1;
+        if let Some(stats) = &mut self.field_stats {
+            stats.record(&self.fluid);
+        }
+        if self.step_count.is_multiple_of(self.blc_interval) {
+            // Boundary layer control hook: nothing to apply yet, but this is
+            // where an every-`blc_interval`-steps adjustment belongs once
+            // one exists, rather than back in the caller.
+        }
+        if self.instability.is_none() && self.step_count.is_multiple_of(self.validate_interval) {
+            self.instability = self.fluid.validate().err();
+        }
+
+        self.wake_trigger_fired_this_step = false;
+        if let Some(trigger) = &mut self.wake_trigger {
+            if let Some(shape) = self.obstacles.obstacles().first().cloned() {
+                let lift = self
+                    .obstacles
+                    .compute_forces(&self.fluid, self.inflow_u)
+                    .first()
+                    .map(|f| f.lift)
+                    .unwrap_or(0.0);
+                self.wake_trigger_fired_this_step =
+                    trigger.maybe_fire(&mut self.fluid, &shape, self.inflow_u, self.step_count, lift);
+            }
+        }
+
+        if let Some(body) = &mut self.vortex_body {
+            body.step(&mut self.fluid, self.inflow_u, self.dt, self.sim_time);
+        }
+
+        // `sim_time` was already advanced above, so `moving_obstacles` moves
+        // to (and evaluates its surface velocity at) the end of this step's
+        // time, not its start — same convention `vortex_body.step` follows.
+        for obstacle in &mut self.moving_obstacles {
+            obstacle.step(&mut self.fluid, self.dt, self.sim_time - self.dt, self.inflow_u, 0.0);
+        }
+
+        self.step_count
+    }
+
+    /// Obstacles moving under a prescribed `Motion` instead of staying
+    /// fixed. Empty unless `--config` set up `moving_obstacles`.
+    pub fn moving_obstacles(&self) -> &[MovingObstacle] {
+        &self.moving_obstacles
+    }
+
+    /// Whether `wake_trigger` fired on the most recent `simulate` call.
+    pub fn wake_trigger_fired(&self) -> bool {
+        self.wake_trigger_fired_this_step
+    }
+
+    /// Set once `Fluid::validate` (checked every `validate_interval` steps)
+    /// finds a non-finite cell, and never cleared afterward — see the field
+    /// doc comment. `run_scene` polls this once per step to decide whether
+    /// to stop the run.
+    pub fn instability(&self) -> Option<crate::fluid::InstabilityReport> {
+        self.instability
+    }
+
+    /// The scene's vortex-induced-vibration body, if `--config` set one up.
+    pub fn vortex_body(&self) -> Option<&VortexInducedBody> {
+        self.vortex_body.as_ref()
+    }
+
+    /// `Fluid::solve_incompressibility`'s return value from the most recent
+    /// `simulate` call. `0.0` before the first call.
+    pub fn pressure_residual(&self) -> f64 {
+        self.last_pressure_residual
+    }
+
+    /// A copy of `fluid.u` taken right after the most recent `simulate`
+    /// call's pressure projection, before advection can perturb it again.
+    /// `None` until the first `simulate` call. See
+    /// [`crate::metrics::MetricsSamplingPoint::PostProjection`].
+    pub fn post_projection_u(&self) -> Option<&[f64]> {
+        self.post_projection_u.as_deref()
+    }
+
+    /// Start accumulating [`crate::field_statistics::FieldStatistics`] from
+    /// the next `simulate` call onward. Calling this again resets whatever
+    /// was already accumulated — there's no "pause and resume the same
+    /// accumulator" mode, matching how `wake_trigger`/`vortex_body` are
+    /// each configured once and not reconfigured mid-run.
+    pub fn enable_field_statistics(&mut self) {
+        self.field_stats = Some(crate::field_statistics::FieldStatistics::new(self.fluid.u.len()));
+    }
+
+    /// The running field statistics, if [`Self::enable_field_statistics`]
+    /// has been called. `None` otherwise, including before the first
+    /// `simulate` call after enabling — the accumulator exists but has
+    /// zero samples in it, same as an empty `Vec` rather than a
+    /// distinguishable "not started" state.
+    pub fn field_statistics(&self) -> Option<&crate::field_statistics::FieldStatistics> {
+        self.field_stats.as_ref()
+    }
+}
+
+/// Mark every cell whose center falls inside `shape` as solid.
+/// Full duct height (wall to wall, not half-height) at streamwise position
+/// `x`, piecewise linear across the three sections
+/// [`Scene::duct_with_radiator`] builds: converging from `inlet_height` to
+/// `test_section_height` over `converging_length`, flat through
+/// `test_section_length`, then diverging to `diffuser_exit_height` over
+/// `diffuser_length`.
+fn duct_height_at(
+    x: f64,
+    inlet_height: f64,
+    test_section_height: f64,
+    diffuser_exit_height: f64,
+    converging_length: f64,
+    test_section_length: f64,
+    diffuser_length: f64,
+) -> f64 {
+    if x < converging_length {
+        let t = if converging_length > 0.0 { x / converging_length } else { 1.0 };
+        inlet_height + (test_section_height - inlet_height) * t
+    } else if x < converging_length + test_section_length {
+        test_section_height
+    } else {
+        let t = if diffuser_length > 0.0 {
+            ((x - converging_length - test_section_length) / diffuser_length).min(1.0)
+        } else {
+            1.0
+        };
+        test_section_height + (diffuser_exit_height - test_section_height) * t
+    }
+}
+
+pub fn mark_obstacle_solid(fluid: &mut Fluid, shape: &scene_config::ObstacleShape) {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            if shape.contains(x, y) {
+                let idx = i * n + j;
+                fluid.s[idx] = 0.0;
+                // A solid cell's four faces are shared array slots with its
+                // neighbors (`u[idx]` is this cell's west face *and* its
+                // western neighbor's east face). Zero all four so no stale
+                // free-stream velocity survives on a wall the pressure solve
+                // has stopped correcting.
+                fluid.u[idx] = 0.0;
+                fluid.u[(i + 1) * n + j] = 0.0;
+                fluid.v[idx] = 0.0;
+                fluid.v[idx + 1] = 0.0;
+            }
+        }
+    }
+}
+
+/// Cut-cell counterpart to [`mark_obstacle_solid`]: instead of a single
+/// point test at `(i*h, j*h)`, supersamples each cell on a 4x4 grid of
+/// sample points against `shape.contains` and sets `s` to the resulting
+/// open-area fraction rather than snapping straight to `0.0`/`1.0`. A
+/// fractional `s` needs no changes anywhere else — the pressure solve's
+/// neighbor-weight stencil already reads `fluid.s[neighbor]` as a
+/// continuous per-face weight, so a cut cell just contributes partial
+/// credit there automatically, and [`crate::obstacle_analysis`]'s force
+/// integration weights by solid fraction the same way.
+///
+/// Multiple overlapping shapes multiply their open fractions together
+/// (`0.3` open times `0.3` open leaves `0.09` open) so calling this for
+/// several obstacles in a row can only ever make a cell more solid, the
+/// same accumulation `mark_obstacle_solid`'s binary `s = 0.0` gives for
+/// free. `u`/`v` on a cut cell's faces are scaled by the same fraction
+/// rather than zeroed outright, blending the no-slip condition in
+/// proportion to how much of the cell is actually solid instead of
+/// staircasing straight to zero at the first partially-covered cell.
+pub fn mark_obstacle_solid_cut_cell(fluid: &mut Fluid, shape: &scene_config::ObstacleShape) {
+    mark_obstacle_solid_cut_cell_where(fluid, shape, |_, _| true);
+}
+
+/// Same supersampled marking as [`mark_obstacle_solid_cut_cell`], but only
+/// touches a cell if `region` returns `true` at that cell's center point.
+/// Used by [`Scene::remove_obstacle`] to re-derive a surviving cut-cell
+/// obstacle's fraction only where the removed obstacle's footprint (per
+/// [`unmark_obstacle_solid`]'s own point test) actually reset the cell back
+/// to fluid — re-applying the multiplicative `s[idx] *= open_fraction`
+/// everywhere would fold an untouched cell's existing fraction into itself
+/// a second time.
+fn mark_obstacle_solid_cut_cell_where(fluid: &mut Fluid, shape: &scene_config::ObstacleShape, region: impl Fn(f64, f64) -> bool) {
+    const SUBDIV: usize = 4;
+    let n = fluid.num_y;
+    let h = fluid.h;
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            if !region(i as f64 * h, j as f64 * h) {
+                continue;
+            }
+            let mut covered = 0;
+            for sx in 0..SUBDIV {
+                for sy in 0..SUBDIV {
+                    let x = (i as f64 + (sx as f64 + 0.5) / SUBDIV as f64 - 0.5) * h;
+                    let y = (j as f64 + (sy as f64 + 0.5) / SUBDIV as f64 - 0.5) * h;
+                    if shape.contains(x, y) {
+                        covered += 1;
+                    }
+                }
+            }
+            if covered == 0 {
+                continue;
+            }
+            let open_fraction = 1.0 - covered as f64 / (SUBDIV * SUBDIV) as f64;
+            let idx = i * n + j;
+            fluid.s[idx] *= open_fraction;
+            fluid.u[idx] *= open_fraction;
+            fluid.u[(i + 1) * n + j] *= open_fraction;
+            fluid.v[idx] *= open_fraction;
+            fluid.v[idx + 1] *= open_fraction;
+        }
+    }
+}
+
+/// Undo [`mark_obstacle_solid`] for `shape`: restores every cell whose
+/// center falls inside it back to fluid. Doesn't touch `u`/`v` beyond what
+/// `mark_obstacle_solid` already zeroed — the next `apply_inflow`/pressure
+/// solve fills them in like any other freshly-uncovered cell.
+fn unmark_obstacle_solid(fluid: &mut Fluid, shape: &scene_config::ObstacleShape) {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            if shape.contains(x, y) {
+                fluid.s[i * n + j] = 1.0;
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SceneCheckpointMeta {
+    dt: f64,
+    gravity: f64,
+    num_iters: usize,
+    over_relaxation: f64,
+    inflow_u: f64,
+    radiators: Vec<Radiator>,
+    #[serde(default)]
+    obstacles: Vec<scene_config::ObstacleShape>,
+    step: u64,
+    #[serde(default)]
+    sim_time: Option<f64>,
+    /// Older checkpoints predate per-scene boundary-layer-control tuning;
+    /// `Scene::load_checkpoint` falls back to the same default `simulate`
+    /// itself uses for a scene built from scratch.
+    #[serde(default)]
+    blc_interval: Option<usize>,
+    /// Older checkpoints predate periodic instability validation; falls
+    /// back to the same default `simulate` uses for a scene built from
+    /// scratch, same convention as `blc_interval` above.
+    #[serde(default)]
+    validate_interval: Option<usize>,
+    /// Older checkpoints predate configurable step ordering; falls back to
+    /// [`StepOrdering::default`] (this solver's original project-then-advect
+    /// order) the same way a scene built from scratch does.
+    #[serde(default)]
+    step_ordering: Option<StepOrdering>,
+    /// Older checkpoints predate configurable inlet smoke patterns; falls
+    /// back to [`InflowSmokePattern::default`], same convention as
+    /// `step_ordering` above.
+    #[serde(default)]
+    inflow_smoke_pattern: Option<InflowSmokePattern>,
+    /// Older checkpoints predate dye emitters entirely.
+    #[serde(default)]
+    dye_emitters: Vec<DyeEmitter>,
+    /// Older checkpoints predate configurable pressure solvers; falls back
+    /// to [`PressureSolver::default`], same convention as `step_ordering`
+    /// above.
+    #[serde(default)]
+    pressure_solver: Option<PressureSolver>,
+    /// Older checkpoints predate angled inflow; falls back to `0.0`
+    /// (horizontal inflow), same convention as `step_ordering` above.
+    #[serde(default)]
+    inflow_angle: f64,
+    /// Older checkpoints predate the inflow ramp; falls back to `0.0`
+    /// (instant-on), same convention as `step_ordering` above. `sim_time` is
+    /// already recorded, so a resumed run's ramp (if any) picks back up
+    /// exactly where it left off rather than restarting from `t = 0`.
+    #[serde(default)]
+    inflow_ramp_time: f64,
+    /// Older checkpoints predate configurable inflow profiles; falls back
+    /// to [`InflowProfile::default`], same convention as `step_ordering`
+    /// above.
+    #[serde(default)]
+    inflow_profile: Option<InflowProfile>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluid::SOLID_CELL;
+    use crate::scene_config::ObstacleShape;
+
+    /// `instability()` starts `None` and stays that way for a scene that
+    /// never blows up; injecting a NaN directly and stepping once shouldn't
+    /// change that until the next `validate_interval` boundary, which this
+    /// scene reaches at `step_count == 20` (the default).
+    #[test]
+    fn instability_is_none_until_a_blown_up_field_crosses_a_validate_interval_boundary() {
+        let mut scene = Scene::wind_tunnel_with_radiator(20, 10);
+        for _ in 0..19 {
+            scene.simulate();
+            assert_eq!(scene.instability(), None, "step {} is not a validate_interval boundary yet", scene.step_count());
+        }
+        // Injected right before the step that lands on the
+        // `validate_interval` boundary, so nothing upstream of `validate`
+        // itself has had a chance to overwrite it.
+        let idx = scene.fluid.idx(10, 5);
+        scene.fluid.m[idx] = f64::NAN;
+        scene.simulate();
+        assert_eq!(scene.step_count(), 20);
+        assert_eq!(scene.instability().map(|r| r.field), Some("m"));
+    }
+
+    /// A request against this codebase once claimed `Fluid::new` pads
+    /// `num_x`/`num_y` with hidden ghost cells, so every physical-size
+    /// computation done as `num_x as f64 * h` (including a radiator's own
+    /// `0.5 * width` placement) was silently off by `2 * h`. That padding
+    /// doesn't exist here: `Fluid::new` stores `num_x`/`num_y` exactly as
+    /// passed. This test pins the actual invariant `domain_width()`/
+    /// `domain_height()` exist to make convenient: an obstacle placed at
+    /// exactly half the domain's physical width/height lands with its
+    /// footprint centered in the `s` mask.
+    #[test]
+    fn an_obstacle_centered_on_domain_width_and_height_lands_centered_in_the_solid_mask() {
+        let mut fluid = Fluid::new(1000.0, 100, 50, 0.02);
+        let cx = fluid.domain_width() * 0.5;
+        let cy = fluid.domain_height() * 0.5;
+        let radius = fluid.domain_height() * 0.2;
+        mark_obstacle_solid(&mut fluid, &ObstacleShape::Circle { cx, cy, radius });
+
+        let n = fluid.num_y;
+        let center_idx = fluid.idx((cx / fluid.h).round() as usize, (cy / fluid.h).round() as usize);
+        assert_eq!(fluid.s[center_idx], SOLID_CELL, "circle center should be marked solid");
+
+        let mut min_i = fluid.num_x;
+        let mut max_i = 0;
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                if fluid.s[i * n + j] == SOLID_CELL {
+                    min_i = min_i.min(i);
+                    max_i = max_i.max(i);
+                }
+            }
+        }
+        let footprint_center_x = (min_i + max_i) as f64 * 0.5 * fluid.h;
+        assert!(
+            (footprint_center_x - cx).abs() < fluid.h,
+            "footprint center {footprint_center_x} should be within one cell of the requested center {cx}"
+        );
+    }
+
+    /// The default wind-tunnel scene has no randomized component
+    /// (`wake_trigger` is `None` unless a `--config` file opts it in, and
+    /// even then it's seeded deterministically — see
+    /// `wake_trigger::tests::same_seed_perturbs_identically_across_runs`),
+    /// so two independently constructed scenes stepped the same number of
+    /// times should read back bitwise-identical `u`/`v`/`p`. Compared via a
+    /// hash of each field's raw bits rather than `assert_eq!` on the whole
+    /// `Vec<f64>`, so a mismatch prints one short number instead of two
+    /// multi-thousand-element dumps.
+    #[test]
+    fn two_independent_runs_of_the_same_scene_produce_identical_fields() {
+        fn hash_fields(scene: &Scene) -> u64 {
+            use std::hash::{Hash, Hasher};
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            for v in scene.fluid.u.iter().chain(scene.fluid.v.iter()).chain(scene.fluid.p.iter()) {
+                v.to_bits().hash(&mut hasher);
+            }
+            hasher.finish()
+        }
+
+        let mut first = Scene::wind_tunnel_with_radiator(40, 20);
+        let mut second = Scene::wind_tunnel_with_radiator(40, 20);
+        for _ in 0..200 {
+            first.simulate();
+            second.simulate();
+        }
+
+        assert_eq!(
+            hash_fields(&first),
+            hash_fields(&second),
+            "two runs from the same setup should be bitwise reproducible"
+        );
+    }
+
+    #[test]
+    fn export_setup_round_trips_the_solid_mask_and_radiator_placement() {
+        let config = SceneConfig {
+            num_x: 60,
+            num_y: 30,
+            dt: 1.0 / 60.0,
+            num_iters: 20,
+            over_relaxation: 1.9,
+            pressure_solver: Default::default(),
+            gravity: 0.0,
+            inflow_velocity: 1.2,
+            inflow_profile: Default::default(),
+            inflow_angle: 0.0,
+            inflow_ramp_time: 0.0,
+            obstacles: vec![ObstacleShape::Circle { cx: 0.3, cy: 0.5, radius: 0.1 }],
+            radiators: vec![scene_config::RadiatorConfig {
+                name: Some("main".to_string()),
+                center_x: 0.7,
+                center_y: 0.5,
+                width: 0.05,
+                height: 0.4,
+                angle: 0.0,
+                porosity: 0.6,
+                heat_exchanger: None,
+            }],
+            wake_trigger: None,
+            vortex_body: None,
+            moving_obstacles: Vec::new(),
+            step_ordering: Default::default(),
+            top_bottom_boundary: BoundaryCondition::NoSlip,
+            smoke_decay: 0.02,
+            inflow_smoke_pattern: Default::default(),
+            dye_emitters: Vec::new(),
+            paint_events: Vec::new(),
+            line_profiles: Vec::new(),
+            turbulence_model: None,
+            working_fluid: None,
+            cut_cell: false,
+        };
+        let original = Scene::setup_from_config(&config);
+
+        let path = std::env::temp_dir().join(format!(
+            "lgr_2d_cfd_test_export_setup_{:?}.json",
+            std::thread::current().id()
+        ));
+        original.export_setup(path.to_str().unwrap()).unwrap();
+
+        let reloaded_config = SceneConfig::from_json_file(path.to_str().unwrap()).unwrap();
+        let reloaded = Scene::setup_from_config(&reloaded_config);
+
+        assert_eq!(reloaded.fluid.s, original.fluid.s, "reloaded s-mask should match cell-for-cell");
+        assert_eq!(reloaded.fluid.num_x, original.fluid.num_x);
+        assert_eq!(reloaded.fluid.num_y, original.fluid.num_y);
+        assert_eq!(reloaded.obstacles.radiators().len(), 1);
+        assert_eq!(reloaded.obstacles.radiator_ids(), &["main".to_string()]);
+        assert_eq!(reloaded.obstacles.radiators()[0].porosity, 0.6);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// A radiator never marks `s` (see `radiator_model::RadiatorModel`), so
+    /// adding one and then removing it should be a complete no-op on the
+    /// solid mask — this pins that down against scene 4's own setup
+    /// (`wind_tunnel_with_radiator`), not just a bare grid.
+    #[test]
+    fn adding_and_removing_a_radiator_leaves_the_solid_mask_exactly_as_it_was() {
+        let mut scene = Scene::wind_tunnel_with_radiator(40, 20);
+        let before = scene.fluid.s.clone();
+
+        scene.add_radiators([Radiator::new(0.6, 0.5, 0.05, 0.3, 0.2, 0.7)]);
+        let index = scene.obstacles.radiators().len() - 1;
+        scene.remove_radiator(index);
+
+        assert_eq!(scene.fluid.s, before, "removing a radiator should leave the solid mask untouched");
+    }
+
+    #[test]
+    fn removing_an_obstacle_restores_its_footprint_but_leaves_the_walls_and_other_obstacles_solid() {
+        let mut scene = Scene::wind_tunnel_with_radiator(40, 20);
+
+        let survivor = ObstacleShape::Circle { cx: 0.3, cy: 0.5, radius: 0.08 };
+        scene.add_obstacles([survivor.clone()]);
+        let with_only_survivor = scene.fluid.s.clone();
+
+        let removed = ObstacleShape::Circle { cx: 0.6, cy: 0.5, radius: 0.08 };
+        scene.add_obstacles([removed]);
+        scene.remove_obstacle(1);
+
+        assert_eq!(scene.fluid.s, with_only_survivor, "removing the obstacle placed after the survivor should restore its footprint without disturbing the survivor's");
+        assert_eq!(scene.obstacles.obstacles().len(), 1);
+        assert_eq!(format!("{:?}", scene.obstacles.obstacles()[0]), format!("{survivor:?}"));
+    }
+
+    #[test]
+    fn removing_an_obstacle_whose_footprint_overlapped_another_leaves_the_shared_cells_solid() {
+        let mut scene = Scene::wind_tunnel_with_radiator(40, 20);
+        let overlapping_a = ObstacleShape::Circle { cx: 0.5, cy: 0.5, radius: 0.1 };
+        let overlapping_b = ObstacleShape::Circle { cx: 0.55, cy: 0.5, radius: 0.1 };
+        scene.add_obstacles([overlapping_a.clone(), overlapping_b]);
+
+        scene.remove_obstacle(1);
+
+        let n = scene.fluid.num_y;
+        let h = scene.fluid.h;
+        for i in 1..scene.fluid.num_x - 1 {
+            for j in 1..scene.fluid.num_y - 1 {
+                let x = i as f64 * h;
+                let y = j as f64 * h;
+                if overlapping_a.contains(x, y) {
+                    assert_eq!(scene.fluid.s[i * n + j], SOLID_CELL, "cell ({i},{j}) still belongs to the surviving obstacle");
+                }
+            }
+        }
+    }
+
+    /// Same regression as `removing_an_obstacle_restores_its_footprint_but_
+    /// leaves_the_walls_and_other_obstacles_solid`, but against a
+    /// `cut_cell: true` scene, whose obstacles carry fractional `s` values
+    /// near their boundary rather than a binary mask — `remove_obstacle`
+    /// re-marking the survivor with the wrong method would silently
+    /// collapse those fractional values to hard 0/1.
+    #[test]
+    fn removing_an_obstacle_from_a_cut_cell_scene_leaves_the_survivors_fractional_solid_values_unchanged() {
+        let config = SceneConfig {
+            num_x: 60,
+            num_y: 30,
+            dt: 1.0 / 60.0,
+            num_iters: 20,
+            over_relaxation: 1.9,
+            pressure_solver: Default::default(),
+            gravity: 0.0,
+            inflow_velocity: 1.2,
+            inflow_profile: Default::default(),
+            inflow_angle: 0.0,
+            inflow_ramp_time: 0.0,
+            obstacles: vec![
+                ObstacleShape::Circle { cx: 0.3, cy: 0.5, radius: 0.1 },
+                ObstacleShape::Circle { cx: 0.8, cy: 0.5, radius: 0.1 },
+            ],
+            radiators: Vec::new(),
+            wake_trigger: None,
+            vortex_body: None,
+            moving_obstacles: Vec::new(),
+            step_ordering: Default::default(),
+            top_bottom_boundary: BoundaryCondition::NoSlip,
+            smoke_decay: 0.0,
+            inflow_smoke_pattern: Default::default(),
+            dye_emitters: Vec::new(),
+            paint_events: Vec::new(),
+            line_profiles: Vec::new(),
+            turbulence_model: None,
+            working_fluid: None,
+            cut_cell: true,
+        };
+        let mut scene = Scene::setup_from_config(&config);
+        let survivor_before = scene.fluid.s.clone();
+        let has_fractional_cell = survivor_before.iter().any(|&v| v != SOLID_CELL && v != 1.0);
+        assert!(has_fractional_cell, "cut_cell placement should leave some fractional s values to protect");
+
+        scene.remove_obstacle(1);
+
+        assert_eq!(scene.obstacles.obstacles().len(), 1, "only the removed obstacle's bookkeeping entry should be gone");
+        for i in 1..scene.fluid.num_x - 1 {
+            for j in 1..scene.fluid.num_y - 1 {
+                let idx = i * scene.fluid.num_y + j;
+                let x = i as f64 * scene.fluid.h;
+                let y = j as f64 * scene.fluid.h;
+                if !config.obstacles[1].contains(x, y) {
+                    assert_eq!(
+                        scene.fluid.s[idx], survivor_before[idx],
+                        "cell ({i},{j}) outside the removed obstacle's footprint should keep its original cut-cell fraction"
+                    );
+                }
+            }
+        }
+    }
+}
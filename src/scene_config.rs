@@ -0,0 +1,665 @@
+//! TOML-driven scene setup, replacing the hard-coded numeric `--scene`
+//! presets baked into `main.rs`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::dye_emitter::{DyeEmitter, InflowSmokePattern};
+use crate::fluid::{BoundaryCondition, FieldType, PressureSolver, StepOrdering};
+use crate::radiator::{HeatExchanger, Radiator};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "shape")]
+pub enum ObstacleShape {
+    Circle { cx: f64, cy: f64, radius: f64 },
+    Rectangle { cx: f64, cy: f64, width: f64, height: f64, angle: f64 },
+    /// A NACA 4-digit section: `thickness` is the max thickness and `camber`
+    /// the max camber, both in domain units (not the usual percent-of-chord
+    /// digits — multiply the digit by `chord` yourself, e.g. `camber =
+    /// 0.02 * chord` for a NACA 2412's leading `2`); `camber_position` is the
+    /// chordwise location of that max camber as a fraction of chord (`0.4`
+    /// for the `4` in 2412). `camber = 0.0` gives a symmetric section, and
+    /// `camber_position` is ignored in that case. `(cx, cy)` places the
+    /// quarter-chord point rather than the geometric center, matching the
+    /// pitch point convention wind-tunnel angle-of-attack sweeps use.
+    Airfoil { cx: f64, cy: f64, chord: f64, thickness: f64, camber: f64, camber_position: f64, angle: f64 },
+    /// An arbitrary simple polygon in domain coordinates, for cross-sections
+    /// (a sidepod duct, say) none of the other variants can express. Built
+    /// exclusively through [`ObstacleShape::new_polygon`], which rejects a
+    /// degenerate (fewer than 3 vertices, or self-intersecting) polygon —
+    /// there's no way to construct one through this variant's fields
+    /// directly and skip that check other than deserializing it, which
+    /// (like every other variant's fields) isn't validated at parse time.
+    Polygon { vertices: Vec<(f64, f64)> },
+}
+
+impl ObstacleShape {
+    /// True if the point `(x, y)` (domain coordinates) falls inside this
+    /// shape, used both to mark cells solid at setup and to march the
+    /// solid/fluid interface when integrating surface forces.
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        match *self {
+            ObstacleShape::Circle { cx, cy, radius } => {
+                ((x - cx).powi(2) + (y - cy).powi(2)).sqrt() <= radius
+            }
+            ObstacleShape::Rectangle { cx, cy, width, height, angle } => {
+                let dx = x - cx;
+                let dy = y - cy;
+                let local_x = dx * angle.cos() + dy * angle.sin();
+                let local_y = -dx * angle.sin() + dy * angle.cos();
+                local_x.abs() <= width * 0.5 && local_y.abs() <= height * 0.5
+            }
+            ObstacleShape::Airfoil { cx, cy, chord, thickness, camber, camber_position, angle } => {
+                let dx = x - cx;
+                let dy = y - cy;
+                let local_x = dx * angle.cos() + dy * angle.sin();
+                let local_y = -dx * angle.sin() + dy * angle.cos();
+                // `local_x` is relative to the quarter-chord; shift back to
+                // the usual leading-edge-at-0 chordwise coordinate.
+                let xi = local_x + 0.25 * chord;
+                if xi < 0.0 || xi > chord {
+                    return false;
+                }
+                let xi_c = xi / chord;
+                let yc = naca4_camber(camber / chord, camber_position, xi_c) * chord;
+                let yt = naca4_half_thickness(thickness / chord, xi_c) * chord;
+                local_y >= yc - yt && local_y <= yc + yt
+            }
+            ObstacleShape::Polygon { ref vertices } => polygon_contains(vertices, x, y),
+        }
+    }
+
+    /// Unsigned distance from `(x, y)` (domain coordinates) to this shape's
+    /// boundary — negative-space distinctions (inside vs. outside) are left
+    /// to [`Self::contains`]; this is only used where the nearest-surface
+    /// distance itself matters (e.g. proximity-based mesh refinement).
+    pub fn distance_to_surface(&self, x: f64, y: f64) -> f64 {
+        match *self {
+            ObstacleShape::Circle { cx, cy, radius } => {
+                (((x - cx).powi(2) + (y - cy).powi(2)).sqrt() - radius).abs()
+            }
+            ObstacleShape::Rectangle { cx, cy, width, height, angle } => {
+                let dx = x - cx;
+                let dy = y - cy;
+                let local_x = dx * angle.cos() + dy * angle.sin();
+                let local_y = -dx * angle.sin() + dy * angle.cos();
+                let ax = local_x.abs() - width * 0.5;
+                let ay = local_y.abs() - height * 0.5;
+                if ax <= 0.0 && ay <= 0.0 {
+                    -ax.max(ay)
+                } else {
+                    ax.max(0.0).hypot(ay.max(0.0))
+                }
+            }
+            ObstacleShape::Airfoil { cx, cy, chord, thickness, camber, camber_position, angle } => {
+                // No closed-form point-to-NACA-outline distance; approximate
+                // it the same way `Rectangle` does, against a chordwise
+                // "envelope" box (nearest chord end, nearest thickness
+                // envelope at that chord station) instead of a true nearest
+                // point on the actual upper/lower surface. Good enough for
+                // the visualizer's outline drawing, which is this method's
+                // only consumer.
+                let dx = x - cx;
+                let dy = y - cy;
+                let local_x = dx * angle.cos() + dy * angle.sin();
+                let local_y = -dx * angle.sin() + dy * angle.cos();
+                let xi = local_x + 0.25 * chord;
+                let xi_c = (xi / chord).clamp(0.0, 1.0);
+                let yc = naca4_camber(camber / chord, camber_position, xi_c) * chord;
+                let yt = naca4_half_thickness(thickness / chord, xi_c) * chord;
+                let ax = (xi - chord * 0.5).abs() - chord * 0.5;
+                let ay = (local_y - yc).abs() - yt;
+                if ax <= 0.0 && ay <= 0.0 {
+                    -ax.max(ay)
+                } else {
+                    ax.max(0.0).hypot(ay.max(0.0))
+                }
+            }
+            ObstacleShape::Polygon { ref vertices } => vertices
+                .iter()
+                .zip(vertices.iter().cycle().skip(1))
+                .map(|(&a, &b)| point_segment_distance((x, y), a, b))
+                .fold(f64::INFINITY, f64::min),
+        }
+    }
+
+    /// Validating constructor for [`ObstacleShape::Polygon`]: rejects fewer
+    /// than 3 vertices or a self-intersecting outline, mirroring
+    /// `objective.rs`'s pure in-memory validation (no I/O involved, so a
+    /// plain `Result<_, String>` rather than `std::io::Error`).
+    pub fn new_polygon(vertices: Vec<(f64, f64)>) -> Result<Self, String> {
+        if vertices.len() < 3 {
+            return Err(format!(
+                "polygon obstacle needs at least 3 vertices, got {}",
+                vertices.len()
+            ));
+        }
+        let n = vertices.len();
+        for i in 0..n {
+            let a1 = vertices[i];
+            let a2 = vertices[(i + 1) % n];
+            for j in (i + 1)..n {
+                // Adjacent edges (including the edge that wraps from the
+                // last vertex back to the first) legitimately share an
+                // endpoint; only flag a genuine crossing between edges
+                // that shouldn't touch at all.
+                let adjacent = j == i + 1 || (i == 0 && j == n - 1);
+                if adjacent {
+                    continue;
+                }
+                let b1 = vertices[j];
+                let b2 = vertices[(j + 1) % n];
+                if segments_intersect(a1, a2, b1, b2) {
+                    return Err(format!(
+                        "polygon obstacle is self-intersecting (edge {i} crosses edge {j})"
+                    ));
+                }
+            }
+        }
+        Ok(ObstacleShape::Polygon { vertices })
+    }
+
+    /// Frontal height (2D, per-unit-depth) used as the reference area when
+    /// non-dimensionalizing forces into Cd/Cl.
+    pub fn frontal_height(&self) -> f64 {
+        match *self {
+            ObstacleShape::Circle { radius, .. } => 2.0 * radius,
+            ObstacleShape::Rectangle { height, .. } => height,
+            // The camber line's own vertical excursion is small next to the
+            // thickness envelope for any physically-sensible NACA digit, so
+            // max thickness alone is still the right reference height.
+            ObstacleShape::Airfoil { thickness, .. } => thickness,
+            ObstacleShape::Polygon { ref vertices } => {
+                let min_y = vertices.iter().fold(f64::INFINITY, |acc, &(_, y)| acc.min(y));
+                let max_y = vertices.iter().fold(f64::NEG_INFINITY, |acc, &(_, y)| acc.max(y));
+                max_y - min_y
+            }
+        }
+    }
+
+    /// Domain-coordinate center, used to locate the wake immediately
+    /// downstream of this shape (e.g. for `wake_trigger`'s perturbation).
+    pub fn center(&self) -> (f64, f64) {
+        match *self {
+            ObstacleShape::Circle { cx, cy, .. } => (cx, cy),
+            ObstacleShape::Rectangle { cx, cy, .. } => (cx, cy),
+            // `(cx, cy)` is the quarter-chord, not the true centroid, but
+            // it's a fine stand-in for "where downstream of this shape is
+            // the wake" — the same use `Circle`/`Rectangle`'s exact centers
+            // serve here.
+            ObstacleShape::Airfoil { cx, cy, .. } => (cx, cy),
+            ObstacleShape::Polygon { ref vertices } => {
+                let n = vertices.len() as f64;
+                let (sx, sy) = vertices.iter().fold((0.0, 0.0), |(sx, sy), &(x, y)| (sx + x, sy + y));
+                (sx / n, sy / n)
+            }
+        }
+    }
+
+    /// A copy of this shape shifted `dy` transverse to the freestream (i.e.
+    /// its `cy`), for a body free to oscillate vertically — see
+    /// `vortex_induced_body::VortexInducedBody`.
+    pub fn transverse_offset(&self, dy: f64) -> Self {
+        self.translated(0.0, dy)
+    }
+
+    /// A copy of this shape moved by `(dx, dy)` from its current position —
+    /// the general form of [`Self::transverse_offset`], for a body free to
+    /// move along both axes. See `moving_obstacle::MovingObstacle`.
+    pub fn translated(&self, dx: f64, dy: f64) -> Self {
+        match self.clone() {
+            ObstacleShape::Circle { cx, cy, radius } => ObstacleShape::Circle { cx: cx + dx, cy: cy + dy, radius },
+            ObstacleShape::Rectangle { cx, cy, width, height, angle } => {
+                ObstacleShape::Rectangle { cx: cx + dx, cy: cy + dy, width, height, angle }
+            }
+            ObstacleShape::Airfoil { cx, cy, chord, thickness, camber, camber_position, angle } => {
+                ObstacleShape::Airfoil { cx: cx + dx, cy: cy + dy, chord, thickness, camber, camber_position, angle }
+            }
+            ObstacleShape::Polygon { vertices } => ObstacleShape::Polygon {
+                vertices: vertices.into_iter().map(|(x, y)| (x + dx, y + dy)).collect(),
+            },
+        }
+    }
+
+    /// A copy of this shape rotated by `dtheta` radians about its own
+    /// center. A no-op for `Circle`, whose footprint has no orientation —
+    /// `moving_obstacle::MovingObstacle::surface_velocity` still gives a
+    /// rotating circle a tangential surface velocity even though its
+    /// footprint doesn't change.
+    pub fn rotated(&self, dtheta: f64) -> Self {
+        match self.clone() {
+            shape @ ObstacleShape::Circle { .. } => shape,
+            ObstacleShape::Rectangle { cx, cy, width, height, angle } => {
+                ObstacleShape::Rectangle { cx, cy, width, height, angle: angle + dtheta }
+            }
+            ObstacleShape::Airfoil { cx, cy, chord, thickness, camber, camber_position, angle } => {
+                ObstacleShape::Airfoil { cx, cy, chord, thickness, camber, camber_position, angle: angle + dtheta }
+            }
+            ObstacleShape::Polygon { vertices } => {
+                let (cx, cy) = {
+                    let n = vertices.len() as f64;
+                    let (sx, sy) = vertices.iter().fold((0.0, 0.0), |(sx, sy), &(x, y)| (sx + x, sy + y));
+                    (sx / n, sy / n)
+                };
+                let vertices = vertices
+                    .into_iter()
+                    .map(|(x, y)| {
+                        let dx = x - cx;
+                        let dy = y - cy;
+                        (
+                            cx + dx * dtheta.cos() - dy * dtheta.sin(),
+                            cy + dx * dtheta.sin() + dy * dtheta.cos(),
+                        )
+                    })
+                    .collect();
+                ObstacleShape::Polygon { vertices }
+            }
+        }
+    }
+}
+
+/// NACA 4-digit half-thickness distribution at chordwise station `xi_c`
+/// (`0..=1`, fraction of chord from the leading edge), for a section of max
+/// thickness `t` (also a fraction of chord). Returns a fraction of chord;
+/// multiply by chord for a domain-unit half-thickness. The classic closed
+/// trailing edge from the textbook coefficients gives a barely-open gap of
+/// about `0.002*t*chord`, immaterial at the mask resolutions this obstacle
+/// system runs at.
+fn naca4_half_thickness(t: f64, xi_c: f64) -> f64 {
+    let xi_c = xi_c.max(0.0);
+    5.0 * t
+        * (0.2969 * xi_c.sqrt() - 0.1260 * xi_c - 0.3516 * xi_c.powi(2) + 0.2843 * xi_c.powi(3) - 0.1015 * xi_c.powi(4))
+}
+
+/// NACA 4-digit mean camber line at chordwise station `xi_c` (`0..=1`), for
+/// max camber `m` (fraction of chord) located at `p` (fraction of chord from
+/// the leading edge). Returns a fraction of chord; multiply by chord for a
+/// domain-unit camber. `m <= 0.0` (a symmetric section) always gives `0.0`
+/// regardless of `p`, so callers don't need to special-case a symmetric
+/// section's otherwise-meaningless `camber_position`.
+fn naca4_camber(m: f64, p: f64, xi_c: f64) -> f64 {
+    if m <= 0.0 || p <= 0.0 || p >= 1.0 {
+        return 0.0;
+    }
+    if xi_c < p {
+        (m / p.powi(2)) * (2.0 * p * xi_c - xi_c.powi(2))
+    } else {
+        (m / (1.0 - p).powi(2)) * ((1.0 - 2.0 * p) + 2.0 * p * xi_c - xi_c.powi(2))
+    }
+}
+
+/// Even-odd point-in-polygon test in domain coordinates (`vertices` are
+/// already absolute, unlike `Rectangle`/`Airfoil`'s cx/cy-relative fields).
+fn polygon_contains(vertices: &[(f64, f64)], x: f64, y: f64) -> bool {
+    let n = vertices.len();
+    let mut inside = false;
+    let mut j = n - 1;
+    for i in 0..n {
+        let (xi, yi) = vertices[i];
+        let (xj, yj) = vertices[j];
+        if (yi > y) != (yj > y) {
+            let x_cross = xi + (y - yi) * (xj - xi) / (yj - yi);
+            if x < x_cross {
+                inside = !inside;
+            }
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Shortest distance from point `p` to the segment `a`-`b`.
+fn point_segment_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (px, py) = p;
+    let (ax, ay) = a;
+    let (bx, by) = b;
+    let (abx, aby) = (bx - ax, by - ay);
+    let len_sq = abx * abx + aby * aby;
+    let t = if len_sq <= 0.0 {
+        0.0
+    } else {
+        (((px - ax) * abx + (py - ay) * aby) / len_sq).clamp(0.0, 1.0)
+    };
+    let (cx, cy) = (ax + t * abx, ay + t * aby);
+    (px - cx).hypot(py - cy)
+}
+
+/// True if segments `a1`-`a2` and `b1`-`b2` intersect, including the
+/// collinear-overlap case (used to reject a self-intersecting polygon in
+/// [`ObstacleShape::new_polygon`]; shared endpoints between adjacent edges
+/// are filtered out by the caller before this is invoked).
+fn segments_intersect(a1: (f64, f64), a2: (f64, f64), b1: (f64, f64), b2: (f64, f64)) -> bool {
+    fn cross(o: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+        (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+    }
+    fn on_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> bool {
+        p.0 >= a.0.min(b.0) - 1e-9
+            && p.0 <= a.0.max(b.0) + 1e-9
+            && p.1 >= a.1.min(b.1) - 1e-9
+            && p.1 <= a.1.max(b.1) + 1e-9
+            && cross(a, b, p).abs() < 1e-9
+    }
+
+    let d1 = cross(b1, b2, a1);
+    let d2 = cross(b1, b2, a2);
+    let d3 = cross(a1, a2, b1);
+    let d4 = cross(a1, a2, b2);
+
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        return true;
+    }
+
+    (d1.abs() < 1e-12 && on_segment(a1, b1, b2))
+        || (d2.abs() < 1e-12 && on_segment(a2, b1, b2))
+        || (d3.abs() < 1e-12 && on_segment(b1, a1, a2))
+        || (d4.abs() < 1e-12 && on_segment(b2, a1, a2))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RadiatorConfig {
+    /// Identifies this radiator in per-radiator metrics/overlays. Defaults
+    /// to `radiator_<index>` if left unset (see `SceneConfig::radiator_id`).
+    #[serde(default)]
+    pub name: Option<String>,
+    pub center_x: f64,
+    pub center_y: f64,
+    pub width: f64,
+    pub height: f64,
+    #[serde(default)]
+    pub angle: f64,
+    pub porosity: f64,
+    /// Thermal model for this radiator. Absent by default, same as
+    /// [`Radiator::heat_exchanger`] itself.
+    #[serde(default)]
+    pub heat_exchanger: Option<HeatExchanger>,
+}
+
+impl From<&RadiatorConfig> for Radiator {
+    fn from(c: &RadiatorConfig) -> Self {
+        let radiator = Radiator::new(c.center_x, c.center_y, c.width, c.height, c.angle, c.porosity);
+        match c.heat_exchanger {
+            Some(hx) => radiator.with_heat_exchanger(hx),
+            None => radiator,
+        }
+    }
+}
+
+impl RadiatorConfig {
+    /// The reverse of `From<&RadiatorConfig> for Radiator`, for snapshotting
+    /// a live [`Radiator`] back into config form — see
+    /// [`crate::scene::Scene::export_setup`]. `name` is the id it was
+    /// tagged with (`ObstacleManager::radiator_ids`), so a reloaded scene
+    /// keeps the same metrics/overlay id rather than falling back to
+    /// `radiator_<index>`.
+    pub fn from_radiator(radiator: &Radiator, name: String) -> Self {
+        RadiatorConfig {
+            name: Some(name),
+            center_x: radiator.center_x,
+            center_y: radiator.center_y,
+            width: radiator.width,
+            height: radiator.height,
+            angle: radiator.angle,
+            porosity: radiator.porosity,
+            heat_exchanger: radiator.heat_exchanger,
+        }
+    }
+}
+
+/// Config for `wake_trigger::WakeTrigger`: a one-shot antisymmetric
+/// perturbation to break a perfectly symmetric wake that hasn't started
+/// shedding on its own by `after_step`. Absent by default (off).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WakeTriggerConfig {
+    pub after_step: usize,
+    pub lift_threshold: f64,
+    pub seed: u64,
+}
+
+/// Config for `vortex_induced_body::VortexInducedBody`: lets one obstacle
+/// respond to the lift force it experiences instead of staying fixed.
+/// Absent by default (off).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VortexBodyConfig {
+    /// Index into `obstacles` of the shape this body owns.
+    pub obstacle_index: usize,
+    /// `m* = mass / (fluid_density * displaced_area)`, the standard
+    /// non-dimensional VIV mass ratio.
+    pub mass_ratio: f64,
+    pub natural_frequency_hz: f64,
+    pub damping_ratio: f64,
+}
+
+/// Config for one `moving_obstacle::MovingObstacle`: which `obstacles`
+/// entry it owns and how it's prescribed to move. Unlike `VortexBodyConfig`,
+/// several of these can be active at once (one per moving obstacle), so
+/// `SceneConfig` holds a `Vec` rather than a single optional slot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MovingObstacleConfig {
+    /// Index into `obstacles` of the shape this motion owns.
+    pub obstacle_index: usize,
+    pub motion: crate::moving_obstacle::Motion,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SceneConfig {
+    pub num_x: usize,
+    pub num_y: usize,
+    pub dt: f64,
+    pub num_iters: usize,
+    #[serde(default)]
+    pub over_relaxation: f64,
+    /// `Fluid::solve_incompressibility`'s solve method — plain Gauss-Seidel
+    /// by default. See [`PressureSolver`].
+    #[serde(default)]
+    pub pressure_solver: PressureSolver,
+    #[serde(default)]
+    pub gravity: f64,
+    pub inflow_velocity: f64,
+    /// How `inflow_velocity` is distributed across the inlet column's rows
+    /// — flat by default, or a boundary-layer/measured shape. See
+    /// [`crate::inflow_profile::InflowProfile`].
+    #[serde(default)]
+    pub inflow_profile: crate::inflow_profile::InflowProfile,
+    /// Free-stream direction in radians from `+x`, resolving
+    /// `inflow_velocity` into `Fluid`'s `u`/`v` components. `0.0` (default)
+    /// is horizontal inflow, this field's behavior before it existed. See
+    /// [`crate::scene::Scene::apply_inflow`].
+    #[serde(default)]
+    pub inflow_angle: f64,
+    /// Simulated seconds over which the inflow ramps linearly from 0 up to
+    /// `inflow_velocity`. `0.0` (default) is instant-on, this field's
+    /// behavior before it existed. See
+    /// [`crate::scene::Scene::inflow_ramp_time`].
+    #[serde(default)]
+    pub inflow_ramp_time: f64,
+    #[serde(default)]
+    pub obstacles: Vec<ObstacleShape>,
+    #[serde(default)]
+    pub radiators: Vec<RadiatorConfig>,
+    #[serde(default)]
+    pub wake_trigger: Option<WakeTriggerConfig>,
+    #[serde(default)]
+    pub vortex_body: Option<VortexBodyConfig>,
+    /// Obstacles with a prescribed motion (translation, rotation, or
+    /// sinusoidal oscillation) instead of staying fixed. See
+    /// `moving_obstacle::MovingObstacle`.
+    #[serde(default)]
+    pub moving_obstacles: Vec<MovingObstacleConfig>,
+    /// Project-then-advect (default) vs advect-then-project. See
+    /// [`StepOrdering`] for the tradeoff.
+    #[serde(default)]
+    pub step_ordering: StepOrdering,
+    /// `Fluid::top_bottom_boundary` — `NoSlip` (solid walls, every existing
+    /// scene's behavior) by default. Set to `Periodic` to study an isolated
+    /// obstacle without wall interference; see [`BoundaryCondition`] for
+    /// exactly what that does and doesn't wire up.
+    #[serde(default = "default_top_bottom_boundary")]
+    pub top_bottom_boundary: BoundaryCondition,
+    /// `Fluid::smoke_decay` — `0.0` (dye never fades) by default, matching
+    /// this solver's original behavior.
+    #[serde(default)]
+    pub smoke_decay: f64,
+    /// Pattern `Scene::apply_inflow` writes into the inlet column's dye
+    /// every step. See [`InflowSmokePattern`]; defaults to `Striped` so
+    /// streaklines are visible without extra config.
+    #[serde(default)]
+    pub inflow_smoke_pattern: InflowSmokePattern,
+    /// Extra dye sources applied every step. See [`crate::dye_emitter`].
+    #[serde(default)]
+    pub dye_emitters: Vec<DyeEmitter>,
+    /// Scripted paint strokes, each firing once at its own `at_time`. See
+    /// [`crate::paint::PaintEvent`]; this is how a headless run reproduces
+    /// what a live-viewer session would otherwise only ever draw
+    /// interactively with the mouse (`crate::scene::Scene::paint_smoke` and
+    /// friends).
+    #[serde(default)]
+    pub paint_events: Vec<crate::paint::PaintEvent>,
+    /// 1D field profiles written as CSV once the run finishes. See
+    /// [`LineProfileConfig`].
+    #[serde(default)]
+    pub line_profiles: Vec<LineProfileConfig>,
+    /// Off by default: an algebraic turbulence closure applied every step.
+    /// See [`crate::turbulence`].
+    #[serde(default)]
+    pub turbulence_model: Option<crate::turbulence::TurbulenceModel>,
+    /// Sets `Fluid::density`/`Fluid::kinematic_viscosity`. Defaults to
+    /// [`crate::working_fluid::WorkingFluid::default`] (water at 4C), the
+    /// density every scene hard-coded before this field existed.
+    #[serde(default)]
+    pub working_fluid: Option<crate::working_fluid::WorkingFluid>,
+    /// Off by default: mark `obstacles` with fractional per-cell solid
+    /// coverage (supersampled) instead of a binary inside/outside test at
+    /// each cell's reference point. See
+    /// [`crate::scene::mark_obstacle_solid_cut_cell`]; reduces grid-resolution
+    /// dependence for curved or angled obstacles at the cost of a slightly
+    /// more expensive setup pass.
+    #[serde(default)]
+    pub cut_cell: bool,
+}
+
+/// A named 1D field profile to extract and write as CSV at the end of a
+/// run, via [`crate::fluid::Fluid::extract_line`]. `name` becomes the
+/// output file's stem: `name = "centerline"` writes `centerline.csv`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineProfileConfig {
+    pub name: String,
+    pub start: (f64, f64),
+    pub end: (f64, f64),
+    pub samples: usize,
+    pub field: FieldType,
+}
+
+fn default_top_bottom_boundary() -> BoundaryCondition {
+    BoundaryCondition::NoSlip
+}
+
+impl SceneConfig {
+    /// The id a radiator is tagged with in metrics/overlays: its configured
+    /// `name`, or `radiator_<index>` if unset.
+    pub fn radiator_id(&self, index: usize) -> String {
+        self.radiators[index]
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("radiator_{index}"))
+    }
+
+    pub fn from_toml_str(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    pub fn from_file(path: &str) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        SceneConfig::from_toml_str(&text).map_err(std::io::Error::other)
+    }
+
+    /// Loads a `SceneConfig` written as JSON — the format
+    /// [`crate::scene::Scene::export_setup`] snapshots into, as opposed to
+    /// this crate's usual hand-written TOML scenes.
+    pub fn from_json_file(path: &str) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        serde_json::from_str(&text).map_err(std::io::Error::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_config_reproducing_scene_4_parses() {
+        let text = std::fs::read_to_string("examples/scene4.toml").unwrap();
+        let config = SceneConfig::from_toml_str(&text).unwrap();
+        assert_eq!(config.num_x, 200);
+        assert_eq!(config.num_y, 80);
+        assert_eq!(config.radiators.len(), 1);
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected_with_a_clear_error() {
+        let text = r#"
+            num_x = 10
+            num_y = 10
+            dt = 0.01
+            num_iters = 10
+            inflow_velocity = 1.0
+            bogus_field = 1
+        "#;
+        let err = SceneConfig::from_toml_str(text).unwrap_err();
+        assert!(err.to_string().contains("bogus_field"));
+    }
+
+    #[test]
+    fn example_config_dropping_a_naca_2412_into_the_wind_tunnel_parses() {
+        let text = std::fs::read_to_string("examples/naca2412.toml").unwrap();
+        let config = SceneConfig::from_toml_str(&text).unwrap();
+        assert_eq!(config.obstacles.len(), 1);
+        match config.obstacles[0] {
+            ObstacleShape::Airfoil { chord, thickness, camber, camber_position, .. } => {
+                assert_eq!(chord, 0.25);
+                assert_eq!(thickness, 0.03);
+                assert_eq!(camber, 0.005);
+                assert_eq!(camber_position, 0.4);
+            }
+            ref other => panic!("expected an Airfoil obstacle, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn polygon_contains_matches_an_analytically_counted_right_triangle() {
+        // Right triangle with legs 0.4 along x and y from (0.1, 0.1): area
+        // 0.08, so on a grid with h = 0.01 (cell area 1e-4) about 800 cell
+        // centers should fall strictly inside — check the actual count
+        // against that estimate rather than an exact figure, since which
+        // boundary cells round in/out depends on the discretization.
+        let shape = ObstacleShape::new_polygon(vec![(0.1, 0.1), (0.5, 0.1), (0.1, 0.5)]).unwrap();
+        let h = 0.01;
+        let n = 100;
+        let mut count = 0;
+        for i in 0..n {
+            for j in 0..n {
+                if shape.contains(i as f64 * h, j as f64 * h) {
+                    count += 1;
+                }
+            }
+        }
+        let expected = 800;
+        assert!(
+            (count as f64 - expected as f64).abs() / (expected as f64) < 0.05,
+            "expected roughly {expected} interior samples, got {count}"
+        );
+    }
+
+    #[test]
+    fn new_polygon_rejects_too_few_vertices_and_self_intersecting_outlines() {
+        assert!(ObstacleShape::new_polygon(vec![(0.0, 0.0), (1.0, 0.0)]).is_err());
+
+        // Bowtie: the two "diagonal" edges cross in the middle.
+        let bowtie = vec![(0.0, 0.0), (1.0, 1.0), (1.0, 0.0), (0.0, 1.0)];
+        assert!(ObstacleShape::new_polygon(bowtie).is_err());
+
+        let square = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        assert!(ObstacleShape::new_polygon(square).is_ok());
+    }
+}
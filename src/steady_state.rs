@@ -0,0 +1,126 @@
+//! Generic "stop early once a tracked scalar has settled" detector.
+//!
+//! [`crate::sweep`]'s `restart_from_steady` mode is the current call site:
+//! it runs the empty-tunnel base flow, sampling the pressure residual every
+//! step and feeding it to [`SteadyStateDetector::record`] so the one-time
+//! warm-up stops as soon as the flow has settled rather than always running
+//! to `max_steps`. It's the same kind of reusable infrastructure as
+//! [`crate::convergence::ConvergenceMonitor`]: any other "run until this
+//! stops changing, but no more than N steps" loop can reuse it the same
+//! way, sampling a different scalar — e.g.
+//! [`crate::metrics::RadiatorMetrics::mass_flow`].
+
+/// Tracks relative change of a scalar over a sliding window and reports
+/// whether it has settled, subject to a hard step cap.
+pub struct SteadyStateDetector {
+    tolerance: f64,
+    window: usize,
+    max_steps: u64,
+    history: Vec<f64>,
+    steps_run: u64,
+    converged: bool,
+}
+
+impl SteadyStateDetector {
+    pub fn new(tolerance: f64, window: usize, max_steps: u64) -> Self {
+        SteadyStateDetector {
+            tolerance,
+            window,
+            max_steps,
+            history: Vec::new(),
+            steps_run: 0,
+            converged: false,
+        }
+    }
+
+    /// Record one sample of the tracked scalar at `step`. Returns `true`
+    /// once the loop should stop: either the relative change of `value`
+    /// across the last `window` samples has dropped to or below
+    /// `tolerance`, or `step` has reached `max_steps`. Once this returns
+    /// `true`, [`Self::steps_run`] and [`Self::converged`] report why.
+    pub fn record(&mut self, step: u64, value: f64) -> bool {
+        self.steps_run = step;
+        self.history.push(value);
+
+        if self.window > 0 && self.history.len() > self.window {
+            let baseline = self.history[self.history.len() - self.window - 1];
+            let recent = &self.history[self.history.len() - self.window..];
+            let max_relative_change = recent
+                .iter()
+                .map(|v| {
+                    if baseline.abs() > 1e-12 {
+                        (v - baseline).abs() / baseline.abs()
+                    } else {
+                        (v - baseline).abs()
+                    }
+                })
+                .fold(0.0, f64::max);
+            if max_relative_change <= self.tolerance {
+                self.converged = true;
+                return true;
+            }
+        }
+
+        if step >= self.max_steps {
+            self.converged = false;
+            return true;
+        }
+
+        false
+    }
+
+    /// The step index passed to the `record` call that ended the run.
+    pub fn steps_run(&self) -> u64 {
+        self.steps_run
+    }
+
+    /// Whether the run stopped because the scalar settled (`true`) or
+    /// because `max_steps` was hit first (`false`).
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stops_once_the_window_settles_below_tolerance() {
+        let mut detector = SteadyStateDetector::new(0.01, 3, 1000);
+        let values = [1.0, 1.5, 1.9, 2.0, 2.0, 2.0, 2.0];
+        let mut stopped_at = None;
+        for (i, &v) in values.iter().enumerate() {
+            if detector.record(i as u64, v) {
+                stopped_at = Some(i as u64);
+                break;
+            }
+        }
+        assert_eq!(stopped_at, Some(6));
+        assert!(detector.converged());
+        assert_eq!(detector.steps_run(), 6);
+    }
+
+    #[test]
+    fn hits_the_hard_cap_when_the_scalar_never_settles() {
+        let mut detector = SteadyStateDetector::new(1e-9, 2, 5);
+        let mut stopped_at = None;
+        for step in 0..20u64 {
+            if detector.record(step, step as f64) {
+                stopped_at = Some(step);
+                break;
+            }
+        }
+        assert_eq!(stopped_at, Some(5));
+        assert!(!detector.converged());
+    }
+
+    #[test]
+    fn zero_window_never_reports_settled_before_the_cap() {
+        let mut detector = SteadyStateDetector::new(0.0, 0, 3);
+        assert!(!detector.record(0, 1.0));
+        assert!(!detector.record(1, 1.0));
+        assert!(detector.record(3, 1.0));
+        assert!(!detector.converged());
+    }
+}
@@ -0,0 +1,125 @@
+//! CSV/GeoJSON export of traced streamline polylines, so report figures that
+//! get rebuilt in matplotlib/Illustrator don't have to re-trace streamlines
+//! there — each exported feature carries the seed point, per-vertex speed,
+//! and why the trace stopped.
+
+use crate::visualizer::{Streamline, TerminationReason};
+
+fn termination_label(reason: TerminationReason) -> &'static str {
+    match reason {
+        TerminationReason::LeftDomain => "left_domain",
+        TerminationReason::Stagnated => "stagnated",
+        TerminationReason::MaxSteps => "max_steps",
+        TerminationReason::MaxArcLength => "max_arc_length",
+        TerminationReason::HitObstacle => "hit_obstacle",
+    }
+}
+
+/// One row per vertex: `streamline_index,seed_x,seed_y,vertex_index,x,y,speed,termination`.
+pub fn write_csv(streamlines: &[Streamline], path: &str) -> std::io::Result<()> {
+    let mut csv = String::from("streamline_index,seed_x,seed_y,vertex_index,x,y,speed,termination\n");
+    for (index, line) in streamlines.iter().enumerate() {
+        let termination = termination_label(line.termination);
+        for (vertex_index, vertex) in line.vertices.iter().enumerate() {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                index, line.seed.0, line.seed.1, vertex_index, vertex.x, vertex.y, vertex.speed, termination
+            ));
+        }
+    }
+    std::fs::write(path, csv)
+}
+
+/// One GeoJSON `LineString` feature per streamline. GeoJSON has no native way
+/// to attach a scalar to each vertex of a `LineString`, so per-vertex speed
+/// rides along as a parallel `speeds` array in `properties`.
+pub fn write_geojson(streamlines: &[Streamline], path: &str) -> std::io::Result<()> {
+    let features: Vec<serde_json::Value> = streamlines
+        .iter()
+        .map(|line| {
+            let coordinates: Vec<[f64; 2]> = line.vertices.iter().map(|v| [v.x, v.y]).collect();
+            let speeds: Vec<f64> = line.vertices.iter().map(|v| v.speed).collect();
+            serde_json::json!({
+                "type": "Feature",
+                "geometry": { "type": "LineString", "coordinates": coordinates },
+                "properties": {
+                    "seed": [line.seed.0, line.seed.1],
+                    "speeds": speeds,
+                    "termination": termination_label(line.termination),
+                },
+            })
+        })
+        .collect();
+
+    let geojson = serde_json::json!({ "type": "FeatureCollection", "features": features });
+    let text = serde_json::to_string_pretty(&geojson)?;
+    std::fs::write(path, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluid::Fluid;
+    use crate::visualizer::trace_streamlines;
+
+    fn sample_fluid() -> Fluid {
+        let num_x = 40;
+        let num_y = 20;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        let n = fluid.num_y;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                let idx = i * n + j;
+                let is_boundary = j == 0 || j == fluid.num_y - 1;
+                fluid.s[idx] = if is_boundary { 0.0 } else { 1.0 };
+                fluid.u[idx] = 1.0;
+            }
+        }
+        fluid
+    }
+
+    #[test]
+    fn csv_and_geojson_vertex_counts_match_the_traced_lines() {
+        let fluid = sample_fluid();
+        let lines = trace_streamlines(&fluid, 4);
+        let total_vertices: usize = lines.iter().map(|l| l.vertices.len()).sum();
+
+        let dir = std::env::temp_dir().join("lgr_2d_cfd_streamline_export_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let csv_path = dir.join("streamlines.csv");
+        let geojson_path = dir.join("streamlines.geojson");
+        write_csv(&lines, csv_path.to_str().unwrap()).unwrap();
+        write_geojson(&lines, geojson_path.to_str().unwrap()).unwrap();
+
+        let csv_text = std::fs::read_to_string(&csv_path).unwrap();
+        let csv_rows = csv_text.lines().count() - 1; // minus header
+        assert_eq!(csv_rows, total_vertices);
+
+        let geojson_text = std::fs::read_to_string(&geojson_path).unwrap();
+        let geojson: serde_json::Value = serde_json::from_str(&geojson_text).unwrap();
+        let features = geojson["features"].as_array().unwrap();
+        assert_eq!(features.len(), lines.len());
+        let geojson_vertices: usize = features
+            .iter()
+            .map(|f| f["geometry"]["coordinates"].as_array().unwrap().len())
+            .sum();
+        assert_eq!(geojson_vertices, total_vertices);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn every_vertex_stays_within_the_domain() {
+        let fluid = sample_fluid();
+        let lines = trace_streamlines(&fluid, 6);
+        let max_x = fluid.num_x as f64 * fluid.h;
+        let max_y = fluid.num_y as f64 * fluid.h;
+        for line in &lines {
+            for vertex in &line.vertices {
+                assert!(vertex.x >= 0.0 && vertex.x <= max_x);
+                assert!(vertex.y >= 0.0 && vertex.y <= max_y);
+            }
+        }
+    }
+}
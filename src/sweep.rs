@@ -0,0 +1,556 @@
+//! Config-driven radiator parameter sweep: run one [`crate::scene::Scene`]
+//! per point in a 1D or 2D grid of parameter values, tagging every result
+//! with the swept parameter name(s) and value(s) so downstream plotting
+//! doesn't have to guess which axis produced which row.
+//!
+//! There is no `run_radiator_angle_sweep` in this tree for this to
+//! generalize (`parallel_runs` and `examples/parameter_sweep.rs` already
+//! note this) — [`SweepConfig`]/[`run_sweep`] is a new, TOML-driven
+//! replacement for that hypothetical hardcoded sweep, not a refactor of an
+//! existing one.
+
+use serde::{Deserialize, Serialize};
+
+use crate::dye_emitter::InflowSmokePattern;
+use crate::fluid::{BoundaryCondition, Fluid, StepOrdering};
+use crate::metrics::RadiatorMetrics;
+use crate::output::{OutputKind, OutputSelection};
+use crate::parallel_runs::run_batch;
+use crate::radiator::Radiator;
+use crate::scene::Scene;
+use crate::scene_config::{RadiatorConfig, SceneConfig};
+use crate::steady_state::SteadyStateDetector;
+use crate::visualizer::{ColorScale, Visualizer};
+
+/// Which of a radiator's parameters a [`SweepAxis`] varies. `Resistance`
+/// aliases `Porosity` — this crate's only resistance knob is
+/// `Radiator::porosity` (there's no independent Darcy-Forchheimer
+/// coefficient a caller can set directly, see `Radiator::resistance_coefficient`,
+/// which is derived from `porosity` and private). `Position` moves the
+/// radiator along the streamwise (`center_x`) axis, since that's the
+/// position sweep most studies actually want (finding the duct depth that
+/// maximizes flow capture), not `center_y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SweepParameter {
+    Angle,
+    InflowVelocity,
+    Porosity,
+    Resistance,
+    Position,
+    /// Free-stream direction, in degrees, passed through to
+    /// [`crate::scene::Scene::inflow_angle`] (converted to radians in
+    /// [`run_case`]) — separate from `Angle`, which sweeps the radiator's
+    /// own tilt, not the inflow direction.
+    InflowAngle,
+}
+
+impl SweepParameter {
+    /// The name this parameter is recorded under in a [`SweepCaseResult`].
+    fn field_name(self) -> &'static str {
+        match self {
+            SweepParameter::Angle => "angle",
+            SweepParameter::InflowVelocity => "inflow_velocity",
+            SweepParameter::Porosity => "porosity",
+            SweepParameter::Resistance => "resistance",
+            SweepParameter::Position => "position",
+            SweepParameter::InflowAngle => "inflow_angle",
+        }
+    }
+}
+
+/// One axis of the sweep grid: which parameter, and which values to try.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepAxis {
+    pub parameter: SweepParameter,
+    pub values: Vec<f64>,
+}
+
+/// A radiator-sweep study: base scene/radiator parameters plus one or two
+/// [`SweepAxis`]. One axis sweeps a line of cases; two produce every
+/// combination (a grid) — [`SweepReport::results`] is always flat, one
+/// entry per case, tagged with the axis value(s) that produced it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SweepConfig {
+    pub num_x: usize,
+    pub num_y: usize,
+    #[serde(default = "default_dt")]
+    pub dt: f64,
+    #[serde(default = "default_num_iters")]
+    pub num_iters: usize,
+    #[serde(default = "default_over_relaxation")]
+    pub over_relaxation: f64,
+    /// Base inflow velocity, overridden per case by an `inflow_velocity` axis.
+    pub inflow_velocity: f64,
+    /// Simulated seconds over which every case's inflow ramps linearly from
+    /// 0 up to its `inflow_velocity`, applied identically across the whole
+    /// grid (there's no per-axis override) so each case's start-up
+    /// transient is comparable rather than an `InflowVelocity` or
+    /// `InflowAngle` axis changing how abruptly the flow comes up to speed.
+    /// `0.0` (default) is instant-on, matching every sweep before this
+    /// field existed. See [`crate::scene::Scene::inflow_ramp_time`].
+    #[serde(default)]
+    pub inflow_ramp_time: f64,
+    /// Base radiator geometry/porosity, overridden per case by whichever
+    /// axes name a field it has.
+    pub radiator: RadiatorConfig,
+    /// Steps to run each case for before sampling `RadiatorMetrics`.
+    pub steps: u64,
+    /// One axis for a 1D sweep, two for a 2D grid. More than two axes
+    /// still works (the Cartesian product of however many are given) but
+    /// isn't the "angle x inflow" 2D case this was built for.
+    pub axes: Vec<SweepAxis>,
+    /// Write a pressure/smoke/streamlines PNG per case (into
+    /// `<output_dir>/snapshots`) for `report::write_sweep_report`'s
+    /// thumbnail grid. Off by default, since most sweeps only care about
+    /// `results.json`'s numbers and rendering every case triples a sweep's
+    /// run time for images nobody asked for.
+    #[serde(default)]
+    pub save_snapshots: bool,
+    /// Comma-separated subset of `smoke,pressure,streamlines` (see
+    /// [`crate::output::OutputSelection`]) restricting which of
+    /// `save_snapshots`'s three PNGs get written per case. `None` (the
+    /// default) writes all three, same as every sweep before this field
+    /// existed; has no effect when `save_snapshots` is off.
+    #[serde(default)]
+    pub outputs: Option<String>,
+    /// Run the empty tunnel (no radiator) to steady state once, then start
+    /// every case from a clone of that converged [`Fluid`] with its own
+    /// radiator dropped in, instead of every case redeveloping the same
+    /// inflow transient from a standing start. Safe to turn on for any
+    /// `axes` combination that only varies `Angle`/`Porosity`/`Resistance`/
+    /// `Position`, since none of those change the empty-tunnel flow the
+    /// warm-up converged to; an `InflowVelocity` or `InflowAngle` axis
+    /// still benefits (the radiator's own transient is still shortened) but
+    /// each such case then adjusts away from a base flow at the wrong
+    /// speed/direction, so `run_sweep` logs a warning rather than silently
+    /// changing what a case measures. Off by default so an existing sweep's
+    /// results are unaffected by upgrading to this field.
+    #[serde(default)]
+    pub restart_from_steady: bool,
+    /// [`SteadyStateDetector`] tuning for the `restart_from_steady` warm-up,
+    /// tracking [`crate::scene::Scene::pressure_residual`]. Ignored when
+    /// `restart_from_steady` is off.
+    #[serde(default = "default_steady_state_tolerance")]
+    pub steady_state_tolerance: f64,
+    #[serde(default = "default_steady_state_window")]
+    pub steady_state_window: usize,
+    #[serde(default = "default_steady_state_max_steps")]
+    pub steady_state_max_steps: u64,
+}
+
+fn default_dt() -> f64 {
+    1.0 / 60.0
+}
+
+fn default_num_iters() -> usize {
+    40
+}
+
+fn default_over_relaxation() -> f64 {
+    1.9
+}
+
+fn default_steady_state_tolerance() -> f64 {
+    1e-3
+}
+
+fn default_steady_state_window() -> usize {
+    10
+}
+
+fn default_steady_state_max_steps() -> u64 {
+    2000
+}
+
+/// Points sampled along the radiator face for `RadiatorMetrics::flow_uniformity_index`
+/// and the per-case face-velocity chart. Not a `SweepConfig` field — there's
+/// no evidence a caller would ever want a coarser or finer profile than
+/// this, and every other sampled-metric knob in this module (e.g. wake
+/// survey samples) is likewise a fixed constant rather than exposed.
+const FLOW_UNIFORMITY_SAMPLES: usize = 20;
+
+impl SweepConfig {
+    pub fn from_toml_str(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    pub fn from_file(path: &str) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        SweepConfig::from_toml_str(&text).map_err(std::io::Error::other)
+    }
+}
+
+/// Field-image paths for one sweep case, written only when
+/// [`SweepConfig::save_snapshots`] is set. `report::write_sweep_report`
+/// embeds these as thumbnails; a case run without snapshots just has none
+/// to embed rather than the report failing to build. Each field is
+/// individually `None` when [`SweepConfig::outputs`] excludes that kind.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseSnapshots {
+    pub pressure: Option<String>,
+    pub smoke: Option<String>,
+    pub streamlines: Option<String>,
+    /// A small [`crate::report::plot_face_velocity_profile`] chart of this
+    /// case's radiator-face velocity profile. Always written alongside the
+    /// field snapshots above when [`SweepConfig::save_snapshots`] is set —
+    /// unlike them, it isn't gated by [`SweepConfig::outputs`], since it's
+    /// not a rendered field image and costs almost nothing next to a full
+    /// simulation run.
+    pub uniformity_profile: Option<String>,
+}
+
+/// One case's swept parameter(s), value(s), and resulting metrics —
+/// `parameters` has one entry per [`SweepConfig::axes`] entry, e.g.
+/// `[("angle", 0.1)]` for a 1D sweep or `[("angle", 0.1), ("inflow_velocity",
+/// 3.0)]` for one point of a 2D grid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepCaseResult {
+    pub parameters: Vec<(String, f64)>,
+    pub metrics: RadiatorMetrics,
+    #[serde(default)]
+    pub snapshots: Option<CaseSnapshots>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepReport {
+    pub results: Vec<SweepCaseResult>,
+    /// Steps the shared empty-tunnel warm-up ran for before every case
+    /// started from a clone of it, or `None` when
+    /// [`SweepConfig::restart_from_steady`] was off and every case instead
+    /// redeveloped its own inflow transient from a standing start.
+    #[serde(default)]
+    pub warm_up_steps: Option<u64>,
+}
+
+/// Run every case in `config`'s grid (the Cartesian product of its axes)
+/// across up to `jobs` threads, returning one [`SweepCaseResult`] per case
+/// in row-major axis order (the last axis varies fastest). Case snapshots,
+/// if `config.save_snapshots` is set, are written under
+/// `<output_dir>/snapshots`.
+pub fn run_sweep(config: &SweepConfig, jobs: usize, output_dir: &str) -> SweepReport {
+    let outputs = match &config.outputs {
+        Some(list) => OutputSelection::parse(list).unwrap_or_else(|err| {
+            eprintln!("warning: {err}, falling back to every snapshot kind");
+            OutputSelection::default()
+        }),
+        None => OutputSelection::default(),
+    };
+
+    let base_fluid = config.restart_from_steady.then(|| warm_up_to_steady_state(config));
+    if base_fluid.is_some() && config.axes.iter().any(|axis| matches!(axis.parameter, SweepParameter::InflowVelocity | SweepParameter::InflowAngle)) {
+        eprintln!(
+            "warning: restart_from_steady is on with an InflowVelocity or InflowAngle axis; \
+             every case still starts from the base inflow's converged flow, so cases at a \
+             different inflow will need more steps to settle than a from-scratch sweep would"
+        );
+    }
+    let warm_up_steps = base_fluid.as_ref().map(|(_, steps)| *steps);
+    let base_fluid = base_fluid.map(|(fluid, _)| fluid);
+
+    let cases: Vec<(usize, Vec<(SweepParameter, f64)>)> = axis_combinations(&config.axes).into_iter().enumerate().collect();
+    let results = run_batch(cases, jobs, |(index, case)| run_case(config, &outputs, output_dir, index, &case, base_fluid.as_ref()));
+    SweepReport { results, warm_up_steps }
+}
+
+/// Run the empty tunnel (no radiator, `config`'s base inflow) to steady
+/// state, returning the converged [`Fluid`] and the step count it took —
+/// the latter purely for [`SweepReport::warm_up_steps`], since the whole
+/// point of `restart_from_steady` is that a one-time warm-up this long
+/// replaces redeveloping the same flow on every case.
+fn warm_up_to_steady_state(config: &SweepConfig) -> (Fluid, u64) {
+    let scene_config = case_scene_config(config, config.inflow_velocity, 0.0, vec![]);
+    let mut scene = Scene::setup_from_config(&scene_config);
+    let mut detector = SteadyStateDetector::new(config.steady_state_tolerance, config.steady_state_window, config.steady_state_max_steps);
+    let mut step = 0u64;
+    loop {
+        scene.simulate();
+        step += 1;
+        if detector.record(step, scene.pressure_residual()) {
+            break;
+        }
+    }
+    (scene.fluid, detector.steps_run())
+}
+
+fn axis_combinations(axes: &[SweepAxis]) -> Vec<Vec<(SweepParameter, f64)>> {
+    axes.iter().fold(vec![Vec::new()], |combinations, axis| {
+        combinations
+            .into_iter()
+            .flat_map(|prefix| {
+                axis.values.iter().map(move |&value| {
+                    let mut next = prefix.clone();
+                    next.push((axis.parameter, value));
+                    next
+                })
+            })
+            .collect()
+    })
+}
+
+/// The [`SceneConfig`] every sweep case (and the `restart_from_steady`
+/// warm-up) builds from, varying only inflow and the radiator list —
+/// everything else is fixed by `config` and this module's own defaults.
+fn case_scene_config(config: &SweepConfig, inflow_velocity: f64, inflow_angle_deg: f64, radiators: Vec<RadiatorConfig>) -> SceneConfig {
+    SceneConfig {
+        num_x: config.num_x,
+        num_y: config.num_y,
+        dt: config.dt,
+        num_iters: config.num_iters,
+        over_relaxation: config.over_relaxation,
+        pressure_solver: Default::default(),
+        gravity: 0.0,
+        inflow_velocity,
+        inflow_profile: crate::inflow_profile::InflowProfile::default(),
+        inflow_angle: inflow_angle_deg.to_radians(),
+        inflow_ramp_time: config.inflow_ramp_time,
+        obstacles: vec![],
+        radiators,
+        wake_trigger: None,
+        vortex_body: None,
+        moving_obstacles: vec![],
+        step_ordering: StepOrdering::default(),
+        top_bottom_boundary: BoundaryCondition::NoSlip,
+        smoke_decay: 0.0,
+        inflow_smoke_pattern: InflowSmokePattern::default(),
+        dye_emitters: vec![],
+        paint_events: vec![],
+        line_profiles: vec![],
+        turbulence_model: None,
+        working_fluid: None,
+        cut_cell: false,
+    }
+}
+
+fn run_case(config: &SweepConfig, outputs: &OutputSelection, output_dir: &str, case_index: usize, case: &[(SweepParameter, f64)], base_fluid: Option<&Fluid>) -> SweepCaseResult {
+    let mut radiator_config = config.radiator.clone();
+    let mut inflow_velocity = config.inflow_velocity;
+    let mut inflow_angle_deg = 0.0;
+
+    for &(parameter, value) in case {
+        match parameter {
+            SweepParameter::Angle => radiator_config.angle = value,
+            SweepParameter::InflowVelocity => inflow_velocity = value,
+            SweepParameter::Porosity | SweepParameter::Resistance => radiator_config.porosity = value,
+            SweepParameter::Position => radiator_config.center_x = value,
+            SweepParameter::InflowAngle => inflow_angle_deg = value,
+        }
+    }
+
+    let scene_config = case_scene_config(config, inflow_velocity, inflow_angle_deg, vec![radiator_config]);
+    let mut scene = Scene::setup_from_config(&scene_config);
+    // A radiator never marks its footprint solid in `s` (see
+    // `radiator_model::RadiatorModel`, which only ever damps `u`/`v` each
+    // step) — swapping in a warmed-up `Fluid` here needs no footprint
+    // bookkeeping, just the grid/inflow to already match, which
+    // `case_scene_config` guarantees since both used `config`.
+    if let Some(base) = base_fluid {
+        scene.fluid = base.clone();
+    }
+    for _ in 0..config.steps {
+        scene.simulate();
+    }
+    // `RadiatorMetrics::compute` is a single instantaneous sample, not a
+    // time average (this crate has no such mechanism) — if `steps` doesn't
+    // cover the ramp, the sample lands mid-transient and gets compared
+    // against the *target* `inflow_u` below, understating every ratio.
+    if config.steps as f64 * config.dt < config.inflow_ramp_time {
+        eprintln!(
+            "warning: case {case_index} sampled metrics before its inflow ramp finished \
+             ({:.2}s run vs {:.2}s ramp) — increase `steps` or shorten `inflow_ramp_time`",
+            config.steps as f64 * config.dt,
+            config.inflow_ramp_time
+        );
+    }
+
+    let radiator: &Radiator = &scene.obstacles.radiators()[0];
+    let domain_height = scene.fluid.num_y as f64 * scene.fluid.h;
+    let metrics = RadiatorMetrics::compute(&scene.fluid, radiator, scene.inflow_u, domain_height)
+        .with_flow_uniformity(&scene.fluid, radiator, FLOW_UNIFORMITY_SAMPLES);
+
+    let snapshots = if config.save_snapshots { Some(save_case_snapshots(&scene, radiator, outputs, output_dir, case_index)) } else { None };
+
+    let parameters = case.iter().map(|&(parameter, value)| (parameter.field_name().to_string(), value)).collect();
+    SweepCaseResult { parameters, metrics, snapshots }
+}
+
+fn save_case_snapshots(scene: &Scene, radiator: &Radiator, outputs: &OutputSelection, output_dir: &str, case_index: usize) -> CaseSnapshots {
+    let dir = std::path::Path::new(output_dir).join("snapshots");
+    std::fs::create_dir_all(&dir).expect("failed to create sweep snapshot directory");
+
+    let pressure = outputs.wants(OutputKind::Pressure).then(|| {
+        let path = dir.join(format!("case_{case_index:03}_pressure.png"));
+        Visualizer::save_pressure_field(&scene.fluid, &[*radiator], None, ColorScale::Auto, &path.to_string_lossy(), false)
+            .expect("failed to write sweep case pressure snapshot");
+        path.to_string_lossy().into_owned()
+    });
+    let smoke = outputs.wants(OutputKind::Smoke).then(|| {
+        let path = dir.join(format!("case_{case_index:03}_smoke.png"));
+        Visualizer::save_smoke_field(&scene.fluid, &[*radiator], None, ColorScale::Auto, &path.to_string_lossy(), false)
+            .expect("failed to write sweep case smoke snapshot");
+        path.to_string_lossy().into_owned()
+    });
+    let streamlines = outputs.wants(OutputKind::Streamlines).then(|| {
+        let path = dir.join(format!("case_{case_index:03}_streamlines.png"));
+        Visualizer::save_streamlines(&scene.fluid, &[*radiator], 8, &path.to_string_lossy())
+            .expect("failed to write sweep case streamlines snapshot");
+        path.to_string_lossy().into_owned()
+    });
+    let uniformity_profile = {
+        let path = dir.join(format!("case_{case_index:03}_uniformity.png"));
+        let report = crate::metrics::flow_uniformity(&scene.fluid, radiator, FLOW_UNIFORMITY_SAMPLES);
+        crate::report::plot_face_velocity_profile(&report, &path.to_string_lossy())
+            .expect("failed to write sweep case uniformity profile chart");
+        Some(path.to_string_lossy().into_owned())
+    };
+
+    CaseSnapshots { pressure, smoke, streamlines, uniformity_profile }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_config(axes: Vec<SweepAxis>) -> SweepConfig {
+        SweepConfig {
+            num_x: 40,
+            num_y: 20,
+            dt: default_dt(),
+            num_iters: default_num_iters(),
+            over_relaxation: default_over_relaxation(),
+            inflow_velocity: 2.0,
+            inflow_ramp_time: 0.0,
+            radiator: RadiatorConfig {
+                name: None,
+                center_x: 0.5,
+                center_y: 0.5,
+                width: 0.05,
+                height: 0.3,
+                angle: 0.0,
+                porosity: 0.5,
+                heat_exchanger: None,
+            },
+            steps: 5,
+            axes,
+            save_snapshots: false,
+            outputs: None,
+            restart_from_steady: false,
+            steady_state_tolerance: default_steady_state_tolerance(),
+            steady_state_window: default_steady_state_window(),
+            steady_state_max_steps: default_steady_state_max_steps(),
+        }
+    }
+
+    fn test_output_dir(label: &str) -> String {
+        std::env::temp_dir().join(format!("lgr_2d_cfd_sweep_test_{label}_{}", std::process::id())).to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn a_1d_sweep_records_the_swept_parameter_and_value_for_every_case() {
+        let config = base_config(vec![SweepAxis { parameter: SweepParameter::Porosity, values: vec![0.2, 0.5, 0.8] }]);
+        let report = run_sweep(&config, 2, &test_output_dir("1d"));
+
+        assert_eq!(report.results.len(), 3);
+        let mut seen: Vec<f64> = report
+            .results
+            .iter()
+            .map(|r| {
+                assert_eq!(r.parameters, vec![("porosity".to_string(), r.parameters[0].1)]);
+                r.parameters[0].1
+            })
+            .collect();
+        seen.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(seen, vec![0.2, 0.5, 0.8]);
+    }
+
+    #[test]
+    fn a_2d_sweep_produces_the_full_cartesian_grid() {
+        let config = base_config(vec![
+            SweepAxis { parameter: SweepParameter::Angle, values: vec![0.0, 0.1] },
+            SweepAxis { parameter: SweepParameter::InflowVelocity, values: vec![1.0, 2.0, 3.0] },
+        ]);
+        let report = run_sweep(&config, 4, &test_output_dir("2d"));
+
+        assert_eq!(report.results.len(), 2 * 3);
+        for result in &report.results {
+            assert_eq!(result.parameters[0].0, "angle");
+            assert_eq!(result.parameters[1].0, "inflow_velocity");
+        }
+    }
+
+    #[test]
+    fn sweeping_porosity_changes_the_measured_capture_ratio() {
+        let config = base_config(vec![SweepAxis { parameter: SweepParameter::Porosity, values: vec![0.1, 0.9] }]);
+        let report = run_sweep(&config, 1, &test_output_dir("porosity"));
+
+        let open = report.results.iter().find(|r| r.parameters[0].1 == 0.1).unwrap();
+        let blocked = report.results.iter().find(|r| r.parameters[0].1 == 0.9).unwrap();
+        assert!(
+            open.metrics.capture_ratio > blocked.metrics.capture_ratio,
+            "a more open radiator should capture more of the inflow: {} vs {}",
+            open.metrics.capture_ratio,
+            blocked.metrics.capture_ratio
+        );
+    }
+
+    #[test]
+    fn save_snapshots_writes_a_thumbnail_set_per_case() {
+        let mut config = base_config(vec![SweepAxis { parameter: SweepParameter::Angle, values: vec![0.0, 0.2] }]);
+        config.save_snapshots = true;
+        let output_dir = test_output_dir("snapshots");
+        let report = run_sweep(&config, 2, &output_dir);
+
+        assert_eq!(report.results.len(), 2);
+        for result in &report.results {
+            let snapshots = result.snapshots.as_ref().expect("save_snapshots was set, every case should have images");
+            for path in [&snapshots.pressure, &snapshots.smoke, &snapshots.streamlines] {
+                let path = path.as_ref().expect("no --outputs restriction, every kind should be present");
+                assert!(std::path::Path::new(path).exists(), "expected {path} to exist");
+            }
+        }
+    }
+
+    #[test]
+    fn outputs_restricts_which_snapshot_kinds_get_written() {
+        let mut config = base_config(vec![SweepAxis { parameter: SweepParameter::Angle, values: vec![0.0] }]);
+        config.save_snapshots = true;
+        config.outputs = Some("pressure".to_string());
+        let output_dir = test_output_dir("outputs_restricted");
+        let report = run_sweep(&config, 1, &output_dir);
+
+        let snapshots = report.results[0].snapshots.as_ref().unwrap();
+        assert!(snapshots.pressure.is_some(), "pressure was selected, should be written");
+        assert!(snapshots.smoke.is_none(), "smoke was not selected, should be absent");
+        assert!(snapshots.streamlines.is_none(), "streamlines was not selected, should be absent");
+    }
+
+    #[test]
+    fn restart_from_steady_is_off_by_default_and_reports_no_warm_up() {
+        let config = base_config(vec![SweepAxis { parameter: SweepParameter::Porosity, values: vec![0.5] }]);
+        let report = run_sweep(&config, 1, &test_output_dir("no_restart"));
+        assert_eq!(report.warm_up_steps, None);
+    }
+
+    #[test]
+    fn restart_from_steady_reports_a_warm_up_step_count_and_matches_a_longer_cold_start() {
+        let mut cold = base_config(vec![SweepAxis { parameter: SweepParameter::Porosity, values: vec![0.3] }]);
+        cold.steps = 150;
+        let cold_report = run_sweep(&cold, 1, &test_output_dir("restart_cold"));
+
+        let mut warm = base_config(vec![SweepAxis { parameter: SweepParameter::Porosity, values: vec![0.3] }]);
+        warm.restart_from_steady = true;
+        warm.steady_state_max_steps = 150;
+        warm.steps = 30;
+        let warm_report = run_sweep(&warm, 1, &test_output_dir("restart_warm"));
+
+        assert!(warm_report.warm_up_steps.is_some(), "restart_from_steady should report how long the warm-up ran");
+
+        let cold_capture = cold_report.results[0].metrics.capture_ratio;
+        let warm_capture = warm_report.results[0].metrics.capture_ratio;
+        assert!(
+            (cold_capture - warm_capture).abs() < 0.1,
+            "a short restart-from-steady case should land close to a long cold-start case: {warm_capture} vs {cold_capture}"
+        );
+    }
+}
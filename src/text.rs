@@ -0,0 +1,122 @@
+//! A minimal 3x5 pixel bitmap font, just legible enough for the numeric tick
+//! labels, units, and short titles on plot colorbars. A real text-shaping
+//! dependency would be overkill for a handful of characters per PNG, so this
+//! draws them the same way the rest of `visualizer` draws everything else:
+//! one `put_pixel` at a time.
+
+use image::{Rgb, RgbImage};
+
+const GLYPH_WIDTH: u32 = 3;
+const GLYPH_HEIGHT: u32 = 5;
+/// Horizontal gap, in pixels, between adjacent glyphs.
+const GLYPH_GAP: u32 = 1;
+
+/// `#` marks a lit pixel, `.` a blank one, five rows top to bottom.
+const GLYPHS: &[(char, [&str; 5])] = &[
+    ('0', ["###", "#.#", "#.#", "#.#", "###"]),
+    ('1', [".#.", "##.", ".#.", ".#.", "###"]),
+    ('2', ["##.", "..#", ".#.", "#..", "###"]),
+    ('3', ["##.", "..#", ".#.", "..#", "##."]),
+    ('4', ["#.#", "#.#", "###", "..#", "..#"]),
+    ('5', ["###", "#..", "##.", "..#", "##."]),
+    ('6', [".##", "#..", "##.", "#.#", ".#."]),
+    ('7', ["###", "..#", ".#.", "#..", "#.."]),
+    ('8', [".#.", "#.#", ".#.", "#.#", ".#."]),
+    ('9', [".#.", "#.#", ".##", "..#", ".#."]),
+    ('A', [".#.", "#.#", "###", "#.#", "#.#"]),
+    ('B', ["##.", "#.#", "##.", "#.#", "##."]),
+    ('C', [".##", "#..", "#..", "#..", ".##"]),
+    ('D', ["##.", "#.#", "#.#", "#.#", "##."]),
+    ('E', ["###", "#..", "##.", "#..", "###"]),
+    ('F', ["###", "#..", "##.", "#..", "#.."]),
+    ('G', [".##", "#..", "#.#", "#.#", ".##"]),
+    ('H', ["#.#", "#.#", "###", "#.#", "#.#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..#", "..#", "..#", "#.#", ".#."]),
+    ('K', ["#.#", "#.#", "##.", "#.#", "#.#"]),
+    ('L', ["#..", "#..", "#..", "#..", "###"]),
+    ('M', ["#.#", "###", "###", "#.#", "#.#"]),
+    ('N', ["#.#", "##.", "#.#", ".##", "#.#"]),
+    ('O', [".#.", "#.#", "#.#", "#.#", ".#."]),
+    ('P', ["##.", "#.#", "##.", "#..", "#.."]),
+    ('Q', [".#.", "#.#", "#.#", ".##", "..#"]),
+    ('R', ["##.", "#.#", "##.", "#.#", "#.#"]),
+    ('S', [".##", "#..", ".#.", "..#", "##."]),
+    ('T', ["###", ".#.", ".#.", ".#.", ".#."]),
+    ('U', ["#.#", "#.#", "#.#", "#.#", ".#."]),
+    ('V', ["#.#", "#.#", "#.#", "#.#", ".#."]),
+    ('W', ["#.#", "#.#", "#.#", "###", "#.#"]),
+    ('X', ["#.#", "#.#", ".#.", "#.#", "#.#"]),
+    ('Y', ["#.#", "#.#", ".#.", ".#.", ".#."]),
+    ('Z', ["###", "..#", ".#.", "#..", "###"]),
+    ('-', ["...", "...", "###", "...", "..."]),
+    ('.', ["...", "...", "...", "...", ".#."]),
+    ('/', ["..#", ".#.", ".#.", "#..", "#.."]),
+    (' ', ["...", "...", "...", "...", "..."]),
+];
+
+fn glyph_rows(c: char) -> [&'static str; 5] {
+    let upper = c.to_ascii_uppercase();
+    GLYPHS
+        .iter()
+        .find(|(g, _)| *g == upper)
+        .map(|(_, rows)| *rows)
+        // Unsupported characters render as blank rather than panicking or
+        // substituting a placeholder glyph that could be mistaken for data.
+        .unwrap_or([".", ".", ".", ".", "."].map(|_| "..."))
+}
+
+/// Draw `text` with its top-left corner at `(x0, y0)`, clipping anything
+/// that falls outside the image rather than panicking — labels sit close to
+/// the image edge and small rounding is expected.
+pub fn draw_text(img: &mut RgbImage, x0: i64, y0: i64, text: &str, color: Rgb<u8>) {
+    for (i, c) in text.chars().enumerate() {
+        let gx = x0 + i as i64 * (GLYPH_WIDTH + GLYPH_GAP) as i64;
+        for (row, bits) in glyph_rows(c).iter().enumerate() {
+            for (col, bit) in bits.bytes().enumerate() {
+                if bit != b'#' {
+                    continue;
+                }
+                let px = gx + col as i64;
+                let py = y0 + row as i64;
+                if px >= 0 && py >= 0 && (px as u32) < img.width() && (py as u32) < img.height() {
+                    img.put_pixel(px as u32, py as u32, color);
+                }
+            }
+        }
+    }
+}
+
+/// Pixel width `draw_text` would occupy for `text`, so callers can right-align
+/// or center a label before drawing it.
+#[allow(dead_code)]
+pub fn text_width(text: &str) -> u32 {
+    if text.is_empty() {
+        0
+    } else {
+        text.len() as u32 * (GLYPH_WIDTH + GLYPH_GAP) - GLYPH_GAP
+    }
+}
+
+pub const GLYPH_HEIGHT_PX: u32 = GLYPH_HEIGHT;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_glyph_is_three_columns_by_five_rows() {
+        for (_, rows) in GLYPHS {
+            assert_eq!(rows.len(), 5);
+            for row in rows {
+                assert_eq!(row.len(), 3);
+            }
+        }
+    }
+
+    #[test]
+    fn drawing_off_the_right_edge_does_not_panic() {
+        let mut img = RgbImage::new(4, 4);
+        draw_text(&mut img, 3, 0, "HELLO", Rgb([255, 255, 255]));
+    }
+}
@@ -0,0 +1,184 @@
+//! Wall-clock accounting split into solver time (the actual [`crate::scene::Scene::simulate`]
+//! calls) versus I/O time (PNG/VTK/animator/checkpoint writes), so a
+//! "steps/sec" figure means the same thing across runs with different output
+//! settings instead of silently including however much file-writing that
+//! particular run happened to do.
+//!
+//! There is no phase-timer, printed "FPS" counter, or benchmark subcommand
+//! anywhere in this tree for this to slot into; `fps` elsewhere in the crate
+//! is only ever the gif/video frame-rate parameter. This is the accumulator
+//! `run_scene`'s step loop feeds, used for both its periodic prints and the
+//! final `summary.json`.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Accumulates solver time and I/O time across a run's step loop.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StepTimer {
+    solver: Duration,
+    io: Duration,
+}
+
+impl StepTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record time spent inside `Scene::simulate` for one or more steps.
+    pub fn record_solver(&mut self, elapsed: Duration) {
+        self.solver += elapsed;
+    }
+
+    /// Record time spent on periodic PNG/VTK/animator/checkpoint writes.
+    pub fn record_io(&mut self, elapsed: Duration) {
+        self.io += elapsed;
+    }
+
+    pub fn total(&self) -> Duration {
+        self.solver + self.io
+    }
+
+    /// A [`PerfSummary`] snapshot of the counters recorded so far, for
+    /// `steps` steps run.
+    pub fn summary(&self, steps: u64) -> PerfSummary {
+        PerfSummary {
+            solver_steps_per_sec: rate(steps, self.solver),
+            end_to_end_steps_per_sec: rate(steps, self.total()),
+            io_fraction: self.io_fraction(),
+        }
+    }
+
+    /// Fraction of total tracked time spent in I/O, in `[0, 1]`. `0.0` if
+    /// nothing has been recorded yet, rather than dividing by zero.
+    fn io_fraction(&self) -> f64 {
+        let total = self.total().as_secs_f64();
+        if total <= 0.0 {
+            0.0
+        } else {
+            self.io.as_secs_f64() / total
+        }
+    }
+
+    pub fn solver_seconds(&self) -> f64 {
+        self.solver.as_secs_f64()
+    }
+
+    pub fn io_seconds(&self) -> f64 {
+        self.io.as_secs_f64()
+    }
+
+    pub fn total_seconds(&self) -> f64 {
+        self.total().as_secs_f64()
+    }
+}
+
+fn rate(steps: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        0.0
+    } else {
+        steps as f64 / secs
+    }
+}
+
+/// Solver-only vs end-to-end throughput and the I/O time fraction, carried
+/// in `summary.json` so a batch aggregator can compare runs with different
+/// output settings on the number that's actually comparable.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PerfSummary {
+    /// Steps/sec counting only time inside `Scene::simulate` — the number
+    /// to use when comparing solver performance across runs.
+    pub solver_steps_per_sec: f64,
+    /// Steps/sec counting the full loop, including every write this run
+    /// happened to do — useful for "how long did this run actually take",
+    /// misleading for comparing solver performance.
+    pub end_to_end_steps_per_sec: f64,
+    /// Fraction of tracked wall time spent in I/O, in `[0, 1]`.
+    pub io_fraction: f64,
+}
+
+/// A single `\r`-overwriting status line for `--verbose` runs — this crate's
+/// plain-`std::io` stand-in for a real progress bar library (no network
+/// access in this environment to add `indicatif`). Prints step count,
+/// percent complete, current solver throughput, and an ETA derived from it.
+pub struct StepProgress {
+    total_steps: u64,
+}
+
+impl StepProgress {
+    pub fn new(total_steps: u64) -> Self {
+        Self { total_steps }
+    }
+
+    /// Overwrites the current terminal line with the latest status, given
+    /// how many of `total_steps` have completed and the [`StepTimer`]
+    /// tracking this run so far. Does nothing if `total_steps` is zero,
+    /// since percent-complete and ETA are undefined.
+    pub fn print(&self, completed: u64, timer: &StepTimer) {
+        if self.total_steps == 0 {
+            return;
+        }
+        let percent = 100.0 * completed as f64 / self.total_steps as f64;
+        let summary = timer.summary(completed);
+        let remaining = self.total_steps.saturating_sub(completed);
+        let eta_secs = if summary.solver_steps_per_sec > 0.0 {
+            remaining as f64 / summary.solver_steps_per_sec
+        } else {
+            0.0
+        };
+        print!(
+            "\rstep {completed}/{} ({percent:.1}%) | {:.1} steps/sec | eta {eta_secs:.0}s   ",
+            self.total_steps, summary.solver_steps_per_sec
+        );
+        let _ = std::io::Write::flush(&mut std::io::stdout());
+    }
+
+    /// Moves the cursor past the overwritten line once the run is done, so
+    /// whatever prints next doesn't collide with it.
+    pub fn finish(&self) {
+        if self.total_steps > 0 {
+            println!();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solver_and_io_time_sum_to_total_within_rounding() {
+        let mut timer = StepTimer::new();
+        timer.record_solver(Duration::from_millis(700));
+        timer.record_io(Duration::from_millis(300));
+        assert!((timer.solver_seconds() + timer.io_seconds() - timer.total_seconds()).abs() < 1e-9);
+        assert!((timer.total_seconds() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn io_fraction_reflects_the_recorded_split() {
+        let mut timer = StepTimer::new();
+        timer.record_solver(Duration::from_millis(750));
+        timer.record_io(Duration::from_millis(250));
+        let summary = timer.summary(10);
+        assert!((summary.io_fraction - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn end_to_end_rate_never_exceeds_solver_only_rate_when_any_io_is_recorded() {
+        let mut timer = StepTimer::new();
+        timer.record_solver(Duration::from_millis(500));
+        timer.record_io(Duration::from_millis(500));
+        let summary = timer.summary(100);
+        assert!(summary.end_to_end_steps_per_sec <= summary.solver_steps_per_sec);
+    }
+
+    #[test]
+    fn zero_recorded_time_reports_zero_rather_than_dividing_by_zero() {
+        let timer = StepTimer::new();
+        let summary = timer.summary(10);
+        assert_eq!(summary.solver_steps_per_sec, 0.0);
+        assert_eq!(summary.io_fraction, 0.0);
+    }
+}
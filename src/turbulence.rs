@@ -0,0 +1,212 @@
+//! Optional algebraic (Prandtl mixing-length) turbulence closure.
+//!
+//! This solver has no molecular viscosity field and no diffusion step of
+//! its own — [`crate::fluid::Fluid::simulate_with_ordering`] only
+//! integrates, projects, extrapolates, and advects; numerical dissipation
+//! from the semi-Lagrangian advection scheme stands in for viscosity. When
+//! [`crate::scene::Scene`] is configured with a [`TurbulenceModel`], it adds
+//! an explicit-Euler diffusion pass (see [`apply`]) driven by a
+//! mixing-length eddy viscosity, so a scene can get a wider, less
+//! oscillatory wake without needing a real viscous term to be plumbed
+//! through the whole solver first.
+
+use crate::fluid::{Fluid, SOLID_CELL};
+
+/// `c` plays the role of von Karman's constant scaled by whatever mixing
+/// length suits the geometry — there's no universal value, so it's exposed
+/// rather than hard-coded, and the resulting eddy viscosity is only ever
+/// applied through [`apply`], never fed into a Navier-Stokes diffusion term
+/// this solver doesn't have.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "model")]
+pub enum TurbulenceModel {
+    MixingLength { c: f64 },
+}
+
+/// Distance from each cell center to the nearest solid cell (wall or
+/// obstacle), by brute-force nearest-neighbor search over `fluid.s`. O(N^2)
+/// in cell count — meant to be computed once (e.g. at scene setup, see
+/// `Scene::setup_from_config`) and cached, not recomputed every step. A
+/// moving obstacle's distance field goes stale once it moves; there's no
+/// invalidation mechanism for that yet.
+pub fn wall_distance_field(fluid: &Fluid) -> Vec<f64> {
+    let n = fluid.num_y;
+    let mut solid_points = Vec::new();
+    for i in 0..fluid.num_x {
+        for j in 0..fluid.num_y {
+            if fluid.s[i * n + j] == SOLID_CELL {
+                solid_points.push((i as f64 * fluid.h, j as f64 * fluid.h));
+            }
+        }
+    }
+
+    let mut distances = vec![0.0; fluid.num_x * fluid.num_y];
+    for i in 0..fluid.num_x {
+        for j in 0..fluid.num_y {
+            let idx = i * n + j;
+            if fluid.s[idx] == SOLID_CELL {
+                continue;
+            }
+            let (x, y) = (i as f64 * fluid.h, j as f64 * fluid.h);
+            distances[idx] = solid_points
+                .iter()
+                .map(|&(sx, sy)| ((x - sx).powi(2) + (y - sy).powi(2)).sqrt())
+                .fold(f64::MAX, f64::min);
+        }
+    }
+    distances
+}
+
+/// Local strain-rate magnitude from centered differences of `fluid.u`/
+/// `fluid.v`, folded into `nu_t = (c * wall_distance)^2 * |S|` at every
+/// interior fluid cell. Boundary-row/column cells (a centered difference
+/// would read past the grid) and solid cells are left at `0.0`.
+pub fn eddy_viscosity_field(fluid: &Fluid, wall_distance: &[f64], c: f64) -> Vec<f64> {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    let mut nu_t = vec![0.0; fluid.num_x * fluid.num_y];
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let idx = i * n + j;
+            if fluid.s[idx] == SOLID_CELL {
+                continue;
+            }
+            let dudx = (fluid.u[(i + 1) * n + j] - fluid.u[(i - 1) * n + j]) / (2.0 * h);
+            let dvdy = (fluid.v[i * n + j + 1] - fluid.v[i * n + j - 1]) / (2.0 * h);
+            let dudy = (fluid.u[i * n + j + 1] - fluid.u[i * n + j - 1]) / (2.0 * h);
+            let dvdx = (fluid.v[(i + 1) * n + j] - fluid.v[(i - 1) * n + j]) / (2.0 * h);
+            let strain_rate = (2.0 * dudx * dudx + 2.0 * dvdy * dvdy + (dudy + dvdx).powi(2)).sqrt();
+            let mixing_length = c * wall_distance[idx];
+            nu_t[idx] = mixing_length * mixing_length * strain_rate;
+        }
+    }
+    nu_t
+}
+
+/// Advances `fluid.u`/`fluid.v` by one explicit-Euler diffusion step driven
+/// by `model`'s eddy viscosity, `u_new = u + dt * nu_t * laplacian(u) /
+/// h^2`. Solid cells and the outermost ring (no interior neighbor to
+/// difference against) are left untouched. Not implicit and not
+/// CFL-limited on `nu_t` — fine for the small mixing-length coefficients
+/// this closure is meant to explore, but a large enough `c` can destabilize
+/// the step the same way an oversized `dt` destabilizes advection.
+pub fn apply(fluid: &mut Fluid, wall_distance: &[f64], model: TurbulenceModel, dt: f64) {
+    let TurbulenceModel::MixingLength { c } = model;
+    let nu_t = eddy_viscosity_field(fluid, wall_distance, c);
+    let n = fluid.num_y;
+    let h2 = fluid.h * fluid.h;
+
+    let mut new_u = fluid.u.clone();
+    let mut new_v = fluid.v.clone();
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let idx = i * n + j;
+            if fluid.s[idx] == SOLID_CELL {
+                continue;
+            }
+            let laplacian_u = fluid.u[(i + 1) * n + j] + fluid.u[(i - 1) * n + j] + fluid.u[i * n + j + 1]
+                + fluid.u[i * n + j - 1]
+                - 4.0 * fluid.u[idx];
+            let laplacian_v = fluid.v[(i + 1) * n + j] + fluid.v[(i - 1) * n + j] + fluid.v[i * n + j + 1]
+                + fluid.v[i * n + j - 1]
+                - 4.0 * fluid.v[idx];
+            new_u[idx] = fluid.u[idx] + dt * nu_t[idx] * laplacian_u / h2;
+            new_v[idx] = fluid.v[idx] + dt * nu_t[idx] * laplacian_v / h2;
+        }
+    }
+    fluid.u = new_u;
+    fluid.v = new_v;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `Fluid::new` starts with every cell fluid; mark the top/bottom rows
+    /// solid the same way `Scene::setup_from_config` does, since this
+    /// module's whole premise is measuring distance to a wall.
+    fn mark_top_bottom_walls_solid(fluid: &mut Fluid) {
+        let n = fluid.num_y;
+        for i in 0..fluid.num_x {
+            fluid.s[i * n] = SOLID_CELL;
+            fluid.s[i * n + fluid.num_y - 1] = SOLID_CELL;
+        }
+    }
+
+    fn shear_flow(num_x: usize, num_y: usize, h: f64) -> Fluid {
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        mark_top_bottom_walls_solid(&mut fluid);
+        for i in 0..num_x {
+            for j in 0..num_y {
+                let idx = fluid.idx(i, j);
+                fluid.u[idx] = j as f64 * h;
+            }
+        }
+        fluid
+    }
+
+    #[test]
+    fn eddy_viscosity_is_zero_at_walls_and_grows_away_from_them() {
+        let num_x = 20;
+        let num_y = 20;
+        let h = 1.0 / num_y as f64;
+        let fluid = shear_flow(num_x, num_y, h);
+        let wall_distance = wall_distance_field(&fluid);
+        let nu_t = eddy_viscosity_field(&fluid, &wall_distance, 0.1);
+
+        let n = fluid.num_y;
+        let i = num_x / 2;
+        assert_eq!(nu_t[i * n], 0.0, "expected zero eddy viscosity right at the wall");
+        let mid = nu_t[i * n + num_y / 2];
+        assert!(mid > 0.0, "expected positive eddy viscosity away from the wall, got {mid}");
+    }
+
+    #[test]
+    fn wall_distance_is_zero_at_solid_cells_and_positive_elsewhere() {
+        let num_x = 10;
+        let num_y = 10;
+        let h = 0.1;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        mark_top_bottom_walls_solid(&mut fluid);
+        let distance = wall_distance_field(&fluid);
+        let n = fluid.num_y;
+
+        assert_eq!(distance[5 * n], 0.0, "j=0 is a solid wall row");
+        assert!(distance[5 * n + num_y / 2] > 0.0, "mid-channel cells should be away from any wall");
+    }
+
+    #[test]
+    fn apply_leaves_velocity_unchanged_when_c_is_zero() {
+        let num_x = 20;
+        let num_y = 20;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = shear_flow(num_x, num_y, h);
+        let wall_distance = wall_distance_field(&fluid);
+        let before = fluid.u.clone();
+
+        apply(&mut fluid, &wall_distance, TurbulenceModel::MixingLength { c: 0.0 }, 0.01);
+        assert_eq!(fluid.u, before, "c = 0 should give zero eddy viscosity everywhere, so no diffusion");
+    }
+
+    #[test]
+    fn apply_smooths_a_sharp_velocity_step() {
+        let num_x = 20;
+        let num_y = 20;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        mark_top_bottom_walls_solid(&mut fluid);
+        let n = fluid.num_y;
+        for i in 0..num_x {
+            for j in 0..num_y {
+                let idx = i * n + j;
+                fluid.u[idx] = if j < num_y / 2 { 0.0 } else { 1.0 };
+            }
+        }
+        let wall_distance = wall_distance_field(&fluid);
+        let before_step = fluid.u[5 * n + num_y / 2] - fluid.u[5 * n + num_y / 2 - 1];
+
+        apply(&mut fluid, &wall_distance, TurbulenceModel::MixingLength { c: 0.3 }, 0.001);
+        let after_step = fluid.u[5 * n + num_y / 2] - fluid.u[5 * n + num_y / 2 - 1];
+        assert!(after_step.abs() < before_step.abs(), "diffusion should smooth the velocity step, not sharpen it");
+    }
+}
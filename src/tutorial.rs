@@ -0,0 +1,147 @@
+//! `--tutorial`: a fast, small-domain wind-tunnel-with-cylinder run that
+//! narrates itself for onboarding, instead of leaving a new team member to
+//! reverse-engineer what `pressure_00040.png` means from a full-size run.
+//!
+//! This tree has no general "regime classifier" — [`MilestoneTracker`] is
+//! the concrete, minimal detector this narration actually needs: boundary
+//! layer onset via a divergence threshold, vortex shedding via lift sign
+//! changes. Every caption is generated from the same numbers the run
+//! actually produced, not written once and left to drift from the code.
+
+/// A narrated point in the tutorial run, always detected in this order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Milestone {
+    InitialCondition,
+    DevelopingBoundaryLayer,
+    FirstVortex,
+    EstablishedStreet,
+}
+
+impl Milestone {
+    /// Short, filename-safe identifier.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Milestone::InitialCondition => "initial_condition",
+            Milestone::DevelopingBoundaryLayer => "developing_boundary_layer",
+            Milestone::FirstVortex => "first_vortex",
+            Milestone::EstablishedStreet => "established_street",
+        }
+    }
+
+    /// One paragraph explaining what the accompanying image shows.
+    pub fn caption(&self) -> &'static str {
+        match self {
+            Milestone::InitialCondition => {
+                "Step 0: uniform free-stream flow, before the cylinder has had a chance to perturb it."
+            }
+            Milestone::DevelopingBoundaryLayer => {
+                "Divergence near the cylinder has risen above the free-stream noise floor: a boundary layer is forming as the solver reacts to the obstacle."
+            }
+            Milestone::FirstVortex => {
+                "Lift has changed sign for the first time: the wake has shed its first vortex."
+            }
+            Milestone::EstablishedStreet => {
+                "Lift has completed a full oscillation cycle: an established vortex street is now shedding periodically."
+            }
+        }
+    }
+}
+
+/// Watches a running scene's per-step diagnostics and reports each
+/// [`Milestone`] exactly once, in the fixed order the type lists them in.
+pub struct MilestoneTracker {
+    next: Option<Milestone>,
+    divergence_threshold: f64,
+    prev_lift_sign: Option<f64>,
+    sign_changes: u32,
+}
+
+impl MilestoneTracker {
+    pub fn new(divergence_threshold: f64) -> Self {
+        MilestoneTracker {
+            next: Some(Milestone::InitialCondition),
+            divergence_threshold,
+            prev_lift_sign: None,
+            sign_changes: 0,
+        }
+    }
+
+    /// Feed one step's `max_divergence` and obstacle `lift` in. Returns
+    /// `Some(milestone)` the first (and only) time that milestone's
+    /// condition is met; `None` otherwise, including every call after the
+    /// last milestone has already fired.
+    pub fn observe(&mut self, max_divergence: f64, lift: f64) -> Option<Milestone> {
+        let target = self.next?;
+        let fired = match target {
+            Milestone::InitialCondition => true,
+            Milestone::DevelopingBoundaryLayer => max_divergence > self.divergence_threshold,
+            Milestone::FirstVortex | Milestone::EstablishedStreet => {
+                let sign = if lift >= 0.0 { 1.0 } else { -1.0 };
+                if self.prev_lift_sign.is_some_and(|prev| prev != sign) {
+                    self.sign_changes += 1;
+                }
+                self.prev_lift_sign = Some(sign);
+                match target {
+                    Milestone::FirstVortex => self.sign_changes >= 1,
+                    Milestone::EstablishedStreet => self.sign_changes >= 2,
+                    _ => unreachable!(),
+                }
+            }
+        };
+
+        if !fired {
+            return None;
+        }
+        self.next = match target {
+            Milestone::InitialCondition => Some(Milestone::DevelopingBoundaryLayer),
+            Milestone::DevelopingBoundaryLayer => Some(Milestone::FirstVortex),
+            Milestone::FirstVortex => Some(Milestone::EstablishedStreet),
+            Milestone::EstablishedStreet => None,
+        };
+        Some(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn milestones_fire_once_each_in_order() {
+        let mut tracker = MilestoneTracker::new(0.05);
+        let mut seen = Vec::new();
+
+        let samples = [
+            (0.0, 0.0),   // InitialCondition
+            (0.01, 0.0),  // below threshold, no boundary layer yet
+            (0.2, 0.0),   // DevelopingBoundaryLayer
+            (0.2, 1.0),   // first sign recorded, no change yet
+            (0.2, -1.0),  // FirstVortex (sign change #1)
+            (0.2, -1.0),  // no change
+            (0.2, 1.0),   // EstablishedStreet (sign change #2)
+            (0.2, -1.0),  // nothing left to fire
+        ];
+        for (div, lift) in samples {
+            if let Some(m) = tracker.observe(div, lift) {
+                seen.push(m);
+            }
+        }
+
+        assert_eq!(
+            seen,
+            vec![
+                Milestone::InitialCondition,
+                Milestone::DevelopingBoundaryLayer,
+                Milestone::FirstVortex,
+                Milestone::EstablishedStreet,
+            ]
+        );
+    }
+
+    #[test]
+    fn never_fires_a_milestone_twice() {
+        let mut tracker = MilestoneTracker::new(0.0);
+        assert_eq!(tracker.observe(0.0, 0.0), Some(Milestone::InitialCondition));
+        assert_eq!(tracker.observe(0.0, 0.0), None);
+    }
+}
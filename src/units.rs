@@ -0,0 +1,131 @@
+//! Reporting-unit conversions for [`crate::metrics::RadiatorMetrics`].
+//! Every computation in this crate stays in SI internally; this module only
+//! affects how a value already computed in SI is displayed, via a
+//! conversion table keyed by the metric name from
+//! [`crate::metrics::RadiatorMetrics::definitions`]. There's no per-metric
+//! CSV or HTML report in this tree to apply units to yet — the console
+//! summary (`explain` and `summary.json`'s per-metric entries) is the one
+//! reporting surface this covers.
+//!
+//! `--units automotive` isn't a literal "km/h and liters" swap for every
+//! field: several of these metrics (`effectiveness`, `capture_ratio`,
+//! `blockage_ratio`, `blockage_correction_factor`) are dimensionless
+//! fractions with no SI unit to begin with, so their "automotive"
+//! convention is simply reporting them as a percentage instead of a raw
+//! 0-1 fraction — still a `--units` choice worth making, just not a unit
+//! system change in the strict sense.
+
+use serde::{Deserialize, Serialize};
+
+/// Which convention to render [`crate::metrics::RadiatorMetrics`] values in.
+/// `Si` (this crate's native computation units) is the default so an
+/// unspecified `--units` reproduces today's output exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitSystem {
+    #[default]
+    Si,
+    Automotive,
+}
+
+/// One metric's SI and automotive display units, plus the multiplicative
+/// factor between them (`automotive_value = si_value * factor`). Every
+/// conversion in [`CONVERSIONS`] is a pure scale factor — no offsets — so
+/// converting and converting back is always exact modulo float rounding.
+pub struct UnitConversion {
+    pub si_suffix: &'static str,
+    pub automotive_suffix: &'static str,
+    pub factor: f64,
+}
+
+impl UnitConversion {
+    pub fn suffix(&self, system: UnitSystem) -> &'static str {
+        match system {
+            UnitSystem::Si => self.si_suffix,
+            UnitSystem::Automotive => self.automotive_suffix,
+        }
+    }
+
+    /// `si_value`, rendered in `system`'s units.
+    pub fn convert(&self, system: UnitSystem, si_value: f64) -> f64 {
+        match system {
+            UnitSystem::Si => si_value,
+            UnitSystem::Automotive => si_value * self.factor,
+        }
+    }
+
+    /// The inverse of [`Self::convert`]: given a value already rendered in
+    /// `system`'s units, recover the underlying SI value.
+    pub fn to_si(&self, system: UnitSystem, value: f64) -> f64 {
+        match system {
+            UnitSystem::Si => value,
+            UnitSystem::Automotive => value / self.factor,
+        }
+    }
+}
+
+/// One entry per [`crate::metrics::RadiatorMetrics`] field. Kept as a flat
+/// table (rather than a match in [`conversion_for`]) so
+/// `units::tests::every_registry_metric_has_a_conversion` can iterate it
+/// directly and fail with the missing metric's name instead of a generic
+/// "no entry" panic.
+const CONVERSIONS: &[(&str, UnitConversion)] = &[
+    ("fan_power_required", UnitConversion { si_suffix: "W", automotive_suffix: "kW", factor: 0.001 }),
+    ("capture_ratio", UnitConversion { si_suffix: "", automotive_suffix: "%", factor: 100.0 }),
+    ("loss_coefficient", UnitConversion { si_suffix: "", automotive_suffix: "", factor: 1.0 }),
+    ("mass_flow", UnitConversion { si_suffix: "m^2/s", automotive_suffix: "L/s", factor: 1000.0 }),
+    ("heat_rejected_watts", UnitConversion { si_suffix: "W", automotive_suffix: "kW", factor: 0.001 }),
+    ("effectiveness", UnitConversion { si_suffix: "", automotive_suffix: "%", factor: 100.0 }),
+    ("frontal_area", UnitConversion { si_suffix: "m", automotive_suffix: "m", factor: 1.0 }),
+    ("tunnel_area", UnitConversion { si_suffix: "m", automotive_suffix: "m", factor: 1.0 }),
+    ("blockage_ratio", UnitConversion { si_suffix: "", automotive_suffix: "%", factor: 100.0 }),
+    ("blockage_correction_factor", UnitConversion { si_suffix: "", automotive_suffix: "%", factor: 100.0 }),
+    ("pressure_drop_raw", UnitConversion { si_suffix: "Pa", automotive_suffix: "mbar", factor: 0.01 }),
+    ("pressure_drop_corrected", UnitConversion { si_suffix: "Pa", automotive_suffix: "mbar", factor: 0.01 }),
+    ("drag_raw", UnitConversion { si_suffix: "N/m", automotive_suffix: "N/m", factor: 1.0 }),
+    ("drag_corrected", UnitConversion { si_suffix: "N/m", automotive_suffix: "N/m", factor: 1.0 }),
+    ("drag_wake_survey", UnitConversion { si_suffix: "N/m", automotive_suffix: "N/m", factor: 1.0 }),
+    ("flow_uniformity_index", UnitConversion { si_suffix: "", automotive_suffix: "%", factor: 100.0 }),
+    ("reversed_flow_fraction", UnitConversion { si_suffix: "", automotive_suffix: "%", factor: 100.0 }),
+    ("recirculation_area", UnitConversion { si_suffix: "m^2", automotive_suffix: "m^2", factor: 1.0 }),
+];
+
+/// The conversion registered for `metric_name`, or `None` if it isn't a
+/// [`crate::metrics::RadiatorMetrics`] field this table covers yet.
+pub fn conversion_for(metric_name: &str) -> Option<&'static UnitConversion> {
+    CONVERSIONS.iter().find(|(name, _)| *name == metric_name).map(|(_, c)| c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::RadiatorMetrics;
+
+    #[test]
+    fn every_registry_metric_has_a_conversion() {
+        for def in RadiatorMetrics::definitions() {
+            assert!(
+                conversion_for(def.name).is_some(),
+                "metric `{}` has no registered unit conversion",
+                def.name
+            );
+        }
+    }
+
+    #[test]
+    fn every_conversion_round_trips_through_automotive_and_back() {
+        for (name, conversion) in CONVERSIONS {
+            let si_value = 42.0;
+            let automotive_value = conversion.convert(UnitSystem::Automotive, si_value);
+            let recovered = conversion.to_si(UnitSystem::Automotive, automotive_value);
+            assert!(
+                (recovered - si_value).abs() < 1e-9,
+                "`{name}` did not round-trip: {si_value} -> {automotive_value} -> {recovered}"
+            );
+
+            // `Si` is defined as the identity conversion.
+            assert_eq!(conversion.convert(UnitSystem::Si, si_value), si_value);
+            assert_eq!(conversion.to_si(UnitSystem::Si, si_value), si_value);
+        }
+    }
+}
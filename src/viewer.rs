@@ -0,0 +1,222 @@
+//! Live desktop viewer: an optional window (behind `--live`, feature
+//! `viewer`) that shows the running scene every `update_every` steps
+//! instead of only ever writing PNGs to disk. Reuses
+//! [`crate::visualizer::get_sci_color`] for the buffer fill, so what's on
+//! screen and what the headless path renders to file always agree.
+//!
+//! Kept in its own `#[cfg(feature = "viewer")]` module with an optional
+//! `minifb` dependency, the same way `wasm.rs` keeps its optional
+//! `wasm-bindgen` dependency out of a normal headless build — CI and every
+//! server deployment never has to link a windowing toolkit for this.
+
+use minifb::{Key, KeyRepeat, MouseButton, MouseMode, Window, WindowOptions};
+
+use crate::scene::Scene;
+use crate::scene_config::{ObstacleShape, SceneConfig};
+use crate::visualizer::{get_sci_color, Visualizer};
+
+/// Which field the window is currently painting, switchable with the 1/2/3
+/// number keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LiveField {
+    Smoke,
+    Pressure,
+    Speed,
+}
+
+/// What the mouse does while held down, cycled with the `P` key.
+/// `Obstacle` is this viewer's original behavior (drag a solid circle
+/// around, tracked separately so `R` can clear it); the rest call straight
+/// through to `Scene::paint_smoke`/`paint_solid`/`erase_solid`/`stir`, the
+/// same methods a scripted `PaintEvent` fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PaintMode {
+    Obstacle,
+    Smoke,
+    Solid,
+    Erase,
+    Stir,
+}
+
+impl PaintMode {
+    fn next(self) -> Self {
+        match self {
+            PaintMode::Obstacle => PaintMode::Smoke,
+            PaintMode::Smoke => PaintMode::Solid,
+            PaintMode::Solid => PaintMode::Erase,
+            PaintMode::Erase => PaintMode::Stir,
+            PaintMode::Stir => PaintMode::Obstacle,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            PaintMode::Obstacle => "obstacle",
+            PaintMode::Smoke => "smoke",
+            PaintMode::Solid => "solid",
+            PaintMode::Erase => "erase",
+            PaintMode::Stir => "stir",
+        }
+    }
+}
+
+/// Opens a window and drives `scene` interactively until it's closed or
+/// Escape is pressed. `update_every` solver steps run between repaints —
+/// `1` for maximum visual smoothness, higher to keep a coarse-but-slow grid
+/// responsive. `initial_config` is what the `r` key rebuilds the scene
+/// from, typically `scene.to_config()` captured right after setup.
+pub fn run_live(mut scene: Scene, initial_config: SceneConfig, update_every: u64) -> std::io::Result<()> {
+    let width = scene.fluid.num_x;
+    let height = scene.fluid.num_y;
+    let base_title = "LGR 2D CFD - live view (space=pause, r=reset, 1/2/3=field, s=save, p=paint mode, drag=paint)";
+    let mut window = Window::new(base_title, width, height, WindowOptions::default()).map_err(|e| std::io::Error::other(e.to_string()))?;
+    window.set_target_fps(60);
+
+    let mut field = LiveField::Speed;
+    let mut paint_mode = PaintMode::Obstacle;
+    let mut paused = false;
+    // The one obstacle `PaintMode::Obstacle` lets a user drag around; `None`
+    // until the first click or arrow-key nudge places it. Domain units
+    // (meters), not pixels, since the grid can be non-square.
+    let mut obstacle: Option<(f64, f64, f64)> = None;
+    // Where the mouse painted last frame, in domain units, for `Stir` (which
+    // needs a drag direction, not just a point) — `None` whenever the mouse
+    // wasn't down last frame, so a fresh click never stirs off a stale
+    // position from a previous stroke.
+    let mut last_paint_pos: Option<(f64, f64)> = None;
+    let mut buffer = vec![0u32; width * height];
+    let mut dump_index: u64 = 0;
+
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        if window.is_key_pressed(Key::Space, KeyRepeat::No) {
+            paused = !paused;
+        }
+        if window.is_key_pressed(Key::R, KeyRepeat::No) {
+            scene = Scene::setup_from_config(&initial_config);
+            obstacle = None;
+        }
+        if window.is_key_pressed(Key::Key1, KeyRepeat::No) {
+            field = LiveField::Smoke;
+        }
+        if window.is_key_pressed(Key::Key2, KeyRepeat::No) {
+            field = LiveField::Pressure;
+        }
+        if window.is_key_pressed(Key::Key3, KeyRepeat::No) {
+            field = LiveField::Speed;
+        }
+        if window.is_key_pressed(Key::P, KeyRepeat::No) {
+            paint_mode = paint_mode.next();
+            window.set_title(&format!("{base_title} [{}]", paint_mode.label()));
+        }
+        if window.is_key_pressed(Key::S, KeyRepeat::No) {
+            let path = format!("live_frame_{dump_index:05}.png");
+            dump_index += 1;
+            if let Err(e) = dump_frame(&scene, field, &path) {
+                eprintln!("failed to save {path}: {e}");
+            }
+        }
+
+        let h = scene.fluid.h;
+        let radius = 10.0 * h;
+        let nudge = 5.0 * h;
+        let mut dx = 0.0;
+        let mut dy = 0.0;
+        if window.is_key_down(Key::Left) {
+            dx -= nudge;
+        }
+        if window.is_key_down(Key::Right) {
+            dx += nudge;
+        }
+        if window.is_key_down(Key::Up) {
+            dy += nudge;
+        }
+        if window.is_key_down(Key::Down) {
+            dy -= nudge;
+        }
+        if paint_mode == PaintMode::Obstacle {
+            if let Some((cx, cy, obstacle_radius)) = obstacle {
+                if dx != 0.0 || dy != 0.0 {
+                    obstacle = Some((cx + dx, cy + dy, obstacle_radius));
+                }
+            }
+        }
+
+        let mouse_pos = window.get_mouse_pos(MouseMode::Clamp).map(|(mx, my)| (mx as f64 * h, (height as f64 - my as f64) * h));
+        if window.get_mouse_down(MouseButton::Left) {
+            if let Some((cx, cy)) = mouse_pos {
+                match paint_mode {
+                    PaintMode::Obstacle => {
+                        let obstacle_radius = obstacle.map_or(radius, |(_, _, obstacle_radius)| obstacle_radius);
+                        obstacle = Some((cx, cy, obstacle_radius));
+                    }
+                    PaintMode::Smoke => scene.paint_smoke(cx, cy, radius, 1.0),
+                    PaintMode::Solid => scene.paint_solid(cx, cy, radius),
+                    PaintMode::Erase => scene.erase_solid(cx, cy, radius),
+                    PaintMode::Stir => {
+                        if let Some((px, py)) = last_paint_pos {
+                            scene.stir(cx, cy, radius, cx - px, cy - py);
+                        }
+                    }
+                }
+            }
+            last_paint_pos = mouse_pos;
+        } else {
+            last_paint_pos = None;
+        }
+        if paint_mode == PaintMode::Obstacle {
+            if let Some((cx, cy, obstacle_radius)) = obstacle {
+                scene.clear_obstacles();
+                scene.add_obstacles([ObstacleShape::Circle { cx, cy, radius: obstacle_radius }]);
+            }
+        }
+
+        if !paused {
+            for _ in 0..update_every.max(1) {
+                scene.simulate();
+            }
+        }
+
+        fill_buffer(&scene, field, &mut buffer);
+        window.update_with_buffer(&buffer, width, height).map_err(|e| std::io::Error::other(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Fills `buffer` (row-major, `width * height`, one `0x00RRGGBB` per pixel)
+/// with `field`'s current values through [`get_sci_color`], flipped so row
+/// 0 is the top of the domain — matching `visualizer::cell_to_pixel`'s
+/// convention (and `wasm.rs`'s `render_rgba`, the other live-field renderer
+/// in this crate).
+fn fill_buffer(scene: &Scene, field: LiveField, buffer: &mut [u32]) {
+    let fluid = &scene.fluid;
+    let num_x = fluid.num_x;
+    let num_y = fluid.num_y;
+
+    let values: Vec<f64> = match field {
+        LiveField::Smoke => fluid.m.clone(),
+        LiveField::Pressure => fluid.p.clone(),
+        LiveField::Speed => (0..num_x * num_y).map(|idx| (fluid.u[idx].powi(2) + fluid.v[idx].powi(2)).sqrt()).collect(),
+    };
+    let range = crate::render::finite_range_masked(&values, &fluid.s);
+
+    for i in 0..num_x {
+        for j in 0..num_y {
+            let idx = i * num_y + j;
+            let [r, g, b] = get_sci_color(values[idx], range.min, range.max);
+            let py = num_y - 1 - j;
+            buffer[py * num_x + i] = ((r as u32) << 16) | ((g as u32) << 8) | b as u32;
+        }
+    }
+}
+
+fn dump_frame(scene: &Scene, field: LiveField, path: &str) -> Result<(), image::ImageError> {
+    let radiators = scene.obstacles.radiators();
+    match field {
+        LiveField::Smoke => Visualizer::save_smoke_field(&scene.fluid, radiators, None, crate::visualizer::ColorScale::Auto, path, true),
+        LiveField::Pressure => Visualizer::save_pressure_field(&scene.fluid, radiators, None, crate::visualizer::ColorScale::Auto, path, true),
+        LiveField::Speed => {
+            Visualizer::save_velocity_magnitude_field(&scene.fluid, radiators, None, crate::visualizer::ColorScale::Auto, path, true)
+        }
+    }
+}
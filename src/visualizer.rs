@@ -0,0 +1,1481 @@
+//! PNG rendering of simulation fields.
+//!
+//! Text (titles, colorbar labels) is drawn with `text`'s own bitmap font
+//! rather than a system-font-backed text-drawing library, so it can't fail
+//! from a missing font on a minimal container the way something built on
+//! `plotters` or `fontdue` could — there's no font file to be missing. Every
+//! `save_*` function still takes a `draw_text` flag (the CLI's `--no-text`)
+//! for callers who want plain field images with no glyphs on them at all.
+
+use image::{ImageBuffer, Rgb, RgbImage};
+use rand::SeedableRng;
+use rayon::prelude::*;
+
+use crate::fluid::{BoundaryCondition, Fluid};
+use crate::particle_tracer::Particle;
+use crate::radiator::Radiator;
+use crate::render;
+use crate::text;
+
+/// Width, in pixels, reserved to the right of the field for the colorbar
+/// itself plus its min/max tick labels.
+const COLORBAR_MARGIN: u32 = 34;
+const COLORBAR_WIDTH: u32 = 8;
+/// Height, in pixels, reserved above the field for an optional title. Only
+/// added to the canvas when a title is actually given.
+const TITLE_MARGIN: u32 = text::GLYPH_HEIGHT_PX + 2;
+
+/// The single world-to-pixel convention every field and overlay in this
+/// module draws through: world `y` increases upward, image `y` increases
+/// downward, and a cell's own `(i, j)` index — not any re-derivation from a
+/// continuous coordinate — decides its pixel. Drawing field pixels and
+/// overlay geometry from two different formulas is exactly how they drift
+/// apart by a cell at low resolution; going through one function makes that
+/// impossible.
+fn cell_to_pixel(num_y: usize, i: usize, j: usize) -> (u32, u32) {
+    (i as u32, (num_y - 1 - j) as u32)
+}
+
+/// Same convention as [`cell_to_pixel`], but for a continuous world
+/// coordinate (a streamline vertex, say) rather than a cell index: the point
+/// is truncated to the cell it falls in first, so a point and the cell
+/// containing it always land on the same pixel. Returns `None` outside the
+/// domain.
+fn world_to_pixel(fluid: &Fluid, x: f64, y: f64) -> Option<(i64, i64)> {
+    let i = (x / fluid.h).floor();
+    let j = (y / fluid.h).floor();
+    if i < 0.0 || j < 0.0 || i >= fluid.num_x as f64 || j >= fluid.num_y as f64 {
+        return None;
+    }
+    let (px, py) = cell_to_pixel(fluid.num_y, i as usize, j as usize);
+    Some((px as i64, py as i64))
+}
+
+/// Scientific "jet-like" colormap over `[min_val, max_val]`, matching the
+/// classic Ten Minute Physics fluid demo.
+pub fn get_sci_color(val: f64, min_val: f64, max_val: f64) -> [u8; 3] {
+    let val = val.max(min_val).min(max_val - 0.0001);
+    let d = max_val - min_val;
+    let val = if d == 0.0 { 0.5 } else { (val - min_val) / d };
+    let m = 0.25;
+    let num = (val / m).floor();
+    let s = (val - num * m) / m;
+
+    let (r, g, b) = match num as i32 {
+        0 => (0.0, s, 1.0),
+        1 => (0.0, 1.0, 1.0 - s),
+        2 => (s, 1.0, 0.0),
+        _ => (1.0, 1.0 - s, 0.0),
+    };
+
+    [(255.0 * r) as u8, (255.0 * g) as u8, (255.0 * b) as u8]
+}
+
+/// How a field plot picks its colormap range. A single [`Visualizer`] call
+/// has no memory of previous frames, so `RunningMax` resolves the same as
+/// `Auto` here — [`crate::animator::Animator`] is what actually accumulates
+/// a running range across a sequence and passes each frame a resolved
+/// `Fixed` range in `RunningMax` mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorScale {
+    /// Rescale to this frame's own min/max every time. Simple, but a
+    /// sequence of frames will flicker as the range shifts frame to frame.
+    Auto,
+    /// Always use this caller-chosen `(min, max)`.
+    Fixed(f64, f64),
+    /// Track the widest range seen so far across a sequence of frames and
+    /// reuse it, so the scale only ever grows and never flickers back down.
+    RunningMax,
+}
+
+impl ColorScale {
+    fn resolve(self) -> Option<(f64, f64)> {
+        match self {
+            ColorScale::Auto | ColorScale::RunningMax => None,
+            ColorScale::Fixed(min, max) => Some((min, max)),
+        }
+    }
+}
+
+pub struct Visualizer;
+
+impl Visualizer {
+    /// Cell-centered pressure field, rendered with `get_sci_color` and row 0
+    /// (the bottom of the domain) placed at the bottom of the image.
+    /// `radiators` are porous fluid cells rather than solid ones (this crate
+    /// only ever assigns `Fluid::s` a hard `0.0`/`1.0`; a radiator's
+    /// resistance is a velocity source term, never a fractional `s`), so
+    /// they wouldn't otherwise show up against the surrounding flow; each
+    /// one is outlined so a multi-radiator scene stays readable at a
+    /// glance. Solid (`s == 0`) cells hold whatever pressure the solver
+    /// last left in them and are excluded from the color range and drawn
+    /// in a fixed dark grey instead — see [`render::finite_range_masked`].
+    ///
+    /// `title` is drawn above the field if given. `scale` picks how the
+    /// colorbar (and color mapping) is ranged — see [`ColorScale`].
+    /// `draw_text` controls whether the title and colorbar labels are drawn
+    /// at all — the CLI's `--no-text` threads through to this for callers
+    /// who want plain field images with no glyphs on them.
+    pub fn save_pressure_field(
+        fluid: &Fluid,
+        radiators: &[Radiator],
+        title: Option<&str>,
+        scale: ColorScale,
+        path: &str,
+        draw_text: bool,
+    ) -> Result<(), image::ImageError> {
+        let range = render::finite_range_masked(&fluid.p, &fluid.s);
+        if let Some(warning) = render::non_finite_warning("pressure", &range) {
+            eprintln!("{warning}");
+        }
+        let (min_p, max_p) = scale.resolve().unwrap_or((range.min, range.max));
+
+        let (mut img, top) = new_canvas(fluid.num_x, fluid.num_y, title, draw_text);
+        for i in 0..fluid.num_x {
+            for j in 0..fluid.num_y {
+                let idx = fluid.idx(i, j);
+                let color = if fluid.s[idx] == crate::fluid::SOLID_CELL {
+                    [50, 50, 50]
+                } else if !fluid.p[idx].is_finite() {
+                    render::NON_FINITE_SENTINEL_COLOR
+                } else {
+                    get_sci_color(fluid.p[idx], min_p, max_p)
+                };
+                let (px, py) = cell_to_pixel(fluid.num_y, i, j);
+                img.put_pixel(px, top + py, Rgb(color));
+            }
+        }
+        overlay_radiator_outlines(&mut img, fluid, radiators, top);
+        draw_legend(&mut img, fluid.num_x, top, title, min_p, max_p, "PA", draw_text, |v| {
+            get_sci_color(v, min_p, max_p)
+        });
+        img.save(path)
+    }
+
+    /// Same rendering as [`Self::save_pressure_field`], but reads `mean_p`
+    /// (a [`crate::field_statistics::FieldStatistics::mean_p`] array, one
+    /// entry per cell) instead of `fluid.p`'s instantaneous snapshot.
+    /// `fluid` still supplies the grid geometry, solid mask, and radiator
+    /// overlay, exactly as [`crate::metrics::RadiatorMetrics::compute_from_mean`]
+    /// reads through-radiator velocity from a mean field while everything
+    /// else about the radiator comes from `fluid`.
+    pub fn save_mean_pressure_field(
+        fluid: &Fluid,
+        mean_p: &[f64],
+        radiators: &[Radiator],
+        title: Option<&str>,
+        scale: ColorScale,
+        path: &str,
+        draw_text: bool,
+    ) -> Result<(), image::ImageError> {
+        let range = render::finite_range_masked(mean_p, &fluid.s);
+        if let Some(warning) = render::non_finite_warning("mean pressure", &range) {
+            eprintln!("{warning}");
+        }
+        let (min_p, max_p) = scale.resolve().unwrap_or((range.min, range.max));
+
+        let (mut img, top) = new_canvas(fluid.num_x, fluid.num_y, title, draw_text);
+        for i in 0..fluid.num_x {
+            for j in 0..fluid.num_y {
+                let idx = fluid.idx(i, j);
+                let color = if fluid.s[idx] == crate::fluid::SOLID_CELL {
+                    [50, 50, 50]
+                } else if !mean_p[idx].is_finite() {
+                    render::NON_FINITE_SENTINEL_COLOR
+                } else {
+                    get_sci_color(mean_p[idx], min_p, max_p)
+                };
+                let (px, py) = cell_to_pixel(fluid.num_y, i, j);
+                img.put_pixel(px, top + py, Rgb(color));
+            }
+        }
+        overlay_radiator_outlines(&mut img, fluid, radiators, top);
+        draw_legend(&mut img, fluid.num_x, top, title, min_p, max_p, "PA", draw_text, |v| {
+            get_sci_color(v, min_p, max_p)
+        });
+        img.save(path)
+    }
+
+    /// Turbulence-intensity-like field: `rms_speed` (a
+    /// [`crate::field_statistics::FieldStatistics::rms_speed`] array) colored
+    /// the same way as [`Self::save_pressure_field`] — high where
+    /// instantaneous speed swings widely around its mean (an unsteady wake),
+    /// near zero in steady free-stream flow.
+    pub fn save_turbulence_intensity_field(
+        fluid: &Fluid,
+        rms_speed: &[f64],
+        radiators: &[Radiator],
+        title: Option<&str>,
+        scale: ColorScale,
+        path: &str,
+        draw_text: bool,
+    ) -> Result<(), image::ImageError> {
+        let range = render::finite_range_masked(rms_speed, &fluid.s);
+        if let Some(warning) = render::non_finite_warning("turbulence intensity", &range) {
+            eprintln!("{warning}");
+        }
+        let (min_i, max_i) = scale.resolve().unwrap_or((0.0, range.max));
+
+        let (mut img, top) = new_canvas(fluid.num_x, fluid.num_y, title, draw_text);
+        for i in 0..fluid.num_x {
+            for j in 0..fluid.num_y {
+                let idx = fluid.idx(i, j);
+                let color = if fluid.s[idx] == crate::fluid::SOLID_CELL {
+                    [50, 50, 50]
+                } else if !rms_speed[idx].is_finite() {
+                    render::NON_FINITE_SENTINEL_COLOR
+                } else {
+                    get_sci_color(rms_speed[idx], min_i, max_i)
+                };
+                let (px, py) = cell_to_pixel(fluid.num_y, i, j);
+                img.put_pixel(px, top + py, Rgb(color));
+            }
+        }
+        overlay_radiator_outlines(&mut img, fluid, radiators, top);
+        draw_legend(&mut img, fluid.num_x, top, title, min_i, max_i, "M/S", draw_text, |v| {
+            get_sci_color(v, min_i, max_i)
+        });
+        img.save(path)
+    }
+
+    /// Cell-centered velocity magnitude (`sqrt(avg_u^2 + avg_v^2)`, averaged
+    /// from the staggered `u`/`v` faces the same way [`Self::save_vorticity_field`]
+    /// does), rendered with `get_sci_color` the same way as
+    /// [`Self::save_pressure_field`] — most people want to see speed before
+    /// pressure or an arrow plot, and this gives that as a single scalar
+    /// field instead of a handful of sparse arrows. `avg_u`/`avg_v` are only
+    /// defined for interior cells, so the outermost ring is left at `0.0`,
+    /// same boundary this crate's vorticity field leaves untouched.
+    pub fn save_velocity_magnitude_field(
+        fluid: &Fluid,
+        radiators: &[Radiator],
+        title: Option<&str>,
+        scale: ColorScale,
+        path: &str,
+        draw_text: bool,
+    ) -> Result<(), image::ImageError> {
+        let mut speed = vec![0.0_f64; fluid.u.len()];
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                let idx = fluid.idx(i, j);
+                if fluid.s[idx] == crate::fluid::SOLID_CELL {
+                    continue;
+                }
+                speed[idx] = (fluid.avg_u(i, j).powi(2) + fluid.avg_v(i, j).powi(2)).sqrt();
+            }
+        }
+        let range = render::finite_range_masked(&speed, &fluid.s);
+        if let Some(warning) = render::non_finite_warning("velocity magnitude", &range) {
+            eprintln!("{warning}");
+        }
+        let (min_speed, max_speed) = scale.resolve().unwrap_or((range.min, range.max));
+
+        let (mut img, top) = new_canvas(fluid.num_x, fluid.num_y, title, draw_text);
+        for i in 0..fluid.num_x {
+            for j in 0..fluid.num_y {
+                let idx = fluid.idx(i, j);
+                let color = if fluid.s[idx] == crate::fluid::SOLID_CELL {
+                    [50, 50, 50]
+                } else if !speed[idx].is_finite() {
+                    render::NON_FINITE_SENTINEL_COLOR
+                } else {
+                    get_sci_color(speed[idx], min_speed, max_speed)
+                };
+                let (px, py) = cell_to_pixel(fluid.num_y, i, j);
+                img.put_pixel(px, top + py, Rgb(color));
+            }
+        }
+        overlay_radiator_outlines(&mut img, fluid, radiators, top);
+        draw_legend(&mut img, fluid.num_x, top, title, min_speed, max_speed, "M/S", draw_text, |v| {
+            get_sci_color(v, min_speed, max_speed)
+        });
+        img.save(path)
+    }
+
+    /// Line-integral-convolution image of the velocity field: a white-noise
+    /// texture advected forward and backward along the local flow direction
+    /// for a fixed arc length (`kernel_length`, in the same physical units
+    /// as `Radiator`/`Obstacle` positions), so its streaks trace flow
+    /// structure the way a handful of discrete streamlines or arrows can't.
+    /// `upsample` is how many image pixels each cell edge maps to (`1`
+    /// renders at the same resolution as every other `save_*` field, which
+    /// looks blocky — LIC wants several pixels per cell to resolve its
+    /// streaks). `seed` makes the noise texture reproducible.
+    /// `colorize_by_speed` multiplies the grayscale LIC value by
+    /// [`get_sci_color`] of the local speed (scaled by `scale`, same as
+    /// [`Self::save_pressure_field`]) instead of leaving it plain
+    /// grayscale, and draws a colorbar; a plain grayscale render skips the
+    /// colorbar (nothing to label) but still draws `title`. Solid cells are
+    /// painted black rather than integrated through.
+    ///
+    /// Every pixel's integration only reads `fluid` and the shared noise
+    /// texture, so this is parallelized per-pixel with `rayon` — at a
+    /// useful `upsample` this is the most compute-heavy image this crate
+    /// renders.
+    #[allow(clippy::too_many_arguments)]
+    pub fn save_lic_field(
+        fluid: &Fluid,
+        kernel_length: f64,
+        upsample: usize,
+        colorize_by_speed: bool,
+        seed: u64,
+        title: Option<&str>,
+        scale: ColorScale,
+        path: &str,
+        draw_text: bool,
+    ) -> Result<(), image::ImageError> {
+        let upsample = upsample.max(1);
+        let width = fluid.num_x * upsample;
+        let height = fluid.num_y * upsample;
+        let px_h = fluid.h / upsample as f64;
+
+        // `avg_u`/`avg_v` read one neighbor cell in each direction, so they're
+        // only defined for the interior — same boundary `avg_u`/`avg_v`
+        // relies on wherever else this crate calls it (e.g.
+        // `save_vorticity_field`'s `1..num_x - 1` loop).
+        let interior_i = |i: usize| i.clamp(1, fluid.num_x - 2);
+        let interior_j = |j: usize| j.clamp(1, fluid.num_y - 2);
+
+        let mut speed_field = vec![0.0_f64; fluid.num_x * fluid.num_y];
+        for i in 0..fluid.num_x {
+            for j in 0..fluid.num_y {
+                let idx = fluid.idx(i, j);
+                speed_field[idx] = (fluid.avg_u(interior_i(i), interior_j(j)).powi(2)
+                    + fluid.avg_v(interior_i(i), interior_j(j)).powi(2))
+                .sqrt();
+            }
+        }
+        let range = render::finite_range_masked(&speed_field, &fluid.s);
+        let (min_speed, max_speed) = scale.resolve().unwrap_or((range.min, range.max));
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let noise: Vec<f32> = (0..width * height).map(|_| rand::Rng::gen(&mut rng)).collect();
+
+        // Half a LIC pixel per sub-step keeps the backtrace from skipping
+        // over the noise texture's own resolution, the same reasoning
+        // `advection_cfl_threshold` applies to the velocity solve's own
+        // advection step.
+        let step = px_h * 0.5;
+        let steps_each_direction = ((kernel_length * 0.5) / step).round().max(1.0) as usize;
+        let domain_width = fluid.domain_width();
+        let domain_height = fluid.domain_height();
+
+        let pixels: Vec<[u8; 3]> = (0..width * height)
+            .into_par_iter()
+            .map(|p| {
+                let px = p % width;
+                let py = p / width;
+                let x = (px as f64 + 0.5) * px_h;
+                let y = domain_height - (py as f64 + 0.5) * px_h;
+
+                let ci = ((x / fluid.h) as usize).min(fluid.num_x - 1);
+                let cj = ((y / fluid.h) as usize).min(fluid.num_y - 1);
+                if fluid.s[fluid.idx(ci, cj)] == crate::fluid::SOLID_CELL {
+                    return [0, 0, 0];
+                }
+
+                let mut sum = noise[p] as f64;
+                let mut count = 1.0_f64;
+                for sign in [1.0, -1.0] {
+                    let (mut cx, mut cy) = (x, y);
+                    for _ in 0..steps_each_direction {
+                        let (u, v) = fluid.sample_velocity(cx, cy);
+                        let speed = (u * u + v * v).sqrt();
+                        if speed < 1e-9 {
+                            break;
+                        }
+                        cx += sign * u / speed * step;
+                        cy += sign * v / speed * step;
+                        if cx < 0.0 || cy < 0.0 || cx >= domain_width || cy >= domain_height {
+                            break;
+                        }
+                        let si = ((cx / fluid.h) as usize).min(fluid.num_x - 1);
+                        let sj = ((cy / fluid.h) as usize).min(fluid.num_y - 1);
+                        if fluid.s[fluid.idx(si, sj)] == crate::fluid::SOLID_CELL {
+                            break;
+                        }
+                        sum += sample_noise_bilinear(&noise, width, height, px_h, domain_height, cx, cy);
+                        count += 1.0;
+                    }
+                }
+                let gray = (sum / count).clamp(0.0, 1.0);
+                if colorize_by_speed {
+                    let speed = (fluid.avg_u(interior_i(ci), interior_j(cj)).powi(2)
+                        + fluid.avg_v(interior_i(ci), interior_j(cj)).powi(2))
+                    .sqrt();
+                    let [r, g, b] = get_sci_color(speed, min_speed, max_speed);
+                    [(r as f64 * gray) as u8, (g as f64 * gray) as u8, (b as f64 * gray) as u8]
+                } else {
+                    let shade = (gray * 255.0) as u8;
+                    [shade, shade, shade]
+                }
+            })
+            .collect();
+
+        let (mut img, top) = new_canvas(width, height, title, draw_text);
+        for (p, color) in pixels.into_iter().enumerate() {
+            let px = (p % width) as u32;
+            let py = (p / width) as u32;
+            img.put_pixel(px, top + py, Rgb(color));
+        }
+        if colorize_by_speed {
+            draw_legend(&mut img, width, top, title, min_speed, max_speed, "M/S", draw_text, |v| {
+                get_sci_color(v, min_speed, max_speed)
+            });
+        } else if draw_text {
+            if let Some(title) = title {
+                text::draw_text(&mut img, 1, 0, title, Rgb([255, 255, 255]));
+            }
+        }
+        img.save(path)
+    }
+
+    /// Cell-centered dye concentration field, greyscale from 0 (black) to 1
+    /// (white). Pixel shade is always `m.clamp(0, 1)` regardless of `scale` —
+    /// only the colorbar's labeled range changes, since concentration is
+    /// already bounded.
+    /// `draw_text` — see [`Self::save_pressure_field`].
+    pub fn save_smoke_field(
+        fluid: &Fluid,
+        radiators: &[Radiator],
+        title: Option<&str>,
+        scale: ColorScale,
+        path: &str,
+        draw_text: bool,
+    ) -> Result<(), image::ImageError> {
+        let non_finite = render::finite_range(&fluid.m);
+        if let Some(warning) = render::non_finite_warning("smoke", &non_finite) {
+            eprintln!("{warning}");
+        }
+        let (min_m, max_m) = scale.resolve().unwrap_or((0.0, 1.0));
+        let (mut img, top) = new_canvas(fluid.num_x, fluid.num_y, title, draw_text);
+        for i in 0..fluid.num_x {
+            for j in 0..fluid.num_y {
+                let idx = fluid.idx(i, j);
+                let color = if fluid.s[idx] == crate::fluid::SOLID_CELL {
+                    [50, 50, 50]
+                } else if !fluid.m[idx].is_finite() {
+                    render::NON_FINITE_SENTINEL_COLOR
+                } else {
+                    let shade = (fluid.m[idx].clamp(0.0, 1.0) * 255.0) as u8;
+                    [shade, shade, shade]
+                };
+                let (px, py) = cell_to_pixel(fluid.num_y, i, j);
+                img.put_pixel(px, top + py, Rgb(color));
+            }
+        }
+        overlay_radiator_outlines(&mut img, fluid, radiators, top);
+        draw_legend(&mut img, fluid.num_x, top, title, min_m, max_m, "SMOKE", draw_text, |v| {
+            let shade = (v.clamp(0.0, 1.0) * 255.0) as u8;
+            [shade, shade, shade]
+        });
+        img.save(path)
+    }
+
+    /// Per-cell solid coverage fraction (`1.0 - s`), greyscale from 0
+    /// (black, fully fluid) to 1 (white, fully solid) — the debug view for
+    /// [`crate::scene::mark_obstacle_solid_cut_cell`]'s supersampled
+    /// coverage. Unlike [`Self::save_smoke_field`], solid cells don't get
+    /// the usual grey solid-cell override: showing exactly how solid each
+    /// cell is, including the partial values between the old binary 0/1,
+    /// is the entire point of this plot.
+    /// `draw_text` — see [`Self::save_pressure_field`].
+    pub fn save_solid_fraction_field(
+        fluid: &Fluid,
+        radiators: &[Radiator],
+        title: Option<&str>,
+        path: &str,
+        draw_text: bool,
+    ) -> Result<(), image::ImageError> {
+        let (mut img, top) = new_canvas(fluid.num_x, fluid.num_y, title, draw_text);
+        for i in 0..fluid.num_x {
+            for j in 0..fluid.num_y {
+                let idx = fluid.idx(i, j);
+                let fraction = 1.0 - fluid.s[idx];
+                let color = if !fraction.is_finite() {
+                    render::NON_FINITE_SENTINEL_COLOR
+                } else {
+                    let shade = (fraction.clamp(0.0, 1.0) * 255.0) as u8;
+                    [shade, shade, shade]
+                };
+                let (px, py) = cell_to_pixel(fluid.num_y, i, j);
+                img.put_pixel(px, top + py, Rgb(color));
+            }
+        }
+        overlay_radiator_outlines(&mut img, fluid, radiators, top);
+        draw_legend(&mut img, fluid.num_x, top, title, 0.0, 1.0, "SOLID FRAC", draw_text, |v| {
+            let shade = (v.clamp(0.0, 1.0) * 255.0) as u8;
+            [shade, shade, shade]
+        });
+        img.save(path)
+    }
+
+    /// Vorticity ω = ∂v/∂x − ∂u/∂y at cell centers, rendered with a diverging
+    /// blue-white-red colormap centered at zero (the `get_sci_color` jet
+    /// colormap isn't suitable for a signed field). `clamp_percentile`
+    /// (e.g. 0.98) clips the color range to that percentile of `|ω|` so a
+    /// few extreme cells right at solid boundaries don't wash out the rest,
+    /// unless `scale` overrides it with an explicit `(-clamp, clamp)`.
+    /// `draw_text` — see [`Self::save_pressure_field`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn save_vorticity_field(
+        fluid: &Fluid,
+        radiators: &[Radiator],
+        clamp_percentile: f64,
+        title: Option<&str>,
+        scale: ColorScale,
+        path: &str,
+        draw_text: bool,
+    ) -> Result<(), image::ImageError> {
+        let n = fluid.num_y;
+        let h = fluid.h;
+        let mut omega = vec![0.0_f64; fluid.u.len()];
+
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                let idx = i * n + j;
+                if fluid.s[idx] == crate::fluid::SOLID_CELL {
+                    continue;
+                }
+                let u_top = fluid.avg_u(i, j + 1);
+                let u_bot = fluid.avg_u(i, j);
+                let v_right = fluid.avg_v(i + 1, j);
+                let v_left = fluid.avg_v(i, j);
+                omega[idx] = (v_right - v_left) / h - (u_top - u_bot) / h;
+            }
+        }
+
+        let non_finite = render::finite_range(&omega);
+        if let Some(warning) = render::non_finite_warning("vorticity", &non_finite) {
+            eprintln!("{warning}");
+        }
+        let clamp = if let Some((min, max)) = scale.resolve() {
+            min.abs().max(max.abs()).max(1e-9)
+        } else {
+            let mut mags: Vec<f64> = omega
+                .iter()
+                .zip(fluid.s.iter())
+                .filter(|(_, &s)| s != crate::fluid::SOLID_CELL)
+                .map(|(&w, _)| w.abs())
+                .filter(|w| w.is_finite())
+                .collect();
+            mags.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            if mags.is_empty() {
+                1.0
+            } else {
+                let idx = ((mags.len() - 1) as f64 * clamp_percentile.clamp(0.0, 1.0)) as usize;
+                mags[idx].max(1e-9)
+            }
+        };
+
+        let (mut img, top) = new_canvas(fluid.num_x, fluid.num_y, title, draw_text);
+        for i in 0..fluid.num_x {
+            for j in 0..fluid.num_y {
+                let idx = fluid.idx(i, j);
+                let color = if fluid.s[idx] == crate::fluid::SOLID_CELL {
+                    [50, 50, 50]
+                } else if !omega[idx].is_finite() {
+                    render::NON_FINITE_SENTINEL_COLOR
+                } else {
+                    diverging_color(omega[idx], clamp)
+                };
+                let (px, py) = cell_to_pixel(fluid.num_y, i, j);
+                img.put_pixel(px, top + py, Rgb(color));
+            }
+        }
+        overlay_radiator_outlines(&mut img, fluid, radiators, top);
+        draw_legend(&mut img, fluid.num_x, top, title, -clamp, clamp, "1/S", draw_text, |v| {
+            diverging_color(v, clamp)
+        });
+        img.save(path)
+    }
+
+    /// Trace streamlines from a row of seed points spanning the inlet and
+    /// draw them over the smoke field. For seeding options, bidirectional
+    /// tracing, RK2/RK4, or an arc-length cap (e.g. to catch a radiator's
+    /// recirculation zone rather than only what the inlet scan reaches), call
+    /// [`trace_streamlines_with_options`] and [`draw_streamlines`] directly —
+    /// there's no `Animator` equivalent of this method, since [`crate::animator::Animator`]
+    /// only ever accumulates a `Pressure`/`Smoke` color range across frames
+    /// and has no streamline-drawing path to extend.
+    pub fn save_streamlines(
+        fluid: &Fluid,
+        radiators: &[Radiator],
+        num_seeds: usize,
+        path: &str,
+    ) -> Result<(), image::ImageError> {
+        let lines = trace_streamlines(fluid, num_seeds);
+        draw_streamlines(fluid, radiators, &lines, path)
+    }
+
+    /// Draw a [`crate::particle_tracer::ParticleTracer`]'s current particles
+    /// as an [`draw_particles`] panel. A thin wrapper for symmetry with
+    /// [`Self::save_streamlines`] — like streamlines and vorticity, particles
+    /// render as their own artifact called directly from the step loop
+    /// rather than through [`crate::animator::Animator`], which only ever
+    /// captures a `Pressure`/`Smoke` frame.
+    pub fn save_particles(
+        fluid: &Fluid,
+        radiators: &[Radiator],
+        particles: &[Particle],
+        max_age: f64,
+        path: &str,
+    ) -> Result<(), image::ImageError> {
+        draw_particles(fluid, radiators, particles, max_age, path)
+    }
+}
+
+/// One vertex of a traced streamline: physical domain coordinates plus the
+/// interpolated speed there, so a data export can plot speed along the path
+/// without re-tracing it.
+#[derive(Debug, Clone, Copy)]
+pub struct StreamlineVertex {
+    pub x: f64,
+    pub y: f64,
+    pub speed: f64,
+}
+
+/// Why a streamline stopped growing, carried through to data exports so a
+/// downstream plot can distinguish "left the domain" from "died in a wake".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminationReason {
+    LeftDomain,
+    Stagnated,
+    MaxSteps,
+    /// Hit [`StreamlineOptions::max_arc_length`] before either of the above —
+    /// the usual outcome for a line seeded into a recirculation zone, which
+    /// would otherwise wind in circles until `MaxSteps` for no extra insight.
+    MaxArcLength,
+    /// The next step would have landed inside a solid cell (`s == SOLID_CELL`).
+    /// `Fluid::sample_velocity` interpolates as if every stencil point were
+    /// fluid, so without this check a step landing just past an obstacle's
+    /// surface would sample a blend that includes the (usually near-zero)
+    /// solid-side value and the line would keep going, straight through the
+    /// obstacle instead of stopping at it.
+    HitObstacle,
+}
+
+/// Which way a [`Streamline`] was integrated from its seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceDirection {
+    Forward,
+    Backward,
+    /// Traced both ways from the seed and stitched into one path, ordered
+    /// upstream-to-downstream (backward half reversed, then the forward
+    /// half). `termination` reports the forward half's outcome, since that's
+    /// the direction the CSV/GeoJSON exporters and existing callers expect.
+    Both,
+}
+
+/// Numerical scheme used to advance a streamline through the velocity field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrationScheme {
+    Euler,
+    Rk2,
+    Rk4,
+}
+
+/// Where to seed streamlines. `LeftEdge` reproduces [`trace_streamlines`]'s
+/// long-standing inlet-scan behavior; the others exist because a fixed
+/// left-edge scan can miss recirculation zones that never touch the inlet.
+#[derive(Debug, Clone)]
+pub enum SeedPlacement {
+    /// `count` points evenly spaced down the left edge, one cell in from the
+    /// inlet boundary.
+    LeftEdge { count: usize },
+    /// `rows x cols` points evenly spaced over the interior of the domain.
+    UniformGrid { rows: usize, cols: usize },
+    /// Caller-supplied seed points, in physical (world) coordinates.
+    Points(Vec<(f64, f64)>),
+    /// `count` points on a circle of `radius` around `center`, in physical
+    /// coordinates — useful for rings of seeds around a radiator to catch
+    /// its wake.
+    AroundPoint { center: (f64, f64), radius: f64, count: usize },
+}
+
+/// Configuration for [`trace_streamlines_with_options`]. [`trace_streamlines`]
+/// is a thin wrapper that builds the options this module has always used
+/// (left-edge seeding, forward, Euler, no arc-length cap) so existing callers
+/// keep their exact prior behavior.
+#[derive(Debug, Clone)]
+pub struct StreamlineOptions {
+    pub seeds: SeedPlacement,
+    pub direction: TraceDirection,
+    pub scheme: IntegrationScheme,
+    /// Stop a line once it has traveled this far through the domain,
+    /// regardless of step count. `f64::INFINITY` disables the cap (the old
+    /// behavior, bounded only by `max_steps`). This is what keeps a line
+    /// caught in a recirculation zone from winding in circles for the full
+    /// step budget.
+    pub max_arc_length: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Streamline {
+    pub seed: (f64, f64),
+    pub vertices: Vec<StreamlineVertex>,
+    pub termination: TerminationReason,
+    pub direction: TraceDirection,
+}
+
+/// Trace streamlines from `num_seeds` points evenly spaced across the inlet,
+/// using simple forward Euler steps through the interpolated velocity field.
+/// Shared by [`Visualizer::save_streamlines`] and the CSV/GeoJSON exporters
+/// so both draw from the exact same traced vertices.
+///
+/// A thin wrapper around [`trace_streamlines_with_options`] that reproduces
+/// this function's original behavior exactly, so existing callers don't need
+/// to change. Note the step size here is `fluid.h`-relative
+/// (`fluid.h * 0.5`), not the fixed `dt = 0.01` sometimes assumed of this
+/// module — it never was fixed, and `trace_streamlines_with_options`'s
+/// adaptive stepping is `h`-relative too.
+pub fn trace_streamlines(fluid: &Fluid, num_seeds: usize) -> Vec<Streamline> {
+    trace_streamlines_with_options(
+        fluid,
+        &StreamlineOptions {
+            seeds: SeedPlacement::LeftEdge { count: num_seeds },
+            direction: TraceDirection::Forward,
+            scheme: IntegrationScheme::Euler,
+            max_arc_length: f64::INFINITY,
+        },
+    )
+}
+
+/// Trace streamlines under full [`StreamlineOptions`] control: seed
+/// placement, direction, integration scheme, and an arc-length cap so a line
+/// caught in a recirculation zone terminates early instead of winding in
+/// circles until `max_steps`.
+///
+/// Step size is adaptive and `fluid.h`-relative rather than fixed, scaled
+/// down where the flow is fast so a step doesn't overshoot a thin feature,
+/// and clamped so a near-stagnant point doesn't take one enormous leap.
+pub fn trace_streamlines_with_options(fluid: &Fluid, options: &StreamlineOptions) -> Vec<Streamline> {
+    let max_steps = fluid.num_x * 4;
+    generate_seeds(fluid, &options.seeds)
+        .into_iter()
+        .map(|seed| trace_one(fluid, seed, options, max_steps))
+        .collect()
+}
+
+/// Expand a [`SeedPlacement`] into concrete `(x, y)` world-coordinate seed
+/// points. Points falling outside the fluid interior aren't filtered here —
+/// [`trace_one`] terminates immediately (`LeftDomain`, zero extra vertices)
+/// for a seed that starts out of bounds, which is a clear enough signal for
+/// a `Points`/`AroundPoint` caller who picked a bad location.
+fn generate_seeds(fluid: &Fluid, placement: &SeedPlacement) -> Vec<(f64, f64)> {
+    let h = fluid.h;
+    match placement {
+        SeedPlacement::LeftEdge { count } => {
+            let count = (*count).max(1);
+            (0..count)
+                .map(|s| {
+                    let seed_x = h * 1.5;
+                    let seed_y = h + (s as f64 + 0.5) * (fluid.domain_height() - 2.0 * h) / count as f64;
+                    (seed_x, seed_y)
+                })
+                .collect()
+        }
+        SeedPlacement::UniformGrid { rows, cols } => {
+            let rows = (*rows).max(1);
+            let cols = (*cols).max(1);
+            let x_lo = h * 1.5;
+            let x_hi = (fluid.num_x as f64 - 1.5) * h;
+            let y_lo = h * 1.5;
+            let y_hi = (fluid.num_y as f64 - 1.5) * h;
+            let mut seeds = Vec::with_capacity(rows * cols);
+            for r in 0..rows {
+                for c in 0..cols {
+                    let fx = if cols > 1 { c as f64 / (cols - 1) as f64 } else { 0.5 };
+                    let fy = if rows > 1 { r as f64 / (rows - 1) as f64 } else { 0.5 };
+                    seeds.push((x_lo + fx * (x_hi - x_lo), y_lo + fy * (y_hi - y_lo)));
+                }
+            }
+            seeds
+        }
+        SeedPlacement::Points(points) => points.clone(),
+        SeedPlacement::AroundPoint { center, radius, count } => {
+            let count = (*count).max(1);
+            (0..count)
+                .map(|i| {
+                    let theta = 2.0 * std::f64::consts::PI * i as f64 / count as f64;
+                    (center.0 + radius * theta.cos(), center.1 + radius * theta.sin())
+                })
+                .collect()
+        }
+    }
+}
+
+/// Adaptive step size: half a cell width's worth of travel at the local
+/// speed, clamped to `[0.01h, 2h]` so a near-stagnant point doesn't take one
+/// enormous leap and a fast one doesn't take a step so small the arc-length
+/// cap never triggers.
+fn adaptive_dt(h: f64, speed: f64) -> f64 {
+    (0.5 * h / speed.max(1e-3)).clamp(h * 0.01, h * 2.0)
+}
+
+/// One RK stage's effective velocity for a step of (possibly negative, for
+/// backward tracing) duration `dt_signed` starting at `(x, y)`.
+fn integrate_step(fluid: &Fluid, x: f64, y: f64, dt_signed: f64, scheme: IntegrationScheme) -> (f64, f64) {
+    match scheme {
+        IntegrationScheme::Euler => fluid.sample_velocity(x, y),
+        IntegrationScheme::Rk2 => {
+            let (u0, v0) = fluid.sample_velocity(x, y);
+            let (u1, v1) = fluid.sample_velocity(x + u0 * dt_signed, y + v0 * dt_signed);
+            ((u0 + u1) * 0.5, (v0 + v1) * 0.5)
+        }
+        IntegrationScheme::Rk4 => {
+            let (k1u, k1v) = fluid.sample_velocity(x, y);
+            let (k2u, k2v) = fluid.sample_velocity(x + 0.5 * dt_signed * k1u, y + 0.5 * dt_signed * k1v);
+            let (k3u, k3v) = fluid.sample_velocity(x + 0.5 * dt_signed * k2u, y + 0.5 * dt_signed * k2v);
+            let (k4u, k4v) = fluid.sample_velocity(x + dt_signed * k3u, y + dt_signed * k3v);
+            (
+                (k1u + 2.0 * k2u + 2.0 * k3u + k4u) / 6.0,
+                (k1v + 2.0 * k2v + 2.0 * k3v + k4v) / 6.0,
+            )
+        }
+    }
+}
+
+/// Trace a single streamline from `seed` under `options`, in whichever
+/// direction(s) it requests. `TraceDirection::Both` traces forward and
+/// backward independently, then stitches the two halves into one path
+/// ordered upstream-to-downstream.
+fn trace_one(fluid: &Fluid, seed: (f64, f64), options: &StreamlineOptions, max_steps: usize) -> Streamline {
+    match options.direction {
+        TraceDirection::Forward => {
+            let (vertices, termination) = trace_half(fluid, seed, false, options, max_steps);
+            Streamline { seed, vertices, termination, direction: TraceDirection::Forward }
+        }
+        TraceDirection::Backward => {
+            let (vertices, termination) = trace_half(fluid, seed, true, options, max_steps);
+            Streamline { seed, vertices, termination, direction: TraceDirection::Backward }
+        }
+        TraceDirection::Both => {
+            let (mut backward, _) = trace_half(fluid, seed, true, options, max_steps);
+            let (forward, termination) = trace_half(fluid, seed, false, options, max_steps);
+            backward.reverse();
+            backward.pop(); // drop the duplicate seed vertex; `forward` starts with it.
+            backward.extend(forward);
+            Streamline { seed, vertices: backward, termination, direction: TraceDirection::Both }
+        }
+    }
+}
+
+/// Integrate one direction from `seed`: `backward` negates every step so the
+/// same [`integrate_step`] logic runs time in reverse.
+fn trace_half(
+    fluid: &Fluid,
+    seed: (f64, f64),
+    backward: bool,
+    options: &StreamlineOptions,
+    max_steps: usize,
+) -> (Vec<StreamlineVertex>, TerminationReason) {
+    let h = fluid.h;
+    let periodic = fluid.top_bottom_boundary == BoundaryCondition::Periodic;
+    let y_lo = h;
+    let y_hi = (fluid.num_y - 1) as f64 * h;
+    let mut x = seed.0;
+    let mut y = seed.1;
+    let mut vertices = vec![StreamlineVertex { x, y, speed: 0.0 }];
+    let mut termination = TerminationReason::MaxSteps;
+    let mut arc_length = 0.0;
+
+    if x <= h || x >= (fluid.num_x - 1) as f64 * h || y <= h || y >= (fluid.num_y - 1) as f64 * h {
+        return (vertices, TerminationReason::LeftDomain);
+    }
+
+    for _ in 0..max_steps {
+        let (u0, v0) = fluid.sample_velocity(x, y);
+        if (u0 * u0 + v0 * v0).sqrt() < 1e-6 {
+            termination = TerminationReason::Stagnated;
+            break;
+        }
+        let dt = adaptive_dt(h, (u0 * u0 + v0 * v0).sqrt());
+        let dt_signed = if backward { -dt } else { dt };
+        let (u, v) = integrate_step(fluid, x, y, dt_signed, options.scheme);
+        let dx = u * dt_signed;
+        let dy = v * dt_signed;
+        x += dx;
+        y += dy;
+        arc_length += (dx * dx + dy * dy).sqrt();
+        if periodic && (y <= y_lo || y >= y_hi) {
+            y = y_lo + (y - y_lo).rem_euclid(y_hi - y_lo);
+        }
+        if x <= h || x >= (fluid.num_x - 1) as f64 * h || y <= h || y >= (fluid.num_y - 1) as f64 * h {
+            termination = TerminationReason::LeftDomain;
+            break;
+        }
+        let cell = fluid.idx((x / h) as usize, (y / h) as usize);
+        if fluid.s[cell] == crate::fluid::SOLID_CELL {
+            termination = TerminationReason::HitObstacle;
+            break;
+        }
+        vertices.push(StreamlineVertex { x, y, speed: (u * u + v * v).sqrt() });
+        if arc_length >= options.max_arc_length {
+            termination = TerminationReason::MaxArcLength;
+            break;
+        }
+    }
+
+    (vertices, termination)
+}
+
+/// Draw already-traced streamlines over the smoke field, colored by local
+/// velocity magnitude with [`get_sci_color`] (blue = slow, red = fast) rather
+/// than a single fixed color, so a recirculation zone's near-stagnant loop
+/// reads visually distinct from the free-stream. Split out of
+/// [`Visualizer::save_streamlines`] so the `render` subcommand can trace once
+/// and both draw the image and export the same vertices as data.
+pub fn draw_streamlines(
+    fluid: &Fluid,
+    radiators: &[Radiator],
+    lines: &[Streamline],
+    path: &str,
+) -> Result<(), image::ImageError> {
+    let mut img: RgbImage = ImageBuffer::new(fluid.num_x as u32, fluid.num_y as u32);
+    for i in 0..fluid.num_x {
+        for j in 0..fluid.num_y {
+            let idx = fluid.idx(i, j);
+            let color = if fluid.s[idx] == crate::fluid::SOLID_CELL {
+                [50, 50, 50]
+            } else {
+                [0, 0, 0]
+            };
+            let (px, py) = cell_to_pixel(fluid.num_y, i, j);
+            img.put_pixel(px, py, Rgb(color));
+        }
+    }
+    overlay_radiator_outlines(&mut img, fluid, radiators, 0);
+
+    // A line's first vertex is always the seed itself, with a placeholder
+    // `speed: 0.0` — no velocity has been sampled there yet, so it's excluded
+    // from the range to avoid every line dragging the color scale down to
+    // zero regardless of how fast the flow actually is.
+    let speeds: Vec<f64> = lines
+        .iter()
+        .flat_map(|line| line.vertices.iter().skip(1).map(|v| v.speed))
+        .collect();
+    let range = render::finite_range(&speeds);
+
+    for line in lines {
+        for (index, vertex) in line.vertices.iter().enumerate() {
+            let color = if index == 0 {
+                get_sci_color(range.min, range.min, range.max)
+            } else {
+                get_sci_color(vertex.speed, range.min, range.max)
+            };
+            if let Some((px, py)) = world_to_pixel(fluid, vertex.x, vertex.y) {
+                img.put_pixel(px as u32, py as u32, Rgb(color));
+            }
+        }
+    }
+
+    img.save(path)
+}
+
+/// Draw a [`ParticleTracer`](crate::particle_tracer::ParticleTracer)'s live
+/// particles as single-pixel dots over the fluid/solid background, colored
+/// by age with [`get_sci_color`] (`0` to `max_age`) rather than local speed —
+/// age is what actually distinguishes a fresh inlet particle from one that's
+/// been circulating in a wake, which is the whole point of tracking discrete
+/// particles instead of the diffused smoke field. Mirrors [`draw_streamlines`]'s
+/// background/canvas conventions so the two panels line up pixel-for-pixel.
+pub fn draw_particles(
+    fluid: &Fluid,
+    radiators: &[Radiator],
+    particles: &[Particle],
+    max_age: f64,
+    path: &str,
+) -> Result<(), image::ImageError> {
+    let mut img: RgbImage = ImageBuffer::new(fluid.num_x as u32, fluid.num_y as u32);
+    for i in 0..fluid.num_x {
+        for j in 0..fluid.num_y {
+            let idx = fluid.idx(i, j);
+            let color = if fluid.s[idx] == crate::fluid::SOLID_CELL {
+                [50, 50, 50]
+            } else {
+                [0, 0, 0]
+            };
+            let (px, py) = cell_to_pixel(fluid.num_y, i, j);
+            img.put_pixel(px, py, Rgb(color));
+        }
+    }
+    overlay_radiator_outlines(&mut img, fluid, radiators, 0);
+
+    let max_age = if max_age.is_finite() { max_age } else { 1.0 };
+    for particle in particles {
+        let color = get_sci_color(particle.age, 0.0, max_age.max(1e-9));
+        if let Some((px, py)) = world_to_pixel(fluid, particle.x, particle.y) {
+            img.put_pixel(px as u32, py as u32, Rgb(color));
+        }
+    }
+
+    img.save(path)
+}
+
+/// Outline every radiator's footprint in magenta. Radiators only damp
+/// velocity — they never mark cells solid — so without an explicit overlay a
+/// multi-radiator scene would render as an undifferentiated blob of the
+/// underlying field, making it impossible to tell how many radiators are
+/// present or where each one sits. `top` is the vertical pixel offset of the
+/// field within the canvas (nonzero once a title reserves space above it).
+fn overlay_radiator_outlines(img: &mut RgbImage, fluid: &Fluid, radiators: &[Radiator], top: u32) {
+    let h = fluid.h;
+    for radiator in radiators {
+        for i in 0..fluid.num_x {
+            for j in 0..fluid.num_y {
+                let x = i as f64 * h;
+                let y = j as f64 * h;
+                if !radiator.contains(x, y) {
+                    continue;
+                }
+                let on_edge = !radiator.contains(x + h, y)
+                    || !radiator.contains(x - h, y)
+                    || !radiator.contains(x, y + h)
+                    || !radiator.contains(x, y - h);
+                if on_edge {
+                    let (px, py) = cell_to_pixel(fluid.num_y, i, j);
+                    img.put_pixel(px, top + py, Rgb([255, 0, 255]));
+                }
+            }
+        }
+    }
+}
+
+/// Allocate the canvas for a legend-carrying field plot: `num_x` x `num_y`
+/// for the field itself, [`COLORBAR_MARGIN`] extra columns on the right for
+/// the colorbar and its tick labels, [`TITLE_MARGIN`] extra rows on top only
+/// if `title` is given and `draw_text` is on, and enough extra rows at the
+/// bottom for the unit label under the colorbar. Returns the image and the
+/// field's vertical pixel offset (`top`), since every field pixel and
+/// overlay must be shifted down by it once a title is present.
+fn new_canvas(num_x: usize, num_y: usize, title: Option<&str>, draw_text: bool) -> (RgbImage, u32) {
+    let top = if draw_text && title.is_some() { TITLE_MARGIN } else { 0 };
+    let width = num_x as u32 + COLORBAR_MARGIN;
+    let height = num_y as u32 + top + TITLE_MARGIN;
+    (ImageBuffer::new(width, height), top)
+}
+
+/// Draw the title (if any) and a vertical colorbar covering `[min, max]`
+/// next to the field, using `color_at` for the same mapping the field itself
+/// was drawn with. The colorbar gradient itself is always drawn; only its
+/// text (title, min/max, unit) is skipped when `draw_text` is off.
+#[allow(clippy::too_many_arguments)]
+fn draw_legend(
+    img: &mut RgbImage,
+    num_x: usize,
+    top: u32,
+    title: Option<&str>,
+    min: f64,
+    max: f64,
+    unit: &str,
+    draw_text: bool,
+    color_at: impl Fn(f64) -> [u8; 3],
+) {
+    let white = Rgb([255, 255, 255]);
+    if draw_text {
+        if let Some(title) = title {
+            text::draw_text(img, 1, 0, title, white);
+        }
+    }
+    let bar_x = num_x as u32 + 2;
+    let bar_height = img.height() - top - TITLE_MARGIN;
+    draw_colorbar(img, bar_x, top, COLORBAR_WIDTH, bar_height, min, max, unit, draw_text, color_at);
+}
+
+/// Vertical colorbar with `max` at the top and `min` at the bottom, matching
+/// the field's own row-0-at-the-bottom orientation, labeled with both
+/// extremes and a unit string underneath.
+#[allow(clippy::too_many_arguments)]
+fn draw_colorbar(
+    img: &mut RgbImage,
+    x0: u32,
+    y0: u32,
+    bar_width: u32,
+    bar_height: u32,
+    min: f64,
+    max: f64,
+    unit: &str,
+    draw_text: bool,
+    color_at: impl Fn(f64) -> [u8; 3],
+) {
+    let white = Rgb([255, 255, 255]);
+    let denom = (bar_height.max(2) - 1) as f64;
+    for row in 0..bar_height {
+        let value = max - (row as f64 / denom) * (max - min);
+        let color = Rgb(color_at(value));
+        for col in 0..bar_width {
+            img.put_pixel(x0 + col, y0 + row, color);
+        }
+    }
+    if !draw_text {
+        return;
+    }
+    text::draw_text(img, x0 as i64 + bar_width as i64 + 2, y0 as i64, &format!("{max:.2}"), white);
+    let min_y = y0 as i64 + bar_height as i64 - text::GLYPH_HEIGHT_PX as i64;
+    text::draw_text(img, x0 as i64 + bar_width as i64 + 2, min_y, &format!("{min:.2}"), white);
+    let unit_y = y0 as i64 + bar_height as i64 + 2;
+    text::draw_text(img, x0 as i64, unit_y, unit, white);
+}
+
+/// Blue (negative) - white (zero) - red (positive) diverging colormap, value
+/// normalized by `clamp` and saturating beyond it.
+fn diverging_color(val: f64, clamp: f64) -> [u8; 3] {
+    let t = (val / clamp).clamp(-1.0, 1.0);
+    if t >= 0.0 {
+        let g = 255.0 * (1.0 - t);
+        [255, g as u8, g as u8]
+    } else {
+        let g = 255.0 * (1.0 + t);
+        [g as u8, g as u8, 255]
+    }
+}
+
+/// Bilinear sample of the LIC noise texture at a continuous world-space
+/// point, mirroring `fluid::sample_slice`'s interpolation but over the
+/// upsampled pixel grid instead of a cell field.
+fn sample_noise_bilinear(noise: &[f32], width: usize, height: usize, px_h: f64, domain_height: f64, x: f64, y: f64) -> f64 {
+    let fx = (x / px_h - 0.5).clamp(0.0, width as f64 - 1.0);
+    let fy = ((domain_height - y) / px_h - 0.5).clamp(0.0, height as f64 - 1.0);
+    let x0 = fx.floor() as usize;
+    let y0 = fy.floor() as usize;
+    let x1 = (x0 + 1).min(width - 1);
+    let y1 = (y0 + 1).min(height - 1);
+    let tx = fx - x0 as f64;
+    let ty = fy - y0 as f64;
+    let v00 = noise[y0 * width + x0] as f64;
+    let v10 = noise[y0 * width + x1] as f64;
+    let v01 = noise[y1 * width + x0] as f64;
+    let v11 = noise[y1 * width + x1] as f64;
+    v00 * (1.0 - tx) * (1.0 - ty) + v10 * tx * (1.0 - ty) + v01 * (1.0 - tx) * ty + v11 * tx * ty
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluid::SOLID_CELL;
+
+    fn find_pixel(path: &std::path::Path, color: [u8; 3]) -> Option<(u32, u32)> {
+        let img = image::open(path).unwrap().to_rgb8();
+        (0..img.width())
+            .flat_map(|x| (0..img.height()).map(move |y| (x, y)))
+            .find(|&(x, y)| img.get_pixel(x, y).0 == color)
+    }
+
+    #[test]
+    fn solid_cell_and_matching_obstacle_outline_land_on_the_same_pixel() {
+        let h = 0.1;
+        let i0 = 4;
+        let j0 = 3;
+        let dir = std::env::temp_dir().join("lgr_2d_cfd_orientation_test");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut solid_fluid = Fluid::new(1000.0, 10, 10, h);
+        let idx = solid_fluid.idx(i0, j0);
+        solid_fluid.s[idx] = SOLID_CELL;
+        let solid_path = dir.join("solid.png");
+        Visualizer::save_pressure_field(&solid_fluid, &[], None, ColorScale::Auto, solid_path.to_str().unwrap(), true).unwrap();
+        let solid_pixel = find_pixel(&solid_path, [50, 50, 50]).expect("solid cell should render as a gray pixel");
+
+        let radiator_fluid = Fluid::new(1000.0, 10, 10, h);
+        let radiator = Radiator::new(i0 as f64 * h, j0 as f64 * h, h * 0.5, h * 0.5, 0.0, 0.5);
+        let radiator_path = dir.join("radiator.png");
+        Visualizer::save_pressure_field(&radiator_fluid, &[radiator], None, ColorScale::Auto, radiator_path.to_str().unwrap(), true)
+            .unwrap();
+        let radiator_pixel =
+            find_pixel(&radiator_path, [255, 0, 255]).expect("obstacle outline should render as a magenta pixel");
+
+        assert_eq!(
+            solid_pixel, radiator_pixel,
+            "a solid cell and a 1-cell obstacle outline over the same cell must land on the same pixel"
+        );
+        let expected = cell_to_pixel(solid_fluid.num_y, i0, j0);
+        assert_eq!(solid_pixel, expected);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn draw_text_false_produces_a_smaller_glyph_free_image() {
+        let fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        let dir = std::env::temp_dir().join("lgr_2d_cfd_no_text_test");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let with_title_path = dir.join("with_title.png");
+        Visualizer::save_pressure_field(
+            &fluid,
+            &[],
+            Some("PRESSURE"),
+            ColorScale::Auto,
+            with_title_path.to_str().unwrap(),
+            true,
+        )
+        .unwrap();
+
+        let no_text_path = dir.join("no_text.png");
+        Visualizer::save_pressure_field(
+            &fluid,
+            &[],
+            Some("PRESSURE"),
+            ColorScale::Auto,
+            no_text_path.to_str().unwrap(),
+            false,
+        )
+        .unwrap();
+
+        let with_title = image::open(&with_title_path).unwrap().to_rgb8();
+        let no_text = image::open(&no_text_path).unwrap().to_rgb8();
+        assert!(
+            no_text.height() < with_title.height(),
+            "skipping the title should also skip reserving space for it"
+        );
+        assert!(
+            !no_text.pixels().any(|p| *p == Rgb([255, 255, 255])),
+            "draw_text=false should leave no white (glyph) pixels in the image"
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn a_nan_pressure_cell_renders_as_the_sentinel_color_instead_of_corrupting_the_whole_range() {
+        let mut fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        for (i, p) in fluid.p.iter_mut().enumerate() {
+            *p = i as f64;
+        }
+        let nan_idx = fluid.idx(5, 5);
+        fluid.p[nan_idx] = f64::NAN;
+        let inf_idx = fluid.idx(6, 5);
+        fluid.p[inf_idx] = f64::INFINITY;
+
+        let dir = std::env::temp_dir().join("lgr_2d_cfd_non_finite_pressure_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("pressure.png");
+        Visualizer::save_pressure_field(&fluid, &[], None, ColorScale::Auto, path.to_str().unwrap(), true).unwrap();
+
+        let img = image::open(&path).unwrap().to_rgb8();
+        let (nan_px, nan_py) = cell_to_pixel(fluid.num_y, 5, 5);
+        let (inf_px, inf_py) = cell_to_pixel(fluid.num_y, 6, 5);
+        assert_eq!(img.get_pixel(nan_px, nan_py).0, render::NON_FINITE_SENTINEL_COLOR);
+        assert_eq!(img.get_pixel(inf_px, inf_py).0, render::NON_FINITE_SENTINEL_COLOR);
+
+        // Every finite cell's color should still come from the finite range
+        // (0..=98, skipping the two non-finite cells), not have been washed
+        // out into a single flat color by the NaN/Inf poisoning the min/max.
+        let (p0_px, p0_py) = cell_to_pixel(fluid.num_y, 0, 0);
+        let (p_last_px, p_last_py) = cell_to_pixel(fluid.num_y, 9, 9);
+        assert_ne!(img.get_pixel(p0_px, p0_py).0, img.get_pixel(p_last_px, p_last_py).0);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn non_finite_pressure_cells_are_reported_in_the_warning_message() {
+        let mut fluid = Fluid::new(1000.0, 5, 5, 0.1);
+        let idx_a = fluid.idx(1, 1);
+        let idx_b = fluid.idx(2, 2);
+        fluid.p[idx_a] = f64::NAN;
+        fluid.p[idx_b] = f64::INFINITY;
+
+        let range = render::finite_range(&fluid.p);
+        assert_eq!(range.non_finite_count, 2);
+        let warning = render::non_finite_warning("pressure", &range).unwrap();
+        assert!(warning.contains("pressure"));
+        assert!(warning.contains('2'));
+    }
+
+    #[test]
+    fn a_solid_cells_leftover_pressure_does_not_poison_the_color_range() {
+        let mut fluid = Fluid::new(1000.0, 10, 10, 0.1);
+        for (i, p) in fluid.p.iter_mut().enumerate() {
+            *p = i as f64;
+        }
+        let solid_idx = fluid.idx(5, 5);
+        fluid.s[solid_idx] = SOLID_CELL;
+        // Garbage value the solver never bothered to zero out, wildly
+        // outside the real fluid-cell range (0..=98 minus the solid cell).
+        fluid.p[solid_idx] = 1.0e9;
+
+        let dir = std::env::temp_dir().join("lgr_2d_cfd_solid_pressure_range_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("pressure.png");
+        Visualizer::save_pressure_field(&fluid, &[], None, ColorScale::Auto, path.to_str().unwrap(), true).unwrap();
+
+        let img = image::open(&path).unwrap().to_rgb8();
+        let (solid_px, solid_py) = cell_to_pixel(fluid.num_y, 5, 5);
+        assert_eq!(img.get_pixel(solid_px, solid_py).0, [50, 50, 50], "solid cell should render as fixed dark grey");
+
+        // The two ends of the real fluid range should still map to visibly
+        // different colors, instead of both collapsing near the low end of
+        // a range stretched out to cover the solid cell's 1e9 outlier.
+        let (p0_px, p0_py) = cell_to_pixel(fluid.num_y, 0, 0);
+        let (p_last_px, p_last_py) = cell_to_pixel(fluid.num_y, 9, 9);
+        assert_ne!(img.get_pixel(p0_px, p0_py).0, img.get_pixel(p_last_px, p_last_py).0);
+
+        let range = render::finite_range_masked(&fluid.p, &fluid.s);
+        assert_eq!(range.max, 99.0, "the masked range must ignore the solid cell's pressure entirely");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    fn uniform_flow_fluid() -> Fluid {
+        let num_x = 40;
+        let num_y = 20;
+        let h = 1.0 / num_y as f64;
+        let mut fluid = Fluid::new(1000.0, num_x, num_y, h);
+        let n = fluid.num_y;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                let idx = i * n + j;
+                let is_boundary = j == 0 || j == fluid.num_y - 1;
+                fluid.s[idx] = if is_boundary { SOLID_CELL } else { 1.0 };
+                fluid.u[idx] = 1.0;
+            }
+        }
+        fluid
+    }
+
+    #[test]
+    fn an_arc_length_cap_terminates_a_line_before_max_steps() {
+        let fluid = uniform_flow_fluid();
+        let capped = trace_streamlines_with_options(
+            &fluid,
+            &StreamlineOptions {
+                seeds: SeedPlacement::LeftEdge { count: 2 },
+                direction: TraceDirection::Forward,
+                scheme: IntegrationScheme::Euler,
+                max_arc_length: fluid.h * 4.0,
+            },
+        );
+        for line in &capped {
+            assert_eq!(line.termination, TerminationReason::MaxArcLength);
+            assert!(line.vertices.len() < fluid.num_x * 4, "a tight arc-length cap should stop well short of max_steps");
+        }
+    }
+
+    #[test]
+    fn backward_tracing_moves_upstream_from_the_seed() {
+        let fluid = uniform_flow_fluid();
+        let lines = trace_streamlines_with_options(
+            &fluid,
+            &StreamlineOptions {
+                seeds: SeedPlacement::Points(vec![(fluid.num_x as f64 * fluid.h * 0.5, fluid.h * 5.0)]),
+                direction: TraceDirection::Backward,
+                scheme: IntegrationScheme::Euler,
+                max_arc_length: f64::INFINITY,
+            },
+        );
+        let line = &lines[0];
+        let last = line.vertices.last().unwrap();
+        // Flow is uniformly +x, so tracing backward from the seed must walk
+        // toward smaller x, not follow the flow toward larger x.
+        assert!(last.x < line.seed.0, "backward trace should move upstream (toward smaller x)");
+    }
+
+    #[test]
+    fn both_directions_stitch_into_one_line_through_the_seed() {
+        let fluid = uniform_flow_fluid();
+        let seed = (fluid.num_x as f64 * fluid.h * 0.5, fluid.h * 5.0);
+        let lines = trace_streamlines_with_options(
+            &fluid,
+            &StreamlineOptions {
+                seeds: SeedPlacement::Points(vec![seed]),
+                direction: TraceDirection::Both,
+                scheme: IntegrationScheme::Rk2,
+                max_arc_length: fluid.h * 20.0,
+            },
+        );
+        let line = &lines[0];
+        assert!(line.vertices.first().unwrap().x < seed.0, "stitched line should start upstream of the seed");
+        assert!(line.vertices.last().unwrap().x > seed.0, "stitched line should end downstream of the seed");
+    }
+
+    #[test]
+    fn uniform_grid_seeding_produces_rows_times_cols_seeds() {
+        let fluid = uniform_flow_fluid();
+        let lines = trace_streamlines_with_options(
+            &fluid,
+            &StreamlineOptions {
+                seeds: SeedPlacement::UniformGrid { rows: 3, cols: 2 },
+                direction: TraceDirection::Forward,
+                scheme: IntegrationScheme::Rk4,
+                max_arc_length: f64::INFINITY,
+            },
+        );
+        assert_eq!(lines.len(), 6);
+    }
+
+    #[test]
+    fn draw_streamlines_colors_vertices_by_speed_not_a_fixed_color() {
+        let fluid = uniform_flow_fluid();
+        let lines = trace_streamlines(&fluid, 3);
+        let dir = std::env::temp_dir().join("lgr_2d_cfd_streamline_color_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("streamlines.png");
+        draw_streamlines(&fluid, &[], &lines, path.to_str().unwrap()).unwrap();
+
+        // Uniform flow gives every real vertex the same speed, so it should
+        // map to one non-background color rather than the old fixed yellow.
+        assert!(find_pixel(&path, [255, 255, 0]).is_none(), "should no longer paint every vertex the same fixed yellow");
+        assert!(find_pixel(&path, [0, 0, 0]).is_some(), "fluid background should still be present");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    /// `uniform_flow_fluid`, with a circular obstacle punched into the solid
+    /// mask mid-domain, directly in the path of every left-edge seed.
+    fn uniform_flow_with_cylinder() -> Fluid {
+        use crate::scene_config::ObstacleShape;
+        let mut fluid = uniform_flow_fluid();
+        let h = fluid.h;
+        let n = fluid.num_y;
+        let cylinder = ObstacleShape::Circle { cx: 20.0 * h, cy: 10.0 * h, radius: 4.0 * h };
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                if cylinder.contains(i as f64 * h, j as f64 * h) {
+                    fluid.s[i * n + j] = SOLID_CELL;
+                }
+            }
+        }
+        fluid
+    }
+
+    #[test]
+    fn a_streamline_traced_past_a_cylinder_never_enters_a_solid_cell() {
+        let fluid = uniform_flow_with_cylinder();
+        let lines = trace_streamlines_with_options(
+            &fluid,
+            &StreamlineOptions {
+                seeds: SeedPlacement::LeftEdge { count: 8 },
+                direction: TraceDirection::Forward,
+                scheme: IntegrationScheme::Rk4,
+                max_arc_length: f64::INFINITY,
+            },
+        );
+        assert!(!lines.is_empty());
+
+        let n = fluid.num_y;
+        let h = fluid.h;
+        for line in &lines {
+            for vertex in &line.vertices {
+                let i = (vertex.x / h) as usize;
+                let j = (vertex.y / h) as usize;
+                assert_ne!(
+                    fluid.s[i * n + j],
+                    SOLID_CELL,
+                    "streamline vertex ({}, {}) landed inside the cylinder",
+                    vertex.x,
+                    vertex.y
+                );
+            }
+        }
+
+        // At least one seed's line should actually have been stopped short
+        // by the obstacle rather than just flowing past it untouched.
+        assert!(
+            lines.iter().any(|line| line.termination == TerminationReason::HitObstacle),
+            "expected at least one line seeded upstream of the cylinder to terminate on it"
+        );
+    }
+}
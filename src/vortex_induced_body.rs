@@ -0,0 +1,327 @@
+//! Two-way fluid-structure coupling for a single solid obstacle: instead of
+//! a fixed footprint, the obstacle is free to oscillate transverse to the
+//! freestream on a 1-DOF spring-mass-damper, driven each step by the lift
+//! force the flow itself just produced on it — the classic
+//! vortex-induced-vibration (VIV) configuration. Reuses
+//! [`obstacle_analysis::compute_obstacle_forces`] for the driving force and
+//! the same restore-then-remark footprint trick [`crate::radiator_model`]
+//! uses for a mid-run parameter change to move the solid mask each step.
+
+use crate::fluid::Fluid;
+use crate::obstacle_analysis;
+use crate::scene_config::ObstacleShape;
+
+/// Amplitude/frequency summary of a run, read once at the end rather than
+/// after every step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LockInReport {
+    /// Half the peak-to-peak transverse displacement over the second half
+    /// of the recorded history, on the assumption that a run long enough to
+    /// report on has already settled into its steady oscillation.
+    pub amplitude: f64,
+    /// Oscillation frequency in Hz, from counting zero-crossings of
+    /// displacement about its mean over the same window.
+    pub frequency: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct VortexInducedBody {
+    obstacle_index: usize,
+    mass: f64,
+    stiffness: f64,
+    damping: f64,
+    /// Solid mask as it was when this body was set up (obstacle marked at
+    /// its rest position), used to restore its old footprint before
+    /// re-marking the new one each step, so no stale solid cell survives a
+    /// move.
+    base_s: Vec<f64>,
+    /// This obstacle's shape at its rest position; the shape at the current
+    /// displacement is `original_shape.transverse_offset(self.displacement)`.
+    original_shape: ObstacleShape,
+    displacement: f64,
+    velocity: f64,
+    history: Vec<(f64, f64)>,
+}
+
+impl VortexInducedBody {
+    /// `mass_ratio` is `m* = mass / (fluid.density * displaced_area)`, with
+    /// `displaced_area` approximated as a circle of diameter
+    /// `shape.frontal_height()` regardless of the obstacle's actual shape.
+    /// `natural_frequency_hz`/`damping_ratio` are the body's own (in-vacuum)
+    /// natural frequency and damping ratio.
+    pub fn new(
+        fluid: &Fluid,
+        obstacle_index: usize,
+        shape: ObstacleShape,
+        mass_ratio: f64,
+        natural_frequency_hz: f64,
+        damping_ratio: f64,
+    ) -> Self {
+        let diameter = shape.frontal_height();
+        let displaced_area = std::f64::consts::PI / 4.0 * diameter * diameter;
+        let mass = mass_ratio * fluid.density * displaced_area;
+        let omega_n = 2.0 * std::f64::consts::PI * natural_frequency_hz;
+        let stiffness = mass * omega_n * omega_n;
+        let damping = 2.0 * damping_ratio * mass * omega_n;
+        VortexInducedBody {
+            obstacle_index,
+            mass,
+            stiffness,
+            damping,
+            base_s: fluid.s.clone(),
+            original_shape: shape,
+            displacement: 0.0,
+            velocity: 0.0,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn obstacle_index(&self) -> usize {
+        self.obstacle_index
+    }
+
+    /// This body's shape at its current displacement.
+    pub fn current_shape(&self) -> ObstacleShape {
+        self.original_shape.transverse_offset(self.displacement)
+    }
+
+    pub fn displacement(&self) -> f64 {
+        self.displacement
+    }
+
+    /// Integrate the body ODE one step with the lift force computed from
+    /// `fluid`'s current pressure field, move the footprint to the new
+    /// position, and record the displacement for [`Self::lock_in_report`].
+    /// Call once per `Scene::simulate`, after the pressure field for this
+    /// step has been solved.
+    pub fn step(&mut self, fluid: &mut Fluid, inflow_u: f64, dt: f64, sim_time: f64) {
+        let shape_before = self.current_shape();
+        let lift = obstacle_analysis::compute_obstacle_forces(fluid, std::slice::from_ref(&shape_before), inflow_u)[0].lift;
+
+        // Semi-implicit (symplectic) Euler, the same explicit-integration
+        // tradeoff the rest of this solver already accepts elsewhere.
+        let acceleration = (lift - self.damping * self.velocity - self.stiffness * self.displacement) / self.mass;
+        self.velocity += acceleration * dt;
+        self.displacement += self.velocity * dt;
+
+        restore_footprint(fluid, &self.base_s, &shape_before);
+        mark_footprint(fluid, &self.current_shape());
+
+        self.history.push((sim_time, self.displacement));
+    }
+
+    /// `None` if too little history has been recorded to say anything
+    /// (fewer than 4 samples).
+    pub fn lock_in_report(&self) -> Option<LockInReport> {
+        if self.history.len() < 4 {
+            return None;
+        }
+        let window = &self.history[self.history.len() / 2..];
+        let values: Vec<f64> = window.iter().map(|(_, y)| *y).collect();
+        let max = values.iter().cloned().fold(f64::MIN, f64::max);
+        let min = values.iter().cloned().fold(f64::MAX, f64::min);
+        let amplitude = (max - min) / 2.0;
+
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let crossings = values
+            .windows(2)
+            .filter(|pair| (pair[0] - mean) * (pair[1] - mean) < 0.0)
+            .count();
+        let duration = window.last().unwrap().0 - window.first().unwrap().0;
+        let frequency = if duration > 0.0 { (crossings as f64 / 2.0) / duration } else { 0.0 };
+
+        Some(LockInReport { amplitude, frequency })
+    }
+}
+
+/// Reset every cell in `shape`'s footprint back to whatever `base_s` says it
+/// was, undoing the previous step's `mark_footprint` before the shape moves.
+fn restore_footprint(fluid: &mut Fluid, base_s: &[f64], shape: &ObstacleShape) {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            if shape.contains(x, y) {
+                let idx = i * n + j;
+                fluid.s[idx] = base_s[idx];
+            }
+        }
+    }
+}
+
+/// Mark every cell inside `shape` solid, matching `scene::mark_obstacle_solid`.
+fn mark_footprint(fluid: &mut Fluid, shape: &ObstacleShape) {
+    let n = fluid.num_y;
+    let h = fluid.h;
+    for i in 1..fluid.num_x - 1 {
+        for j in 1..fluid.num_y - 1 {
+            let x = i as f64 * h;
+            let y = j as f64 * h;
+            if shape.contains(x, y) {
+                let idx = i * n + j;
+                fluid.s[idx] = 0.0;
+                fluid.u[idx] = 0.0;
+                fluid.u[(i + 1) * n + j] = 0.0;
+                fluid.v[idx] = 0.0;
+                fluid.v[idx + 1] = 0.0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_fluid() -> Fluid {
+        let mut fluid = Fluid::new(1000.0, 40, 20, 0.05);
+        for j in 0..fluid.num_y {
+            fluid.u[j] = 1.0;
+        }
+        fluid
+    }
+
+    fn sample_shape() -> ObstacleShape {
+        ObstacleShape::Circle { cx: 0.5, cy: 0.5, radius: 0.1 }
+    }
+
+    #[test]
+    fn a_still_flow_at_rest_produces_no_motion() {
+        let fluid = Fluid::new(1000.0, 40, 20, 0.05);
+        let mut body = VortexInducedBody::new(&fluid, 0, sample_shape(), 2.0, 1.0, 0.1);
+        let mut fluid = fluid;
+        for step in 0..10 {
+            body.step(&mut fluid, 0.0, 1.0 / 60.0, step as f64 / 60.0);
+        }
+        assert_eq!(body.displacement(), 0.0);
+    }
+
+    #[test]
+    fn stepping_moves_the_footprint_and_restores_the_old_one() {
+        let mut fluid = sample_fluid();
+        let mut body = VortexInducedBody::new(&fluid, 0, sample_shape(), 2.0, 1.0, 0.1);
+        for step in 0..20 {
+            body.step(&mut fluid, 1.0, 1.0 / 60.0, step as f64 / 60.0);
+        }
+        // A cylinder in a uniform (unperturbed, perfectly symmetric) flow
+        // produces no net lift, so displacement should stay at (numerical)
+        // zero; the point of this test is that `step` runs without leaving
+        // two overlapping (old + new) footprints marked solid.
+        let expected = sample_shape().transverse_offset(body.displacement());
+        let n = fluid.num_y;
+        let h = fluid.h;
+        for i in 1..fluid.num_x - 1 {
+            for j in 1..fluid.num_y - 1 {
+                let x = i as f64 * h;
+                let y = j as f64 * h;
+                let idx = i * n + j;
+                if expected.contains(x, y) {
+                    assert_eq!(fluid.s[idx], 0.0, "expected footprint cell ({i},{j}) to be solid");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lock_in_report_is_none_before_enough_history_accumulates() {
+        let mut fluid = sample_fluid();
+        let mut body = VortexInducedBody::new(&fluid, 0, sample_shape(), 2.0, 1.0, 0.1);
+        body.step(&mut fluid, 1.0, 1.0 / 60.0, 0.0);
+        assert!(body.lock_in_report().is_none());
+    }
+
+    #[test]
+    fn lock_in_report_measures_amplitude_and_frequency_of_a_synthetic_history() {
+        let fluid = sample_fluid();
+        let mut body = VortexInducedBody::new(&fluid, 0, sample_shape(), 2.0, 1.0, 0.1);
+        // Bypass `step`'s fluid coupling and hand-feed a clean 1 Hz sine
+        // wave of amplitude 2.0, so this test checks the measurement, not
+        // the ODE integration.
+        for i in 0..400 {
+            let t = i as f64 * 0.01;
+            body.history.push((t, 2.0 * (2.0 * std::f64::consts::PI * t).sin()));
+        }
+        let report = body.lock_in_report().unwrap();
+        assert!((report.amplitude - 2.0).abs() < 0.05, "amplitude {}", report.amplitude);
+        assert!((report.frequency - 1.0).abs() < 0.05, "frequency {}", report.frequency);
+    }
+
+    /// Full solver runs at four reduced velocities (`U* = inflow_u / (f_n *
+    /// diameter)`), swept by varying the body's natural frequency at fixed
+    /// inflow: amplitude should stay near zero at the two lowest `U*` and
+    /// grow substantially as `U*` rises through the range that includes
+    /// lock-in — the qualitative VIV trend. Slow (four ~600-step CFD runs),
+    /// so ignored by default.
+    #[test]
+    #[ignore]
+    fn amplitude_grows_with_reduced_velocity_across_a_lock_in_sweep() {
+        use crate::scene::Scene;
+        use crate::scene_config::{SceneConfig, VortexBodyConfig};
+
+        fn amplitude_at(natural_frequency_hz: f64) -> f64 {
+            let config = SceneConfig {
+                num_x: 80,
+                num_y: 40,
+                dt: 1.0 / 60.0,
+                num_iters: 40,
+                over_relaxation: 1.9,
+                pressure_solver: Default::default(),
+                gravity: 0.0,
+                inflow_velocity: 1.0,
+                inflow_profile: crate::inflow_profile::InflowProfile::default(),
+                inflow_angle: 0.0,
+                inflow_ramp_time: 0.0,
+                obstacles: vec![ObstacleShape::Circle { cx: 0.2, cy: 0.5, radius: 0.05 }],
+                radiators: vec![],
+                wake_trigger: None,
+                vortex_body: Some(VortexBodyConfig {
+                    obstacle_index: 0,
+                    mass_ratio: 2.0,
+                    natural_frequency_hz,
+                    damping_ratio: 0.05,
+                }),
+                step_ordering: Default::default(),
+                top_bottom_boundary: crate::fluid::BoundaryCondition::NoSlip,
+                moving_obstacles: vec![],
+                smoke_decay: 0.0,
+                inflow_smoke_pattern: Default::default(),
+                dye_emitters: vec![],
+                paint_events: vec![],
+                line_profiles: vec![],
+                turbulence_model: None,
+                working_fluid: None,
+                cut_cell: false,
+            };
+            let mut scene = Scene::setup_from_config(&config);
+            for _ in 0..600 {
+                scene.simulate();
+            }
+            scene.vortex_body().unwrap().lock_in_report().unwrap().amplitude
+        }
+
+        // Diameter is 0.1, inflow is 1.0, so U* = 1 / (f_n * 0.1) = 10 / f_n.
+        // Two low-U* points (not just one) exercise `sample_slice`'s
+        // renormalization floor (see `fluid::sample_slice`) across more
+        // than the single natural frequency it was tuned against, since
+        // it's specifically this off-resonance, near-stationary-footprint
+        // regime where an under-floored renormalization spuriously excites
+        // the body.
+        let very_low_u_star = amplitude_at(6.0); // U* = 1.67
+        let low_u_star = amplitude_at(5.0); // U* = 2
+        let mid_u_star = amplitude_at(2.0); // U* = 5
+        let high_u_star = amplitude_at(1.0); // U* = 10
+
+        assert!(very_low_u_star < 1e-3, "expected near-zero amplitude at very low U*, got {very_low_u_star}");
+        assert!(low_u_star < 1e-3, "expected near-zero amplitude at low U*, got {low_u_star}");
+        assert!(
+            mid_u_star > low_u_star * 5.0,
+            "expected amplitude to grow substantially from U*=2 to U*=5: {low_u_star} -> {mid_u_star}"
+        );
+        assert!(
+            high_u_star > mid_u_star * 5.0,
+            "expected amplitude to keep growing from U*=5 to U*=10: {mid_u_star} -> {high_u_star}"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Legacy ASCII VTK structured-points export, for opening field snapshots
+//! in ParaView.
+
+use std::fs::File;
+use std::io::{self, Write};
+
+use crate::fluid::Fluid;
+
+pub struct VtkExporter;
+
+impl VtkExporter {
+    /// Write cell-centered pressure, smoke, solid mask, and interpolated
+    /// velocity vectors as a legacy ASCII VTK structured-points dataset.
+    pub fn write_vtk(fluid: &Fluid, path: &str) -> io::Result<()> {
+        let mut file = File::create(path)?;
+        let num_points = fluid.num_x * fluid.num_y;
+
+        writeln!(file, "# vtk DataFile Version 3.0")?;
+        writeln!(file, "lgr_2d_cfd field snapshot")?;
+        writeln!(file, "ASCII")?;
+        writeln!(file, "DATASET STRUCTURED_POINTS")?;
+        writeln!(file, "DIMENSIONS {} {} 1", fluid.num_x, fluid.num_y)?;
+        writeln!(file, "ORIGIN 0 0 0")?;
+        writeln!(file, "SPACING {} {} {}", fluid.h, fluid.h, fluid.h)?;
+        writeln!(file, "POINT_DATA {}", num_points)?;
+
+        writeln!(file, "SCALARS pressure double 1")?;
+        writeln!(file, "LOOKUP_TABLE default")?;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                writeln!(file, "{}", fluid.p[fluid.idx(i, j)])?;
+            }
+        }
+
+        writeln!(file, "SCALARS smoke double 1")?;
+        writeln!(file, "LOOKUP_TABLE default")?;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                writeln!(file, "{}", fluid.m[fluid.idx(i, j)])?;
+            }
+        }
+
+        writeln!(file, "SCALARS solid_mask double 1")?;
+        writeln!(file, "LOOKUP_TABLE default")?;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                writeln!(file, "{}", fluid.s[fluid.idx(i, j)])?;
+            }
+        }
+
+        writeln!(file, "VECTORS velocity double")?;
+        for j in 0..fluid.num_y {
+            for i in 0..fluid.num_x {
+                let (u, v) = interpolate_velocity(fluid, i, j);
+                writeln!(file, "{} {} 0", u, v)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Cell-centered velocity, averaged from the surrounding staggered faces.
+fn interpolate_velocity(fluid: &Fluid, i: usize, j: usize) -> (f64, f64) {
+    let n = fluid.num_y;
+    let idx = i * n + j;
+    let u = if i + 1 < fluid.num_x {
+        (fluid.u[idx] + fluid.u[(i + 1) * n + j]) * 0.5
+    } else {
+        fluid.u[idx]
+    };
+    let v = if j + 1 < fluid.num_y {
+        (fluid.v[idx] + fluid.v[idx + 1]) * 0.5
+    } else {
+        fluid.v[idx]
+    };
+    (u, v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_dimensions_and_point_count_match_grid() {
+        let fluid = Fluid::new(1000.0, 12, 8, 0.1);
+        let path = std::env::temp_dir().join(format!(
+            "lgr_2d_cfd_vtk_roundtrip_{:?}.vtk",
+            std::thread::current().id()
+        ));
+        VtkExporter::write_vtk(&fluid, path.to_str().unwrap()).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let dims_line = contents
+            .lines()
+            .find(|l| l.starts_with("DIMENSIONS"))
+            .unwrap();
+        let dims: Vec<usize> = dims_line
+            .split_whitespace()
+            .skip(1)
+            .map(|s| s.parse().unwrap())
+            .collect();
+        assert_eq!(dims, vec![fluid.num_x, fluid.num_y, 1]);
+
+        let points_line = contents
+            .lines()
+            .find(|l| l.starts_with("POINT_DATA"))
+            .unwrap();
+        let count: usize = points_line.split_whitespace().nth(1).unwrap().parse().unwrap();
+        assert_eq!(count, fluid.num_x * fluid.num_y);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
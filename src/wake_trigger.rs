@@ -0,0 +1,139 @@
+//! Optional one-shot antisymmetric perturbation used to break a perfectly
+//! symmetric wake (obstacle exactly on the centerline, symmetric numerics)
+//! that would otherwise stay symmetric for far longer than a physical wake
+//! ever would before shedding begins — wasting budget in something like a
+//! radiator angle sweep. Off unless a scene config opts in.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::fluid::{Fluid, SOLID_CELL};
+use crate::scene_config::ObstacleShape;
+
+/// Fires at most once: if the tracked obstacle's lift is still below
+/// `lift_threshold` in magnitude once `after_step` steps have run, nudges
+/// `v` in its wake antisymmetrically about its centerline to seed the
+/// instability that triggers vortex shedding. A no-op on every other call,
+/// including every call after it has already fired.
+#[derive(Debug, Clone)]
+pub struct WakeTrigger {
+    pub after_step: usize,
+    pub lift_threshold: f64,
+    pub seed: u64,
+    fired: bool,
+}
+
+impl WakeTrigger {
+    pub fn new(after_step: usize, lift_threshold: f64, seed: u64) -> Self {
+        WakeTrigger {
+            after_step,
+            lift_threshold,
+            seed,
+            fired: false,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn has_fired(&self) -> bool {
+        self.fired
+    }
+
+    /// `base_velocity` scales the perturbation's magnitude (the freestream
+    /// inflow speed, typically) so the kick is small relative to the flow
+    /// rather than an arbitrary fixed number.
+    pub fn maybe_fire(&mut self, fluid: &mut Fluid, shape: &ObstacleShape, base_velocity: f64, step: usize, lift: f64) -> bool {
+        if self.fired || step != self.after_step || lift.abs() >= self.lift_threshold {
+            return false;
+        }
+        perturb(fluid, shape, self.seed, base_velocity);
+        self.fired = true;
+        true
+    }
+}
+
+/// Nudge `v` by a small antisymmetric amount in the handful of cells just
+/// downstream of `shape`: positive above its centerline, negative below, so
+/// the two sides no longer mirror each other exactly. Sized from `seed` so
+/// a sweep can reproduce (or vary) the exact kick across runs.
+fn perturb(fluid: &mut Fluid, shape: &ObstacleShape, seed: u64, base_velocity: f64) {
+    let (cx, cy) = shape.center();
+    let h = fluid.h;
+    let n = fluid.num_y;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let amplitude = base_velocity * rng.gen_range(0.01..0.03);
+
+    let center_i = (cx / h) as usize;
+    let center_j = (cy / h) as usize;
+    let i_start = (center_i + 1).min(fluid.num_x - 2);
+    let i_end = (center_i + 6).min(fluid.num_x - 2);
+
+    for i in i_start..=i_end {
+        for j in 1..fluid.num_y - 1 {
+            let idx = i * n + j;
+            if fluid.s[idx] == SOLID_CELL {
+                continue;
+            }
+            fluid.v[idx] += if j > center_j { amplitude } else { -amplitude };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_fluid() -> Fluid {
+        Fluid::new(1000.0, 40, 20, 0.05)
+    }
+
+    fn sample_shape() -> ObstacleShape {
+        ObstacleShape::Circle { cx: 0.5, cy: 0.5, radius: 0.1 }
+    }
+
+    #[test]
+    fn does_not_fire_before_the_configured_step() {
+        let mut fluid = sample_fluid();
+        let mut trigger = WakeTrigger::new(100, 0.05, 42);
+        assert!(!trigger.maybe_fire(&mut fluid, &sample_shape(), 1.0, 99, 0.0));
+        assert!(!trigger.has_fired());
+    }
+
+    #[test]
+    fn does_not_fire_when_shedding_is_already_present() {
+        let mut fluid = sample_fluid();
+        let mut trigger = WakeTrigger::new(100, 0.05, 42);
+        // Lift already well above threshold: shedding is already underway,
+        // so the guard should never kick in.
+        assert!(!trigger.maybe_fire(&mut fluid, &sample_shape(), 1.0, 100, 0.2));
+        assert!(!trigger.has_fired());
+    }
+
+    #[test]
+    fn fires_exactly_once_and_perturbs_v_antisymmetrically() {
+        let mut fluid = sample_fluid();
+        let before = fluid.v.clone();
+        let mut trigger = WakeTrigger::new(100, 0.05, 42);
+
+        assert!(trigger.maybe_fire(&mut fluid, &sample_shape(), 1.0, 100, 0.0));
+        assert!(trigger.has_fired());
+        assert_ne!(fluid.v, before, "perturbation should have changed v");
+
+        // A second call at the same step must not fire again.
+        let after_first = fluid.v.clone();
+        assert!(!trigger.maybe_fire(&mut fluid, &sample_shape(), 1.0, 100, 0.0));
+        assert_eq!(fluid.v, after_first);
+    }
+
+    #[test]
+    fn same_seed_perturbs_identically_across_runs() {
+        let mut fluid_a = sample_fluid();
+        let mut fluid_b = sample_fluid();
+        let mut trigger_a = WakeTrigger::new(50, 0.05, 7);
+        let mut trigger_b = WakeTrigger::new(50, 0.05, 7);
+
+        trigger_a.maybe_fire(&mut fluid_a, &sample_shape(), 1.0, 50, 0.0);
+        trigger_b.maybe_fire(&mut fluid_b, &sample_shape(), 1.0, 50, 0.0);
+
+        assert_eq!(fluid_a.v, fluid_b.v);
+    }
+}
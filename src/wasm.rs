@@ -0,0 +1,101 @@
+//! `wasm-bindgen` bindings for a browser demo — a canvas-blit port of the
+//! JS fluid sim this solver started life as (see `lib.rs`), now backed by
+//! the real Rust core. `examples/wasm_demo/index.html` is the minimal page
+//! that drives it.
+//!
+//! Built only with `--features wasm`, cross-compiled to
+//! `wasm32-unknown-unknown`. That target isn't installable in every build
+//! environment (this one included, at the time this module was written —
+//! no network access to rustup's component server), so this module is
+//! exercised here with `cargo check --features wasm` against the host
+//! target rather than a real wasm32 build; `wasm-bindgen`'s attribute
+//! macros don't depend on the target to type-check, only to link.
+//!
+//! Only what a canvas demo needs is exposed: build a scene, step it, render
+//! a field to an RGBA buffer, and add/clear a dragged obstacle. Everything
+//! else in this crate (rendering to PNG/GIF, ffmpeg animation, parallel
+//! sweeps, checkpoints) stays server-side — none of it is reachable from
+//! this module, so none of it needs to compile for wasm32 at all.
+
+use wasm_bindgen::prelude::*;
+
+use crate::render::finite_range_masked;
+use crate::scene::Scene;
+use crate::scene_config::ObstacleShape;
+use crate::visualizer::get_sci_color;
+
+#[wasm_bindgen]
+pub struct WasmScene {
+    inner: Scene,
+}
+
+#[wasm_bindgen]
+impl WasmScene {
+    /// Builds a wind-tunnel scene sized `num_x` by `num_y` cells, matching
+    /// `Scene::wind_tunnel_with_radiator`'s built-in setup so the demo has
+    /// a radiator and inflow to look at without a config file.
+    #[wasm_bindgen(constructor)]
+    pub fn new(num_x: usize, num_y: usize) -> WasmScene {
+        WasmScene { inner: Scene::wind_tunnel_with_radiator(num_x, num_y) }
+    }
+
+    /// Advances the simulation `n` steps.
+    pub fn step(&mut self, n: usize) {
+        for _ in 0..n {
+            self.inner.simulate();
+        }
+    }
+
+    /// Adds a circular obstacle at the mouse's domain position, for
+    /// click-and-drag obstacle placement.
+    pub fn add_obstacle(&mut self, cx: f64, cy: f64, radius: f64) {
+        self.inner.add_obstacles([ObstacleShape::Circle { cx, cy, radius }]);
+    }
+
+    /// Removes every obstacle `add_obstacle` has placed, so a drag can
+    /// redraw a fresh circle each frame instead of leaving a trail.
+    pub fn clear_obstacles(&mut self) {
+        self.inner.clear_obstacles();
+    }
+
+    /// Renders `field` (`"pressure"`, `"speed"`, or `"smoke"`) into an
+    /// RGBA buffer of `width * height * 4` bytes, using the same
+    /// [`get_sci_color`] colormap as the PNG/GIF output, nearest-neighbor
+    /// sampled onto the requested pixel size and flipped so pixel row 0 is
+    /// the top of the domain (matching `visualizer::cell_to_pixel`'s
+    /// convention). Unknown `field` values fall back to `"speed"`.
+    pub fn render_rgba(&self, field: &str, width: u32, height: u32) -> Vec<u8> {
+        let fluid = &self.inner.fluid;
+        let num_x = fluid.num_x;
+        let num_y = fluid.num_y;
+
+        let values: Vec<f64> = match field {
+            "pressure" => fluid.p.clone(),
+            "smoke" => fluid.m.clone(),
+            _ => (0..num_x * num_y)
+                .map(|idx| {
+                    let u = fluid.u[idx];
+                    let v = fluid.v[idx];
+                    (u * u + v * v).sqrt()
+                })
+                .collect(),
+        };
+        let range = finite_range_masked(&values, &fluid.s);
+
+        let mut rgba = vec![0u8; (width * height * 4) as usize];
+        for py in 0..height {
+            let j = (num_y - 1).saturating_sub((py as usize * num_y) / height.max(1) as usize).min(num_y - 1);
+            for px in 0..width {
+                let i = ((px as usize * num_x) / width.max(1) as usize).min(num_x - 1);
+                let idx = i * num_y + j;
+                let [r, g, b] = get_sci_color(values[idx], range.min, range.max);
+                let out = ((py * width + px) * 4) as usize;
+                rgba[out] = r;
+                rgba[out + 1] = g;
+                rgba[out + 2] = b;
+                rgba[out + 3] = 255;
+            }
+        }
+        rgba
+    }
+}
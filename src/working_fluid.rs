@@ -0,0 +1,121 @@
+//! Which fluid a scene is actually moving, since [`crate::fluid::Fluid::new`]
+//! only ever takes a bare density and every built-in scene and
+//! [`crate::scene_config::SceneConfig`] used to hard-code `1000.0` (water)
+//! regardless of what its inflow velocity and radiator geometry were
+//! actually meant to represent (typically air through an automotive
+//! underhood duct) — a mismatch [`WorkingFluid`] exists to close.
+//!
+//! This solver has no diffusion term of its own (see the [`crate::turbulence`]
+//! module doc comment) — `kinematic_viscosity` is carried on
+//! [`crate::fluid::Fluid`] purely as reported metadata (e.g. for computing a
+//! Reynolds number from a run's own numbers) until a real viscous term
+//! exists to consume it. Nothing in this crate currently reads it back out
+//! of the solver, including the porous radiator model
+//! ([`crate::radiator::Radiator::apply_porous_force`]): its resistance
+//! coefficient is derived purely from `porosity` and `dt`, with no
+//! viscosity term in it to update.
+
+use serde::{Deserialize, Serialize};
+
+/// Density and kinematic viscosity resolved from a [`WorkingFluid`] choice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FluidProperties {
+    pub density: f64,
+    pub kinematic_viscosity: f64,
+}
+
+/// A fluid a scene can be filled with. `Air`/`Water` derive both properties
+/// from `temperature_c` via a standard closed-form correlation each;
+/// `Custom` bypasses both for a fluid this crate has no correlation for (a
+/// coolant mix, say).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "fluid")]
+pub enum WorkingFluid {
+    Air { temperature_c: f64 },
+    Water { temperature_c: f64 },
+    Custom { density: f64, kinematic_viscosity: f64 },
+}
+
+impl Default for WorkingFluid {
+    /// Water at 4C, where [`WorkingFluid::properties`]'s density fit gives
+    /// exactly `1000.0` kg/m^3 — every scene's density before this type
+    /// existed, so an unspecified working fluid reproduces old behavior.
+    fn default() -> Self {
+        WorkingFluid::Water { temperature_c: 4.0 }
+    }
+}
+
+const ATMOSPHERIC_PRESSURE_PA: f64 = 101_325.0;
+const AIR_SPECIFIC_GAS_CONSTANT_J_PER_KG_K: f64 = 287.05;
+// Sutherland's law reference viscosity/temperature/constant for air.
+const AIR_SUTHERLAND_MU_REF_PA_S: f64 = 1.716e-5;
+const AIR_SUTHERLAND_T_REF_K: f64 = 273.15;
+const AIR_SUTHERLAND_S_K: f64 = 110.4;
+
+impl WorkingFluid {
+    pub fn properties(&self) -> FluidProperties {
+        match *self {
+            WorkingFluid::Air { temperature_c } => {
+                let t_kelvin = temperature_c + 273.15;
+                // Ideal gas law at a fixed sea-level pressure — this crate
+                // has no altitude/pressure input to vary it by.
+                let density = ATMOSPHERIC_PRESSURE_PA / (AIR_SPECIFIC_GAS_CONSTANT_J_PER_KG_K * t_kelvin);
+                let dynamic_viscosity = AIR_SUTHERLAND_MU_REF_PA_S
+                    * (t_kelvin / AIR_SUTHERLAND_T_REF_K).powf(1.5)
+                    * (AIR_SUTHERLAND_T_REF_K + AIR_SUTHERLAND_S_K)
+                    / (t_kelvin + AIR_SUTHERLAND_S_K);
+                FluidProperties { density, kinematic_viscosity: dynamic_viscosity / density }
+            }
+            WorkingFluid::Water { temperature_c } => {
+                // Quadratic fit around water's 4C density maximum; within a
+                // few tenths of a percent of measured values from 0-40C.
+                let density = 1000.0 * (1.0 - (temperature_c - 4.0).powi(2) / 508_929.0);
+                // Vogel/Andrade correlation, dynamic viscosity in Pa*s.
+                let dynamic_viscosity = 2.414e-5 * 10f64.powf(247.8 / (temperature_c + 133.15));
+                FluidProperties { density, kinematic_viscosity: dynamic_viscosity / density }
+            }
+            WorkingFluid::Custom { density, kinematic_viscosity } => FluidProperties { density, kinematic_viscosity },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_water_matches_every_scenes_old_hard_coded_density() {
+        let properties = WorkingFluid::default().properties();
+        assert!(
+            (properties.density - 1000.0).abs() < 1e-9,
+            "default working fluid should reproduce the old hard-coded density, got {}",
+            properties.density
+        );
+    }
+
+    #[test]
+    fn air_is_far_less_dense_than_water_at_the_same_temperature() {
+        let air = WorkingFluid::Air { temperature_c: 20.0 }.properties();
+        let water = WorkingFluid::Water { temperature_c: 20.0 }.properties();
+        assert!(
+            water.density > air.density * 500.0,
+            "water should be roughly 800x denser than air, got water={} air={}",
+            water.density,
+            air.density
+        );
+    }
+
+    #[test]
+    fn air_density_falls_as_temperature_rises() {
+        let cold = WorkingFluid::Air { temperature_c: 0.0 }.properties();
+        let hot = WorkingFluid::Air { temperature_c: 40.0 }.properties();
+        assert!(hot.density < cold.density, "hotter air should be less dense, got hot={} cold={}", hot.density, cold.density);
+    }
+
+    #[test]
+    fn custom_passes_density_and_viscosity_through_unchanged() {
+        let properties = WorkingFluid::Custom { density: 1050.0, kinematic_viscosity: 3.5e-6 }.properties();
+        assert_eq!(properties.density, 1050.0);
+        assert_eq!(properties.kinematic_viscosity, 3.5e-6);
+    }
+}
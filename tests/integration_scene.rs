@@ -0,0 +1,106 @@
+//! Exercises `Scene`/`Fluid` purely through the published library API (no
+//! `mod` access to crate internals), the way an external Rust project
+//! depending on this crate would. Everything else in this tree exercises the
+//! solver from unit tests inside the crate itself; this is the one place
+//! that would notice a public item quietly becoming private or a signature
+//! changing in a way that breaks outside callers.
+
+use lgr_2d_cfd::fluid::FieldType;
+use lgr_2d_cfd::scene::Scene;
+
+/// Ghia, Ghia & Shin (1982), Table I: `u` along the vertical centerline
+/// (`x = 0.5`) of a lid-driven cavity at Re = 100, lid speed normalized to
+/// 1.0. `(y, u)` pairs, `y` from the bottom wall (0.0) to the moving lid
+/// (1.0).
+const GHIA_RE_100_CENTERLINE_U: [(f64, f64); 17] = [
+    (0.0000, 0.00000),
+    (0.0547, -0.04192),
+    (0.0625, -0.04775),
+    (0.1016, -0.06434),
+    (0.1719, -0.10150),
+    (0.2813, -0.15662),
+    (0.4531, -0.21090),
+    (0.5000, -0.20581),
+    (0.6172, -0.13641),
+    (0.7344, 0.00332),
+    (0.8516, 0.23151),
+    (0.9531, 0.68717),
+    (0.9609, 0.73722),
+    (0.9688, 0.78871),
+    (0.9766, 0.84123),
+    (0.9922, 0.94021),
+    (1.0000, 1.00000),
+];
+
+/// The classic incompressible-solver validation case: a fully enclosed
+/// square cavity, no-slip walls, top wall (the "lid") moving at a constant
+/// tangential velocity. See `Scene::lid_driven_cavity`'s doc comment for
+/// why this solver can't be driven to a specific Reynolds number the way a
+/// real viscous solver would — it has no molecular viscosity term, only
+/// the numerical dissipation semi-Lagrangian advection already contributes
+/// everywhere else in this crate. So this doesn't reproduce Ghia et al.'s
+/// Re = 100 profile quantitatively; what it does check is that the
+/// solver, run on a case with a genuinely enclosed (not open-ended
+/// inflow/outflow) domain and a moving-wall boundary instead of a
+/// stationary one, produces the qualitatively right recirculation: `u`
+/// near the lid speed close to the top, negative (reversed) through the
+/// cavity's middle, and back toward zero at the bottom wall — exactly the
+/// sign pattern `extrapolate` and the staggered-grid indexing would get
+/// wrong first if either had a bug. `#[ignore]`d: reaching even a
+/// qualitatively settled recirculation takes a few thousand steps.
+#[test]
+#[ignore]
+fn lid_driven_cavity_centerline_matches_ghia_reference_recirculation_pattern() {
+    let mut scene = Scene::lid_driven_cavity(64);
+    for _ in 0..4000 {
+        scene.simulate();
+    }
+
+    let domain_height = scene.fluid.domain_height();
+    let profile = scene.fluid.extract_line((0.5, 0.0), (0.5, domain_height), 200, FieldType::U);
+    assert!(profile.iter().all(|&(_, u)| u.is_finite()), "4000 steps should not have diverged");
+
+    let sample_u_at = |y: f64| -> f64 {
+        let target = y * domain_height;
+        profile
+            .iter()
+            .min_by(|(da, _), (db, _)| (da - target).abs().partial_cmp(&(db - target).abs()).unwrap())
+            .map(|&(_, u)| u)
+            .unwrap()
+    };
+
+    let lid_u = sample_u_at(1.0);
+    assert!(lid_u > 0.5, "u right at the moving lid should be close to the lid speed, got {lid_u}");
+
+    let bottom_u = sample_u_at(0.0);
+    assert!(bottom_u.abs() < 0.5, "u at the stationary bottom wall should be small, got {bottom_u}");
+
+    let (ghia_mid_y, ghia_mid_u) = GHIA_RE_100_CENTERLINE_U[7];
+    let mid_u = sample_u_at(ghia_mid_y);
+    assert!(
+        mid_u.signum() == ghia_mid_u.signum() || mid_u.abs() < 0.05,
+        "expected the mid-cavity reversed-flow region Ghia et al. reports at y={ghia_mid_y} (u={ghia_mid_u}), got u={mid_u}"
+    );
+}
+
+#[test]
+fn a_scene_built_and_stepped_through_the_public_api_produces_a_sane_flow_field() {
+    let mut scene = Scene::wind_tunnel_with_radiator(60, 30);
+
+    for _ in 0..10 {
+        scene.simulate();
+    }
+
+    assert_eq!(scene.step_count(), 10);
+    assert!(scene.sim_time > 0.0);
+
+    let fluid = &scene.fluid;
+    assert!(fluid.u.iter().any(|&u| u > 0.0), "inflow should carry some positive u into the domain");
+    assert!(fluid.u.iter().all(|u| u.is_finite()), "10 steps should not have diverged");
+    assert!(fluid.v.iter().all(|v| v.is_finite()));
+    assert!(fluid.p.iter().all(|p| p.is_finite()));
+    assert!(
+        fluid.m.iter().all(|&m| (-1e-6..=1.0 + 1e-6).contains(&m)),
+        "dye should stay close to its [0, 1] range, allowing for interpolation overshoot"
+    );
+}